@@ -12,6 +12,10 @@ impl KeyDerivationFunction {
         Self { kdf_work }
     }
 
+    pub fn kdf_work(&self) -> u32 {
+        self.kdf_work
+    }
+
     pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> RawKey {
         let config = argon2::Config {
             hash_length: 32,
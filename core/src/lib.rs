@@ -59,6 +59,12 @@ pub use pending_key::PendingKey;
 mod pending_info;
 pub use pending_info::PendingInfo;
 
+mod peer_reputation;
+pub use peer_reputation::{MisbehaviorExclusion, PeerReputation};
+
+mod rep_stats;
+pub use rep_stats::{RepStatsBucket, RepStatsKey};
+
 mod difficulty;
 pub use difficulty::{Difficulty, DifficultyV1, StubDifficulty, WorkVersion};
 
@@ -72,6 +78,9 @@ pub use unchecked_info::{UncheckedInfo, UncheckedKey};
 
 mod kdf;
 pub use kdf::KeyDerivationFunction;
+
+mod mnemonic;
+pub use mnemonic::{DerivationScheme, Mnemonic, Slip10Key, BANANO_COIN_TYPE};
 use utils::{BufferWriter, Deserialize, Serialize, Stream};
 
 use std::{
@@ -0,0 +1,212 @@
+use crate::RawKey;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+use std::fmt::Display;
+
+/// SLIP-44 coin type of Banano
+pub const BANANO_COIN_TYPE: u32 = 198;
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// How the deterministic keys of a wallet are derived from its seed
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, FromPrimitive, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DerivationScheme {
+    /// Blake2b(seed || index), as used by the node and by Natrium
+    #[default]
+    Legacy = 0,
+    /// SLIP-10 ed25519 derivation along m/44'/198'/index', as used by hardware wallets
+    Bip44 = 1,
+}
+
+impl DerivationScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DerivationScheme::Legacy => "legacy",
+            DerivationScheme::Bip44 => "bip44",
+        }
+    }
+}
+
+/// A BIP39 mnemonic phrase (english word list)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// Generates a new 24 word mnemonic
+    pub fn random() -> Self {
+        Self::from_seed(&RawKey::random())
+    }
+
+    /// Encodes a 32 byte legacy seed as 24 words. This is how Natrium displays seeds.
+    pub fn from_seed(seed: &RawKey) -> Self {
+        Self(bip39::Mnemonic::from_entropy(seed.as_bytes()).unwrap())
+    }
+
+    pub fn parse(phrase: &str) -> anyhow::Result<Self> {
+        bip39::Mnemonic::parse(phrase)
+            .map(Self)
+            .map_err(|e| anyhow!("invalid mnemonic: {}", e))
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.0.word_count()
+    }
+
+    /// The legacy seed encoded by a 24 word mnemonic
+    pub fn legacy_seed(&self) -> Option<RawKey> {
+        let entropy = self.0.to_entropy();
+        Some(RawKey::from_bytes(entropy.try_into().ok()?))
+    }
+
+    /// The 64 byte BIP39 seed, stretched from the phrase and the passphrase with PBKDF2
+    pub fn bip39_seed(&self, passphrase: &str) -> [u8; 64] {
+        self.0.to_seed(passphrase)
+    }
+}
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Extended private key for SLIP-10 ed25519 derivation.
+/// Ed25519 only supports hardened child keys.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Slip10Key {
+    pub key: RawKey,
+    pub chain_code: RawKey,
+}
+
+impl Slip10Key {
+    pub fn master(seed: &[u8]) -> Self {
+        Self::from_hmac(b"ed25519 seed", &[seed])
+    }
+
+    /// The key at m/44'/198'. Account `i` of a BIP44 wallet is its hardened child `i`
+    pub fn banano_root(bip39_seed: &[u8]) -> Self {
+        Self::master(bip39_seed).derive(44).derive(BANANO_COIN_TYPE)
+    }
+
+    /// Derives the hardened child with the given index
+    pub fn derive(&self, index: u32) -> Self {
+        let index = (index | HARDENED_OFFSET).to_be_bytes();
+        Self::from_hmac(
+            self.chain_code.as_bytes(),
+            &[&[0], self.key.as_bytes(), &index],
+        )
+    }
+
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).unwrap();
+        for d in data {
+            mac.update(d);
+        }
+        let result = mac.finalize().into_bytes();
+        Self {
+            key: RawKey::from_slice(&result[..32]).unwrap(),
+            chain_code: RawKey::from_slice(&result[32..]).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PublicKey;
+
+    #[test]
+    fn slip10_test_vector() {
+        // Test vector 1 for ed25519 from the SLIP-10 specification
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = Slip10Key::master(&seed);
+        assert_eq!(
+            master.key,
+            RawKey::decode_hex("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7")
+                .unwrap()
+        );
+        assert_eq!(
+            master.chain_code,
+            RawKey::decode_hex("90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb")
+                .unwrap()
+        );
+
+        let child = master.derive(0);
+        assert_eq!(
+            child.key,
+            RawKey::decode_hex("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3")
+                .unwrap()
+        );
+        assert_eq!(
+            child.chain_code,
+            RawKey::decode_hex("8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn bip39_seed_and_bip44_key() {
+        // Published BIP39/BIP44 test vector for the Nano Ledger app (coin type 165)
+        let mnemonic = Mnemonic::parse("edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur").unwrap();
+        let seed = mnemonic.bip39_seed("some password");
+        assert_eq!(
+            hex::encode(seed),
+            "0dc285fde768f7ff29b66ce7252d56ed92fe003b605907f7a4f683c3dc8586d34a914d3c71fc099bb38ee4a59e5b081a3497b7a323e90cc68f67b5837690310c"
+        );
+
+        let key = Slip10Key::master(&seed)
+            .derive(44)
+            .derive(165)
+            .derive(0)
+            .key;
+        assert_eq!(
+            key,
+            RawKey::decode_hex("3be4fc2ef3f3b7374e6fc4fb6e7bb153f8a2998b3b3dab50853eabe128024143")
+                .unwrap()
+        );
+        assert_eq!(
+            PublicKey::try_from(&key).unwrap(),
+            PublicKey::decode_hex(
+                "5b65b0e8173ee0802c2c3e6c9080d1a16b06de1176c938a924f58670904e82c4"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn banano_root() {
+        let seed = [1; 64];
+        assert_eq!(
+            Slip10Key::banano_root(&seed),
+            Slip10Key::master(&seed).derive(44).derive(198)
+        );
+    }
+
+    #[test]
+    fn legacy_seed_as_mnemonic() {
+        let mnemonic = Mnemonic::from_seed(&RawKey::zero());
+        let expected = format!("{}art", "abandon ".repeat(23));
+        assert_eq!(mnemonic.to_string(), expected);
+        assert_eq!(mnemonic.word_count(), 24);
+        assert_eq!(mnemonic.legacy_seed(), Some(RawKey::zero()));
+        assert_eq!(Mnemonic::parse(&expected).unwrap(), mnemonic);
+    }
+
+    #[test]
+    fn twelve_words_have_no_legacy_seed() {
+        let mnemonic = Mnemonic::parse(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        assert_eq!(mnemonic.word_count(), 12);
+        assert_eq!(mnemonic.legacy_seed(), None);
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        let phrase = format!("{}abandon", "abandon ".repeat(23));
+        assert!(Mnemonic::parse(&phrase).is_err());
+    }
+}
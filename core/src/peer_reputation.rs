@@ -0,0 +1,167 @@
+use crate::{
+    utils::{
+        BufferWriter, Deserialize, FixedSizeSerialize, MutStreamAdapter, Serialize, Stream,
+        StreamExt,
+    },
+    NodeId,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What a node learned about a peer over time.
+/// It is persisted, so that it survives restarts of the node.
+#[derive(Default, PartialEq, Eq, Debug, Clone)]
+pub struct PeerReputation {
+    /// The node ID the peer presented the last time it was connected
+    pub node_id: Option<NodeId>,
+    /// Number of times the peer misbehaved. It doesn't drop when the peer gets forgiven
+    pub misbehavior_score: u64,
+    /// Number of answered bootstrap requests
+    pub bootstrap_responses: u64,
+    /// Moving average of the time it took the peer to answer a bootstrap request
+    pub average_latency: Duration,
+    /// Total time the peer was connected as realtime peer
+    pub uptime: Duration,
+    /// The exclusion of the peer that was active when the reputation was persisted
+    pub exclusion: Option<MisbehaviorExclusion>,
+}
+
+/// An exclusion of a misbehaving peer, with an end time that stays valid across restarts
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct MisbehaviorExclusion {
+    /// The score of the peer exclusion. Unlike the misbehavior score of the
+    /// reputation it is reset when the peer gets forgiven
+    pub score: u64,
+    pub until: SystemTime,
+}
+
+impl PeerReputation {
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut buffer = [0; 80];
+        let mut stream = MutStreamAdapter::new(&mut buffer);
+        self.serialize(&mut stream);
+        buffer
+    }
+
+    pub fn new_test_instance() -> Self {
+        Self {
+            node_id: Some(NodeId::from(42)),
+            misbehavior_score: 1,
+            bootstrap_responses: 100,
+            average_latency: Duration::from_millis(250),
+            uptime: Duration::from_secs(3600),
+            exclusion: None,
+        }
+    }
+}
+
+impl Serialize for PeerReputation {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        self.node_id.unwrap_or(NodeId::ZERO).serialize(writer);
+        writer.write_u64_be_safe(self.misbehavior_score);
+        writer.write_u64_be_safe(self.bootstrap_responses);
+        writer.write_u64_be_safe(self.average_latency.as_millis() as u64);
+        writer.write_u64_be_safe(self.uptime.as_secs());
+        match &self.exclusion {
+            Some(exclusion) => {
+                writer.write_u64_be_safe(exclusion.score);
+                writer.write_u64_be_safe(
+                    exclusion
+                        .until
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as u64,
+                );
+            }
+            None => {
+                writer.write_u64_be_safe(0);
+                writer.write_u64_be_safe(0);
+            }
+        }
+    }
+}
+
+impl FixedSizeSerialize for PeerReputation {
+    fn serialized_size() -> usize {
+        32 + 6 * std::mem::size_of::<u64>()
+    }
+}
+
+impl Deserialize for PeerReputation {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let node_id = NodeId::deserialize(stream)?;
+        let misbehavior_score = stream.read_u64_be()?;
+        let bootstrap_responses = stream.read_u64_be()?;
+        let average_latency = Duration::from_millis(stream.read_u64_be()?);
+        let uptime = Duration::from_secs(stream.read_u64_be()?);
+        let exclusion_score = stream.read_u64_be()?;
+        let excluded_until = stream.read_u64_be()?;
+        Ok(Self {
+            node_id: if node_id == NodeId::ZERO {
+                None
+            } else {
+                Some(node_id)
+            },
+            misbehavior_score,
+            bootstrap_responses,
+            average_latency,
+            uptime,
+            exclusion: if exclusion_score == 0 {
+                None
+            } else {
+                Some(MisbehaviorExclusion {
+                    score: exclusion_score,
+                    until: UNIX_EPOCH + Duration::from_millis(excluded_until),
+                })
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::BufferReader;
+
+    #[test]
+    fn serialize() {
+        let reputation = PeerReputation::new_test_instance();
+        let bytes = reputation.to_bytes();
+        assert_eq!(bytes.len(), PeerReputation::serialized_size());
+        let mut stream = BufferReader::new(&bytes);
+        assert_eq!(
+            PeerReputation::deserialize(&mut stream).unwrap(),
+            reputation
+        );
+    }
+
+    #[test]
+    fn serialize_unknown_node_id() {
+        let reputation = PeerReputation::default();
+        let bytes = reputation.to_bytes();
+        let mut stream = BufferReader::new(&bytes);
+        assert_eq!(
+            PeerReputation::deserialize(&mut stream).unwrap(),
+            reputation
+        );
+    }
+
+    #[test]
+    fn serialize_exclusion() {
+        let reputation = PeerReputation {
+            misbehavior_score: 5,
+            exclusion: Some(MisbehaviorExclusion {
+                score: 3,
+                until: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+            }),
+            ..PeerReputation::new_test_instance()
+        };
+        let bytes = reputation.to_bytes();
+        let mut stream = BufferReader::new(&bytes);
+        assert_eq!(
+            PeerReputation::deserialize(&mut stream).unwrap(),
+            reputation
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    utils::{
+        BufferWriter, Deserialize, FixedSizeSerialize, MutStreamAdapter, Serialize, Stream,
+        StreamExt,
+    },
+    PublicKey,
+};
+
+/// Key of a representative statistics bucket.
+/// The representative comes first, so that all buckets of a representative are stored next
+/// to each other and ordered by time
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+pub struct RepStatsKey {
+    pub representative: PublicKey,
+    /// Start of the bucket in seconds since epoch
+    pub bucket_start: u64,
+}
+
+impl RepStatsKey {
+    pub fn new(representative: PublicKey, bucket_start: u64) -> Self {
+        Self {
+            representative,
+            bucket_start,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut buffer = [0; 40];
+        let mut stream = MutStreamAdapter::new(&mut buffer);
+        self.serialize(&mut stream);
+        buffer
+    }
+}
+
+impl Serialize for RepStatsKey {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        self.representative.serialize(writer);
+        writer.write_u64_be_safe(self.bucket_start);
+    }
+}
+
+impl FixedSizeSerialize for RepStatsKey {
+    fn serialized_size() -> usize {
+        PublicKey::serialized_size() + std::mem::size_of::<u64>()
+    }
+}
+
+impl Deserialize for RepStatsKey {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        Ok(Self {
+            representative: PublicKey::deserialize(stream)?,
+            bucket_start: stream.read_u64_be()?,
+        })
+    }
+}
+
+/// What was observed about a representative during one time bucket
+#[derive(Default, PartialEq, Eq, Debug, Clone)]
+pub struct RepStatsBucket {
+    /// How often the online status of representatives was sampled
+    pub samples: u32,
+    /// How many of those samples saw the representative online
+    pub online_samples: u32,
+    /// Number of confirmed elections
+    pub elections: u32,
+    /// Number of confirmed elections the representative voted in
+    pub votes: u32,
+    /// Number of confirmed elections the representative sent a final vote for
+    pub final_votes: u32,
+    /// Sum of the time between election start and vote arrival in milliseconds
+    pub vote_latency_ms: u64,
+}
+
+impl RepStatsBucket {
+    pub fn to_bytes(&self) -> [u8; 28] {
+        let mut buffer = [0; 28];
+        let mut stream = MutStreamAdapter::new(&mut buffer);
+        self.serialize(&mut stream);
+        buffer
+    }
+
+    pub fn merge(&mut self, other: &RepStatsBucket) {
+        self.samples += other.samples;
+        self.online_samples += other.online_samples;
+        self.elections += other.elections;
+        self.votes += other.votes;
+        self.final_votes += other.final_votes;
+        self.vote_latency_ms += other.vote_latency_ms;
+    }
+
+    pub fn new_test_instance() -> Self {
+        Self {
+            samples: 60,
+            online_samples: 55,
+            elections: 100,
+            votes: 90,
+            final_votes: 80,
+            vote_latency_ms: 45000,
+        }
+    }
+}
+
+impl Serialize for RepStatsBucket {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        writer.write_u32_be_safe(self.samples);
+        writer.write_u32_be_safe(self.online_samples);
+        writer.write_u32_be_safe(self.elections);
+        writer.write_u32_be_safe(self.votes);
+        writer.write_u32_be_safe(self.final_votes);
+        writer.write_u64_be_safe(self.vote_latency_ms);
+    }
+}
+
+impl FixedSizeSerialize for RepStatsBucket {
+    fn serialized_size() -> usize {
+        5 * std::mem::size_of::<u32>() + std::mem::size_of::<u64>()
+    }
+}
+
+impl Deserialize for RepStatsBucket {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        Ok(Self {
+            samples: stream.read_u32_be()?,
+            online_samples: stream.read_u32_be()?,
+            elections: stream.read_u32_be()?,
+            votes: stream.read_u32_be()?,
+            final_votes: stream.read_u32_be()?,
+            vote_latency_ms: stream.read_u64_be()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::BufferReader;
+
+    #[test]
+    fn serialize_bucket() {
+        let bucket = RepStatsBucket::new_test_instance();
+        let bytes = bucket.to_bytes();
+        assert_eq!(bytes.len(), RepStatsBucket::serialized_size());
+        let mut stream = BufferReader::new(&bytes);
+        assert_eq!(RepStatsBucket::deserialize(&mut stream).unwrap(), bucket);
+    }
+
+    #[test]
+    fn serialize_key() {
+        let key = RepStatsKey::new(PublicKey::from(42), 3600);
+        let bytes = key.to_bytes();
+        assert_eq!(bytes.len(), RepStatsKey::serialized_size());
+        let mut stream = BufferReader::new(&bytes);
+        assert_eq!(RepStatsKey::deserialize(&mut stream).unwrap(), key);
+    }
+
+    #[test]
+    fn keys_are_ordered_by_representative_then_time() {
+        let a = RepStatsKey::new(PublicKey::from(1), 7200);
+        let b = RepStatsKey::new(PublicKey::from(2), 0);
+        assert!(a < b);
+        assert!(a.to_bytes() < b.to_bytes());
+        let c = RepStatsKey::new(PublicKey::from(1), 3600);
+        assert!(c < a);
+        assert!(c.to_bytes() < a.to_bytes());
+    }
+
+    #[test]
+    fn merge() {
+        let mut bucket = RepStatsBucket::new_test_instance();
+        bucket.merge(&RepStatsBucket::new_test_instance());
+        assert_eq!(bucket.samples, 120);
+        assert_eq!(bucket.vote_latency_ms, 90000);
+    }
+}
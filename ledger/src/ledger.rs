@@ -17,7 +17,8 @@ use rsban_store_lmdb::{
     ConfiguredConfirmationHeightDatabaseBuilder, ConfiguredPeersDatabaseBuilder,
     ConfiguredPendingDatabaseBuilder, ConfiguredPrunedDatabaseBuilder, LedgerCache,
     LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore,
-    LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction,
+    LmdbLocalVoteStore, LmdbOnlineWeightStore, LmdbPeerBanStore, LmdbPeerReputationStore,
+    LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction, LmdbRepStatsStore,
     LmdbRepWeightStore, LmdbStore, LmdbVersionStore, LmdbWriteTransaction, Transaction,
 };
 use std::{
@@ -188,10 +189,14 @@ impl NullLedgerBuilder {
             block: Arc::new(LmdbBlockStore::new(env.clone()).unwrap()),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone()).unwrap()),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone()).unwrap()),
+            local_vote: Arc::new(LmdbLocalVoteStore::new(env.clone()).unwrap()),
             online_weight: Arc::new(LmdbOnlineWeightStore::new(env.clone()).unwrap()),
             peer: Arc::new(LmdbPeerStore::new(env.clone()).unwrap()),
+            peer_ban: Arc::new(LmdbPeerBanStore::new(env.clone()).unwrap()),
+            peer_reputation: Arc::new(LmdbPeerReputationStore::new(env.clone()).unwrap()),
             pending: Arc::new(LmdbPendingStore::new(env.clone()).unwrap()),
             pruned: Arc::new(LmdbPrunedStore::new(env.clone()).unwrap()),
+            rep_stats: Arc::new(LmdbRepStatsStore::new(env.clone()).unwrap()),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone()).unwrap()),
             version: Arc::new(LmdbVersionStore::new(env.clone()).unwrap()),
         };
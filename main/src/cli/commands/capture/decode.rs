@@ -0,0 +1,55 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use rsban_core::Networks;
+use rsban_node::{transport::CaptureReader, NetworkParams};
+use serde_json::json;
+use std::str::FromStr;
+
+#[derive(Parser)]
+pub(crate) struct DecodeArgs {
+    /// The capture file
+    #[arg(long)]
+    file: String,
+    /// The network on which the messages were captured (live, test, beta or dev)
+    #[arg(long)]
+    network: Option<String>,
+}
+
+impl DecodeArgs {
+    pub(crate) async fn decode(&self) -> Result<()> {
+        let network = self
+            .network
+            .as_ref()
+            .map(|s| Networks::from_str(s).map_err(|e| anyhow!(e)))
+            .transpose()?
+            .unwrap_or(Networks::BananoLiveNetwork);
+        let network_params = NetworkParams::new(network);
+        let protocol_info = network_params.network.protocol_info();
+
+        for record in CaptureReader::open(&self.file)? {
+            let record = record?;
+            let mut json = json!({
+                "timestamp": record.timestamp,
+                "channel_id": record.channel_id.as_usize(),
+                "peer": record.peer_addr.to_string(),
+                "direction": format!("{:?}", record.direction).to_lowercase(),
+                "size": record.bytes.len(),
+            });
+            match record
+                .decode(protocol_info, network_params.work.clone())
+                .await
+            {
+                Ok(message) => {
+                    json["message_type"] = message.message_type().as_str().into();
+                    json["message"] = message.to_string().into();
+                }
+                Err(e) => {
+                    json["error"] = format!("{:?}", e).into();
+                }
+            }
+            println!("{}", json);
+        }
+
+        Ok(())
+    }
+}
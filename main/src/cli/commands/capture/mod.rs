@@ -0,0 +1,33 @@
+use anyhow::Result;
+use clap::{CommandFactory, Parser, Subcommand};
+use decode::DecodeArgs;
+use replay::ReplayArgs;
+
+pub(crate) mod decode;
+pub(crate) mod replay;
+
+#[derive(Subcommand)]
+pub(crate) enum CaptureSubcommands {
+    /// Prints the messages of a capture file as JSON, one message per line
+    Decode(DecodeArgs),
+    /// Feeds the inbound messages of a capture file into a node without network connections
+    Replay(ReplayArgs),
+}
+
+#[derive(Parser)]
+pub(crate) struct CaptureCommand {
+    #[command(subcommand)]
+    pub subcommand: Option<CaptureSubcommands>,
+}
+
+impl CaptureCommand {
+    pub(crate) async fn run(&self) -> Result<()> {
+        match &self.subcommand {
+            Some(CaptureSubcommands::Decode(args)) => args.decode().await?,
+            Some(CaptureSubcommands::Replay(args)) => args.replay().await?,
+            None => CaptureCommand::command().print_long_help()?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use rsban_core::{utils::get_cpu_count, Networks};
+use rsban_node::{
+    config::{DaemonConfig, NodeFlags},
+    transport::{CaptureReader, CaptureReplayer},
+    working_path_for, NetworkParams, NodeBuilder, NodeExt,
+};
+use std::{path::PathBuf, str::FromStr, sync::Arc, time::Duration};
+
+#[derive(Parser)]
+pub(crate) struct ReplayArgs {
+    /// The capture file
+    #[arg(long)]
+    file: String,
+    /// The data directory of the replay node. Use a copy of the ledger,
+    /// because the replayed messages modify it
+    #[arg(long)]
+    data_path: PathBuf,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long)]
+    network: Option<String>,
+    /// Keeps the original time between the messages instead of replaying as fast as possible
+    #[arg(long)]
+    realtime: bool,
+}
+
+impl ReplayArgs {
+    pub(crate) async fn replay(&self) -> Result<()> {
+        let network = self
+            .network
+            .as_ref()
+            .map(|s| Networks::from_str(s).map_err(|e| anyhow!(e)))
+            .transpose()?
+            .unwrap_or(Networks::BananoLiveNetwork);
+        let data_path = self.data_path.clone();
+        if working_path_for(network).is_some_and(|default_path| default_path == data_path) {
+            bail!("Refusing to replay into the default data directory. Use a copy of it instead");
+        }
+
+        // The node must not talk to the network, so that only the captured messages are processed
+        let mut network_params = NetworkParams::new(network);
+        network_params.network.merge_period = Duration::ZERO;
+        let mut config =
+            DaemonConfig::load_from_data_path(network, get_cpu_count(), &data_path)?.node;
+        config.preconfigured_peers.clear();
+        config.peering_port = Some(0);
+        config.tcp_incoming_connections_max = 0;
+        config.tcp.max_inbound_connections = 0;
+        config.tcp.max_outbound_connections = 0;
+        config.port_mapping.enabled = false;
+        config.message_capture.enabled = false;
+
+        let mut flags = NodeFlags::new();
+        flags.disable_bootstrap_listener = true;
+        flags.disable_ongoing_bootstrap = true;
+        flags.disable_ascending_bootstrap = true;
+        flags.disable_legacy_bootstrap = true;
+        flags.disable_lazy_bootstrap = true;
+        flags.disable_wallet_bootstrap = true;
+        flags.disable_rep_crawler = true;
+
+        let node = Arc::new(
+            NodeBuilder::new(network)
+                .data_path(data_path)
+                .config(config)
+                .network_params(network_params.clone())
+                .flags(flags)
+                .finish()?,
+        );
+        node.start();
+
+        let mut replayer = CaptureReplayer::new(
+            node.inbound_message_queue.clone(),
+            network_params.network.protocol_info(),
+            network_params.work.clone(),
+            node.steady_clock.clone(),
+        );
+
+        let mut replayed = 0;
+        let mut skipped = 0;
+        let mut invalid = 0;
+        let mut last_timestamp = None;
+        for record in CaptureReader::open(&self.file)? {
+            let record = record?;
+            if self.realtime {
+                if let Some(last) = last_timestamp {
+                    let delay = record.timestamp.saturating_sub(last);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+                last_timestamp = Some(record.timestamp);
+            }
+            match replayer.replay(&record).await {
+                Ok(true) => replayed += 1,
+                Ok(false) => skipped += 1,
+                Err(e) => {
+                    eprintln!("Invalid message on channel {}: {:?}", record.channel_id, e);
+                    invalid += 1;
+                }
+            }
+        }
+
+        while node.inbound_message_queue.size() > 0 || node.block_processor.total_queue_len() > 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        println!(
+            "Replayed {} messages, skipped {}, invalid {}",
+            replayed, skipped, invalid
+        );
+        println!(
+            "Block count: {}, cemented: {}",
+            node.ledger.block_count(),
+            node.ledger.cemented_count()
+        );
+
+        node.stop();
+        Ok(())
+    }
+}
@@ -1,13 +1,19 @@
 use crate::cli::get_path;
 use anyhow::Result;
 use clap::{ArgGroup, Parser};
-use rsban_core::Root;
-use rsban_store_lmdb::{LmdbEnv, LmdbFinalVoteStore};
-use std::sync::Arc;
+use rsban_core::{Amount, Root};
+use rsban_ledger::{Ledger, RepWeightCache};
+use rsban_node::{
+    config::NetworkConstants,
+    consensus::{FinalVoteCleanup, FinalVoteCleanupConfig},
+    NetworkParams,
+};
+use rsban_store_lmdb::{LmdbEnv, LmdbFinalVoteStore, LmdbStore};
+use std::{path::Path, sync::Arc};
 
 #[derive(Parser)]
 #[command(group = ArgGroup::new("input1")
-    .args(&["root", "all"])
+    .args(&["root", "all", "depth"])
     .required(true))]
 #[command(group = ArgGroup::new("input2")
     .args(&["data_path", "network"]))]
@@ -18,6 +24,9 @@ pub(crate) struct FinalVoteArgs {
     /// Clears all final votes (not recommended)
     #[arg(long, group = "input1")]
     all: bool,
+    /// Clears the final votes whose account is cemented at least the supplied number of blocks past the voted block
+    #[arg(long, group = "input1")]
+    depth: Option<u64>,
     /// Uses the supplied path as the data directory
     #[arg(long, group = "input")]
     data_path: Option<String>,
@@ -30,6 +39,10 @@ impl FinalVoteArgs {
     pub(crate) fn final_vote(&self) -> Result<()> {
         let path = get_path(&self.data_path, &self.network).join("data.ldb");
 
+        if let Some(depth) = self.depth {
+            return self.prune(&path, depth);
+        }
+
         let env = Arc::new(LmdbEnv::new(&path)?);
 
         let final_vote_store = LmdbFinalVoteStore::new(env.clone())?;
@@ -47,4 +60,21 @@ impl FinalVoteArgs {
 
         Ok(())
     }
+
+    fn prune(&self, path: &Path, depth: u64) -> Result<()> {
+        let network_params = NetworkParams::new(NetworkConstants::active_network());
+
+        let ledger = Ledger::new(
+            Arc::new(LmdbStore::open(path).build()?),
+            network_params.ledger,
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )?;
+
+        let cleanup = FinalVoteCleanup::new(Arc::new(ledger));
+        let deleted = cleanup.prune(depth, FinalVoteCleanupConfig::default().batch_size);
+        println!("{} final votes were cleared from the database", deleted);
+
+        Ok(())
+    }
 }
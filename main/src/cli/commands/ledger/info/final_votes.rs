@@ -0,0 +1,61 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsban_core::{Amount, Root};
+use rsban_ledger::{Ledger, RepWeightCache};
+use rsban_node::{config::NetworkConstants, consensus::FinalVoteCleanup, NetworkParams};
+use rsban_store_lmdb::LmdbStore;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct FinalVotesArgs {
+    /// Displays the final votes for the supplied root instead of the final vote count
+    #[arg(long)]
+    root: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl FinalVotesArgs {
+    pub(crate) fn final_votes(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let network_params = NetworkParams::new(NetworkConstants::active_network());
+
+        let ledger = Ledger::new(
+            Arc::new(LmdbStore::open(&path).build()?),
+            network_params.ledger,
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )?;
+
+        let final_votes = FinalVoteCleanup::new(Arc::new(ledger));
+
+        if let Some(root) = &self.root {
+            let entries = final_votes.entries(&Root::decode_hex(root)?);
+            if entries.is_empty() {
+                println!("No final vote found for root {}", root);
+            }
+            for entry in entries {
+                let depth = entry
+                    .cemented_depth
+                    .map(|depth| depth.to_string())
+                    .unwrap_or_else(|| "not cemented".to_string());
+                println!(
+                    "Previous: {} Hash: {} Cemented depth: {}",
+                    entry.qualified_root.previous, entry.hash, depth
+                );
+            }
+        } else {
+            println!("Final vote count: {}", final_votes.count());
+        }
+
+        Ok(())
+    }
+}
@@ -4,7 +4,9 @@ use block_count::BlockCountArgs;
 use blocks::Blocks;
 use cemented_block_count::CementedBlockCountArgs;
 use clap::{CommandFactory, Parser, Subcommand};
+use final_votes::FinalVotesArgs;
 use peers::PeersArgs;
+use quorum_simulation::QuorumSimulationArgs;
 use representatives::RepresentativesArgs;
 use trended_online_weight::TrendedOnlineWeightArgs;
 
@@ -12,7 +14,9 @@ pub(crate) mod account_count;
 pub(crate) mod block_count;
 pub(crate) mod blocks;
 pub(crate) mod cemented_block_count;
+pub(crate) mod final_votes;
 pub(crate) mod peers;
+pub(crate) mod quorum_simulation;
 pub(crate) mod representatives;
 pub(crate) mod trended_online_weight;
 
@@ -28,6 +32,10 @@ pub(crate) enum InfoSubcommands {
     Peers(PeersArgs),
     /// Displays the number of cemented (confirmed) blocks
     CementedBlockCount(CementedBlockCountArgs),
+    /// Displays the number of final votes or the final votes for a root
+    FinalVotes(FinalVotesArgs),
+    /// Displays the quorum before and after hypothetical representative weight changes
+    QuorumSimulation(QuorumSimulationArgs),
     /// Displays representatives and their weights
     Representatives(RepresentativesArgs),
     /// Displays trended online weight over time
@@ -47,7 +55,9 @@ impl InfoCommand {
             Some(InfoSubcommands::BlockCount(args)) => args.block_count()?,
             Some(InfoSubcommands::Blocks(args)) => args.blocks()?,
             Some(InfoSubcommands::CementedBlockCount(args)) => args.cemented_block_count()?,
+            Some(InfoSubcommands::FinalVotes(args)) => args.final_votes()?,
             Some(InfoSubcommands::Peers(args)) => args.peers()?,
+            Some(InfoSubcommands::QuorumSimulation(args)) => args.quorum_simulation()?,
             Some(InfoSubcommands::TrendedOnlineWeight(args)) => args.trended_online_weight()?,
             Some(InfoSubcommands::Representatives(args)) => args.dump_representatives()?,
             None => InfoCommand::command().print_long_help()?,
@@ -0,0 +1,117 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsban_core::{Account, Amount};
+use rsban_ledger::{Ledger, RepWeightCache};
+use rsban_node::{
+    config::{NetworkConstants, NodeConfig},
+    consensus::RepTier,
+    representatives::{QuorumSimulator, QuorumSnapshot, WeightChange},
+    NetworkParams, OnlineWeightSampler,
+};
+use rsban_store_lmdb::LmdbStore;
+use std::{cmp::max, sync::Arc};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct QuorumSimulationArgs {
+    /// Moves weight between representatives. Format: FROM_ACCOUNT:TO_ACCOUNT:RAW_AMOUNT
+    #[arg(long = "move")]
+    moves: Vec<String>,
+    /// Takes the supplied representative offline
+    #[arg(long)]
+    offline: Vec<String>,
+    /// Brings the supplied representative online
+    #[arg(long)]
+    online: Vec<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl QuorumSimulationArgs {
+    pub(crate) fn quorum_simulation(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let network = NetworkConstants::active_network();
+        let network_params = NetworkParams::new(network);
+        let online_weight_minimum = NodeConfig::default_for(network, 1).online_weight_minimum;
+
+        let ledger = Arc::new(Ledger::new(
+            Arc::new(LmdbStore::open(&path).build()?),
+            network_params.ledger.clone(),
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )?);
+
+        let sampler = OnlineWeightSampler::new(
+            ledger.clone(),
+            network_params.node.max_weight_samples as usize,
+        );
+        let trended = sampler.calculate_trend();
+
+        // The ledger doesn't know which representatives are online,
+        // so all principal representatives are assumed to be online
+        let weights = ledger.rep_weights.read().clone();
+        let trended_or_minimum = max(trended, online_weight_minimum);
+        let online = weights
+            .iter()
+            .filter(|(_, weight)| {
+                RepTier::for_weight(**weight, trended_or_minimum) != RepTier::None
+            })
+            .map(|(rep, _)| *rep)
+            .collect();
+
+        let simulator = QuorumSimulator::new(weights, online, trended, online_weight_minimum);
+        let (before, after) = simulator.simulate(&self.changes()?)?;
+
+        println!("Before:");
+        print_snapshot(&before);
+        println!();
+        println!("After:");
+        print_snapshot(&after);
+
+        Ok(())
+    }
+
+    fn changes(&self) -> Result<Vec<WeightChange>> {
+        let mut changes = Vec::new();
+        for change in &self.moves {
+            let parts: Vec<_> = change.split(':').collect();
+            let [from, to, amount] = parts[..] else {
+                return Err(anyhow!("Invalid move: {}", change));
+            };
+            changes.push(WeightChange::Move {
+                from: Account::decode_account(from)?.into(),
+                to: Account::decode_account(to)?.into(),
+                amount: Amount::decode_dec(amount)?,
+            });
+        }
+        for rep in &self.offline {
+            changes.push(WeightChange::Offline(Account::decode_account(rep)?.into()));
+        }
+        for rep in &self.online {
+            changes.push(WeightChange::Online(Account::decode_account(rep)?.into()));
+        }
+        Ok(changes)
+    }
+}
+
+fn print_snapshot(snapshot: &QuorumSnapshot) {
+    println!("Online weight {}", snapshot.online_weight.number());
+    println!("Trended weight {}", snapshot.trended_weight.number());
+    println!("Quorum delta {}", snapshot.quorum_delta.number());
+    for rep in &snapshot.principal_reps {
+        println!(
+            "{} {} {} {}",
+            rep.representative.as_account().encode_account(),
+            rep.weight.number(),
+            if rep.online { "online" } else { "offline" },
+            rep.tier.as_str()
+        );
+    }
+}
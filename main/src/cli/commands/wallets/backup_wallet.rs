@@ -0,0 +1,110 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsban_core::WalletId;
+use rsban_node::wallets::{Wallets, WalletsExt};
+use rsban_store_lmdb::LmdbEnv;
+use std::{fs::OpenOptions, io::Write, path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct BackupWalletArgs {
+    /// The wallet to be backed up
+    #[arg(long)]
+    wallet: String,
+    /// The path of the backup file
+    #[arg(long)]
+    file: String,
+    /// The password used to encrypt the backup
+    #[arg(long)]
+    backup_password: String,
+    /// Optional password to unlock the wallet
+    #[arg(long)]
+    password: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl BackupWalletArgs {
+    pub(crate) async fn backup_wallet(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let wallet_id = WalletId::decode_hex(&self.wallet)?;
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let mut wallets = Wallets::new_null_with_env(env, tokio::runtime::Handle::current());
+        wallets.initialize()?;
+        let wallets = Arc::new(wallets);
+
+        let password = self.password.clone().unwrap_or_default();
+
+        wallets.ensure_wallet_is_unlocked(wallet_id, &password);
+
+        let backup = wallets
+            .backup_wallet(&wallet_id, &self.backup_password)
+            .map_err(|e| anyhow!("Failed to backup wallet: {:?}", e))?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(PathBuf::from(&self.file))?;
+        file.write_all(&backup)?;
+
+        println!("Wallet {} backed up to {}", wallet_id, self.file);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Cli, CliInfrastructure};
+    use clap::Parser;
+    use rsban_core::WalletId;
+    use rsban_node::{
+        unique_path,
+        wallets::{WalletBackup, Wallets, WalletsExt},
+    };
+    use rsban_store_lmdb::LmdbEnv;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn backup_is_encrypted_with_strong_kdf_work() {
+        let data_path = unique_path().unwrap();
+        let wallet_id = WalletId::random();
+        {
+            let env = Arc::new(LmdbEnv::new(data_path.join("wallets.ldb")).unwrap());
+            let mut wallets = Wallets::new_null_with_env(env, tokio::runtime::Handle::current());
+            wallets.initialize().unwrap();
+            Arc::new(wallets).create(wallet_id);
+        }
+        let file = data_path.join("wallet.backup");
+
+        let cli = Cli::try_parse_from([
+            "nulled_node",
+            "wallets",
+            "backup-wallet",
+            "--wallet",
+            &wallet_id.encode_hex(),
+            "--file",
+            file.to_str().unwrap(),
+            "--backup-password",
+            "secret",
+            "--data-path",
+            data_path.to_str().unwrap(),
+        ])
+        .unwrap();
+        cli.run(&mut CliInfrastructure::new_null()).await.unwrap();
+
+        let backup = std::fs::read(&file).unwrap();
+        assert_eq!(backup[9..13], WalletBackup::MAX_KDF_WORK.to_be_bytes());
+        std::fs::remove_dir_all(data_path).unwrap();
+    }
+}
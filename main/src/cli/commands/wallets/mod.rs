@@ -1,5 +1,6 @@
 use add_private_key::AddPrivateKeyArgs;
 use anyhow::Result;
+use backup_wallet::BackupWalletArgs;
 use change_wallet_seed::ChangeWalletSeedArgs;
 use clap::{CommandFactory, Parser, Subcommand};
 use clear_send_ids::ClearSendIdsArgs;
@@ -11,9 +12,11 @@ use get_wallet_representative::GetWalletRepresentativeArgs;
 use import_keys::ImportKeysArgs;
 use list_wallets::ListWalletsArgs;
 use remove_account::RemoveAccountArgs;
+use restore_wallet::RestoreWalletArgs;
 use set_wallet_representative::SetWalletRepresentativeArgs;
 
 pub(crate) mod add_private_key;
+pub(crate) mod backup_wallet;
 pub(crate) mod change_wallet_seed;
 pub(crate) mod clear_send_ids;
 pub(crate) mod create_account;
@@ -24,6 +27,7 @@ pub(crate) mod get_wallet_representative;
 pub(crate) mod import_keys;
 pub(crate) mod list_wallets;
 pub(crate) mod remove_account;
+pub(crate) mod restore_wallet;
 pub(crate) mod set_wallet_representative;
 
 #[derive(Subcommand)]
@@ -52,6 +56,10 @@ pub(crate) enum WalletSubcommands {
     ListWallets(ListWalletsArgs),
     /// Removes all send IDs from the wallets (dangerous: not intended for production use)
     ClearSendIds(ClearSendIdsArgs),
+    /// Writes an encrypted backup of a wallet to a file
+    BackupWallet(BackupWalletArgs),
+    /// Restores a wallet from an encrypted backup file
+    RestoreWallet(RestoreWalletArgs),
 }
 
 #[derive(Parser)]
@@ -79,6 +87,8 @@ impl WalletsCommand {
                 args.set_representative_wallet().await?
             }
             Some(WalletSubcommands::ClearSendIds(args)) => args.clear_send_ids().await?,
+            Some(WalletSubcommands::BackupWallet(args)) => args.backup_wallet().await?,
+            Some(WalletSubcommands::RestoreWallet(args)) => args.restore_wallet().await?,
             None => WalletsCommand::command().print_long_help()?,
         }
 
@@ -0,0 +1,46 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsban_node::wallets::{Wallets, WalletsExt};
+use rsban_store_lmdb::LmdbEnv;
+use std::{fs, path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RestoreWalletArgs {
+    /// The path of the backup file
+    #[arg(long)]
+    file: String,
+    /// The password used to encrypt the backup
+    #[arg(long)]
+    backup_password: String,
+    /// The password that protects the restored wallet
+    #[arg(long)]
+    wallet_password: String,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RestoreWalletArgs {
+    pub(crate) async fn restore_wallet(&self) -> Result<()> {
+        let backup = fs::read(PathBuf::from(&self.file))?;
+
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let mut wallets = Wallets::new_null_with_env(env, tokio::runtime::Handle::current());
+        wallets.initialize()?;
+        let wallets = Arc::new(wallets);
+
+        let wallet_id =
+            wallets.restore_wallet(&backup, &self.backup_password, &self.wallet_password)?;
+
+        println!("Wallet restored: {}", wallet_id);
+
+        Ok(())
+    }
+}
@@ -1,14 +1,14 @@
 use bitvec::prelude::BitArray;
 use num_traits::FromPrimitive;
 use rsban_core::{
-    utils::{BufferWriter, Deserialize, Serialize, Stream, StreamExt},
+    utils::{BufferReader, BufferWriter, Deserialize, MemoryStream, Serialize, Stream, StreamExt},
     Account, Block, BlockHash, BlockType, Frontier,
 };
 use serde::ser::SerializeStruct;
 use serde_derive::Serialize;
 use std::{collections::VecDeque, fmt::Display, mem::size_of};
 
-use super::{AscPullPayloadId, MessageVariant};
+use super::{AscPullPayloadId, CompressedPayload, MessageVariant};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum AscPullAckType {
@@ -65,6 +65,15 @@ impl AscPullAck {
                 }
                 AscPullAckType::Frontiers(frontiers)
             }
+            AscPullPayloadId::CompressedBlocks => {
+                let compressed = CompressedPayload::deserialize(stream).ok()?;
+                let uncompressed = compressed.decompress().ok()?;
+                let mut payload = BlocksAckPayload::default();
+                payload
+                    .deserialize(&mut BufferReader::new(&uncompressed))
+                    .ok()?;
+                AscPullAckType::Blocks(payload)
+            }
         };
 
         Some(AscPullAck { id, pull_type })
@@ -78,6 +87,28 @@ impl AscPullAck {
         }
     }
 
+    /// Compresses the blocks payload. Returns `None` if this isn't a blocks ack
+    /// or if compression wouldn't make the payload smaller.
+    pub fn compressed_blocks(&self) -> Option<CompressedPayload> {
+        let AscPullAckType::Blocks(blocks) = &self.pull_type else {
+            return None;
+        };
+        let mut stream = MemoryStream::new();
+        blocks.serialize(&mut stream);
+        CompressedPayload::compress_if_smaller(stream.as_bytes())
+    }
+
+    /// Serializes the ack with the given compressed blocks payload instead of the plain blocks
+    pub fn serialize_compressed(
+        &self,
+        compressed: &CompressedPayload,
+        writer: &mut dyn BufferWriter,
+    ) {
+        writer.write_u8_safe(AscPullPayloadId::CompressedBlocks as u8);
+        writer.write_u64_be_safe(self.id);
+        compressed.serialize(writer);
+    }
+
     fn serialize_pull_type(&self, writer: &mut dyn BufferWriter) {
         match &self.pull_type {
             AscPullAckType::Blocks(blocks) => blocks.serialize(writer),
@@ -262,6 +293,31 @@ mod tests {
         assert_deserializable(&original);
     }
 
+    #[test]
+    fn serialize_compressed_blocks() {
+        let original = AscPullAck {
+            id: 7,
+            pull_type: AscPullAckType::Blocks(BlocksAckPayload::new(VecDeque::from(vec![
+                Block::new_test_instance();
+                10
+            ]))),
+        };
+        let compressed = original.compressed_blocks().unwrap();
+        let mut stream = MemoryStream::new();
+        original.serialize_compressed(&compressed, &mut stream);
+
+        let bytes = stream.to_vec();
+        let deserialized = AscPullAck::deserialize(&mut BufferReader::new(&bytes)).unwrap();
+        assert_eq!(deserialized, original);
+    }
+
+    #[test]
+    fn dont_compress_account_info() {
+        assert!(AscPullAck::new_test_instance_account()
+            .compressed_blocks()
+            .is_none());
+    }
+
     #[test]
     fn serialize_account_info() {
         let original = Message::AscPullAck(AscPullAck {
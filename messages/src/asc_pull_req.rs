@@ -19,6 +19,8 @@ pub enum AscPullPayloadId {
     Blocks = 0x1,
     AccountInfo = 0x2,
     Frontiers = 0x3,
+    /// Blocks ack with a compressed payload. Only sent to peers that negotiated compression
+    CompressedBlocks = 0x4,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize)]
@@ -208,6 +210,7 @@ impl AscPullReq {
                 payload.deserialize(stream).ok()?;
                 AscPullReqType::Frontiers(payload)
             }
+            AscPullPayloadId::CompressedBlocks => return None,
         };
         Some(Self { id, req_type })
     }
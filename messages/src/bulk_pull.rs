@@ -14,12 +14,20 @@ pub struct BulkPull {
     pub end: BlockHash,
     pub count: u32,
     pub ascending: bool,
+    /// The client can decompress a compressed response
+    pub compression: bool,
 }
 
 impl BulkPull {
     pub const COUNT_PRESENT_FLAG: usize = 0;
     pub const ASCENDING_FLAG: usize = 1;
+    pub const COMPRESSION_FLAG: usize = 2;
     pub const EXTENDED_PARAMETERS_SIZE: usize = 8;
+    /// Maximum number of blocks in one frame of a compressed response
+    pub const MAX_COMPRESSED_BLOCKS: usize = 128;
+    /// First byte of a compressed response. It is no valid block type, so a client
+    /// can tell it apart from the plain response of a server that doesn't compress.
+    pub const COMPRESSED_RESPONSE_MARKER: u8 = 0xff;
 
     pub fn new_test_instance() -> BulkPull {
         Self {
@@ -27,6 +35,7 @@ impl BulkPull {
             end: 2.into(),
             count: 3,
             ascending: true,
+            compression: false,
         }
     }
 
@@ -64,12 +73,14 @@ impl BulkPull {
         };
 
         let ascending = extensions[BulkPull::ASCENDING_FLAG];
+        let compression = extensions[BulkPull::COMPRESSION_FLAG];
 
         Some(BulkPull {
             start,
             end,
             count,
             ascending,
+            compression,
         })
     }
 }
@@ -94,6 +105,7 @@ impl MessageVariant for BulkPull {
         let mut extensions = BitArray::default();
         extensions.set(BulkPull::COUNT_PRESENT_FLAG, self.count > 0);
         extensions.set(BulkPull::ASCENDING_FLAG, self.ascending);
+        extensions.set(BulkPull::COMPRESSION_FLAG, self.compression);
         extensions
     }
 }
@@ -118,4 +130,13 @@ mod tests {
         let message = Message::BulkPull(BulkPull::new_test_instance());
         assert_deserializable(&message);
     }
+
+    #[test]
+    fn bulk_pull_with_compression_flag() {
+        let message = Message::BulkPull(BulkPull {
+            compression: true,
+            ..BulkPull::new_test_instance()
+        });
+        assert_deserializable(&message);
+    }
 }
@@ -0,0 +1,161 @@
+use rsban_core::utils::{BufferWriter, Serialize, Stream};
+
+/// An LZ4 compressed payload. LZ4 is used, because it is fast enough to not
+/// slow down the bootstrap server, while still saving bandwidth on the
+/// repeated accounts and representatives of a chain.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompressedPayload {
+    uncompressed_len: u16,
+    data: Vec<u8>,
+}
+
+impl CompressedPayload {
+    /// uncompressed length + compressed length
+    pub const HEADER_SIZE: usize = 4;
+
+    pub fn compress(payload: &[u8]) -> Self {
+        assert!(payload.len() <= u16::MAX as usize);
+        Self {
+            uncompressed_len: payload.len() as u16,
+            data: lz4_flex::block::compress(payload),
+        }
+    }
+
+    pub fn decompress(&self) -> anyhow::Result<Vec<u8>> {
+        let mut output = vec![0; self.uncompressed_len as usize];
+        let len = lz4_flex::block::decompress_into(&self.data, &mut output)
+            .map_err(|e| anyhow!("could not decompress payload: {e}"))?;
+        if len != output.len() {
+            bail!("decompressed payload has wrong length");
+        }
+        Ok(output)
+    }
+
+    /// Length of the compressed data that follows the given header
+    pub fn data_len(header: &[u8; Self::HEADER_SIZE]) -> usize {
+        u16::from_be_bytes([header[2], header[3]]) as usize
+    }
+
+    pub fn compression(&self) -> PayloadCompression {
+        PayloadCompression {
+            uncompressed: self.uncompressed_len(),
+            compressed: self.serialized_size(),
+        }
+    }
+
+    pub fn uncompressed_len(&self) -> usize {
+        self.uncompressed_len as usize
+    }
+
+    pub fn serialized_size(&self) -> usize {
+        Self::HEADER_SIZE + self.data.len()
+    }
+
+    /// Only returns a compressed payload if it is smaller than the uncompressed one
+    pub fn compress_if_smaller(payload: &[u8]) -> Option<Self> {
+        let compressed = Self::compress(payload);
+        if compressed.serialized_size() < payload.len() {
+            Some(compressed)
+        } else {
+            None
+        }
+    }
+
+    pub fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let uncompressed_len = read_u16_be(stream)?;
+        let compressed_len = read_u16_be(stream)? as usize;
+        let mut data = vec![0; compressed_len];
+        stream.read_bytes(&mut data, compressed_len)?;
+        Ok(Self {
+            uncompressed_len,
+            data,
+        })
+    }
+}
+
+impl Serialize for CompressedPayload {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        writer.write_bytes_safe(&self.uncompressed_len.to_be_bytes());
+        writer.write_bytes_safe(&(self.data.len() as u16).to_be_bytes());
+        writer.write_bytes_safe(&self.data);
+    }
+}
+
+fn read_u16_be(stream: &mut dyn Stream) -> anyhow::Result<u16> {
+    let mut buffer = [0; 2];
+    stream.read_bytes(&mut buffer, 2)?;
+    Ok(u16::from_be_bytes(buffer))
+}
+
+/// Size of a payload before and after compression
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PayloadCompression {
+    pub uncompressed: usize,
+    pub compressed: usize,
+}
+
+impl PayloadCompression {
+    /// Compressed size in percent of the uncompressed size
+    pub fn ratio_percent(&self) -> i64 {
+        if self.uncompressed == 0 {
+            return 100;
+        }
+        (self.compressed * 100 / self.uncompressed) as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::utils::{BufferReader, MemoryStream};
+
+    #[test]
+    fn compress_and_decompress() {
+        let payload = [7u8; 1000];
+        let compressed = CompressedPayload::compress(&payload);
+        assert!(compressed.serialized_size() < payload.len());
+        assert_eq!(compressed.uncompressed_len(), payload.len());
+        assert_eq!(compressed.decompress().unwrap(), payload);
+    }
+
+    #[test]
+    fn serialize() {
+        let compressed = CompressedPayload::compress(&[1, 2, 3, 1, 2, 3, 1, 2, 3, 1, 2, 3]);
+        let mut stream = MemoryStream::new();
+        compressed.serialize(&mut stream);
+        assert_eq!(stream.bytes_written(), compressed.serialized_size());
+
+        let bytes = stream.to_vec();
+        assert_eq!(
+            CompressedPayload::data_len(bytes[..4].try_into().unwrap()),
+            bytes.len() - CompressedPayload::HEADER_SIZE
+        );
+        let mut reader = BufferReader::new(&bytes);
+        assert_eq!(
+            CompressedPayload::deserialize(&mut reader).unwrap(),
+            compressed
+        );
+    }
+
+    #[test]
+    fn dont_compress_random_data() {
+        let payload: Vec<u8> = (0..64).map(|_| rand::random()).collect();
+        assert!(CompressedPayload::compress_if_smaller(&payload).is_none());
+    }
+
+    #[test]
+    fn reject_wrong_uncompressed_length() {
+        let mut compressed = CompressedPayload::compress(&[7u8; 100]);
+        compressed.uncompressed_len = 50;
+        assert!(compressed.decompress().is_err());
+    }
+
+    #[test]
+    fn ratio() {
+        let compression = PayloadCompression {
+            uncompressed: 1000,
+            compressed: 250,
+        };
+        assert_eq!(compression.ratio_percent(), 25);
+    }
+}
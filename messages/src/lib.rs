@@ -50,6 +50,9 @@ pub use asc_pull_req::*;
 mod asc_pull_ack;
 pub use asc_pull_ack::*;
 
+mod compression;
+pub use compression::*;
+
 pub trait MessageVisitor {
     fn received(&mut self, message: &Message);
 }
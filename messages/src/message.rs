@@ -244,6 +244,8 @@ mod tests {
             query: Some(NodeIdHandshakeQuery { cookie: [1; 32] }),
             response: None,
             is_v2: true,
+            encryption: false,
+            compression: false,
         });
         assert_deserializable(&message);
     }
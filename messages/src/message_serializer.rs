@@ -1,4 +1,4 @@
-use super::{Message, MessageHeader, ProtocolInfo};
+use super::{Message, MessageHeader, MessageVariant, PayloadCompression, ProtocolInfo};
 use rsban_core::utils::MutStreamAdapter;
 
 #[derive(Clone)]
@@ -39,6 +39,38 @@ impl MessageSerializer {
         }
         &self.buffer[..MessageHeader::SERIALIZED_SIZE + payload_len]
     }
+
+    /// Serializes the message with a compressed payload, if the message type supports
+    /// compression and the compressed payload is smaller than the plain one.
+    pub fn serialize_compressed(
+        &mut self,
+        message: &Message,
+    ) -> (&[u8], Option<PayloadCompression>) {
+        let Message::AscPullAck(ack) = message else {
+            return (self.serialize(message), None);
+        };
+        let Some(compressed) = ack.compressed_blocks() else {
+            return (self.serialize(message), None);
+        };
+
+        let payload_len;
+        {
+            let mut payload_writer =
+                MutStreamAdapter::new(&mut self.buffer[MessageHeader::SERIALIZED_SIZE..]);
+            ack.serialize_compressed(&compressed, &mut payload_writer);
+            payload_len = payload_writer.bytes_written();
+
+            let mut header_writer =
+                MutStreamAdapter::new(&mut self.buffer[..MessageHeader::SERIALIZED_SIZE]);
+            let mut header = MessageHeader::new(message.message_type(), self.protocol);
+            header.extensions = ack.header_extensions(payload_len as u16);
+            header.serialize(&mut header_writer);
+        }
+        (
+            &self.buffer[..MessageHeader::SERIALIZED_SIZE + payload_len],
+            Some(compressed.compression()),
+        )
+    }
 }
 
 impl Default for MessageSerializer {
@@ -46,3 +78,44 @@ impl Default for MessageSerializer {
         Self::new(ProtocolInfo::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{deserialize_message, AscPullAck, AscPullAckType, BlocksAckPayload, Keepalive};
+    use rsban_core::Block;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn serialize_compressed_blocks_ack() {
+        let message = Message::AscPullAck(AscPullAck {
+            id: 1,
+            pull_type: AscPullAckType::Blocks(BlocksAckPayload::new(VecDeque::from(vec![
+                Block::new_test_instance();
+                10
+            ]))),
+        });
+        let mut serializer = MessageSerializer::default();
+        let plain_len = serializer.serialize(&message).len();
+
+        let (buffer, compression) = serializer.serialize_compressed(&message);
+
+        assert!(buffer.len() < plain_len);
+        let compression = compression.unwrap();
+        assert!(compression.compressed < compression.uncompressed);
+        let (_, deserialized) = deserialize_message(buffer).unwrap();
+        assert_eq!(deserialized, message);
+    }
+
+    #[test]
+    fn dont_compress_other_messages() {
+        let message = Message::Keepalive(Keepalive::default());
+        let mut serializer = MessageSerializer::default();
+        let plain = serializer.serialize(&message).to_vec();
+
+        let (buffer, compression) = serializer.serialize_compressed(&message);
+
+        assert_eq!(buffer, plain);
+        assert_eq!(compression, None);
+    }
+}
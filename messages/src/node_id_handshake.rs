@@ -168,12 +168,20 @@ pub struct NodeIdHandshake {
     pub query: Option<NodeIdHandshakeQuery>,
     pub response: Option<NodeIdHandshakeResponse>,
     pub is_v2: bool,
+    /// The sender supports encrypted transport. Its query cookie is an X25519 public key
+    /// and its response signs both ephemeral keys if the peer set this flag too.
+    /// Legacy nodes ignore this flag.
+    pub encryption: bool,
+    /// The sender can decompress compressed bootstrap responses. Legacy nodes ignore this flag.
+    pub compression: bool,
 }
 
 impl NodeIdHandshake {
     pub const QUERY_FLAG: usize = 0;
     pub const RESPONSE_FLAG: usize = 1;
     pub const V2_FLAG: usize = 2;
+    pub const ENCRYPTION_FLAG: usize = 3;
+    pub const COMPRESSION_FLAG: usize = 4;
 
     pub fn is_query(extensions: BitArray<u16>) -> bool {
         extensions[NodeIdHandshake::QUERY_FLAG]
@@ -187,6 +195,14 @@ impl NodeIdHandshake {
         extensions[NodeIdHandshake::V2_FLAG]
     }
 
+    pub fn has_encryption_flag(extensions: BitArray<u16>) -> bool {
+        extensions[NodeIdHandshake::ENCRYPTION_FLAG]
+    }
+
+    pub fn has_compression_flag(extensions: BitArray<u16>) -> bool {
+        extensions[NodeIdHandshake::COMPRESSION_FLAG]
+    }
+
     pub fn serialized_size(extensions: BitArray<u16>) -> usize {
         let mut size = 0;
         if Self::is_query(extensions) {
@@ -215,6 +231,8 @@ impl NodeIdHandshake {
             query,
             response,
             is_v2: Self::has_v2_flag(extensions),
+            encryption: Self::has_encryption_flag(extensions),
+            compression: Self::has_compression_flag(extensions),
         })
     }
 
@@ -224,6 +242,8 @@ impl NodeIdHandshake {
             query: Some(query),
             response: None,
             is_v2: true,
+            encryption: false,
+            compression: false,
         }
     }
 
@@ -237,6 +257,8 @@ impl NodeIdHandshake {
             query: None,
             response: Some(response),
             is_v2: false,
+            encryption: false,
+            compression: false,
         }
     }
 
@@ -253,6 +275,8 @@ impl NodeIdHandshake {
             query: None,
             response: Some(response),
             is_v2: true,
+            encryption: false,
+            compression: false,
         }
     }
 }
@@ -274,6 +298,8 @@ impl MessageVariant for NodeIdHandshake {
         extensions.set(NodeIdHandshake::QUERY_FLAG, self.query.is_some());
         extensions.set(NodeIdHandshake::RESPONSE_FLAG, self.response.is_some());
         extensions.set(Self::V2_FLAG, self.is_v2);
+        extensions.set(Self::ENCRYPTION_FLAG, self.encryption);
+        extensions.set(Self::COMPRESSION_FLAG, self.compression);
         extensions
     }
 }
@@ -309,6 +335,37 @@ mod tests {
         assert_deserializable(&message);
     }
 
+    #[test]
+    fn serialize_query_with_encryption_flag() {
+        let message = Message::NodeIdHandshake(NodeIdHandshake {
+            encryption: true,
+            ..NodeIdHandshake::new_test_query()
+        });
+        assert_deserializable(&message);
+    }
+
+    #[test]
+    fn serialize_query_with_compression_flag() {
+        let message = Message::NodeIdHandshake(NodeIdHandshake {
+            compression: true,
+            ..NodeIdHandshake::new_test_query()
+        });
+        assert_deserializable(&message);
+    }
+
+    #[test]
+    fn encryption_flag_doesnt_change_size() {
+        let query = NodeIdHandshake::new_test_query();
+        let with_flag = NodeIdHandshake {
+            encryption: true,
+            ..query.clone()
+        };
+        assert_eq!(
+            NodeIdHandshake::serialized_size(with_flag.header_extensions(0)),
+            NodeIdHandshake::serialized_size(query.header_extensions(0))
+        );
+    }
+
     #[test]
     fn serialize_response_v1() {
         let message = Message::NodeIdHandshake(NodeIdHandshake::new_test_response_v1());
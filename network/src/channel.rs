@@ -2,8 +2,9 @@ use crate::{
     bandwidth_limiter::BandwidthLimiter,
     utils::into_ipv6_socket_address,
     write_queue::{WriteQueue, WriteQueueReceiver},
-    AsyncBufferReader, ChannelDirection, ChannelId, ChannelInfo, DropPolicy, NetworkObserver,
-    NullNetworkObserver, TrafficType, WriteQueueAdapter,
+    AsyncBufferReader, ChannelDirection, ChannelEncryption, ChannelId, ChannelInfo, DropPolicy,
+    FrameDecoder, NetworkObserver, NullNetworkObserver, SessionKeys, TrafficType,
+    WriteQueueAdapter, FRAME_HEADER_SIZE,
 };
 use async_trait::async_trait;
 use rsban_core::utils::{TEST_ENDPOINT_1, TEST_ENDPOINT_2};
@@ -12,7 +13,7 @@ use rsban_nullable_tcp::TcpStream;
 use std::{
     fmt::Display,
     net::{Ipv6Addr, SocketAddrV6},
-    sync::{Arc, Weak},
+    sync::{Arc, OnceLock, Weak},
     time::Duration,
 };
 use tokio::{select, time::sleep};
@@ -28,6 +29,7 @@ pub struct Channel {
     clock: Arc<SteadyClock>,
     observer: Arc<dyn NetworkObserver>,
     cancel_token: CancellationToken,
+    encryption: Arc<OnceLock<ChannelEncryption>>,
 }
 
 impl Channel {
@@ -52,6 +54,7 @@ impl Channel {
             clock,
             observer,
             cancel_token,
+            encryption: Arc::new(OnceLock::new()),
         };
 
         (channel, receiver)
@@ -102,6 +105,7 @@ impl Channel {
             cancel_token.clone(),
         );
 
+        let encryption = channel.encryption.clone();
         let write_queue = Arc::downgrade(&channel.write_queue);
         info.set_write_queue(Box::new(WriteQueueAdapterImpl {
             queue: write_queue,
@@ -120,7 +124,14 @@ impl Channel {
 
                 if let Some((entry, _)) = res {
                     let mut written = 0;
-                    let buffer = &entry.buffer;
+                    let encrypted;
+                    let buffer = match encryption.get() {
+                        Some(encryption) if entry.encrypt => {
+                            encrypted = encryption.encode(&entry.buffer);
+                            &encrypted
+                        }
+                        _ => &entry.buffer,
+                    };
                     loop {
                         select! {
                             _ = cancel_token.cancelled() =>{
@@ -133,6 +144,7 @@ impl Channel {
                                     written += n;
                                     if written >= buffer.len() {
                                         observer.send_succeeded(written);
+                                        info.add_bytes_out(written);
                                         info.set_last_activity(clock.now());
                                         break;
                                     }
@@ -209,7 +221,11 @@ impl Channel {
 
         let result = self
             .write_queue
-            .insert(Arc::new(buffer.to_vec()), traffic_type) // TODO don't copy into vec. Split into fixed size packets
+            .insert(
+                Arc::new(buffer.to_vec()), // TODO don't copy into vec. Split into fixed size packets
+                traffic_type,
+                self.info.is_encrypted(),
+            )
             .await;
 
         if result.is_ok() {
@@ -236,24 +252,30 @@ impl Channel {
         }
 
         if drop_policy == DropPolicy::CanDrop && self.info.is_queue_full(traffic_type) {
+            self.info.add_queue_drop();
             return false;
         }
 
         let should_pass = self.limiter.should_pass(buffer.len(), traffic_type);
         if !should_pass && drop_policy == DropPolicy::CanDrop {
+            self.info.add_queue_drop();
             return false;
         } else {
             // TODO notify bandwidth limiter that we are sending it anyway
         }
 
-        let (inserted, write_error) = self
-            .write_queue
-            .try_insert(Arc::new(buffer.to_vec()), traffic_type); // TODO don't copy into vec. Split into fixed size packets
+        let (inserted, write_error) = self.write_queue.try_insert(
+            Arc::new(buffer.to_vec()), // TODO don't copy into vec. Split into fixed size packets
+            traffic_type,
+            self.info.is_encrypted(),
+        );
 
         if write_error {
             self.observer.send_failed();
             self.info.close();
             debug!(peer_addr = ?self.info.peer_addr(), channel_id = %self.channel_id(), mode = ?self.info.mode(), "Closing socket after write error");
+        } else if !inserted {
+            self.info.add_queue_drop();
         }
         inserted
     }
@@ -277,6 +299,42 @@ impl Channel {
             }
         }
     }
+
+    /// Encrypts all data that is sent or received from now on.
+    /// Data that is already queued for sending stays plaintext.
+    pub fn enable_encryption(&self, keys: SessionKeys) -> anyhow::Result<()> {
+        if self.encryption.set(ChannelEncryption::new(keys)).is_err() {
+            bail!("encryption already enabled");
+        }
+        self.info.set_encrypted();
+        Ok(())
+    }
+
+    async fn read_encrypted(
+        &self,
+        encryption: &ChannelEncryption,
+        buffer: &mut [u8],
+    ) -> anyhow::Result<()> {
+        let mut read = encryption.decoder.lock().unwrap().take(buffer);
+        while read < buffer.len() {
+            let mut header = [0; FRAME_HEADER_SIZE];
+            self.read_raw(&mut header, FRAME_HEADER_SIZE).await?;
+            let frame_size = FrameDecoder::frame_size(&header).inspect_err(|_| {
+                self.info.close();
+            })?;
+            let mut frame = vec![0; frame_size];
+            self.read_raw(&mut frame, frame_size).await?;
+
+            let mut decoder = encryption.decoder.lock().unwrap();
+            let plaintext = decoder
+                .decoder
+                .decode(&header, frame)
+                .inspect_err(|_| self.info.close())?;
+            decoder.push(plaintext);
+            read += decoder.take(&mut buffer[read..]);
+        }
+        Ok(())
+    }
 }
 
 impl Display for Channel {
@@ -291,13 +349,8 @@ impl Drop for Channel {
     }
 }
 
-#[async_trait]
-impl AsyncBufferReader for Channel {
-    async fn read(&self, buffer: &mut [u8], count: usize) -> anyhow::Result<()> {
-        if count > buffer.len() {
-            return Err(anyhow!("buffer is too small for read count"));
-        }
-
+impl Channel {
+    async fn read_raw(&self, buffer: &mut [u8], count: usize) -> anyhow::Result<()> {
         if self.info.is_closed() {
             return Err(anyhow!("Tried to read from a closed TcpStream"));
         }
@@ -326,6 +379,7 @@ impl AsyncBufferReader for Channel {
                             read += n;
                             if read >= count {
                                 self.observer.read_succeeded(count);
+                                self.info.add_bytes_in(count);
                                 self.info.set_last_activity(self.clock.now());
                                 return Ok(());
                             }
@@ -350,6 +404,20 @@ impl AsyncBufferReader for Channel {
     }
 }
 
+#[async_trait]
+impl AsyncBufferReader for Channel {
+    async fn read(&self, buffer: &mut [u8], count: usize) -> anyhow::Result<()> {
+        if count > buffer.len() {
+            return Err(anyhow!("buffer is too small for read count"));
+        }
+
+        match self.encryption.get() {
+            Some(encryption) => self.read_encrypted(encryption, &mut buffer[..count]).await,
+            None => self.read_raw(buffer, count).await,
+        }
+    }
+}
+
 pub struct ChannelReader(Arc<Channel>);
 
 impl ChannelReader {
@@ -0,0 +1,398 @@
+use blake2::{
+    digest::{consts::U16, Mac, Update, VariableOutput},
+    Blake2bMac, Blake2bVar,
+};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use curve25519_dalek::MontgomeryPoint;
+use rand::{thread_rng, Rng};
+use std::sync::Mutex;
+
+type Aes256Ctr = ctr::Ctr64BE<aes::Aes256>;
+type FrameMac = Blake2bMac<U16>;
+
+/// Whether node to node traffic should be encrypted after the handshake
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TransportEncryption {
+    /// Never offer encryption
+    #[default]
+    Disabled,
+    /// Encrypt if the peer supports it, otherwise fall back to plaintext
+    Preferred,
+    /// Only connect to peers which support encryption
+    Required,
+}
+
+impl TransportEncryption {
+    pub fn is_enabled(&self) -> bool {
+        *self != TransportEncryption::Disabled
+    }
+}
+
+/// Size of the plaintext length prefix of a frame
+pub const FRAME_HEADER_SIZE: usize = 4;
+/// Size of the authentication tag at the end of a frame
+pub const FRAME_MAC_SIZE: usize = 16;
+/// Larger buffers get split into multiple frames
+pub const MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+const KEY_DERIVATION_LABEL: &[u8] = b"rsban transport v1";
+
+/// X25519 key pair which is only used for a single handshake.
+/// The public key is sent as the node id handshake cookie, so that it gets
+/// signed with the node id key of the peer. That authenticates the session keys.
+pub struct EphemeralKey {
+    secret: [u8; 32],
+    public: [u8; 32],
+}
+
+impl EphemeralKey {
+    pub fn new_random() -> Self {
+        Self::from_secret(thread_rng().gen())
+    }
+
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        Self {
+            secret,
+            public: MontgomeryPoint::mul_base_clamped(secret).to_bytes(),
+        }
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public
+    }
+
+    /// Returns `None` if the peer sent a low order point
+    pub fn session_keys(&self, peer_public: &[u8; 32]) -> Option<SessionKeys> {
+        let shared = MontgomeryPoint(*peer_public)
+            .mul_clamped(self.secret)
+            .to_bytes();
+        if shared == [0; 32] {
+            return None;
+        }
+
+        Some(SessionKeys {
+            send: DirectionKeys::derive(&shared, &self.public, peer_public),
+            receive: DirectionKeys::derive(&shared, peer_public, &self.public),
+        })
+    }
+}
+
+/// Hash of both ephemeral keys. It gets signed instead of the plain cookie if both
+/// nodes support encryption, so that the node id signature also covers the
+/// signer's own ephemeral key and a man in the middle can't replace it.
+pub fn handshake_transcript(query_cookie: &[u8; 32], signer_key: &[u8; 32]) -> [u8; 32] {
+    let mut result = [0; 32];
+    let mut hasher = Blake2bVar::new(result.len()).unwrap();
+    hasher.update(KEY_DERIVATION_LABEL);
+    hasher.update(query_cookie);
+    hasher.update(signer_key);
+    hasher.finalize_variable(&mut result).unwrap();
+    result
+}
+
+pub struct SessionKeys {
+    send: DirectionKeys,
+    receive: DirectionKeys,
+}
+
+#[derive(Clone)]
+struct DirectionKeys {
+    cipher_key: [u8; 32],
+    mac_key: [u8; 32],
+}
+
+impl DirectionKeys {
+    fn derive(shared: &[u8; 32], sender: &[u8; 32], receiver: &[u8; 32]) -> Self {
+        let mut buffer = [0; 64];
+        let mut hasher = Blake2bVar::new(buffer.len()).unwrap();
+        hasher.update(KEY_DERIVATION_LABEL);
+        hasher.update(shared);
+        hasher.update(sender);
+        hasher.update(receiver);
+        hasher.finalize_variable(&mut buffer).unwrap();
+        Self {
+            cipher_key: buffer[..32].try_into().unwrap(),
+            mac_key: buffer[32..].try_into().unwrap(),
+        }
+    }
+
+    fn apply_keystream(&self, counter: u64, data: &mut [u8]) {
+        let mut iv = [0; 16];
+        iv[..8].copy_from_slice(&counter.to_be_bytes());
+        let mut cipher = Aes256Ctr::new(&self.cipher_key.into(), &iv.into());
+        cipher.apply_keystream(data);
+    }
+
+    fn mac(&self, counter: u64, header_and_ciphertext: &[u8]) -> FrameMac {
+        let mut mac = <FrameMac as Mac>::new_from_slice(&self.mac_key).unwrap();
+        Mac::update(&mut mac, &counter.to_be_bytes());
+        Mac::update(&mut mac, header_and_ciphertext);
+        mac
+    }
+}
+
+/// Encrypts outgoing data into frames:
+/// `[u32 BE plaintext length][AES-256-CTR ciphertext][16 byte blake2b MAC]`.
+/// The frame counter is used as IV and is part of the MAC, so frames can't be
+/// replayed or reordered.
+pub struct FrameEncoder {
+    keys: DirectionKeys,
+    counter: u64,
+}
+
+impl FrameEncoder {
+    pub fn encode(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let frame_count = plaintext.len().div_ceil(MAX_FRAME_SIZE);
+        let mut result = Vec::with_capacity(
+            plaintext.len() + frame_count * (FRAME_HEADER_SIZE + FRAME_MAC_SIZE),
+        );
+        for chunk in plaintext.chunks(MAX_FRAME_SIZE) {
+            self.encode_frame(chunk, &mut result);
+        }
+        result
+    }
+
+    fn encode_frame(&mut self, chunk: &[u8], output: &mut Vec<u8>) {
+        let start = output.len();
+        output.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+        output.extend_from_slice(chunk);
+        self.keys
+            .apply_keystream(self.counter, &mut output[start + FRAME_HEADER_SIZE..]);
+        let mac = self.keys.mac(self.counter, &output[start..]).finalize();
+        output.extend_from_slice(&mac.into_bytes());
+        self.counter += 1;
+    }
+}
+
+pub struct FrameDecoder {
+    keys: DirectionKeys,
+    counter: u64,
+}
+
+impl FrameDecoder {
+    /// Returns the number of bytes that follow the header (ciphertext + MAC)
+    pub fn frame_size(header: &[u8; FRAME_HEADER_SIZE]) -> anyhow::Result<usize> {
+        let len = u32::from_be_bytes(*header) as usize;
+        if len > MAX_FRAME_SIZE {
+            bail!("frame too large: {}", len);
+        }
+        Ok(len + FRAME_MAC_SIZE)
+    }
+
+    /// Verifies and decrypts a frame. `frame` contains the ciphertext and the MAC
+    pub fn decode(
+        &mut self,
+        header: &[u8; FRAME_HEADER_SIZE],
+        mut frame: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>> {
+        if frame.len() != Self::frame_size(header)? {
+            bail!("invalid frame size");
+        }
+        let ciphertext_len = frame.len() - FRAME_MAC_SIZE;
+        let mut mac = self.keys.mac(self.counter, header);
+        Mac::update(&mut mac, &frame[..ciphertext_len]);
+        mac.verify_slice(&frame[ciphertext_len..])
+            .map_err(|_| anyhow!("invalid frame MAC"))?;
+
+        frame.truncate(ciphertext_len);
+        self.keys.apply_keystream(self.counter, &mut frame);
+        self.counter += 1;
+        Ok(frame)
+    }
+}
+
+/// Encryption state of a channel
+pub struct ChannelEncryption {
+    pub(crate) encoder: Mutex<FrameEncoder>,
+    pub(crate) decoder: Mutex<DecoderState>,
+}
+
+impl ChannelEncryption {
+    pub fn new(keys: SessionKeys) -> Self {
+        Self {
+            encoder: Mutex::new(FrameEncoder {
+                keys: keys.send,
+                counter: 0,
+            }),
+            decoder: Mutex::new(DecoderState {
+                decoder: FrameDecoder {
+                    keys: keys.receive,
+                    counter: 0,
+                },
+                plaintext: Vec::new(),
+                position: 0,
+            }),
+        }
+    }
+
+    pub fn encode(&self, plaintext: &[u8]) -> Vec<u8> {
+        self.encoder.lock().unwrap().encode(plaintext)
+    }
+}
+
+/// Decrypted data which wasn't consumed by a read yet
+pub(crate) struct DecoderState {
+    pub decoder: FrameDecoder,
+    plaintext: Vec<u8>,
+    position: usize,
+}
+
+impl DecoderState {
+    /// Copies buffered plaintext into `buffer` and returns the number of bytes copied
+    pub fn take(&mut self, buffer: &mut [u8]) -> usize {
+        let available = &self.plaintext[self.position..];
+        let count = available.len().min(buffer.len());
+        buffer[..count].copy_from_slice(&available[..count]);
+        self.position += count;
+        count
+    }
+
+    pub fn push(&mut self, plaintext: Vec<u8>) {
+        if self.position >= self.plaintext.len() {
+            self.plaintext = plaintext;
+            self.position = 0;
+        } else {
+            self.plaintext.drain(..self.position);
+            self.position = 0;
+            self.plaintext.extend_from_slice(&plaintext);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_pairs() -> (EphemeralKey, EphemeralKey) {
+        (
+            EphemeralKey::from_secret([1; 32]),
+            EphemeralKey::from_secret([2; 32]),
+        )
+    }
+
+    fn decode_all(decoder: &mut FrameDecoder, mut data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut result = Vec::new();
+        while !data.is_empty() {
+            let header: [u8; FRAME_HEADER_SIZE] = data[..FRAME_HEADER_SIZE].try_into()?;
+            let size = FrameDecoder::frame_size(&header)?;
+            let frame = data[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + size].to_vec();
+            result.extend(decoder.decode(&header, frame)?);
+            data = &data[FRAME_HEADER_SIZE + size..];
+        }
+        Ok(result)
+    }
+
+    #[test]
+    fn both_sides_derive_matching_keys() {
+        let (a, b) = key_pairs();
+        let keys_a = a.session_keys(&b.public_key()).unwrap();
+        let keys_b = b.session_keys(&a.public_key()).unwrap();
+        assert_eq!(keys_a.send.cipher_key, keys_b.receive.cipher_key);
+        assert_eq!(keys_a.receive.mac_key, keys_b.send.mac_key);
+        assert_ne!(keys_a.send.cipher_key, keys_a.receive.cipher_key);
+    }
+
+    #[test]
+    fn reject_low_order_point() {
+        let (a, _) = key_pairs();
+        assert!(a.session_keys(&[0; 32]).is_none());
+    }
+
+    #[test]
+    fn round_trip() {
+        let (a, b) = key_pairs();
+        let mut encoder = ChannelEncryption::new(a.session_keys(&b.public_key()).unwrap())
+            .encoder
+            .into_inner()
+            .unwrap();
+        let mut decoder = ChannelEncryption::new(b.session_keys(&a.public_key()).unwrap())
+            .decoder
+            .into_inner()
+            .unwrap()
+            .decoder;
+
+        let first = encoder.encode(b"hello");
+        let second = encoder.encode(b"world");
+        assert_ne!(&first[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + 5], b"hello");
+        assert_eq!(decode_all(&mut decoder, &first).unwrap(), b"hello");
+        assert_eq!(decode_all(&mut decoder, &second).unwrap(), b"world");
+    }
+
+    #[test]
+    fn split_large_buffers() {
+        let (a, b) = key_pairs();
+        let keys = a.session_keys(&b.public_key()).unwrap();
+        let mut encoder = FrameEncoder {
+            keys: keys.send.clone(),
+            counter: 0,
+        };
+        let mut decoder = FrameDecoder {
+            keys: keys.send,
+            counter: 0,
+        };
+        let plaintext = vec![7; MAX_FRAME_SIZE + 10];
+        let encoded = encoder.encode(&plaintext);
+        assert_eq!(
+            encoded.len(),
+            plaintext.len() + 2 * (FRAME_HEADER_SIZE + FRAME_MAC_SIZE)
+        );
+        assert_eq!(decode_all(&mut decoder, &encoded).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn reject_tampered_frame() {
+        let (a, b) = key_pairs();
+        let keys = a.session_keys(&b.public_key()).unwrap();
+        let mut encoder = FrameEncoder {
+            keys: keys.send.clone(),
+            counter: 0,
+        };
+        let mut decoder = FrameDecoder {
+            keys: keys.send,
+            counter: 0,
+        };
+        let mut encoded = encoder.encode(b"hello");
+        encoded[FRAME_HEADER_SIZE] ^= 1;
+        assert!(decode_all(&mut decoder, &encoded).is_err());
+    }
+
+    #[test]
+    fn reject_replayed_frame() {
+        let (a, b) = key_pairs();
+        let keys = a.session_keys(&b.public_key()).unwrap();
+        let mut encoder = FrameEncoder {
+            keys: keys.send.clone(),
+            counter: 0,
+        };
+        let mut decoder = FrameDecoder {
+            keys: keys.send,
+            counter: 0,
+        };
+        let encoded = encoder.encode(b"hello");
+        decode_all(&mut decoder, &encoded).unwrap();
+        assert!(decode_all(&mut decoder, &encoded).is_err());
+    }
+
+    #[test]
+    fn reject_oversized_frame() {
+        let header = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes();
+        assert!(FrameDecoder::frame_size(&header).is_err());
+    }
+
+    #[test]
+    fn buffered_plaintext() {
+        let (a, b) = key_pairs();
+        let mut state = ChannelEncryption::new(a.session_keys(&b.public_key()).unwrap())
+            .decoder
+            .into_inner()
+            .unwrap();
+        state.push(vec![1, 2, 3]);
+        let mut buffer = [0; 2];
+        assert_eq!(state.take(&mut buffer), 2);
+        assert_eq!(buffer, [1, 2]);
+        state.push(vec![4]);
+        assert_eq!(state.take(&mut buffer), 2);
+        assert_eq!(buffer, [3, 4]);
+        assert_eq!(state.take(&mut buffer), 0);
+    }
+}
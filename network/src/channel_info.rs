@@ -5,6 +5,7 @@ use rsban_core::{
 };
 use rsban_nullable_clock::Timestamp;
 use std::{
+    collections::BTreeMap,
     net::{Ipv6Addr, SocketAddrV6},
     sync::{
         atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicU8, Ordering},
@@ -45,7 +46,24 @@ pub struct ChannelInfo {
     /// error codes as the OS may have already completed the async operation.
     closed: AtomicBool,
 
+    /// Set after the handshake negotiated an encrypted transport
+    encrypted: AtomicBool,
+
+    /// Set after the handshake showed that both nodes support compressed bootstrap responses
+    compression: AtomicBool,
+
     socket_type: AtomicU8,
+
+    /// Bytes that were read from or written to the socket. This includes
+    /// handshake and bootstrap data, which isn't counted per message type
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+
+    /// Messages that were dropped because the write queue was full or
+    /// the bandwidth limit was reached
+    queue_drops: AtomicU64,
+
+    message_traffic: Mutex<BTreeMap<&'static str, MessageTraffic>>,
 }
 
 impl ChannelInfo {
@@ -70,6 +88,12 @@ impl ChannelInfo {
             timed_out: AtomicBool::new(false),
             socket_type: AtomicU8::new(ChannelMode::Undefined as u8),
             closed: AtomicBool::new(false),
+            encrypted: AtomicBool::new(false),
+            compression: AtomicBool::new(false),
+            bytes_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+            queue_drops: AtomicU64::new(0),
+            message_traffic: Mutex::new(BTreeMap::new()),
             data: Mutex::new(ChannelInfoData {
                 node_id: None,
                 write_queue: None,
@@ -184,6 +208,22 @@ impl ChannelInfo {
         self.closed.load(Ordering::Relaxed)
     }
 
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_encrypted(&self) {
+        self.encrypted.store(true, Ordering::Relaxed);
+    }
+
+    pub fn supports_compression(&self) -> bool {
+        self.compression.load(Ordering::Relaxed)
+    }
+
+    pub fn set_supports_compression(&self) {
+        self.compression.store(true, Ordering::Relaxed);
+    }
+
     pub fn close(&self) {
         self.closed.store(true, Ordering::Relaxed);
         self.set_timeout(Duration::ZERO);
@@ -218,6 +258,41 @@ impl ChannelInfo {
             .store(now.into(), Ordering::Relaxed);
     }
 
+    pub(crate) fn add_bytes_in(&self, count: usize) {
+        self.bytes_in.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_bytes_out(&self, count: usize) {
+        self.bytes_out.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_queue_drop(&self) {
+        self.queue_drops.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn add_message_in(&self, message_type: &'static str, size: usize) {
+        let mut guard = self.message_traffic.lock().unwrap();
+        let traffic = guard.entry(message_type).or_default();
+        traffic.messages_in += 1;
+        traffic.bytes_in += size as u64;
+    }
+
+    pub fn add_message_out(&self, message_type: &'static str, size: usize) {
+        let mut guard = self.message_traffic.lock().unwrap();
+        let traffic = guard.entry(message_type).or_default();
+        traffic.messages_out += 1;
+        traffic.bytes_out += size as u64;
+    }
+
+    pub fn traffic(&self) -> ChannelTraffic {
+        ChannelTraffic {
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            queue_drops: self.queue_drops.load(Ordering::Relaxed),
+            by_message_type: self.message_traffic.lock().unwrap().clone(),
+        }
+    }
+
     pub fn is_queue_full(&self, traffic_type: TrafficType) -> bool {
         let guard = self.data.lock().unwrap();
         match &guard.write_queue {
@@ -227,6 +302,33 @@ impl ChannelInfo {
     }
 }
 
+/// Messages and bytes of a single message type that went through a channel
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct MessageTraffic {
+    pub messages_in: u64,
+    pub bytes_in: u64,
+    pub messages_out: u64,
+    pub bytes_out: u64,
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct ChannelTraffic {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub queue_drops: u64,
+    pub by_message_type: BTreeMap<&'static str, MessageTraffic>,
+}
+
+impl ChannelTraffic {
+    pub fn messages_in(&self) -> u64 {
+        self.by_message_type.values().map(|i| i.messages_in).sum()
+    }
+
+    pub fn messages_out(&self) -> u64 {
+        self.by_message_type.values().map(|i| i.messages_out).sum()
+    }
+}
+
 struct ChannelInfoData {
     node_id: Option<NodeId>,
     peering_addr: Option<SocketAddrV6>,
@@ -237,3 +339,50 @@ pub(crate) trait WriteQueueAdapter: Send + Sync {
     fn is_queue_full(&self, traffic_type: TrafficType) -> bool;
     fn close(&self);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_traffic_initially() {
+        let info = ChannelInfo::new_test_instance();
+        assert_eq!(info.traffic(), ChannelTraffic::default());
+    }
+
+    #[test]
+    fn count_traffic_per_message_type() {
+        let info = ChannelInfo::new_test_instance();
+        info.add_message_in("publish", 200);
+        info.add_message_in("publish", 210);
+        info.add_message_out("confirm_ack", 150);
+        info.add_bytes_in(410);
+        info.add_bytes_out(150);
+        info.add_queue_drop();
+
+        let traffic = info.traffic();
+        assert_eq!(traffic.bytes_in, 410);
+        assert_eq!(traffic.bytes_out, 150);
+        assert_eq!(traffic.queue_drops, 1);
+        assert_eq!(traffic.messages_in(), 2);
+        assert_eq!(traffic.messages_out(), 1);
+        assert_eq!(
+            traffic.by_message_type.get("publish"),
+            Some(&MessageTraffic {
+                messages_in: 2,
+                bytes_in: 410,
+                messages_out: 0,
+                bytes_out: 0
+            })
+        );
+        assert_eq!(
+            traffic.by_message_type.get("confirm_ack"),
+            Some(&MessageTraffic {
+                messages_in: 0,
+                bytes_in: 0,
+                messages_out: 1,
+                bytes_out: 150
+            })
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::utils::{into_ipv6_address, is_ipv4_mapped};
+use std::{
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// An IP address range. IPv4 ranges are stored as IPv4 mapped IPv6 ranges.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct IpSubnet {
+    network: Ipv6Addr,
+    prefix_len: u8,
+}
+
+impl IpSubnet {
+    pub fn new(ip: Ipv6Addr, prefix_len: u8) -> Self {
+        let prefix_len = prefix_len.min(128);
+        Self {
+            network: Ipv6Addr::from_bits(ip.to_bits() & Self::mask(prefix_len)),
+            prefix_len,
+        }
+    }
+
+    /// A range which contains a single address
+    pub fn host(ip: Ipv6Addr) -> Self {
+        Self::new(ip, 128)
+    }
+
+    pub fn network(&self) -> Ipv6Addr {
+        self.network
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    pub fn contains(&self, ip: &Ipv6Addr) -> bool {
+        ip.to_bits() & Self::mask(self.prefix_len) == self.network.to_bits()
+    }
+
+    fn mask(prefix_len: u8) -> u128 {
+        if prefix_len == 0 {
+            0
+        } else {
+            u128::MAX << (128 - prefix_len as u32)
+        }
+    }
+}
+
+impl From<Ipv6Addr> for IpSubnet {
+    fn from(value: Ipv6Addr) -> Self {
+        Self::host(value)
+    }
+}
+
+impl FromStr for IpSubnet {
+    type Err = anyhow::Error;
+
+    /// Parses addresses like "10.0.0.1", "10.0.0.0/8", "::ffff:10.0.0.1" or "2001:db8::/32"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ip, prefix_len) = match s.split_once('/') {
+            Some((ip, prefix_len)) => (ip, Some(prefix_len.parse::<u8>()?)),
+            None => (s, None),
+        };
+        let ip: IpAddr = ip.parse()?;
+        let prefix_len = match (ip, prefix_len) {
+            (IpAddr::V4(_), Some(len)) if len <= 32 => len + 96,
+            (IpAddr::V6(_), Some(len)) if len <= 128 => len,
+            (_, Some(_)) => bail!("invalid prefix length"),
+            (_, None) => 128,
+        };
+        Ok(Self::new(into_ipv6_address(ip), prefix_len))
+    }
+}
+
+impl Display for IpSubnet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if is_ipv4_mapped(&self.network) && self.prefix_len >= 96 {
+            let ip = Ipv4Addr::from_bits(self.network.to_bits() as u32);
+            if self.prefix_len == 128 {
+                write!(f, "{}", ip)
+            } else {
+                write!(f, "{}/{}", ip, self.prefix_len - 96)
+            }
+        } else if self.prefix_len == 128 {
+            write!(f, "{}", self.network)
+        } else {
+            write!(f, "{}/{}", self.network, self.prefix_len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ipv4_address() {
+        let subnet: IpSubnet = "10.0.0.1".parse().unwrap();
+        assert_eq!(subnet.prefix_len(), 128);
+        assert!(subnet.contains(&Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped()));
+        assert!(!subnet.contains(&Ipv4Addr::new(10, 0, 0, 2).to_ipv6_mapped()));
+        assert_eq!(subnet.to_string(), "10.0.0.1");
+    }
+
+    #[test]
+    fn parse_ipv4_subnet() {
+        let subnet: IpSubnet = "10.1.2.3/16".parse().unwrap();
+        assert_eq!(subnet.prefix_len(), 112);
+        assert!(subnet.contains(&Ipv4Addr::new(10, 1, 200, 1).to_ipv6_mapped()));
+        assert!(!subnet.contains(&Ipv4Addr::new(10, 2, 0, 1).to_ipv6_mapped()));
+        assert_eq!(subnet.to_string(), "10.1.0.0/16");
+    }
+
+    #[test]
+    fn parse_ipv6_subnet() {
+        let subnet: IpSubnet = "2001:db8::1/32".parse().unwrap();
+        assert!(subnet.contains(&"2001:db8:1::5".parse().unwrap()));
+        assert!(!subnet.contains(&"2001:db9::1".parse().unwrap()));
+        assert_eq!(subnet.to_string(), "2001:db8::/32");
+    }
+
+    #[test]
+    fn parse_ipv4_mapped_address() {
+        let subnet: IpSubnet = "::ffff:10.0.0.1".parse().unwrap();
+        assert_eq!(subnet, "10.0.0.1".parse().unwrap());
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!("10.0.0.1/33".parse::<IpSubnet>().is_err());
+        assert!("::1/129".parse::<IpSubnet>().is_err());
+        assert!("foo".parse::<IpSubnet>().is_err());
+    }
+
+    #[test]
+    fn zero_prefix_contains_everything() {
+        let subnet = IpSubnet::new(Ipv6Addr::LOCALHOST, 0);
+        assert!(subnet.contains(&"2001:db8::1".parse().unwrap()));
+    }
+}
@@ -1,13 +1,17 @@
 pub mod attempt_container;
 pub mod bandwidth_limiter;
 mod channel;
+mod channel_encryption;
 mod channel_info;
 mod dead_channel_cleanup;
+mod ip_subnet;
 mod network;
 mod network_info;
 mod network_observer;
+mod node_id_allowlist;
 mod peer_connector;
 pub mod peer_exclusion;
+mod peer_reputation;
 mod response_server_spawner;
 mod tcp_listener;
 pub mod token_bucket;
@@ -16,13 +20,17 @@ pub mod write_queue;
 
 use async_trait::async_trait;
 pub use channel::*;
+pub use channel_encryption::*;
 pub use channel_info::*;
 pub use dead_channel_cleanup::*;
+pub use ip_subnet::*;
 pub use network::*;
 pub use network_info::*;
 pub use network_observer::*;
+pub use node_id_allowlist::*;
 use num_derive::FromPrimitive;
 pub use peer_connector::*;
+pub use peer_reputation::*;
 pub use response_server_spawner::*;
 use std::fmt::{Debug, Display};
 pub use tcp_listener::*;
@@ -69,6 +77,15 @@ pub enum ChannelDirection {
     Outbound,
 }
 
+impl ChannelDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelDirection::Inbound => "inbound",
+            ChannelDirection::Outbound => "outbound",
+        }
+    }
+}
+
 #[derive(FromPrimitive, Copy, Clone, Debug)]
 pub enum TrafficType {
     Generic,
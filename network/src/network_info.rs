@@ -1,20 +1,23 @@
 use super::ChannelDirection;
 use crate::{
     attempt_container::AttemptContainer,
-    peer_exclusion::PeerExclusion,
+    peer_exclusion::{ExcludedPeer, PeerExclusion},
     utils::{is_ipv4_mapped, map_address_to_subnetwork, reserved_address},
-    ChannelId, ChannelInfo, ChannelMode, TrafficType,
+    ChannelId, ChannelInfo, ChannelMode, IpSubnet, NodeIdAllowlist, PeerReputations, TrafficType,
+    TransportEncryption,
 };
 use rand::{seq::SliceRandom, thread_rng};
-use rsban_core::{utils::ContainerInfo, Networks, NodeId};
+use rsban_core::{utils::ContainerInfo, Networks, NodeId, PeerReputation};
 use rsban_nullable_clock::Timestamp;
+use rsban_nullable_tcp::Socks5Proxy;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::{Ipv6Addr, SocketAddrV6},
-    sync::Arc,
+    path::PathBuf,
+    sync::{Arc, Mutex},
     time::Duration,
 };
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 pub struct NetworkConfig {
     pub max_inbound_connections: usize,
@@ -33,6 +36,21 @@ pub struct NetworkConfig {
     pub disable_max_peers_per_subnetwork: bool, // For testing only
     pub disable_network: bool,
     pub listening_port: u16,
+
+    /// Private network mode: only nodes with these ids can become realtime peers.
+    /// The mode is enabled if node ids or an allowlist file are configured.
+    pub allowed_node_ids: Vec<NodeId>,
+    /// File with one allowed node id per line. It is reloaded when it changes
+    pub allowed_node_ids_file: Option<PathBuf>,
+
+    /// Encrypt node to node traffic if the peer supports it
+    pub transport_encryption: TransportEncryption,
+
+    /// Compress bootstrap responses if the peer supports it
+    pub bootstrap_compression: bool,
+
+    /// All outbound connections are opened through this proxy
+    pub socks5_proxy: Option<Socks5Proxy>,
 }
 
 impl NetworkConfig {
@@ -61,6 +79,11 @@ impl NetworkConfig {
                 Networks::BananoTestNetwork => 17019,
                 _ => 7072,
             },
+            allowed_node_ids: Vec::new(),
+            allowed_node_ids_file: None,
+            transport_encryption: TransportEncryption::Disabled,
+            bootstrap_compression: false,
+            socks5_proxy: None,
         }
     }
 }
@@ -75,6 +98,8 @@ pub enum NetworkError {
     InvalidIp,
     /// We are already connected to that peer and we tried to connect a second time
     DuplicateConnection,
+    /// Private network mode is enabled, but no node is allowed to connect
+    NodeIdNotAllowed,
 }
 
 pub struct NetworkInfo {
@@ -85,10 +110,31 @@ pub struct NetworkInfo {
     attempts: AttemptContainer,
     network_config: NetworkConfig,
     excluded_peers: PeerExclusion,
+    /// Channels to these peers are never purged
+    pinned_peers: HashSet<SocketAddrV6>,
+    /// Only set in private network mode
+    allowlist: Option<NodeIdAllowlist>,
+    reputations: Mutex<PeerReputations>,
 }
 
 impl NetworkInfo {
     pub fn new(network_config: NetworkConfig) -> Self {
+        let allowlist = if network_config.allowed_node_ids.is_empty()
+            && network_config.allowed_node_ids_file.is_none()
+        {
+            None
+        } else {
+            let allowlist = NodeIdAllowlist::new(
+                network_config.allowed_node_ids.iter().cloned(),
+                network_config.allowed_node_ids_file.clone(),
+            );
+            info!(
+                "Private network mode enabled. {} node ids are allowed",
+                allowlist.len()
+            );
+            Some(allowlist)
+        };
+
         Self {
             next_channel_id: 1,
             channels: HashMap::new(),
@@ -97,6 +143,9 @@ impl NetworkInfo {
             attempts: Default::default(),
             network_config,
             excluded_peers: PeerExclusion::new(),
+            pinned_peers: HashSet::new(),
+            allowlist,
+            reputations: Mutex::new(PeerReputations::new()),
         }
     }
 
@@ -132,6 +181,130 @@ impl NetworkInfo {
         self.excluded_peers.is_excluded(peer_addr, now)
     }
 
+    pub fn excluded_peers(&self, now: Timestamp) -> Vec<ExcludedPeer> {
+        self.excluded_peers.excluded_peers(now)
+    }
+
+    /// Bans the subnet until `until` and closes all channels from within it.
+    /// Returns the number of closed channels.
+    pub fn ban(&mut self, subnet: IpSubnet, until: Timestamp) -> usize {
+        self.excluded_peers.ban(subnet, until);
+        let mut closed = 0;
+        for channel in self.channels.values() {
+            if channel.is_alive() && subnet.contains(channel.peer_addr().ip()) {
+                warn!(peer_addr = ?channel.peer_addr(), %subnet, "Closing channel of banned peer");
+                channel.close();
+                closed += 1;
+            }
+        }
+        closed
+    }
+
+    /// Returns the bans that expired since the last call
+    pub fn take_expired_bans(&mut self, now: Timestamp) -> Vec<IpSubnet> {
+        self.excluded_peers.take_expired_bans(now)
+    }
+
+    pub fn unban(&mut self, subnet: &IpSubnet) -> bool {
+        // The persisted exclusions of the forgiven peers have to be cleared
+        self.reputations
+            .lock()
+            .unwrap()
+            .mark_changed(|ip| subnet.contains(ip));
+        self.excluded_peers.unban(subnet)
+    }
+
+    /// Channels with this peer or peering address are never purged
+    pub fn pin(&mut self, peer_addr: SocketAddrV6) {
+        self.pinned_peers.insert(peer_addr);
+    }
+
+    pub fn unpin(&mut self, peer_addr: &SocketAddrV6) -> bool {
+        self.pinned_peers.remove(peer_addr)
+    }
+
+    pub fn pinned_peers(&self) -> Vec<SocketAddrV6> {
+        let mut result: Vec<_> = self.pinned_peers.iter().cloned().collect();
+        result.sort();
+        result
+    }
+
+    fn is_pinned(&self, channel: &ChannelInfo) -> bool {
+        self.pinned_peers.contains(&channel.peer_addr())
+            || channel
+                .peering_addr()
+                .map(|addr| self.pinned_peers.contains(&addr))
+                .unwrap_or_default()
+    }
+
+    pub fn transport_encryption(&self) -> TransportEncryption {
+        self.network_config.transport_encryption
+    }
+
+    pub fn bootstrap_compression(&self) -> bool {
+        self.network_config.bootstrap_compression
+    }
+
+    pub fn socks5_proxy(&self) -> Option<&Socks5Proxy> {
+        self.network_config.socks5_proxy.as_ref()
+    }
+
+    pub fn is_private_network(&self) -> bool {
+        self.allowlist.is_some()
+    }
+
+    /// Always true if private network mode is disabled
+    pub fn is_node_id_allowed(&self, node_id: &NodeId) -> bool {
+        match &self.allowlist {
+            Some(allowlist) => allowlist.contains(node_id),
+            None => true,
+        }
+    }
+
+    /// Reloads the allowlist file if it changed and closes channels to nodes
+    /// which are not allowed anymore
+    fn reload_allowlist(&mut self) {
+        let Some(allowlist) = &mut self.allowlist else {
+            return;
+        };
+        if !allowlist.reload_if_changed() {
+            return;
+        }
+        for channel in self.channels.values() {
+            if let Some(node_id) = channel.node_id() {
+                if channel.is_alive() && !allowlist.contains(&node_id) {
+                    info!(%node_id, peer_addr = ?channel.peer_addr(), "Closing channel of node which isn't allowed anymore");
+                    channel.close();
+                }
+            }
+        }
+    }
+
+    /// Closes the channel. Returns false if there is no such channel
+    pub fn drop_channel(&self, channel_id: ChannelId) -> bool {
+        match self.channels.get(&channel_id) {
+            Some(channel) if channel.is_alive() => {
+                debug!(%channel_id, peer_addr = ?channel.peer_addr(), "Dropping channel");
+                channel.close();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Closes all channels to the given node. Returns the number of closed channels
+    pub fn drop_node(&self, node_id: &NodeId) -> usize {
+        let mut closed = 0;
+        for channel in self.channels.values() {
+            if channel.is_alive() && channel.node_id() == Some(*node_id) {
+                debug!(channel_id = %channel.channel_id(), peer_addr = ?channel.peer_addr(), "Dropping channel");
+                channel.close();
+                closed += 1;
+            }
+        }
+        closed
+    }
+
     pub fn add_outbound_attempt(
         &mut self,
         peer: SocketAddrV6,
@@ -284,6 +457,7 @@ impl NetworkInfo {
     /// Returns channel IDs of removed channels
     pub fn purge(&mut self, now: Timestamp, cutoff_period: Duration) -> Vec<Arc<ChannelInfo>> {
         self.close_idle_channels(now, cutoff_period);
+        self.reload_allowlist();
 
         // Check if any tcp channels belonging to old protocol versions which may still be alive due to async operations
         self.close_old_protocol_versions(self.network_config.min_protocol_version);
@@ -298,7 +472,7 @@ impl NetworkInfo {
 
     fn close_idle_channels(&mut self, now: Timestamp, cutoff_period: Duration) {
         for entry in self.channels.values() {
-            if now - entry.last_activity() >= cutoff_period {
+            if now - entry.last_activity() >= cutoff_period && !self.is_pinned(entry) {
                 debug!(remote_addr = ?entry.peer_addr(), channel_id = %entry.channel_id(), mode = ?entry.mode(), "Closing idle channel");
                 entry.close();
             }
@@ -481,6 +655,14 @@ impl NetworkInfo {
             return Err(NetworkError::PeerExcluded);
         }
 
+        // The node id is only known after the handshake. But with an empty
+        // allowlist no handshake can succeed, so don't even try
+        if let Some(allowlist) = &self.allowlist {
+            if allowlist.is_empty() {
+                return Err(NetworkError::NodeIdNotAllowed);
+            }
+        }
+
         if !self.network_config.disable_max_peers_per_ip {
             let count = self.count_by_ip(peer.ip());
             if count >= self.network_config.max_peers_per_ip as usize {
@@ -546,6 +728,10 @@ impl NetworkInfo {
 
         self.excluded_peers
             .peer_misbehaved(&channel.peer_addr(), now);
+        self.reputations
+            .lock()
+            .unwrap()
+            .peer_misbehaved(*channel.peer_addr().ip());
 
         let peer_addr = channel.peer_addr();
         let mode = channel.mode();
@@ -555,6 +741,54 @@ impl NetworkInfo {
         warn!(?peer_addr, ?mode, ?direction, "Peer misbehaved!");
     }
 
+    /// Restores the reputation of a peer which was persisted before a restart
+    pub fn restore_reputation(&mut self, ip: Ipv6Addr, reputation: PeerReputation) {
+        self.reputations.lock().unwrap().insert(ip, reputation);
+    }
+
+    /// Restores the exclusion of a misbehaving peer which was active before a restart
+    pub fn restore_exclusion(&mut self, ip: Ipv6Addr, score: u64, until: Timestamp) {
+        self.excluded_peers.restore_exclusion(ip, score, until);
+    }
+
+    /// Score and end of the exclusion, if the peer is currently excluded because it misbehaved
+    pub fn misbehavior_exclusion(&self, ip: &Ipv6Addr, now: Timestamp) -> Option<(u64, Timestamp)> {
+        self.excluded_peers.misbehavior_exclusion(ip, now)
+    }
+
+    pub fn peer_reputation(&self, ip: &Ipv6Addr) -> Option<PeerReputation> {
+        self.reputations.lock().unwrap().get(ip).cloned()
+    }
+
+    pub fn bootstrap_response(&self, channel_id: ChannelId, latency: Duration) {
+        if let Some(channel) = self.channels.get(&channel_id) {
+            self.reputations
+                .lock()
+                .unwrap()
+                .bootstrap_response(*channel.peer_addr().ip(), latency);
+        }
+    }
+
+    /// Adds `elapsed` to the uptime of all realtime peers. Returns the reputations
+    /// that changed and the IPs of the reputations that were evicted.
+    pub fn update_reputations(
+        &self,
+        elapsed: Duration,
+    ) -> (Vec<(Ipv6Addr, PeerReputation)>, Vec<Ipv6Addr>) {
+        let mut reputations = self.reputations.lock().unwrap();
+        for channel in self.channels.values() {
+            if channel.mode() == ChannelMode::Realtime && channel.is_alive() {
+                reputations.add_uptime(*channel.peer_addr().ip(), channel.node_id(), elapsed);
+            }
+        }
+        reputations.take_changes()
+    }
+
+    /// Sorts the peers, so that the most reliable peers come first
+    pub fn sort_by_reputation(&self, peers: &mut [SocketAddrV6]) {
+        self.reputations.lock().unwrap().sort_by_reliability(peers);
+    }
+
     pub fn close(&mut self) {}
 
     pub fn stop(&mut self) -> bool {
@@ -612,6 +846,10 @@ impl NetworkInfo {
             return None;
         };
 
+        if !self.is_node_id_allowed(&node_id) {
+            return None;
+        }
+
         if let Some(other) = self.find_node_id(&node_id) {
             if other.ipv4_address_or_ipv6_subnet() == channel.ipv4_address_or_ipv6_subnet() {
                 // We already have a connection to that node. We allow duplicate node ids, but
@@ -654,6 +892,11 @@ impl NetworkInfo {
                 ChannelDirection::Inbound => info.inbound += 1,
                 ChannelDirection::Outbound => info.outbound += 1,
             }
+            if channel.is_encrypted() {
+                info.encrypted += 1;
+            } else {
+                info.plaintext += 1;
+            }
         }
         info
     }
@@ -679,6 +922,10 @@ impl NetworkInfo {
                 AttemptContainer::ELEMENT_SIZE,
             )
             .node("excluded_peers", self.excluded_peers.container_info())
+            .node(
+                "reputations",
+                self.reputations.lock().unwrap().container_info(),
+            )
             .finish()
     }
 }
@@ -696,6 +943,8 @@ pub struct ChannelsInfo {
     pub bootstrap: usize,
     pub inbound: usize,
     pub outbound: usize,
+    pub encrypted: usize,
+    pub plaintext: usize,
 }
 
 #[cfg(test)]
@@ -794,6 +1043,99 @@ mod tests {
         assert!(endpoints.contains(&TEST_ENDPOINT_3));
     }
 
+    mod reputation {
+        use super::*;
+
+        #[test]
+        fn update_uptime_of_realtime_peers() {
+            let mut network = NetworkInfo::new_test_instance();
+            add_realtime_channel_with_peering_addr(&mut network, TEST_ENDPOINT_1);
+
+            let (changed, removed) = network.update_reputations(Duration::from_secs(60));
+
+            assert_eq!(changed.len(), 1);
+            assert_eq!(changed[0].0, *TEST_ENDPOINT_1.ip());
+            assert_eq!(changed[0].1.uptime, Duration::from_secs(60));
+            assert!(changed[0].1.node_id.is_some());
+            assert!(removed.is_empty());
+        }
+
+        #[test]
+        fn restore_reputation() {
+            let mut network = NetworkInfo::new_test_instance();
+            let reputation = PeerReputation {
+                misbehavior_score: 3,
+                ..Default::default()
+            };
+
+            network.restore_reputation(*TEST_ENDPOINT_1.ip(), reputation.clone());
+
+            assert_eq!(
+                network.peer_reputation(TEST_ENDPOINT_1.ip()),
+                Some(reputation)
+            );
+            let (changed, _) = network.update_reputations(Duration::ZERO);
+            assert!(changed.is_empty());
+        }
+
+        #[test]
+        fn forgiven_repeat_offender_is_not_excluded_after_restart() {
+            let mut network = NetworkInfo::new_test_instance();
+            let now = Timestamp::new_test_instance();
+            let reputation = PeerReputation {
+                misbehavior_score: 10,
+                ..Default::default()
+            };
+
+            network.restore_reputation(*TEST_ENDPOINT_1.ip(), reputation);
+
+            assert!(!network.is_excluded(&TEST_ENDPOINT_1, now));
+        }
+
+        #[test]
+        fn restore_exclusion() {
+            let mut network = NetworkInfo::new_test_instance();
+            let now = Timestamp::new_test_instance();
+            let until = now + Duration::from_secs(60);
+
+            network.restore_exclusion(*TEST_ENDPOINT_1.ip(), 3, until);
+
+            assert!(network.is_excluded(&TEST_ENDPOINT_1, now));
+            assert_eq!(
+                network.misbehavior_exclusion(TEST_ENDPOINT_1.ip(), now),
+                Some((3, until))
+            );
+        }
+
+        #[test]
+        fn unban_marks_reputation_as_changed() {
+            let mut network = NetworkInfo::new_test_instance();
+            network.restore_reputation(*TEST_ENDPOINT_1.ip(), PeerReputation::default());
+
+            network.unban(&IpSubnet::host(*TEST_ENDPOINT_1.ip()));
+
+            let (changed, _) = network.update_reputations(Duration::ZERO);
+            assert_eq!(changed.len(), 1);
+        }
+
+        #[test]
+        fn sort_by_reputation() {
+            let mut network = NetworkInfo::new_test_instance();
+            network.restore_reputation(
+                *TEST_ENDPOINT_2.ip(),
+                PeerReputation {
+                    uptime: Duration::from_secs(100),
+                    ..Default::default()
+                },
+            );
+            let mut peers = [TEST_ENDPOINT_1, TEST_ENDPOINT_2];
+
+            network.sort_by_reputation(&mut peers);
+
+            assert_eq!(peers, [TEST_ENDPOINT_2, TEST_ENDPOINT_1]);
+        }
+    }
+
     fn add_realtime_channel_with_peering_addr(
         network: &mut NetworkInfo,
         peering_addr: SocketAddrV6,
@@ -814,6 +1156,101 @@ mod tests {
         );
     }
 
+    mod private_network {
+        use super::*;
+
+        fn private_network(allowed: Vec<NodeId>) -> NetworkInfo {
+            let mut config = NetworkConfig::default_for(Networks::BananoDevNetwork);
+            config.allowed_node_ids = allowed;
+            NetworkInfo::new(config)
+        }
+
+        #[test]
+        fn disabled_by_default() {
+            let network = NetworkInfo::new_test_instance();
+            assert!(!network.is_private_network());
+            assert!(network.is_node_id_allowed(&NodeId::from(1)));
+        }
+
+        #[test]
+        fn dont_upgrade_unknown_node() {
+            let mut network = private_network(vec![NodeId::from(1)]);
+            let channel = network
+                .add(
+                    TEST_ENDPOINT_1,
+                    TEST_ENDPOINT_2,
+                    ChannelDirection::Inbound,
+                    ChannelMode::Undefined,
+                    Timestamp::new_test_instance(),
+                )
+                .unwrap();
+
+            assert!(network
+                .upgrade_to_realtime_connection(channel.channel_id(), NodeId::from(2))
+                .is_none());
+            assert!(network
+                .upgrade_to_realtime_connection(channel.channel_id(), NodeId::from(1))
+                .is_some());
+        }
+
+        #[test]
+        fn reject_all_connections_if_nothing_is_allowed() {
+            let mut config = NetworkConfig::default_for(Networks::BananoDevNetwork);
+            config.allowed_node_ids_file = Some("/this/file/does/not/exist".into());
+            let mut network = NetworkInfo::new(config);
+
+            assert!(network.is_private_network());
+            assert!(matches!(
+                network.validate_new_connection(
+                    &TEST_ENDPOINT_1,
+                    ChannelDirection::Inbound,
+                    ChannelMode::Realtime,
+                    Timestamp::new_test_instance()
+                ),
+                Err(NetworkError::NodeIdNotAllowed)
+            ));
+        }
+    }
+
+    #[test]
+    fn ban_closes_channels() {
+        let mut network = NetworkInfo::new_test_instance();
+        let now = Timestamp::new_test_instance();
+        let channel = network
+            .add(
+                TEST_ENDPOINT_1,
+                TEST_ENDPOINT_2,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            )
+            .unwrap();
+        let closed = network.ban(IpSubnet::host(*TEST_ENDPOINT_2.ip()), Timestamp::MAX);
+        assert_eq!(closed, 1);
+        assert!(!channel.is_alive());
+        assert!(network.is_excluded(&TEST_ENDPOINT_2, now));
+        assert!(matches!(
+            network.add(
+                TEST_ENDPOINT_1,
+                TEST_ENDPOINT_2,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            ),
+            Err(NetworkError::PeerExcluded)
+        ));
+    }
+
+    #[test]
+    fn drop_node() {
+        let mut network = NetworkInfo::new_test_instance();
+        add_realtime_channel_with_peering_addr(&mut network, TEST_ENDPOINT_1);
+        let node_id = NodeId::from(TEST_ENDPOINT_1.ip().to_bits());
+        assert_eq!(network.drop_node(&node_id), 1);
+        assert_eq!(network.drop_node(&node_id), 0);
+        assert_eq!(network.list_realtime_channels(0).len(), 0);
+    }
+
     mod purging {
         use super::*;
 
@@ -859,6 +1296,29 @@ mod tests {
             assert_eq!(network.len(), 0);
         }
 
+        #[test]
+        fn dont_purge_pinned_peer() {
+            let mut network = NetworkInfo::new_test_instance();
+            let now = Timestamp::new_test_instance();
+            let channel = network
+                .add(
+                    TEST_ENDPOINT_1,
+                    TEST_ENDPOINT_2,
+                    ChannelDirection::Outbound,
+                    ChannelMode::Realtime,
+                    now,
+                )
+                .unwrap();
+            channel.set_last_activity(now - Duration::from_secs(300));
+            network.pin(TEST_ENDPOINT_2);
+            network.purge(now, Duration::from_secs(1));
+            assert_eq!(network.len(), 1);
+
+            assert!(network.unpin(&TEST_ENDPOINT_2));
+            network.purge(now, Duration::from_secs(1));
+            assert_eq!(network.len(), 0);
+        }
+
         #[test]
         fn dont_purge_if_packet_sent_within_timeout() {
             let mut network = NetworkInfo::new_test_instance();
@@ -0,0 +1,133 @@
+use rsban_core::NodeId;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tracing::{info, warn};
+
+/// Node ids which are allowed to become realtime peers in a private network.
+/// The ids are taken from a static list and optionally from a file which
+/// contains one node id per line. Lines starting with '#' are ignored.
+pub struct NodeIdAllowlist {
+    static_ids: HashSet<NodeId>,
+    file_ids: HashSet<NodeId>,
+    file: Option<PathBuf>,
+    file_modified: Option<SystemTime>,
+}
+
+impl NodeIdAllowlist {
+    pub fn new(node_ids: impl IntoIterator<Item = NodeId>, file: Option<PathBuf>) -> Self {
+        let mut allowlist = Self {
+            static_ids: node_ids.into_iter().collect(),
+            file_ids: HashSet::new(),
+            file,
+            file_modified: None,
+        };
+        allowlist.load();
+        allowlist
+    }
+
+    pub fn contains(&self, node_id: &NodeId) -> bool {
+        self.static_ids.contains(node_id) || self.file_ids.contains(node_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.static_ids.union(&self.file_ids).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.static_ids.is_empty() && self.file_ids.is_empty()
+    }
+
+    /// Reads the allowlist file again if it was modified since it was last read.
+    /// Returns true if the file was reloaded.
+    pub fn reload_if_changed(&mut self) -> bool {
+        let Some(file) = &self.file else {
+            return false;
+        };
+        let modified = fs::metadata(file).and_then(|m| m.modified()).ok();
+        if modified == self.file_modified {
+            return false;
+        }
+        self.load()
+    }
+
+    fn load(&mut self) -> bool {
+        let Some(file) = &self.file else {
+            return false;
+        };
+        self.file_modified = fs::metadata(file).and_then(|m| m.modified()).ok();
+
+        // An unreadable file keeps the previous ids, so that a half written file
+        // doesn't disconnect all peers
+        match read_node_ids(file) {
+            Ok(ids) => {
+                info!(
+                    "Loaded {} node ids from allowlist file {:?}",
+                    ids.len(),
+                    file
+                );
+                self.file_ids = ids;
+                true
+            }
+            Err(e) => {
+                warn!("Could not read node id allowlist {:?}: {:?}", file, e);
+                false
+            }
+        }
+    }
+}
+
+fn read_node_ids(file: &Path) -> anyhow::Result<HashSet<NodeId>> {
+    let content = fs::read_to_string(file)?;
+    parse_node_ids(&content)
+}
+
+fn parse_node_ids(content: &str) -> anyhow::Result<HashSet<NodeId>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.parse::<NodeId>())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_ids() {
+        let allowlist = NodeIdAllowlist::new([NodeId::from(1), NodeId::from(2)], None);
+        assert_eq!(allowlist.len(), 2);
+        assert!(allowlist.contains(&NodeId::from(1)));
+        assert!(!allowlist.contains(&NodeId::from(3)));
+    }
+
+    #[test]
+    fn parse_file_content() {
+        let content = format!(
+            "# consortium nodes\n{}\n\n  {}  \n",
+            NodeId::from(1),
+            NodeId::from(2)
+        );
+        let ids = parse_node_ids(&content).unwrap();
+        assert_eq!(ids, [NodeId::from(1), NodeId::from(2)].into());
+    }
+
+    #[test]
+    fn invalid_file_content() {
+        assert!(parse_node_ids("foo").is_err());
+    }
+
+    #[test]
+    fn missing_file() {
+        let allowlist = NodeIdAllowlist::new(
+            [NodeId::from(1)],
+            Some(PathBuf::from("/this/file/does/not/exist")),
+        );
+        assert_eq!(allowlist.len(), 1);
+    }
+}
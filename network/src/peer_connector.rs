@@ -3,7 +3,7 @@ use crate::{
     NullResponseServerSpawner, ResponseServerSpawner,
 };
 use rsban_nullable_clock::SteadyClock;
-use rsban_nullable_tcp::TcpStream;
+use rsban_nullable_tcp::{Socks5Proxy, TcpStream};
 use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
 use std::{net::SocketAddrV6, sync::Arc, time::Duration};
 use tokio_util::sync::CancellationToken;
@@ -98,6 +98,7 @@ impl PeerConnector {
         self.network_observer.connection_attempt(&peer);
         self.network_observer.merge_peer();
 
+        let proxy = self.network.info.read().unwrap().socks5_proxy().cloned();
         let network_l = self.network.clone();
         let response_server_spawner_l = self.response_server_spawner.clone();
         let connect_timeout = self.connect_timeout;
@@ -106,7 +107,7 @@ impl PeerConnector {
 
         self.tokio.spawn(async move {
             tokio::select! {
-                result =  connect_impl(peer, &network_l, &*response_server_spawner_l) =>{
+                result =  connect_impl(peer, proxy, &network_l, &*response_server_spawner_l) =>{
                     if let Err(e) = result {
                         observer.connect_error(peer, e);
                     }
@@ -135,10 +136,14 @@ impl PeerConnector {
 
 async fn connect_impl(
     peer: SocketAddrV6,
+    proxy: Option<Socks5Proxy>,
     network: &Network,
     response_server_spawner: &dyn ResponseServerSpawner,
 ) -> anyhow::Result<()> {
-    let tcp_stream = connect_stream(peer).await?;
+    let tcp_stream = match proxy {
+        Some(proxy) => connect_via_proxy(peer, &proxy).await?,
+        None => connect_stream(peer).await?,
+    };
 
     let channel = network.add(
         tcp_stream,
@@ -156,6 +161,16 @@ async fn connect_stream(peer: SocketAddrV6) -> tokio::io::Result<TcpStream> {
     Ok(TcpStream::new(tcp_stream))
 }
 
+/// The channel gets the address of the peer and not the address of the proxy,
+/// so that all peer checks are applied to the intended peer
+async fn connect_via_proxy(
+    peer: SocketAddrV6,
+    proxy: &Socks5Proxy,
+) -> tokio::io::Result<TcpStream> {
+    let tcp_stream = proxy.connect(peer.into()).await?;
+    Ok(TcpStream::new_proxied(tcp_stream, peer.into()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
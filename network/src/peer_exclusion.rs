@@ -1,3 +1,4 @@
+use crate::IpSubnet;
 use rsban_core::utils::ContainerInfo;
 use rsban_nullable_clock::Timestamp;
 use std::{
@@ -14,6 +15,10 @@ pub struct PeerExclusion {
     by_ip: HashMap<Ipv6Addr, Peer>,
     max_size: usize,
     perma_bans: HashSet<SocketAddrV6>,
+    /// Bans which were added by the node operator
+    bans: HashMap<IpSubnet, Timestamp>,
+    /// Bans which expired since the last call to `take_expired_bans`
+    expired_bans: Vec<IpSubnet>,
 }
 
 impl PeerExclusion {
@@ -28,6 +33,8 @@ impl PeerExclusion {
             by_ip: HashMap::new(),
             max_size,
             perma_bans: HashSet::new(),
+            bans: HashMap::new(),
+            expired_bans: Vec::new(),
         }
     }
 
@@ -51,14 +58,93 @@ impl PeerExclusion {
         }
     }
 
+    /// Restores an exclusion that was active before a restart
+    pub fn restore_exclusion(&mut self, ip: Ipv6Addr, score: u64, until: Timestamp) {
+        if score == 0 || self.by_ip.contains_key(&ip) {
+            return;
+        }
+        self.clean_old_peers();
+        let peer = Peer {
+            address: SocketAddrV6::new(ip, 0, 0, 0),
+            exclude_until: until,
+            score,
+        };
+        self.insert(&peer);
+    }
+
+    /// Returns the score and the exclusion end of a peer that is currently
+    /// excluded because it misbehaved
+    pub fn misbehavior_exclusion(&self, ip: &Ipv6Addr, now: Timestamp) -> Option<(u64, Timestamp)> {
+        self.by_ip
+            .get(ip)
+            .filter(|peer| peer.is_excluded(now))
+            .map(|peer| (peer.score, peer.exclude_until))
+    }
+
     /// Perma bans are used for prohibiting a node to connect to itself.
     pub fn perma_ban(&mut self, peer_addr: SocketAddrV6) {
         self.perma_bans.insert(peer_addr);
     }
 
+    /// Excludes all addresses of the subnet until `until`.
+    /// `Timestamp::MAX` bans the subnet permanently.
+    pub fn ban(&mut self, subnet: IpSubnet, until: Timestamp) {
+        self.expired_bans.retain(|s| *s != subnet);
+        self.bans.insert(subnet, until);
+    }
+
+    /// Removes the expired bans and returns all bans that expired since the
+    /// last call, so that they can be deleted from the database as well
+    pub fn take_expired_bans(&mut self, now: Timestamp) -> Vec<IpSubnet> {
+        self.remove_expired_bans(now);
+        std::mem::take(&mut self.expired_bans)
+    }
+
+    fn remove_expired_bans(&mut self, now: Timestamp) {
+        let expired: Vec<_> = self
+            .bans
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(subnet, _)| *subnet)
+            .collect();
+        for subnet in expired {
+            self.bans.remove(&subnet);
+            self.expired_bans.push(subnet);
+        }
+    }
+
+    /// Lifts the ban of the subnet and forgets the misbehavior of all peers within it.
+    /// Returns false if nothing was excluded.
+    pub fn unban(&mut self, subnet: &IpSubnet) -> bool {
+        let mut removed = self.bans.remove(subnet).is_some();
+        let ips: Vec<_> = self
+            .by_ip
+            .keys()
+            .filter(|ip| subnet.contains(ip))
+            .cloned()
+            .collect();
+        for ip in ips {
+            if let Some(peer) = self.by_ip.remove(&ip) {
+                self.ordered_by_date.remove(&ip, peer.exclude_until);
+                removed = true;
+            }
+        }
+        removed
+    }
+
+    fn ban_end(&self, ip: &Ipv6Addr) -> Option<Timestamp> {
+        self.bans
+            .iter()
+            .filter(|(subnet, _)| subnet.contains(ip))
+            .map(|(_, until)| *until)
+            .max()
+    }
+
     #[allow(dead_code)]
     pub fn contains(&self, endpoint: &SocketAddrV6) -> bool {
-        self.by_ip.contains_key(&endpoint.ip()) || self.perma_bans.contains(endpoint)
+        self.by_ip.contains_key(&endpoint.ip())
+            || self.perma_bans.contains(endpoint)
+            || self.ban_end(endpoint.ip()).is_some()
     }
 
     #[allow(dead_code)]
@@ -66,18 +152,62 @@ impl PeerExclusion {
         if self.perma_bans.contains(endpoint) {
             Some(Timestamp::MAX)
         } else {
-            self.by_ip
+            let misbehavior_end = self
+                .by_ip
                 .get(&endpoint.ip())
-                .map(|item| item.exclude_until)
+                .map(|item| item.exclude_until);
+            misbehavior_end.max(self.ban_end(endpoint.ip()))
         }
     }
 
+    /// All peers which are currently excluded
+    pub fn excluded_peers(&self, now: Timestamp) -> Vec<ExcludedPeer> {
+        let mut result: Vec<_> = self
+            .perma_bans
+            .iter()
+            .map(|addr| ExcludedPeer {
+                subnet: IpSubnet::host(*addr.ip()),
+                reason: ExclusionReason::PermaBan,
+                score: 0,
+                excluded_until: Timestamp::MAX,
+            })
+            .collect();
+
+        result.extend(self.bans.iter().filter(|(_, until)| **until > now).map(
+            |(subnet, until)| ExcludedPeer {
+                subnet: *subnet,
+                reason: ExclusionReason::Banned,
+                score: 0,
+                excluded_until: *until,
+            },
+        ));
+
+        result.extend(
+            self.by_ip
+                .values()
+                .filter(|peer| peer.is_excluded(now))
+                .map(|peer| ExcludedPeer {
+                    subnet: IpSubnet::host(*peer.address.ip()),
+                    reason: ExclusionReason::Misbehavior,
+                    score: peer.score,
+                    excluded_until: peer.exclude_until,
+                }),
+        );
+        result.sort_by_key(|peer| peer.subnet);
+        result
+    }
+
     /// Checks if an endpoint is currently excluded.
     pub fn is_excluded(&mut self, peer_addr: &SocketAddrV6, now: Timestamp) -> bool {
         if self.perma_bans.contains(&peer_addr) {
             return true;
         }
 
+        self.remove_expired_bans(now);
+        if self.ban_end(peer_addr.ip()).is_some() {
+            return true;
+        }
+
         if let Some(peer) = self.by_ip.get(&peer_addr.ip()).cloned() {
             if peer.has_expired(now) {
                 self.remove(&peer.address);
@@ -97,7 +227,7 @@ impl PeerExclusion {
 
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
-        self.by_ip.len() + self.perma_bans.len()
+        self.by_ip.len() + self.perma_bans.len() + self.bans.len()
     }
 
     fn clean_old_peers(&mut self) {
@@ -114,7 +244,11 @@ impl PeerExclusion {
     }
 
     pub fn container_info(&self) -> ContainerInfo {
-        [("peers", self.by_ip.len(), size_of::<Peer>())].into()
+        [
+            ("peers", self.by_ip.len(), size_of::<Peer>()),
+            ("bans", self.bans.len(), size_of::<(IpSubnet, Timestamp)>()),
+        ]
+        .into()
     }
 }
 
@@ -124,6 +258,35 @@ impl Default for PeerExclusion {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExclusionReason {
+    /// The peer behaved badly, for example by sending invalid messages
+    Misbehavior,
+    /// The node operator banned the peer
+    Banned,
+    /// The peer is excluded permanently, for example because it is this node itself
+    PermaBan,
+}
+
+impl ExclusionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExclusionReason::Misbehavior => "misbehavior",
+            ExclusionReason::Banned => "banned",
+            ExclusionReason::PermaBan => "perma_ban",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExcludedPeer {
+    pub subnet: IpSubnet,
+    pub reason: ExclusionReason,
+    /// Misbehavior score. Zero for banned peers
+    pub score: u64,
+    pub excluded_until: Timestamp,
+}
+
 /// Information about a peer and its exclusion status
 #[derive(Clone)]
 struct Peer {
@@ -280,6 +443,43 @@ mod tests {
         }
     }
 
+    mod restore {
+        use super::*;
+
+        #[test]
+        fn restore_exclusion() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = test_endpoint(1);
+            let until = NOW + Duration::from_secs(60);
+            peers.restore_exclusion(*endpoint.ip(), 3, until);
+            assert!(peers.is_excluded(&endpoint, NOW));
+            assert_eq!(peers.excluded_until(&endpoint), Some(until));
+            assert_eq!(
+                peers.misbehavior_exclusion(endpoint.ip(), NOW),
+                Some((3, until))
+            );
+            assert_eq!(peers.peer_misbehaved(&endpoint, NOW), 4);
+        }
+
+        #[test]
+        fn restored_exclusion_ends() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = test_endpoint(1);
+            let until = NOW + Duration::from_secs(60);
+            peers.restore_exclusion(*endpoint.ip(), 3, until);
+            assert!(!peers.is_excluded(&endpoint, until));
+            assert_eq!(peers.misbehavior_exclusion(endpoint.ip(), until), None);
+        }
+
+        #[test]
+        fn single_misbehavior_is_no_exclusion() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = test_endpoint(1);
+            peers.peer_misbehaved(&endpoint, NOW);
+            assert_eq!(peers.misbehavior_exclusion(endpoint.ip(), NOW), None);
+        }
+    }
+
     mod max_size {
         use super::*;
 
@@ -324,6 +524,124 @@ mod tests {
         }
     }
 
+    mod bans {
+        use super::*;
+
+        #[test]
+        fn ban_subnet() {
+            let mut peers = PeerExclusion::new();
+            peers.ban("::/112".parse().unwrap(), NOW + Duration::from_secs(60));
+            assert!(peers.is_excluded(&test_endpoint(1), NOW));
+            assert!(peers.is_excluded(&test_endpoint(2), NOW));
+            assert_eq!(
+                peers.excluded_until(&test_endpoint(1)),
+                Some(NOW + Duration::from_secs(60))
+            );
+            let other = SocketAddrV6::new(Ipv6Addr::new(1, 0, 0, 0, 0, 0, 0, 1), 0, 0, 0);
+            assert!(!peers.is_excluded(&other, NOW));
+        }
+
+        #[test]
+        fn ban_expires() {
+            let mut peers = PeerExclusion::new();
+            peers.ban(
+                (*test_endpoint(1).ip()).into(),
+                NOW + Duration::from_secs(60),
+            );
+            assert!(!peers.is_excluded(&test_endpoint(1), NOW + Duration::from_secs(60)));
+            assert_eq!(peers.len(), 0);
+        }
+
+        #[test]
+        fn take_expired_bans() {
+            let mut peers = PeerExclusion::new();
+            let expired = IpSubnet::host(*test_endpoint(1).ip());
+            let active = IpSubnet::host(*test_endpoint(2).ip());
+            peers.ban(expired, NOW + Duration::from_secs(60));
+            peers.ban(active, NOW + Duration::from_secs(120));
+
+            let later = NOW + Duration::from_secs(60);
+            assert_eq!(peers.take_expired_bans(later), vec![expired]);
+            assert_eq!(peers.take_expired_bans(later), Vec::new());
+            assert_eq!(peers.len(), 1);
+        }
+
+        #[test]
+        fn bans_expired_during_check_are_taken() {
+            let mut peers = PeerExclusion::new();
+            let subnet = IpSubnet::host(*test_endpoint(1).ip());
+            peers.ban(subnet, NOW + Duration::from_secs(60));
+            let later = NOW + Duration::from_secs(60);
+            assert!(!peers.is_excluded(&test_endpoint(1), later));
+            assert_eq!(peers.take_expired_bans(later), vec![subnet]);
+        }
+
+        #[test]
+        fn renewed_ban_is_not_taken() {
+            let mut peers = PeerExclusion::new();
+            let subnet = IpSubnet::host(*test_endpoint(1).ip());
+            peers.ban(subnet, NOW + Duration::from_secs(60));
+            let later = NOW + Duration::from_secs(60);
+            peers.is_excluded(&test_endpoint(1), later);
+            peers.ban(subnet, Timestamp::MAX);
+            assert_eq!(peers.take_expired_bans(later), Vec::new());
+        }
+
+        #[test]
+        fn unban() {
+            let mut peers = PeerExclusion::new();
+            let subnet = IpSubnet::host(*test_endpoint(1).ip());
+            peers.ban(subnet, Timestamp::MAX);
+            assert!(peers.unban(&subnet));
+            assert!(!peers.is_excluded(&test_endpoint(1), NOW));
+            assert!(!peers.unban(&subnet));
+        }
+
+        #[test]
+        fn unban_forgets_misbehavior() {
+            let mut peers = PeerExclusion::new();
+            peers.peer_misbehaved(&test_endpoint(1), NOW);
+            peers.peer_misbehaved(&test_endpoint(1), NOW);
+            assert!(peers.unban(&"::/112".parse().unwrap()));
+            assert!(!peers.is_excluded(&test_endpoint(1), NOW));
+        }
+
+        #[test]
+        fn list_excluded_peers() {
+            let mut peers = PeerExclusion::new();
+            peers.peer_misbehaved(&test_endpoint(1), NOW);
+            peers.peer_misbehaved(&test_endpoint(1), NOW);
+            // Not excluded yet:
+            peers.peer_misbehaved(&test_endpoint(2), NOW);
+            peers.ban(IpSubnet::host(*test_endpoint(3).ip()), Timestamp::MAX);
+            peers.perma_ban(test_endpoint(4));
+
+            assert_eq!(
+                peers.excluded_peers(NOW),
+                vec![
+                    ExcludedPeer {
+                        subnet: IpSubnet::host(*test_endpoint(1).ip()),
+                        reason: ExclusionReason::Misbehavior,
+                        score: 2,
+                        excluded_until: NOW + Peer::EXCLUDE_TIME
+                    },
+                    ExcludedPeer {
+                        subnet: IpSubnet::host(*test_endpoint(3).ip()),
+                        reason: ExclusionReason::Banned,
+                        score: 0,
+                        excluded_until: Timestamp::MAX
+                    },
+                    ExcludedPeer {
+                        subnet: IpSubnet::host(*test_endpoint(4).ip()),
+                        reason: ExclusionReason::PermaBan,
+                        score: 0,
+                        excluded_until: Timestamp::MAX
+                    },
+                ]
+            );
+        }
+    }
+
     fn test_endpoint(i: usize) -> SocketAddrV6 {
         SocketAddrV6::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, i as u16), 0, 0, 0)
     }
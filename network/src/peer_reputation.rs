@@ -0,0 +1,313 @@
+use rsban_core::{utils::ContainerInfo, NodeId, PeerReputation};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    mem::size_of,
+    net::{Ipv6Addr, SocketAddrV6},
+    time::Duration,
+};
+use tracing::debug;
+
+/// Keeps track of how reliable peers were in the past.
+/// The reputations are persisted, so that a restarted node can prefer
+/// reliable peers and remembers repeat offenders.
+pub struct PeerReputations {
+    by_ip: HashMap<Ipv6Addr, PeerReputation>,
+    /// Peers whose reputation changed since the last call to `take_changes`
+    changed: HashSet<Ipv6Addr>,
+    /// Peers which were evicted since the last call to `take_changes`
+    removed: Vec<Ipv6Addr>,
+    max_size: usize,
+}
+
+impl PeerReputations {
+    pub fn new() -> Self {
+        Self::with_max_size(5000)
+    }
+
+    pub fn with_max_size(max_size: usize) -> Self {
+        Self {
+            by_ip: HashMap::new(),
+            changed: HashSet::new(),
+            removed: Vec::new(),
+            max_size,
+        }
+    }
+
+    /// Inserts a reputation that was loaded from the database
+    pub fn insert(&mut self, ip: Ipv6Addr, reputation: PeerReputation) {
+        if !self.by_ip.contains_key(&ip) && self.by_ip.len() >= self.max_size {
+            self.evict_least_reliable();
+        }
+        if self.by_ip.len() < self.max_size || self.by_ip.contains_key(&ip) {
+            self.by_ip.insert(ip, reputation);
+        } else {
+            debug!(%ip, "Dropped peer reputation, because the reputation table is full");
+        }
+    }
+
+    pub fn get(&self, ip: &Ipv6Addr) -> Option<&PeerReputation> {
+        self.by_ip.get(ip)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_ip.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_ip.is_empty()
+    }
+
+    pub fn bootstrap_response(&mut self, ip: Ipv6Addr, latency: Duration) {
+        let reputation = self.get_or_insert(ip);
+        reputation.average_latency = if reputation.bootstrap_responses == 0 {
+            latency
+        } else {
+            (reputation.average_latency * 7 + latency) / 8
+        };
+        reputation.bootstrap_responses += 1;
+        self.changed.insert(ip);
+    }
+
+    /// The peer was connected as realtime peer for the given duration
+    pub fn add_uptime(&mut self, ip: Ipv6Addr, node_id: Option<NodeId>, uptime: Duration) {
+        let reputation = self.get_or_insert(ip);
+        reputation.uptime += uptime;
+        if node_id.is_some() {
+            reputation.node_id = node_id;
+        }
+        self.changed.insert(ip);
+    }
+
+    /// Increments the misbehavior count of the peer. The count is never lowered,
+    /// so that repeat offenders are remembered even after they were forgiven.
+    pub fn peer_misbehaved(&mut self, ip: Ipv6Addr) {
+        self.get_or_insert(ip).misbehavior_score += 1;
+        self.changed.insert(ip);
+    }
+
+    /// Marks the reputations of the matching peers as changed, so that they are persisted again
+    pub fn mark_changed(&mut self, filter: impl Fn(&Ipv6Addr) -> bool) {
+        self.changed
+            .extend(self.by_ip.keys().filter(|ip| filter(ip)).cloned());
+    }
+
+    /// Returns the reputations that changed and the IPs of the reputations that
+    /// were evicted since the last call
+    pub fn take_changes(&mut self) -> (Vec<(Ipv6Addr, PeerReputation)>, Vec<Ipv6Addr>) {
+        let changed = self
+            .changed
+            .drain()
+            .filter_map(|ip| self.by_ip.get(&ip).map(|r| (ip, r.clone())))
+            .collect();
+        (changed, std::mem::take(&mut self.removed))
+    }
+
+    /// Sorts the peers, so that the most reliable peers come first:
+    /// Peers that misbehaved come last. The others are ordered by their uptime
+    /// and the number of bootstrap requests they answered.
+    pub fn sort_by_reliability(&self, peers: &mut [SocketAddrV6]) {
+        peers.sort_by_cached_key(|peer| match self.by_ip.get(peer.ip()) {
+            Some(r) => (
+                r.misbehavior_score,
+                Reverse(r.uptime),
+                Reverse(r.bootstrap_responses),
+            ),
+            None => (0, Reverse(Duration::ZERO), Reverse(0)),
+        });
+    }
+
+    fn get_or_insert(&mut self, ip: Ipv6Addr) -> &mut PeerReputation {
+        if !self.by_ip.contains_key(&ip) && self.by_ip.len() >= self.max_size {
+            self.evict_least_reliable();
+        }
+        self.by_ip.entry(ip).or_default()
+    }
+
+    /// Evicts the least reliable peer. Peers that misbehaved are only evicted
+    /// if all peers misbehaved, starting with the smallest misbehavior count.
+    fn evict_least_reliable(&mut self) {
+        let least_reliable = self
+            .by_ip
+            .iter()
+            .min_by_key(|(_, r)| {
+                (
+                    Reverse(r.misbehavior_score == 0),
+                    r.misbehavior_score,
+                    r.uptime,
+                    r.bootstrap_responses,
+                )
+            })
+            .map(|(ip, _)| *ip);
+
+        if let Some(ip) = least_reliable {
+            self.by_ip.remove(&ip);
+            self.changed.remove(&ip);
+            self.removed.push(ip);
+        }
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        [(
+            "reputations",
+            self.by_ip.len(),
+            size_of::<(Ipv6Addr, PeerReputation)>(),
+        )]
+        .into()
+    }
+}
+
+impl Default for PeerReputations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IP1: Ipv6Addr = Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0a00, 0x0001);
+    const IP2: Ipv6Addr = Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0a00, 0x0002);
+    const IP3: Ipv6Addr = Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0a00, 0x0003);
+
+    #[test]
+    fn empty() {
+        let mut reputations = PeerReputations::new();
+        assert_eq!(reputations.len(), 0);
+        assert_eq!(reputations.take_changes(), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn bootstrap_response() {
+        let mut reputations = PeerReputations::new();
+        reputations.bootstrap_response(IP1, Duration::from_millis(800));
+        reputations.bootstrap_response(IP1, Duration::from_millis(0));
+
+        let reputation = reputations.get(&IP1).unwrap();
+        assert_eq!(reputation.bootstrap_responses, 2);
+        assert_eq!(reputation.average_latency, Duration::from_millis(700));
+    }
+
+    #[test]
+    fn add_uptime() {
+        let mut reputations = PeerReputations::new();
+        let node_id = NodeId::from(1);
+        reputations.add_uptime(IP1, Some(node_id), Duration::from_secs(10));
+        reputations.add_uptime(IP1, None, Duration::from_secs(5));
+
+        let reputation = reputations.get(&IP1).unwrap();
+        assert_eq!(reputation.uptime, Duration::from_secs(15));
+        assert_eq!(reputation.node_id, Some(node_id));
+    }
+
+    #[test]
+    fn take_changes() {
+        let mut reputations = PeerReputations::new();
+        reputations.insert(IP2, PeerReputation::default());
+        reputations.add_uptime(IP1, None, Duration::from_secs(10));
+
+        let (changed, removed) = reputations.take_changes();
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0, IP1);
+        assert!(removed.is_empty());
+        assert_eq!(reputations.take_changes(), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn misbehavior_scores() {
+        let mut reputations = PeerReputations::new();
+        reputations.insert(
+            IP1,
+            PeerReputation {
+                misbehavior_score: 3,
+                ..Default::default()
+            },
+        );
+
+        reputations.peer_misbehaved(IP1);
+        reputations.peer_misbehaved(IP2);
+
+        assert_eq!(reputations.get(&IP1).unwrap().misbehavior_score, 4);
+        assert_eq!(reputations.get(&IP2).unwrap().misbehavior_score, 1);
+        assert_eq!(reputations.take_changes().0.len(), 2);
+    }
+
+    #[test]
+    fn evict_least_reliable_peer() {
+        let mut reputations = PeerReputations::with_max_size(2);
+        reputations.add_uptime(IP1, None, Duration::from_secs(10));
+        reputations.add_uptime(IP2, None, Duration::from_secs(5));
+        reputations.take_changes();
+
+        reputations.add_uptime(IP3, None, Duration::from_secs(1));
+
+        assert_eq!(reputations.len(), 2);
+        assert!(reputations.get(&IP2).is_none());
+        assert_eq!(reputations.take_changes().1, vec![IP2]);
+    }
+
+    #[test]
+    fn evict_when_inserting_into_full_table() {
+        let mut reputations = PeerReputations::with_max_size(2);
+        reputations.add_uptime(IP1, None, Duration::from_secs(10));
+        reputations.peer_misbehaved(IP2);
+        reputations.take_changes();
+
+        reputations.insert(
+            IP3,
+            PeerReputation {
+                uptime: Duration::from_secs(1),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(reputations.len(), 2);
+        assert!(reputations.get(&IP1).is_none());
+        assert!(reputations.get(&IP3).is_some());
+        assert_eq!(reputations.take_changes().1, vec![IP1]);
+    }
+
+    #[test]
+    fn evict_smallest_offender_when_all_peers_misbehaved() {
+        let mut reputations = PeerReputations::with_max_size(2);
+        reputations.peer_misbehaved(IP1);
+        reputations.peer_misbehaved(IP1);
+        reputations.peer_misbehaved(IP2);
+        reputations.take_changes();
+
+        reputations.peer_misbehaved(IP3);
+
+        assert_eq!(reputations.len(), 2);
+        assert!(reputations.get(&IP2).is_none());
+        assert_eq!(reputations.take_changes().1, vec![IP2]);
+    }
+
+    #[test]
+    fn sort_by_reliability() {
+        let mut reputations = PeerReputations::new();
+        reputations.add_uptime(IP1, None, Duration::from_secs(10));
+        reputations.add_uptime(IP2, None, Duration::from_secs(100));
+        reputations.peer_misbehaved(IP3);
+        let unknown = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 1, 0, 0);
+        let mut peers = [
+            SocketAddrV6::new(IP3, 1, 0, 0),
+            unknown,
+            SocketAddrV6::new(IP1, 1, 0, 0),
+            SocketAddrV6::new(IP2, 1, 0, 0),
+        ];
+
+        reputations.sort_by_reliability(&mut peers);
+
+        assert_eq!(
+            peers,
+            [
+                SocketAddrV6::new(IP2, 1, 0, 0),
+                SocketAddrV6::new(IP1, 1, 0, 0),
+                unknown,
+                SocketAddrV6::new(IP3, 1, 0, 0),
+            ]
+        );
+    }
+}
@@ -25,8 +25,9 @@ impl WriteQueue {
         &self,
         buffer: Arc<Vec<u8>>,
         traffic_type: TrafficType,
+        encrypt: bool,
     ) -> anyhow::Result<()> {
-        let entry = Entry { buffer };
+        let entry = Entry { buffer, encrypt };
         self.queue_for(traffic_type)
             .send(entry)
             .await
@@ -34,8 +35,13 @@ impl WriteQueue {
     }
 
     /// returns: inserted | write_error
-    pub fn try_insert(&self, buffer: Arc<Vec<u8>>, traffic_type: TrafficType) -> (bool, bool) {
-        let entry = Entry { buffer };
+    pub fn try_insert(
+        &self,
+        buffer: Arc<Vec<u8>>,
+        traffic_type: TrafficType,
+        encrypt: bool,
+    ) -> (bool, bool) {
+        let entry = Entry { buffer, encrypt };
         match self.queue_for(traffic_type).try_send(entry) {
             Ok(()) => (true, false),
             Err(mpsc::error::TrySendError::Full(_)) => (false, false),
@@ -80,4 +86,7 @@ impl WriteQueueReceiver {
 
 pub struct Entry {
     pub buffer: Arc<Vec<u8>>,
+    /// Decided when the entry is queued, because the handshake response must
+    /// still be sent in plaintext after encryption was enabled
+    pub encrypt: bool,
 }
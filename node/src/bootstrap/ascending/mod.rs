@@ -487,6 +487,11 @@ impl BootstrapAscending {
         );
 
         guard.scoring.received_message(channel_id);
+        guard
+            .network_info
+            .read()
+            .unwrap()
+            .bootstrap_response(channel_id, tag.timestamp.elapsed(self.clock.now()));
         drop(guard);
 
         // Process the response payload
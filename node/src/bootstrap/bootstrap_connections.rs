@@ -565,7 +565,11 @@ impl BootstrapConnectionsExt for Arc<BootstrapConnections> {
 
         self.network_stats.connection_attempt(&peer_addr);
 
-        let tcp_stream_factory = Arc::new(TcpStreamFactory::new());
+        let proxy = self.network_info.read().unwrap().socks5_proxy().cloned();
+        let tcp_stream_factory = match proxy {
+            Some(proxy) => TcpStreamFactory::with_proxy(proxy),
+            None => TcpStreamFactory::new(),
+        };
         let tcp_stream = match tokio::time::timeout(
             self.config.tcp_io_timeout,
             tcp_stream_factory.connect(peer_addr),
@@ -675,6 +679,7 @@ impl BootstrapConnectionsExt for Arc<BootstrapConnections> {
                         disable_legacy_bootstrap: self.config.disable_legacy_bootstrap,
                         retry_limit: self.config.lazy_retry_limit,
                         work_thresholds: self.config.work_thresholds.clone(),
+                        compression: self.network_info.read().unwrap().bootstrap_compression(),
                     };
 
                     if let Some(initiator) = initiator {
@@ -6,14 +6,15 @@ use crate::{
     block_processing::{BlockProcessor, BlockSource},
     bootstrap::BootstrapMode,
     stats::{DetailType, Direction, StatType, Stats},
-    transport::read_block,
+    transport::{read_block, read_block_of_type, read_compressed_blocks},
     utils::ThreadPool,
 };
 use async_trait::async_trait;
 use rsban_core::{work::WorkThresholds, Account, Block, BlockHash};
 use rsban_messages::{BulkPull, Message};
-use rsban_network::ChannelReader;
+use rsban_network::{AsyncBufferReader, ChannelReader};
 use std::{
+    collections::VecDeque,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex,
@@ -46,12 +47,19 @@ pub struct BulkPullClient {
     /// 0 if the account is unknown
     known_account: Mutex<Account>,
     bootstrap_initiator: Arc<BootstrapInitiator>,
+    /// Blocks of the last compressed frame that were not processed yet
+    decompressed_blocks: Mutex<VecDeque<Option<Block>>>,
+    /// Whether the server compresses its response. Unknown until the first byte
+    /// of the response was received
+    compressed_response: Mutex<Option<bool>>,
 }
 
 pub struct BulkPullClientConfig {
     pub disable_legacy_bootstrap: bool,
     pub retry_limit: u32,
     pub work_thresholds: WorkThresholds,
+    /// Request a compressed response
+    pub compression: bool,
 }
 
 impl BulkPullClient {
@@ -83,10 +91,50 @@ impl BulkPullClient {
             unexpected_count: AtomicU64::new(0),
             known_account: Mutex::new(Account::zero()),
             bootstrap_initiator,
+            decompressed_blocks: Mutex::new(VecDeque::new()),
+            compressed_response: Mutex::new(None),
         };
         result.attempt.notify();
         result
     }
+
+    async fn receive_block(&self) -> anyhow::Result<Option<Block>> {
+        let reader = ChannelReader::new(self.connection.get_channel().clone());
+        if !self.config.compression {
+            return read_block(&reader).await;
+        }
+
+        let buffered = self.decompressed_blocks.lock().unwrap().pop_front();
+        if let Some(block) = buffered {
+            return Ok(block);
+        }
+
+        // Servers with compression disabled and legacy servers ignore the
+        // compression flag and respond with plain blocks
+        let compressed_response = *self.compressed_response.lock().unwrap();
+        match compressed_response {
+            Some(false) => return read_block(&reader).await,
+            Some(true) => {}
+            None => {
+                let mut first_byte = [0; 1];
+                reader.read(&mut first_byte, 1).await?;
+                let compressed = first_byte[0] == BulkPull::COMPRESSED_RESPONSE_MARKER;
+                *self.compressed_response.lock().unwrap() = Some(compressed);
+                if !compressed {
+                    return read_block_of_type(first_byte[0], &reader).await;
+                }
+            }
+        }
+
+        let (blocks, compression) = read_compressed_blocks(&reader).await?;
+        self.stats.payload_compressed(Direction::In, &compression);
+        let mut buffered = self.decompressed_blocks.lock().unwrap();
+        buffered.extend(blocks);
+        match buffered.pop_front() {
+            Some(block) => Ok(block),
+            None => Err(anyhow!("Received empty compressed frame")),
+        }
+    }
 }
 
 impl Drop for BulkPullClient {
@@ -142,6 +190,7 @@ impl BulkPullClientExt for Arc<BulkPullClient> {
         payload.end = self.pull.end;
         payload.count = self.pull.count;
         payload.ascending = false;
+        payload.compression = self.config.compression;
 
         trace!(
             account_or_head = %self.pull.account_or_head,
@@ -182,9 +231,7 @@ impl BulkPullClientExt for Arc<BulkPullClient> {
     async fn throttled_receive_block(&self) {
         debug_assert!(!self.network_error.load(Ordering::Relaxed));
         if self.block_processor.queue_len(BlockSource::BootstrapLegacy) < 1024 {
-            let Ok(block) =
-                read_block(&ChannelReader::new(self.connection.get_channel().clone())).await
-            else {
+            let Ok(block) = self.receive_block().await else {
                 self.network_error.store(true, Ordering::SeqCst);
                 return;
             };
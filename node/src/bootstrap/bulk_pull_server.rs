@@ -1,10 +1,14 @@
 use crate::{
+    stats::{Direction, Stats},
     transport::{ResponseServer, ResponseServerExt},
     utils::ThreadPool,
 };
-use rsban_core::{utils::MemoryStream, Account, Block, BlockHash, BlockType};
+use rsban_core::{
+    utils::{BufferWriter, MemoryStream, Serialize},
+    Account, Block, BlockHash, BlockType,
+};
 use rsban_ledger::Ledger;
-use rsban_messages::BulkPull;
+use rsban_messages::{BulkPull, CompressedPayload};
 use rsban_network::TrafficType;
 use std::sync::{Arc, Mutex, Weak};
 use tracing::{debug, trace};
@@ -23,6 +27,9 @@ use tracing::{debug, trace};
  * [start, end); In the case that a block hash is not specified the
  * range will be exclusive of the frontier for that account with
  * a range of (frontier, end)
+ *
+ * If compression was requested, the blocks are sent in compressed
+ * batches instead of one by one.
  */
 pub struct BulkPullServer {
     server_impl: Arc<Mutex<BulkPullServerImpl>>,
@@ -35,7 +42,10 @@ impl BulkPullServer {
         ledger: Arc<Ledger>,
         thread_pool: Arc<dyn ThreadPool>,
         tokio: tokio::runtime::Handle,
+        stats: Arc<Stats>,
+        compression_enabled: bool,
     ) -> Self {
+        let compression = request.compression && compression_enabled;
         let mut server_impl = BulkPullServerImpl {
             include_start: false,
             sent_count: 0,
@@ -46,6 +56,9 @@ impl BulkPullServer {
             ledger,
             thread_pool: Arc::downgrade(&thread_pool),
             tokio,
+            stats,
+            compression,
+            compressed_marker_sent: false,
         };
 
         server_impl.set_current_end();
@@ -110,6 +123,9 @@ struct BulkPullServerImpl {
     max_count: u32,
     current: BlockHash,
     request: BulkPull,
+    stats: Arc<Stats>,
+    compression: bool,
+    compressed_marker_sent: bool,
 }
 
 impl BulkPullServerImpl {
@@ -281,6 +297,11 @@ impl BulkPullServerImpl {
     }
 
     pub fn send_next(&mut self, server_impl: Arc<Mutex<Self>>) {
+        if self.compression {
+            self.send_next_compressed(server_impl);
+            return;
+        }
+
         if let Some(block) = self.get_next() {
             trace!(block = %block.hash(), remote = %self.connection.remote_endpoint(), "Sending block");
             let mut stream = MemoryStream::new();
@@ -306,6 +327,56 @@ impl BulkPullServerImpl {
         }
     }
 
+    /// Sends the next batch of blocks as one compressed frame.
+    /// The first frame is preceded by a marker, which tells the client that
+    /// the response is compressed. The not-a-block terminator is part of the last frame.
+    fn send_next_compressed(&mut self, server_impl: Arc<Mutex<Self>>) {
+        let mut stream = MemoryStream::new();
+        let mut finished = false;
+        for _ in 0..BulkPull::MAX_COMPRESSED_BLOCKS {
+            match self.get_next() {
+                Some(block) => block.serialize(&mut stream),
+                None => {
+                    stream.write_u8_safe(BlockType::NotABlock as u8);
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        let compressed = CompressedPayload::compress(stream.as_bytes());
+        self.stats
+            .payload_compressed(Direction::Out, &compressed.compression());
+        let mut frame = MemoryStream::new();
+        if !self.compressed_marker_sent {
+            frame.write_u8_safe(BulkPull::COMPRESSED_RESPONSE_MARKER);
+            self.compressed_marker_sent = true;
+        }
+        compressed.serialize(&mut frame);
+        let send_buffer = Arc::new(frame.to_vec());
+
+        let conn = self.connection.clone();
+        self.tokio.spawn(async move {
+            match conn
+                .channel()
+                .send_buffer(&send_buffer, TrafficType::Bootstrap)
+                .await
+            {
+                Ok(()) if finished => {
+                    debug!("Bulk sending finished");
+                    let guard = server_impl.lock().unwrap();
+                    let connection = guard.connection.clone();
+                    guard.tokio.spawn(async move { connection.run().await });
+                }
+                Ok(()) => {
+                    let server_impl_clone = server_impl.clone();
+                    server_impl.lock().unwrap().sent_action(server_impl_clone);
+                }
+                Err(e) => debug!("Unable to bulk send compressed blocks ({:?})", e),
+            }
+        });
+    }
+
     fn sent_action(&mut self, server_impl: Arc<Mutex<Self>>) {
         let Some(thread_pool) = self.thread_pool.upgrade() else {
             return;
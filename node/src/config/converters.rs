@@ -73,6 +73,11 @@ impl From<&GlobalConfig> for NetworkConfig {
             disable_network: value.flags.disable_tcp_realtime,
             min_protocol_version: value.network_params.network.protocol_info().version_min,
             listening_port: value.node_config.peering_port.unwrap_or(0),
+            allowed_node_ids: value.node_config.private_network.allowed_node_ids.clone(),
+            allowed_node_ids_file: value.node_config.private_network.allowlist_file.clone(),
+            transport_encryption: value.node_config.transport_encryption,
+            bootstrap_compression: value.node_config.bootstrap_compression,
+            socks5_proxy: value.node_config.socks5_proxy.clone(),
         }
     }
 }
@@ -6,21 +6,26 @@ use crate::{
     bootstrap::{BootstrapAscendingConfig, BootstrapInitiatorConfig, BootstrapServerConfig},
     cementation::ConfirmingSetConfig,
     consensus::{
-        ActiveElectionsConfig, HintedSchedulerConfig, OptimisticSchedulerConfig,
-        PriorityBucketConfig, RequestAggregatorConfig, VoteCacheConfig, VoteProcessorConfig,
+        ActiveElectionsConfig, ConfirmationLatencyConfig, ElectionTracerConfig,
+        FinalVoteCleanupConfig, ForkTrackerConfig, HintedSchedulerConfig,
+        OptimisticSchedulerConfig, PersistentVoteHistoryConfig, PriorityBucketConfig,
+        RepVotingPolicy, RequestAggregatorConfig, VoteCacheConfig, VoteProcessorConfig,
     },
+    representatives::RepStatsConfig,
     stats::StatsConfig,
-    transport::{MessageProcessorConfig, TcpConfig},
+    transport::{MessageCaptureConfig, MessageProcessorConfig, PortMappingConfig, TcpConfig},
     IpcConfig, NetworkParams, DEV_NETWORK_PARAMS,
 };
 use once_cell::sync::Lazy;
 use rand::{thread_rng, Rng};
 use rsban_core::{
     utils::{get_env_or_default_string, is_sanitizer_build, Peer},
-    Account, Amount, PublicKey,
+    Account, Amount, NodeId, PublicKey,
 };
+use rsban_network::TransportEncryption;
+use rsban_nullable_tcp::Socks5Proxy;
 use rsban_store_lmdb::LmdbConfig;
-use std::{cmp::max, net::Ipv6Addr, time::Duration};
+use std::{cmp::max, net::Ipv6Addr, path::PathBuf, time::Duration};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct NodeConfig {
@@ -68,6 +73,8 @@ pub struct NodeConfig {
     pub bootstrap_server: BootstrapServerConfig,
     pub bootstrap_bandwidth_limit: usize,
     pub bootstrap_bandwidth_burst_ratio: f64,
+    /// Compress bootstrap responses for peers that support it
+    pub bootstrap_compression: bool,
     pub confirming_set_batch_time: Duration,
     pub backup_before_upgrade: bool,
     pub max_work_generate_multiplier: f64,
@@ -103,6 +110,22 @@ pub struct NodeConfig {
     pub monitor: MonitorConfig,
     pub backlog: BacklogPopulationConfig,
     pub network_duplicate_filter_cutoff: u64,
+    /// Send ids older than this are removed from the wallets database. Zero keeps them forever.
+    pub send_id_expiry: Duration,
+    pub private_network: PrivateNetworkConfig,
+    pub transport_encryption: TransportEncryption,
+    /// Outbound realtime and bootstrap connections are opened through this proxy
+    pub socks5_proxy: Option<Socks5Proxy>,
+    pub port_mapping: PortMappingConfig,
+    pub message_capture: MessageCaptureConfig,
+    pub rep_stats: RepStatsConfig,
+    pub fork_tracker: ForkTrackerConfig,
+    pub election_tracer: ElectionTracerConfig,
+    pub confirmation_latency: ConfirmationLatencyConfig,
+    pub persistent_vote_history: PersistentVoteHistoryConfig,
+    pub final_vote_cleanup: FinalVoteCleanupConfig,
+    /// Per representative voting behaviour
+    pub voting_policies: Vec<RepVotingPolicy>,
 }
 
 static DEFAULT_LIVE_PEER_NETWORK: Lazy<String> =
@@ -275,6 +298,7 @@ impl NodeConfig {
             bootstrap_bandwidth_limit: 5 * 1024 * 1024,
             // Bootstrap traffic does not need bursts
             bootstrap_bandwidth_burst_ratio: 1.,
+            bootstrap_compression: false,
             bootstrap_ascending: Default::default(),
             bootstrap_server: Default::default(),
             confirming_set_batch_time: Duration::from_millis(250),
@@ -334,6 +358,22 @@ impl NodeConfig {
             monitor: Default::default(),
             backlog: Default::default(),
             network_duplicate_filter_cutoff: 60,
+            send_id_expiry: Duration::ZERO,
+            private_network: Default::default(),
+            transport_encryption: TransportEncryption::Disabled,
+            socks5_proxy: None,
+            port_mapping: PortMappingConfig {
+                enabled: !network_params.network.is_dev_network(),
+                ..Default::default()
+            },
+            message_capture: Default::default(),
+            rep_stats: Default::default(),
+            fork_tracker: Default::default(),
+            election_tracer: Default::default(),
+            confirmation_latency: Default::default(),
+            persistent_vote_history: Default::default(),
+            final_vote_cleanup: Default::default(),
+            voting_policies: Vec::new(),
         }
     }
 
@@ -361,3 +401,17 @@ impl Default for MonitorConfig {
         }
     }
 }
+
+/// Private network mode. If enabled, only nodes in the allowlist can become realtime peers
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PrivateNetworkConfig {
+    pub allowed_node_ids: Vec<NodeId>,
+    /// File with one node id per line. Changes are picked up while the node is running
+    pub allowlist_file: Option<PathBuf>,
+}
+
+impl PrivateNetworkConfig {
+    pub fn enabled(&self) -> bool {
+        !self.allowed_node_ids.is_empty() || self.allowlist_file.is_some()
+    }
+}
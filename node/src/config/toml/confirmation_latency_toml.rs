@@ -0,0 +1,23 @@
+use crate::consensus::ConfirmationLatencyConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct ConfirmationLatencyToml {
+    pub max_tracked: Option<usize>,
+}
+
+impl ConfirmationLatencyConfig {
+    pub fn merge_toml(&mut self, toml: &ConfirmationLatencyToml) {
+        if let Some(max_tracked) = toml.max_tracked {
+            self.max_tracked = max_tracked;
+        }
+    }
+}
+
+impl From<&ConfirmationLatencyConfig> for ConfirmationLatencyToml {
+    fn from(config: &ConfirmationLatencyConfig) -> Self {
+        Self {
+            max_tracked: Some(config.max_tracked),
+        }
+    }
+}
@@ -64,6 +64,7 @@ impl From<&DaemonConfig> for OpenclToml {
 mod tests {
     use crate::config::{DaemonConfig, DaemonToml};
     use rsban_core::Networks;
+    use rsban_nullable_tcp::Socks5Proxy;
     use std::path::PathBuf;
 
     static CUSTOM_TOML_STR: &str = r#"[node]
@@ -73,6 +74,7 @@ mod tests {
         bandwidth_limit_burst_ratio = 999.9
         bootstrap_bandwidth_limit = 999
         bootstrap_bandwidth_burst_ratio = 999.9
+        bootstrap_compression = true
         block_processor_batch_max_time = 999
         bootstrap_connections = 999
         bootstrap_connections_max = 999
@@ -97,9 +99,11 @@ mod tests {
         preconfigured_peers = ["dev.org"]
         preconfigured_representatives = ["ban_3arg3asgtigae3xckabaaewkx3bzsh7nwz7jkmjos79ihyaxwphhm6qgjps4"]
         receive_minimum = "999"
+        send_id_expiry = 999
         signature_checker_threads = 999
         tcp_incoming_connections_max = 999
         tcp_io_timeout = 999
+        transport_encryption = "required"
         unchecked_cutoff_time = 999
         use_memory_pools = false
         vote_generator_delay = 999
@@ -154,6 +158,64 @@ mod tests {
         enable = false
         interval = 999
 
+        [node.private_network]
+        allowed_node_ids = ["node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3"]
+        allowlist_file = "/tmp/allowlist"
+
+        [node.socks5_proxy]
+        address = "127.0.0.1:1080"
+        username = "user"
+        password = "secret"
+
+        [node.port_mapping]
+        enable = false
+        discovery_address = "192.168.1.1:1900"
+        discovery_timeout = 999
+        natpmp_gateway = "192.168.1.1:5351"
+
+        [node.message_capture]
+        enable = true
+        path = "/tmp/capture"
+        max_file_size = 999
+        max_files = 999
+
+        [node.rep_stats]
+        enable = false
+        sample_interval = 999
+        bucket_duration = 999
+        max_buckets = 999
+
+        [node.fork_tracker]
+        max_active = 999
+        max_history = 999
+
+        [node.election_tracer]
+        accounts = ["ban_1111111111111111111111111111111111111111111111111113b8661hfk"]
+        hashes = ["0000000000000000000000000000000000000000000000000000000000000001"]
+        sample_rate = 0.5
+        max_traces = 999
+        max_events = 999
+
+        [node.confirmation_latency]
+        max_tracked = 999
+
+        [node.persistent_vote_history]
+        enable = false
+        window = 999
+
+        [node.final_vote_cleanup]
+        enable = false
+        interval = 999
+        min_depth = 999
+        batch_size = 999
+
+        [[node.voting_policies]]
+        representative = "ban_11111111111111111111111111111111111111111111111111159bmrz31g"
+        enable = false
+        final_votes = false
+        max_votes_per_second = 999
+        key_file = "rep.key"
+
         [node.ipc.local]
         allow_unsafe = true
         enable = true
@@ -303,6 +365,10 @@ mod tests {
             deserialized.node.bootstrap_bandwidth_burst_ratio,
             default_cfg.node.bootstrap_bandwidth_burst_ratio
         );
+        assert_ne!(
+            deserialized.node.bootstrap_compression,
+            default_cfg.node.bootstrap_compression
+        );
         assert_ne!(
             deserialized.node.block_processor_batch_max_time_ms,
             default_cfg.node.block_processor_batch_max_time_ms
@@ -396,6 +462,10 @@ mod tests {
             deserialized.node.receive_minimum,
             default_cfg.node.receive_minimum
         );
+        assert_ne!(
+            deserialized.node.send_id_expiry,
+            default_cfg.node.send_id_expiry
+        );
         assert_ne!(
             deserialized.node.signature_checker_threads,
             default_cfg.node.signature_checker_threads
@@ -408,6 +478,10 @@ mod tests {
             deserialized.node.tcp_io_timeout_s,
             default_cfg.node.tcp_io_timeout_s
         );
+        assert_ne!(
+            deserialized.node.transport_encryption,
+            default_cfg.node.transport_encryption
+        );
         assert_ne!(
             deserialized.node.unchecked_cutoff_time_s,
             default_cfg.node.unchecked_cutoff_time_s
@@ -590,6 +664,144 @@ mod tests {
             default_cfg.node.monitor.interval
         );
 
+        // Private network section
+        assert_ne!(
+            deserialized.node.private_network.allowed_node_ids,
+            default_cfg.node.private_network.allowed_node_ids
+        );
+        assert_ne!(
+            deserialized.node.private_network.allowlist_file,
+            default_cfg.node.private_network.allowlist_file
+        );
+
+        // SOCKS5 proxy section
+        assert_eq!(
+            deserialized.node.socks5_proxy,
+            Some(
+                Socks5Proxy::new("127.0.0.1:1080".parse().unwrap())
+                    .with_credentials("user", "secret")
+            )
+        );
+
+        // Port mapping section
+        assert_ne!(
+            deserialized.node.port_mapping.enabled,
+            default_cfg.node.port_mapping.enabled
+        );
+        assert_ne!(
+            deserialized.node.port_mapping.discovery_address,
+            default_cfg.node.port_mapping.discovery_address
+        );
+        assert_ne!(
+            deserialized.node.port_mapping.discovery_timeout,
+            default_cfg.node.port_mapping.discovery_timeout
+        );
+        assert_ne!(
+            deserialized.node.port_mapping.natpmp_gateway,
+            default_cfg.node.port_mapping.natpmp_gateway
+        );
+
+        // Message capture section
+        assert_ne!(
+            deserialized.node.message_capture.enabled,
+            default_cfg.node.message_capture.enabled
+        );
+        assert_ne!(
+            deserialized.node.message_capture.path,
+            default_cfg.node.message_capture.path
+        );
+        assert_ne!(
+            deserialized.node.message_capture.max_file_size,
+            default_cfg.node.message_capture.max_file_size
+        );
+        assert_ne!(
+            deserialized.node.message_capture.max_files,
+            default_cfg.node.message_capture.max_files
+        );
+
+        // Rep stats section
+        assert_ne!(
+            deserialized.node.rep_stats.enabled,
+            default_cfg.node.rep_stats.enabled
+        );
+        assert_ne!(
+            deserialized.node.rep_stats.sample_interval,
+            default_cfg.node.rep_stats.sample_interval
+        );
+        assert_ne!(
+            deserialized.node.rep_stats.bucket_duration,
+            default_cfg.node.rep_stats.bucket_duration
+        );
+        assert_ne!(
+            deserialized.node.rep_stats.max_buckets,
+            default_cfg.node.rep_stats.max_buckets
+        );
+        assert_ne!(
+            deserialized.node.fork_tracker.max_active,
+            default_cfg.node.fork_tracker.max_active
+        );
+        assert_ne!(
+            deserialized.node.fork_tracker.max_history,
+            default_cfg.node.fork_tracker.max_history
+        );
+        assert_ne!(
+            deserialized.node.election_tracer.accounts,
+            default_cfg.node.election_tracer.accounts
+        );
+        assert_ne!(
+            deserialized.node.election_tracer.hashes,
+            default_cfg.node.election_tracer.hashes
+        );
+        assert_ne!(
+            deserialized.node.election_tracer.sample_rate,
+            default_cfg.node.election_tracer.sample_rate
+        );
+        assert_ne!(
+            deserialized.node.election_tracer.max_traces,
+            default_cfg.node.election_tracer.max_traces
+        );
+        assert_ne!(
+            deserialized.node.election_tracer.max_events,
+            default_cfg.node.election_tracer.max_events
+        );
+        assert_ne!(
+            deserialized.node.confirmation_latency.max_tracked,
+            default_cfg.node.confirmation_latency.max_tracked
+        );
+        assert_ne!(
+            deserialized.node.persistent_vote_history.enabled,
+            default_cfg.node.persistent_vote_history.enabled
+        );
+        assert_ne!(
+            deserialized.node.persistent_vote_history.window,
+            default_cfg.node.persistent_vote_history.window
+        );
+        assert_ne!(
+            deserialized.node.final_vote_cleanup.enabled,
+            default_cfg.node.final_vote_cleanup.enabled
+        );
+        assert_ne!(
+            deserialized.node.final_vote_cleanup.interval,
+            default_cfg.node.final_vote_cleanup.interval
+        );
+        assert_ne!(
+            deserialized.node.final_vote_cleanup.min_depth,
+            default_cfg.node.final_vote_cleanup.min_depth
+        );
+        assert_ne!(
+            deserialized.node.final_vote_cleanup.batch_size,
+            default_cfg.node.final_vote_cleanup.batch_size
+        );
+
+        assert_ne!(
+            deserialized.node.voting_policies,
+            default_cfg.node.voting_policies
+        );
+        assert_eq!(
+            deserialized.node.voting_policies[0].max_votes_per_second,
+            999
+        );
+
         // IPC Local section
         assert_ne!(
             deserialized
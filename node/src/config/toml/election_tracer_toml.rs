@@ -0,0 +1,50 @@
+use crate::consensus::ElectionTracerConfig;
+use rsban_core::{Account, BlockHash};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct ElectionTracerToml {
+    pub accounts: Option<Vec<String>>,
+    pub hashes: Option<Vec<String>>,
+    pub sample_rate: Option<f64>,
+    pub max_traces: Option<usize>,
+    pub max_events: Option<usize>,
+}
+
+impl ElectionTracerConfig {
+    pub fn merge_toml(&mut self, toml: &ElectionTracerToml) {
+        if let Some(accounts) = &toml.accounts {
+            self.accounts = accounts
+                .iter()
+                .map(|a| Account::decode_account(a).expect("Invalid election tracer account"))
+                .collect();
+        }
+        if let Some(hashes) = &toml.hashes {
+            self.hashes = hashes
+                .iter()
+                .map(|h| BlockHash::decode_hex(h).expect("Invalid election tracer hash"))
+                .collect();
+        }
+        if let Some(sample_rate) = toml.sample_rate {
+            self.sample_rate = sample_rate;
+        }
+        if let Some(max_traces) = toml.max_traces {
+            self.max_traces = max_traces;
+        }
+        if let Some(max_events) = toml.max_events {
+            self.max_events = max_events;
+        }
+    }
+}
+
+impl From<&ElectionTracerConfig> for ElectionTracerToml {
+    fn from(config: &ElectionTracerConfig) -> Self {
+        Self {
+            accounts: Some(config.accounts.iter().map(|a| a.encode_account()).collect()),
+            hashes: Some(config.hashes.iter().map(|h| h.to_string()).collect()),
+            sample_rate: Some(config.sample_rate),
+            max_traces: Some(config.max_traces),
+            max_events: Some(config.max_events),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::consensus::FinalVoteCleanupConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct FinalVoteCleanupToml {
+    pub enable: Option<bool>,
+    pub interval: Option<u64>,
+    pub min_depth: Option<u64>,
+    pub batch_size: Option<usize>,
+}
+
+impl FinalVoteCleanupConfig {
+    pub fn merge_toml(&mut self, toml: &FinalVoteCleanupToml) {
+        if let Some(enable) = toml.enable {
+            self.enabled = enable;
+        }
+        if let Some(interval) = toml.interval {
+            self.interval = Duration::from_secs(interval);
+        }
+        if let Some(min_depth) = toml.min_depth {
+            self.min_depth = min_depth;
+        }
+        if let Some(batch_size) = toml.batch_size {
+            self.batch_size = batch_size;
+        }
+    }
+}
+
+impl From<&FinalVoteCleanupConfig> for FinalVoteCleanupToml {
+    fn from(config: &FinalVoteCleanupConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            interval: Some(config.interval.as_secs()),
+            min_depth: Some(config.min_depth),
+            batch_size: Some(config.batch_size),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::consensus::ForkTrackerConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct ForkTrackerToml {
+    pub max_active: Option<usize>,
+    pub max_history: Option<usize>,
+}
+
+impl ForkTrackerConfig {
+    pub fn merge_toml(&mut self, toml: &ForkTrackerToml) {
+        if let Some(max_active) = toml.max_active {
+            self.max_active = max_active;
+        }
+        if let Some(max_history) = toml.max_history {
+            self.max_history = max_history;
+        }
+    }
+}
+
+impl From<&ForkTrackerConfig> for ForkTrackerToml {
+    fn from(config: &ForkTrackerConfig) -> Self {
+        Self {
+            max_active: Some(config.max_active),
+            max_history: Some(config.max_history),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::transport::MessageCaptureConfig;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Deserialize, Serialize)]
+pub struct MessageCaptureToml {
+    pub enable: Option<bool>,
+    pub path: Option<String>,
+    pub max_file_size: Option<u64>,
+    pub max_files: Option<usize>,
+}
+
+impl MessageCaptureConfig {
+    pub fn merge_toml(&mut self, toml: &MessageCaptureToml) {
+        if let Some(enable) = toml.enable {
+            self.enabled = enable;
+        }
+        if let Some(path) = &toml.path {
+            self.path = if path.is_empty() {
+                None
+            } else {
+                Some(PathBuf::from(path))
+            };
+        }
+        if let Some(max_file_size) = toml.max_file_size {
+            self.max_file_size = max_file_size;
+        }
+        if let Some(max_files) = toml.max_files {
+            self.max_files = max_files;
+        }
+    }
+}
+
+impl From<&MessageCaptureConfig> for MessageCaptureToml {
+    fn from(config: &MessageCaptureConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            path: Some(
+                config
+                    .path
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            ),
+            max_file_size: Some(config.max_file_size),
+            max_files: Some(config.max_files),
+        }
+    }
+}
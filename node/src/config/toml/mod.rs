@@ -3,25 +3,36 @@ mod backlog_population_toml;
 mod block_processor_toml;
 mod bootstrap_ascending_toml;
 mod bootstrap_server_toml;
+mod confirmation_latency_toml;
 mod daemon_toml;
 mod diagnostics_toml;
+mod election_tracer_toml;
 mod experimental_toml;
+mod final_vote_cleanup_toml;
+mod fork_tracker_toml;
 mod hinted_scheduler_toml;
 mod httpcallback_toml;
 mod ipc_toml;
 mod lmdb_toml;
+mod message_capture_toml;
 mod message_processor_toml;
 mod monitor_toml;
 mod node_rpc_toml;
 mod node_toml;
 mod opencl_toml;
 mod optimistic_scheduler_toml;
+mod persistent_vote_history_toml;
+mod port_mapping_toml;
 mod priority_bucket_toml;
+mod private_network_toml;
 mod rep_crawler_toml;
+mod rep_stats_toml;
 mod request_aggregator_toml;
+mod socks5_proxy_toml;
 mod stats_toml;
 mod vote_cache_toml;
 mod vote_processor_toml;
+mod voting_policy_toml;
 mod websocket_toml;
 
 pub use active_elections_toml::*;
@@ -29,23 +40,34 @@ pub use backlog_population_toml::*;
 pub use block_processor_toml::*;
 pub use bootstrap_ascending_toml::*;
 pub use bootstrap_server_toml::*;
+pub use confirmation_latency_toml::*;
 pub use daemon_toml::*;
 pub use diagnostics_toml::*;
+pub use election_tracer_toml::*;
 pub use experimental_toml::*;
+pub use final_vote_cleanup_toml::*;
+pub use fork_tracker_toml::*;
 pub use hinted_scheduler_toml::*;
 pub use httpcallback_toml::*;
 pub use ipc_toml::*;
 pub use lmdb_toml::*;
+pub use message_capture_toml::*;
 pub use message_processor_toml::*;
 pub use monitor_toml::*;
 pub use node_rpc_toml::*;
 pub use node_toml::*;
 pub use opencl_toml::*;
 pub use optimistic_scheduler_toml::*;
+pub use persistent_vote_history_toml::*;
+pub use port_mapping_toml::*;
 pub use priority_bucket_toml::*;
+pub use private_network_toml::*;
 pub use rep_crawler_toml::*;
+pub use rep_stats_toml::*;
 pub use request_aggregator_toml::*;
+pub use socks5_proxy_toml::*;
 pub use stats_toml::*;
 pub use vote_cache_toml::*;
 pub use vote_processor_toml::*;
+pub use voting_policy_toml::*;
 pub use websocket_toml::*;
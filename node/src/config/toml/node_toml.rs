@@ -1,6 +1,7 @@
 use super::*;
 use crate::config::NodeConfig;
 use rsban_core::{utils::Peer, Account, Amount};
+use rsban_network::TransportEncryption;
 use serde::{Deserialize, Serialize};
 use std::{str::FromStr, time::Duration};
 
@@ -16,6 +17,7 @@ pub struct NodeToml {
     pub block_processor_batch_max_time: Option<i64>,
     pub bootstrap_bandwidth_burst_ratio: Option<f64>,
     pub bootstrap_bandwidth_limit: Option<usize>,
+    pub bootstrap_compression: Option<bool>,
     pub bootstrap_connections: Option<u32>,
     pub bootstrap_connections_max: Option<u32>,
     pub bootstrap_fraction_numerator: Option<u32>,
@@ -38,12 +40,14 @@ pub struct NodeToml {
     pub preconfigured_peers: Option<Vec<String>>,
     pub preconfigured_representatives: Option<Vec<String>>,
     pub receive_minimum: Option<String>,
+    pub send_id_expiry: Option<u64>,
     pub rep_crawler_weight_minimum: Option<String>,
     pub representative_vote_weight_minimum: Option<String>,
     pub request_aggregator_threads: Option<u32>,
     pub signature_checker_threads: Option<u32>,
     pub tcp_incoming_connections_max: Option<u32>,
     pub tcp_io_timeout: Option<i64>,
+    pub transport_encryption: Option<String>,
     pub unchecked_cutoff_time: Option<i64>,
     pub use_memory_pools: Option<bool>,
     pub vote_generator_delay: Option<i64>,
@@ -60,12 +64,23 @@ pub struct NodeToml {
     pub httpcallback: Option<HttpcallbackToml>,
     pub ipc: Option<IpcToml>,
     pub lmdb: Option<LmdbToml>,
+    pub message_capture: Option<MessageCaptureToml>,
+    pub rep_stats: Option<RepStatsToml>,
+    pub fork_tracker: Option<ForkTrackerToml>,
+    pub election_tracer: Option<ElectionTracerToml>,
+    pub confirmation_latency: Option<ConfirmationLatencyToml>,
+    pub persistent_vote_history: Option<PersistentVoteHistoryToml>,
+    pub final_vote_cleanup: Option<FinalVoteCleanupToml>,
+    pub voting_policies: Option<Vec<VotingPolicyToml>>,
     pub message_processor: Option<MessageProcessorToml>,
     pub monitor: Option<MonitorToml>,
     pub optimistic_scheduler: Option<OptimisticSchedulerToml>,
+    pub port_mapping: Option<PortMappingToml>,
     pub hinted_scheduler: Option<HintedSchedulerToml>,
     pub priority_bucket: Option<PriorityBucketToml>,
+    pub private_network: Option<PrivateNetworkToml>,
     pub rep_crawler: Option<RepCrawlerToml>,
+    pub socks5_proxy: Option<Socks5ProxyToml>,
     pub request_aggregator: Option<RequestAggregatorToml>,
     pub statistics: Option<StatsToml>,
     pub vote_cache: Option<VoteCacheToml>,
@@ -106,6 +121,9 @@ impl NodeConfig {
         if let Some(bootstrap_bandwidth_limit) = toml.bootstrap_bandwidth_limit {
             self.bootstrap_bandwidth_limit = bootstrap_bandwidth_limit;
         }
+        if let Some(bootstrap_compression) = toml.bootstrap_compression {
+            self.bootstrap_compression = bootstrap_compression;
+        }
         if let Some(bootstrap_connections) = toml.bootstrap_connections {
             self.bootstrap_connections = bootstrap_connections;
         }
@@ -182,6 +200,9 @@ impl NodeConfig {
             self.receive_minimum =
                 Amount::decode_dec(&receive_minimum).expect("Invalid receive minimum");
         }
+        if let Some(send_id_expiry) = toml.send_id_expiry {
+            self.send_id_expiry = Duration::from_secs(send_id_expiry);
+        }
         if let Some(rep_crawler) = &toml.rep_crawler {
             if let Some(query_timeout) = rep_crawler.query_timeout {
                 self.rep_crawler_query_timeout = Duration::from_millis(query_timeout);
@@ -204,6 +225,14 @@ impl NodeConfig {
         if let Some(tcp_io_timeout_s) = toml.tcp_io_timeout {
             self.tcp_io_timeout_s = tcp_io_timeout_s;
         }
+        if let Some(transport_encryption) = &toml.transport_encryption {
+            self.transport_encryption = match transport_encryption.as_str() {
+                "disabled" => TransportEncryption::Disabled,
+                "preferred" => TransportEncryption::Preferred,
+                "required" => TransportEncryption::Required,
+                _ => panic!("Invalid transport encryption value"),
+            }
+        }
         if let Some(unchecked_cutoff_time_s) = toml.unchecked_cutoff_time {
             self.unchecked_cutoff_time_s = unchecked_cutoff_time_s;
         }
@@ -318,6 +347,41 @@ impl NodeConfig {
         if let Some(monitor_toml) = &toml.monitor {
             self.monitor = monitor_toml.into();
         }
+        if let Some(private_network_toml) = &toml.private_network {
+            self.private_network = private_network_toml.into();
+        }
+        if let Some(message_capture_toml) = &toml.message_capture {
+            self.message_capture.merge_toml(message_capture_toml);
+        }
+        if let Some(rep_stats_toml) = &toml.rep_stats {
+            self.rep_stats.merge_toml(rep_stats_toml);
+        }
+        if let Some(fork_tracker_toml) = &toml.fork_tracker {
+            self.fork_tracker.merge_toml(fork_tracker_toml);
+        }
+        if let Some(election_tracer_toml) = &toml.election_tracer {
+            self.election_tracer.merge_toml(election_tracer_toml);
+        }
+        if let Some(confirmation_latency_toml) = &toml.confirmation_latency {
+            self.confirmation_latency
+                .merge_toml(confirmation_latency_toml);
+        }
+        if let Some(persistent_vote_history_toml) = &toml.persistent_vote_history {
+            self.persistent_vote_history
+                .merge_toml(persistent_vote_history_toml);
+        }
+        if let Some(final_vote_cleanup_toml) = &toml.final_vote_cleanup {
+            self.final_vote_cleanup.merge_toml(final_vote_cleanup_toml);
+        }
+        if let Some(voting_policies) = &toml.voting_policies {
+            self.voting_policies = voting_policies.iter().map(|p| p.into()).collect();
+        }
+        if let Some(port_mapping_toml) = &toml.port_mapping {
+            self.port_mapping.merge_toml(port_mapping_toml);
+        }
+        if let Some(socks5_proxy_toml) = &toml.socks5_proxy {
+            self.socks5_proxy = socks5_proxy_toml.to_proxy();
+        }
         if let Some(rep_crawler_weight_minimum) = &toml.rep_crawler_weight_minimum {
             self.rep_crawler_weight_minimum = Amount::decode_dec(&rep_crawler_weight_minimum)
                 .expect("Invalid rep crawler weight minimum");
@@ -352,6 +416,7 @@ impl From<&NodeConfig> for NodeToml {
             block_processor_batch_max_time: Some(config.block_processor_batch_max_time_ms),
             bootstrap_bandwidth_burst_ratio: Some(config.bootstrap_bandwidth_burst_ratio),
             bootstrap_bandwidth_limit: Some(config.bootstrap_bandwidth_limit),
+            bootstrap_compression: Some(config.bootstrap_compression),
             bootstrap_connections: Some(config.bootstrap_connections),
             bootstrap_connections_max: Some(config.bootstrap_connections_max),
             bootstrap_fraction_numerator: Some(config.bootstrap_fraction_numerator),
@@ -386,6 +451,7 @@ impl From<&NodeConfig> for NodeToml {
                     .collect(),
             ),
             receive_minimum: Some(config.receive_minimum.to_string_dec()),
+            send_id_expiry: Some(config.send_id_expiry.as_secs()),
             rep_crawler_weight_minimum: Some(config.rep_crawler_weight_minimum.to_string_dec()),
             representative_vote_weight_minimum: Some(
                 config.representative_vote_weight_minimum.to_string_dec(),
@@ -394,6 +460,14 @@ impl From<&NodeConfig> for NodeToml {
             signature_checker_threads: Some(config.signature_checker_threads),
             tcp_incoming_connections_max: Some(config.tcp_incoming_connections_max),
             tcp_io_timeout: Some(config.tcp_io_timeout_s),
+            transport_encryption: Some(
+                match config.transport_encryption {
+                    TransportEncryption::Disabled => "disabled",
+                    TransportEncryption::Preferred => "preferred",
+                    TransportEncryption::Required => "required",
+                }
+                .to_string(),
+            ),
             unchecked_cutoff_time: Some(config.unchecked_cutoff_time_s),
             use_memory_pools: Some(config.use_memory_pools),
             vote_generator_delay: Some(config.vote_generator_delay_ms),
@@ -424,6 +498,17 @@ impl From<&NodeConfig> for NodeToml {
             request_aggregator: Some((&config.request_aggregator).into()),
             message_processor: Some((&config.message_processor).into()),
             monitor: Some((&config.monitor).into()),
+            private_network: Some((&config.private_network).into()),
+            socks5_proxy: config.socks5_proxy.as_ref().map(|proxy| proxy.into()),
+            port_mapping: Some((&config.port_mapping).into()),
+            message_capture: Some((&config.message_capture).into()),
+            rep_stats: Some((&config.rep_stats).into()),
+            fork_tracker: Some((&config.fork_tracker).into()),
+            election_tracer: Some((&config.election_tracer).into()),
+            confirmation_latency: Some((&config.confirmation_latency).into()),
+            persistent_vote_history: Some((&config.persistent_vote_history).into()),
+            final_vote_cleanup: Some((&config.final_vote_cleanup).into()),
+            voting_policies: Some(config.voting_policies.iter().map(|p| p.into()).collect()),
             httpcallback: Some(config.into()),
             rep_crawler: Some(config.into()),
             experimental: Some(config.into()),
@@ -0,0 +1,29 @@
+use crate::consensus::PersistentVoteHistoryConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct PersistentVoteHistoryToml {
+    pub enable: Option<bool>,
+    pub window: Option<u64>,
+}
+
+impl PersistentVoteHistoryConfig {
+    pub fn merge_toml(&mut self, toml: &PersistentVoteHistoryToml) {
+        if let Some(enable) = toml.enable {
+            self.enabled = enable;
+        }
+        if let Some(window) = toml.window {
+            self.window = Duration::from_secs(window);
+        }
+    }
+}
+
+impl From<&PersistentVoteHistoryConfig> for PersistentVoteHistoryToml {
+    fn from(config: &PersistentVoteHistoryConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            window: Some(config.window.as_secs()),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::transport::PortMappingConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct PortMappingToml {
+    pub enable: Option<bool>,
+    pub discovery_address: Option<String>,
+    pub discovery_timeout: Option<u64>,
+    pub natpmp_gateway: Option<String>,
+}
+
+impl PortMappingConfig {
+    pub fn merge_toml(&mut self, toml: &PortMappingToml) {
+        if let Some(enable) = toml.enable {
+            self.enabled = enable;
+        }
+        if let Some(address) = &toml.discovery_address {
+            self.discovery_address = address
+                .parse()
+                .expect("Invalid port mapping discovery address");
+        }
+        if let Some(timeout) = toml.discovery_timeout {
+            self.discovery_timeout = Duration::from_millis(timeout);
+        }
+        if let Some(gateway) = &toml.natpmp_gateway {
+            self.natpmp_gateway = if gateway.is_empty() {
+                None
+            } else {
+                Some(gateway.parse().expect("Invalid NAT-PMP gateway address"))
+            };
+        }
+    }
+}
+
+impl From<&PortMappingConfig> for PortMappingToml {
+    fn from(config: &PortMappingConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            discovery_address: Some(config.discovery_address.to_string()),
+            discovery_timeout: Some(config.discovery_timeout.as_millis() as u64),
+            natpmp_gateway: Some(
+                config
+                    .natpmp_gateway
+                    .map(|gateway| gateway.to_string())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::config::PrivateNetworkConfig;
+use rsban_core::NodeId;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Deserialize, Serialize)]
+pub struct PrivateNetworkToml {
+    pub allowed_node_ids: Option<Vec<String>>,
+    pub allowlist_file: Option<String>,
+}
+
+impl Default for PrivateNetworkToml {
+    fn default() -> Self {
+        let config = PrivateNetworkConfig::default();
+        (&config).into()
+    }
+}
+
+impl From<&PrivateNetworkToml> for PrivateNetworkConfig {
+    fn from(toml: &PrivateNetworkToml) -> Self {
+        let mut config = PrivateNetworkConfig::default();
+
+        if let Some(node_ids) = &toml.allowed_node_ids {
+            config.allowed_node_ids = node_ids
+                .iter()
+                .map(|id| id.parse::<NodeId>().expect("Invalid allowed node id"))
+                .collect();
+        }
+        if let Some(file) = &toml.allowlist_file {
+            if !file.is_empty() {
+                config.allowlist_file = Some(PathBuf::from(file));
+            }
+        }
+        config
+    }
+}
+
+impl From<&PrivateNetworkConfig> for PrivateNetworkToml {
+    fn from(config: &PrivateNetworkConfig) -> Self {
+        Self {
+            allowed_node_ids: Some(
+                config
+                    .allowed_node_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect(),
+            ),
+            allowlist_file: config
+                .allowlist_file
+                .as_ref()
+                .map(|file| file.to_string_lossy().into_owned()),
+        }
+    }
+}
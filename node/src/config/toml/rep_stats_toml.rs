@@ -0,0 +1,39 @@
+use crate::representatives::RepStatsConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct RepStatsToml {
+    pub enable: Option<bool>,
+    pub sample_interval: Option<u64>,
+    pub bucket_duration: Option<u64>,
+    pub max_buckets: Option<usize>,
+}
+
+impl RepStatsConfig {
+    pub fn merge_toml(&mut self, toml: &RepStatsToml) {
+        if let Some(enable) = toml.enable {
+            self.enabled = enable;
+        }
+        if let Some(interval) = toml.sample_interval {
+            self.sample_interval = Duration::from_secs(interval);
+        }
+        if let Some(duration) = toml.bucket_duration {
+            self.bucket_duration = Duration::from_secs(duration);
+        }
+        if let Some(max_buckets) = toml.max_buckets {
+            self.max_buckets = max_buckets;
+        }
+    }
+}
+
+impl From<&RepStatsConfig> for RepStatsToml {
+    fn from(config: &RepStatsConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            sample_interval: Some(config.sample_interval.as_secs()),
+            bucket_duration: Some(config.bucket_duration.as_secs()),
+            max_buckets: Some(config.max_buckets),
+        }
+    }
+}
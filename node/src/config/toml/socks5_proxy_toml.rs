@@ -0,0 +1,37 @@
+use rsban_nullable_tcp::Socks5Proxy;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct Socks5ProxyToml {
+    pub address: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Socks5ProxyToml {
+    /// Returns `None` if no proxy address is configured
+    pub fn to_proxy(&self) -> Option<Socks5Proxy> {
+        let address = self.address.as_ref().filter(|a| !a.is_empty())?;
+        let address = address
+            .parse::<SocketAddr>()
+            .expect("Invalid SOCKS5 proxy address");
+        let proxy = Socks5Proxy::new(address);
+        match &self.username {
+            Some(username) if !username.is_empty() => Some(
+                proxy.with_credentials(username.clone(), self.password.clone().unwrap_or_default()),
+            ),
+            _ => Some(proxy),
+        }
+    }
+}
+
+impl From<&Socks5Proxy> for Socks5ProxyToml {
+    fn from(proxy: &Socks5Proxy) -> Self {
+        Self {
+            address: Some(proxy.address.to_string()),
+            username: proxy.credentials.as_ref().map(|c| c.username.clone()),
+            password: proxy.credentials.as_ref().map(|c| c.password.clone()),
+        }
+    }
+}
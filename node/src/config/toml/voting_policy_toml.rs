@@ -0,0 +1,44 @@
+use crate::consensus::RepVotingPolicy;
+use rsban_core::Account;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Deserialize, Serialize)]
+pub struct VotingPolicyToml {
+    pub representative: String,
+    pub enable: Option<bool>,
+    pub final_votes: Option<bool>,
+    pub max_votes_per_second: Option<usize>,
+    pub key_file: Option<PathBuf>,
+}
+
+impl From<&VotingPolicyToml> for RepVotingPolicy {
+    fn from(toml: &VotingPolicyToml) -> Self {
+        let representative = Account::decode_account(&toml.representative)
+            .expect("Invalid voting policy representative");
+        let mut policy = RepVotingPolicy::new(representative);
+        if let Some(enable) = toml.enable {
+            policy.enabled = enable;
+        }
+        if let Some(final_votes) = toml.final_votes {
+            policy.final_votes = final_votes;
+        }
+        if let Some(max_votes_per_second) = toml.max_votes_per_second {
+            policy.max_votes_per_second = max_votes_per_second;
+        }
+        policy.key_file = toml.key_file.clone();
+        policy
+    }
+}
+
+impl From<&RepVotingPolicy> for VotingPolicyToml {
+    fn from(policy: &RepVotingPolicy) -> Self {
+        Self {
+            representative: policy.representative.encode_account(),
+            enable: Some(policy.enabled),
+            final_votes: Some(policy.final_votes),
+            max_votes_per_second: Some(policy.max_votes_per_second),
+            key_file: policy.key_file.clone(),
+        }
+    }
+}
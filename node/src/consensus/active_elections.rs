@@ -1,8 +1,8 @@
 use super::{
     confirmation_solicitor::ConfirmationSolicitor, election_schedulers::ElectionSchedulers,
     Election, ElectionBehavior, ElectionData, ElectionState, ElectionStatus, ElectionStatusType,
-    RecentlyConfirmedCache, VoteApplier, VoteCache, VoteCacheProcessor, VoteGenerators, VoteRouter,
-    NEXT_ELECTION_ID,
+    ElectionTracer, RecentlyConfirmedCache, VoteApplier, VoteCache, VoteCacheProcessor,
+    VoteGenerators, VoteRouter, NEXT_ELECTION_ID,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -13,7 +13,6 @@ use crate::{
     stats::{DetailType, Direction, Sample, StatType, Stats},
     transport::{MessagePublisher, NetworkFilter},
     utils::HardenedConstants,
-    wallets::Wallets,
     NetworkParams,
 };
 use bounded_vec_deque::BoundedVecDeque;
@@ -34,7 +33,7 @@ use std::{
     ops::Deref,
     sync::{atomic::Ordering, Arc, Condvar, Mutex, MutexGuard, RwLock, Weak},
     thread::JoinHandle,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use tracing::{debug, trace};
 
@@ -44,6 +43,9 @@ pub type ElectionEndCallback = Box<
     dyn Fn(&ElectionStatus, &Vec<VoteWithWeightInfo>, Account, Amount, bool, bool) + Send + Sync,
 >;
 
+/// Called when an election expired or was cancelled without being confirmed
+pub type ElectionDroppedCallback = Box<dyn Fn(&Election) + Send + Sync>;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ActiveElectionsConfig {
     /// Maximum number of simultaneous active elections (AEC size)
@@ -78,7 +80,6 @@ pub struct ActiveElections {
     mutex: Mutex<ActiveElectionsState>,
     condition: Condvar,
     network_params: NetworkParams,
-    wallets: Arc<Wallets>,
     node_config: NodeConfig,
     config: ActiveElectionsConfig,
     ledger: Arc<Ledger>,
@@ -96,6 +97,7 @@ pub struct ActiveElections {
     active_started_observer: Mutex<Vec<Box<dyn Fn(BlockHash) + Send + Sync>>>,
     active_stopped_observer: Mutex<Vec<Box<dyn Fn(BlockHash) + Send + Sync>>>,
     election_end: Mutex<Vec<ElectionEndCallback>>,
+    election_dropped: Mutex<Vec<ElectionDroppedCallback>>,
     online_reps: Arc<Mutex<OnlineReps>>,
     thread: Mutex<Option<JoinHandle<()>>>,
     flags: NodeFlags,
@@ -103,12 +105,12 @@ pub struct ActiveElections {
     pub vote_router: Arc<VoteRouter>,
     vote_cache_processor: Arc<VoteCacheProcessor>,
     message_publisher: Mutex<MessagePublisher>,
+    election_tracer: Arc<ElectionTracer>,
 }
 
 impl ActiveElections {
     pub(crate) fn new(
         network_params: NetworkParams,
-        wallets: Arc<Wallets>,
         node_config: NodeConfig,
         ledger: Arc<Ledger>,
         confirming_set: Arc<ConfirmingSet>,
@@ -127,6 +129,7 @@ impl ActiveElections {
         vote_cache_processor: Arc<VoteCacheProcessor>,
         steady_clock: Arc<SteadyClock>,
         message_publisher: MessagePublisher,
+        election_tracer: Arc<ElectionTracer>,
     ) -> Self {
         Self {
             mutex: Mutex::new(ActiveElectionsState {
@@ -139,7 +142,6 @@ impl ActiveElections {
             }),
             condition: Condvar::new(),
             network_params,
-            wallets,
             ledger,
             confirming_set,
             recently_confirmed,
@@ -157,6 +159,7 @@ impl ActiveElections {
             active_started_observer: Mutex::new(Vec::new()),
             active_stopped_observer: Mutex::new(Vec::new()),
             election_end: Mutex::new(vec![election_end]),
+            election_dropped: Mutex::new(Vec::new()),
             online_reps,
             thread: Mutex::new(None),
             flags,
@@ -166,6 +169,7 @@ impl ActiveElections {
             steady_clock,
             message_publisher: Mutex::new(message_publisher),
             election_schedulers: RwLock::new(None),
+            election_tracer,
         }
     }
 
@@ -192,6 +196,10 @@ impl ActiveElections {
         self.election_end.lock().unwrap().push(f);
     }
 
+    pub fn on_election_dropped(&self, f: ElectionDroppedCallback) {
+        self.election_dropped.lock().unwrap().push(f);
+    }
+
     pub fn on_active_started(&self, f: Box<dyn Fn(BlockHash) + Send + Sync>) {
         self.active_started_observer.lock().unwrap().push(f);
     }
@@ -594,7 +602,7 @@ impl ActiveElections {
             return;
         }
         election_guard.set_last_vote();
-        if self.node_config.enable_voting && self.wallets.voting_reps_count() > 0 {
+        if self.vote_applier.is_voting() {
             self.stats
                 .inc(StatType::Election, DetailType::BroadcastVote);
 
@@ -638,6 +646,8 @@ impl ActiveElections {
             blocks = election_guard.last_blocks.clone();
             election_winner = election_guard.status.winner.as_ref().unwrap().hash();
             election_state = election_guard.state;
+            self.election_tracer
+                .election_stopped(&election.qualified_root, &election_guard);
         }
 
         self.vote_router.disconnect_election(election);
@@ -690,6 +700,12 @@ impl ActiveElections {
             callback(election)
         }
 
+        if !state.is_confirmed() {
+            for callback in self.election_dropped.lock().unwrap().iter() {
+                callback(election)
+            }
+        }
+
         self.vacancy_updated();
 
         for (hash, block) in blocks {
@@ -923,6 +939,15 @@ impl ActiveElections {
     ) {
         if self.confirm_req_time(election) < election.last_req_elapsed() {
             if !solicitor.add(election, election_guard) {
+                if self.election_tracer.is_tracing(&election.qualified_root) {
+                    let hash = election_guard.status.winner.as_ref().unwrap().hash();
+                    self.election_tracer.confirm_req_sent(
+                        &election.qualified_root,
+                        hash,
+                        solicitor.requested_channels(&hash),
+                        SystemTime::now(),
+                    );
+                }
                 election.set_last_req();
                 election
                     .confirmation_request_count
@@ -1267,6 +1292,8 @@ impl ActiveElectionsExt for Arc<ActiveElections> {
             drop(guard);
             result = self.publish(block, &election);
             if !result {
+                self.election_tracer
+                    .block_added(&root, block.hash(), SystemTime::now());
                 guard = self.mutex.lock().unwrap();
                 self.vote_router
                     .connect(block.hash(), Arc::downgrade(&election));
@@ -1300,6 +1327,7 @@ impl ActiveElectionsExt for Arc<ActiveElections> {
 
         let root = block.qualified_root();
         let hash = block.hash();
+        let account = block.account();
         let existing = guard.roots.get(&root);
 
         if let Some(existing) = existing {
@@ -1323,7 +1351,7 @@ impl ActiveElectionsExt for Arc<ActiveElections> {
                     observer_rep_cb,
                 ));
                 guard.roots.insert(Entry {
-                    root,
+                    root: root.clone(),
                     election: election.clone(),
                     erased_callback,
                 });
@@ -1353,6 +1381,13 @@ impl ActiveElectionsExt for Arc<ActiveElections> {
 
         if inserted {
             debug_assert!(election_result.is_some());
+            self.election_tracer.election_started(
+                &root,
+                account,
+                hash,
+                election_behavior,
+                SystemTime::now(),
+            );
 
             self.vote_cache_processor.trigger(hash);
 
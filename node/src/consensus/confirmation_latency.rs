@@ -0,0 +1,381 @@
+use super::{ElectionBehavior, ElectionStatus, ElectionStatusType};
+use crate::block_processing::BlockSource;
+use rsban_core::{utils::ContainerInfo, BlockHash};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfirmationLatencyConfig {
+    /// Maximum number of blocks whose timestamps are kept until they get cemented
+    pub max_tracked: usize,
+}
+
+impl Default for ConfirmationLatencyConfig {
+    fn default() -> Self {
+        Self {
+            max_tracked: 64 * 1024,
+        }
+    }
+}
+
+/// Points in time a block passed on its way to being cemented
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockTimestamps {
+    pub source: Option<BlockSource>,
+    pub behavior: Option<ElectionBehavior>,
+    /// First arrival in the block processor
+    pub arrival: Option<SystemTime>,
+    pub election_started: Option<SystemTime>,
+    pub quorum_reached: Option<SystemTime>,
+    pub cemented: Option<SystemTime>,
+}
+
+impl BlockTimestamps {
+    pub fn arrival_to_election(&self) -> Option<Duration> {
+        between(self.arrival, self.election_started)
+    }
+
+    pub fn election_to_quorum(&self) -> Option<Duration> {
+        between(self.election_started, self.quorum_reached)
+    }
+
+    pub fn quorum_to_cemented(&self) -> Option<Duration> {
+        between(self.quorum_reached, self.cemented)
+    }
+
+    pub fn arrival_to_cemented(&self) -> Option<Duration> {
+        between(self.arrival, self.cemented)
+    }
+}
+
+fn between(from: Option<SystemTime>, to: Option<SystemTime>) -> Option<Duration> {
+    Some(to?.duration_since(from?).unwrap_or_default())
+}
+
+/// Upper bounds of the histogram buckets in milliseconds. Samples above the last bound
+/// are only counted in the overflow bucket
+pub const LATENCY_BUCKETS_MS: [u64; 12] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000, 60_000, 300_000, 600_000,
+];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    /// Sample count per bucket of `LATENCY_BUCKETS_MS` plus one overflow bucket
+    pub buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    pub count: u64,
+    pub sum_ms: u64,
+}
+
+impl LatencyHistogram {
+    pub fn add(&mut self, latency: Duration) {
+        let ms = latency.as_millis() as u64;
+        let index = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[index] += 1;
+        self.count += 1;
+        self.sum_ms = self.sum_ms.saturating_add(ms);
+    }
+}
+
+/// Histograms for each stage a block goes through until it is cemented
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyHistograms {
+    pub arrival_to_election: LatencyHistogram,
+    pub election_to_quorum: LatencyHistogram,
+    pub quorum_to_cemented: LatencyHistogram,
+    pub arrival_to_cemented: LatencyHistogram,
+}
+
+impl LatencyHistograms {
+    fn add(&mut self, timestamps: &BlockTimestamps) {
+        if let Some(latency) = timestamps.arrival_to_election() {
+            self.arrival_to_election.add(latency);
+        }
+        if let Some(latency) = timestamps.election_to_quorum() {
+            self.election_to_quorum.add(latency);
+        }
+        if let Some(latency) = timestamps.quorum_to_cemented() {
+            self.quorum_to_cemented.add(latency);
+        }
+        if let Some(latency) = timestamps.arrival_to_cemented() {
+            self.arrival_to_cemented.add(latency);
+        }
+    }
+}
+
+/// Number of cemented blocks whose timestamps stay available for lookups
+const MAX_MEASURED: usize = 1024;
+
+/// Measures how long blocks take from their first arrival in the block processor
+/// until they are cemented
+pub struct ConfirmationLatency {
+    config: ConfirmationLatencyConfig,
+    data: Mutex<ConfirmationLatencyData>,
+}
+
+#[derive(Default)]
+struct ConfirmationLatencyData {
+    tracked: HashMap<BlockHash, BlockTimestamps>,
+    /// Oldest first. May contain hashes that were already removed from `tracked`
+    order: VecDeque<BlockHash>,
+    /// Recently cemented blocks, so that observers of the same cementation can still look them up
+    measured: HashMap<BlockHash, BlockTimestamps>,
+    measured_order: VecDeque<BlockHash>,
+    by_source: HashMap<BlockSource, LatencyHistograms>,
+    by_behavior: HashMap<ElectionBehavior, LatencyHistograms>,
+}
+
+impl ConfirmationLatencyData {
+    fn insert_tracked(&mut self, hash: BlockHash, max_tracked: usize) -> &mut BlockTimestamps {
+        if !self.tracked.contains_key(&hash) {
+            while self.tracked.len() >= max_tracked {
+                let Some(oldest) = self.order.pop_front() else {
+                    break;
+                };
+                self.tracked.remove(&oldest);
+            }
+            self.order.push_back(hash);
+        }
+        self.tracked.entry(hash).or_default()
+    }
+
+    fn insert_measured(&mut self, hash: BlockHash, timestamps: BlockTimestamps) {
+        if self.measured.insert(hash, timestamps).is_none() {
+            self.measured_order.push_back(hash);
+        }
+        while self.measured_order.len() > MAX_MEASURED {
+            if let Some(oldest) = self.measured_order.pop_front() {
+                self.measured.remove(&oldest);
+            }
+        }
+    }
+}
+
+impl ConfirmationLatency {
+    pub fn new(config: ConfirmationLatencyConfig) -> Self {
+        Self {
+            config,
+            data: Mutex::new(ConfirmationLatencyData::default()),
+        }
+    }
+
+    pub fn block_arrived(&self, hash: BlockHash, source: BlockSource, arrival: SystemTime) {
+        if self.config.max_tracked == 0 {
+            return;
+        }
+        let mut data = self.data.lock().unwrap();
+        let entry = data.insert_tracked(hash, self.config.max_tracked);
+        if entry.arrival.is_none() {
+            entry.arrival = Some(arrival);
+            entry.source = Some(source);
+        }
+    }
+
+    /// Called for every cemented block. Election start and quorum are derived from the
+    /// status if the block was confirmed by its own election
+    pub fn election_ended(&self, status: &ElectionStatus, now: SystemTime) {
+        if self.config.max_tracked == 0 {
+            return;
+        }
+        let Some(winner) = &status.winner else {
+            return;
+        };
+        let hash = winner.hash();
+        let confirmed_by_election =
+            status.election_status_type == ElectionStatusType::ActiveConfirmedQuorum;
+
+        let mut data = self.data.lock().unwrap();
+        let mut timestamps = match data.tracked.remove(&hash) {
+            Some(timestamps) => timestamps,
+            None if confirmed_by_election => BlockTimestamps::default(),
+            None => return,
+        };
+        if confirmed_by_election {
+            timestamps.behavior = status.behavior;
+            timestamps.election_started = status.election_end.checked_sub(status.election_duration);
+            timestamps.quorum_reached = Some(status.election_end);
+        }
+        timestamps.cemented = Some(now);
+
+        if let Some(source) = timestamps.source {
+            data.by_source.entry(source).or_default().add(&timestamps);
+        }
+        if let Some(behavior) = timestamps.behavior {
+            data.by_behavior
+                .entry(behavior)
+                .or_default()
+                .add(&timestamps);
+        }
+        data.insert_measured(hash, timestamps);
+
+        // Keep the order queue from growing when blocks get cemented quickly
+        if data.order.len() > data.tracked.len() * 2 + MAX_MEASURED {
+            let ConfirmationLatencyData { tracked, order, .. } = &mut *data;
+            order.retain(|h| tracked.contains_key(h));
+        }
+    }
+
+    /// Timestamps of a block that is not cemented yet or was cemented recently
+    pub fn timestamps(&self, hash: &BlockHash) -> Option<BlockTimestamps> {
+        let data = self.data.lock().unwrap();
+        data.tracked
+            .get(hash)
+            .or_else(|| data.measured.get(hash))
+            .cloned()
+    }
+
+    pub fn by_source(&self) -> HashMap<BlockSource, LatencyHistograms> {
+        self.data.lock().unwrap().by_source.clone()
+    }
+
+    pub fn by_behavior(&self) -> HashMap<ElectionBehavior, LatencyHistograms> {
+        self.data.lock().unwrap().by_behavior.clone()
+    }
+
+    pub fn clear(&self) {
+        let mut data = self.data.lock().unwrap();
+        data.by_source.clear();
+        data.by_behavior.clear();
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        let data = self.data.lock().unwrap();
+        [
+            ("tracked", data.tracked.len(), 0),
+            ("order", data.order.len(), 0),
+            ("measured", data.measured.len(), 0),
+        ]
+        .into()
+    }
+}
+
+impl Default for ConfirmationLatency {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::{MaybeSavedBlock, SavedBlock};
+
+    #[test]
+    fn histogram_buckets() {
+        let mut histogram = LatencyHistogram::default();
+        histogram.add(Duration::from_millis(0));
+        histogram.add(Duration::from_millis(50));
+        histogram.add(Duration::from_millis(51));
+        histogram.add(Duration::from_secs(3600));
+        assert_eq!(histogram.count, 4);
+        assert_eq!(histogram.sum_ms, 3_600_101);
+        assert_eq!(histogram.buckets[0], 2);
+        assert_eq!(histogram.buckets[1], 1);
+        assert_eq!(histogram.buckets[LATENCY_BUCKETS_MS.len()], 1);
+    }
+
+    #[test]
+    fn measure_all_stages() {
+        let latency = ConfirmationLatency::default();
+        let block = SavedBlock::new_test_instance();
+        let arrival = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        latency.block_arrived(block.hash(), BlockSource::Live, arrival);
+
+        let status = ElectionStatus {
+            winner: Some(MaybeSavedBlock::Saved(block.clone())),
+            election_status_type: ElectionStatusType::ActiveConfirmedQuorum,
+            election_end: arrival + Duration::from_millis(700),
+            election_duration: Duration::from_millis(600),
+            behavior: Some(ElectionBehavior::Priority),
+            ..Default::default()
+        };
+        latency.election_ended(&status, arrival + Duration::from_millis(900));
+
+        let timestamps = latency.timestamps(&block.hash()).unwrap();
+        assert_eq!(
+            timestamps.arrival_to_election(),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            timestamps.election_to_quorum(),
+            Some(Duration::from_millis(600))
+        );
+
+        let by_source = latency.by_source();
+        let live = by_source.get(&BlockSource::Live).unwrap();
+        assert_eq!(live.arrival_to_election.sum_ms, 100);
+        assert_eq!(live.election_to_quorum.sum_ms, 600);
+        assert_eq!(live.quorum_to_cemented.sum_ms, 200);
+        assert_eq!(live.arrival_to_cemented.sum_ms, 900);
+
+        let by_behavior = latency.by_behavior();
+        let priority = by_behavior.get(&ElectionBehavior::Priority).unwrap();
+        assert_eq!(priority.arrival_to_cemented.count, 1);
+    }
+
+    #[test]
+    fn cemented_without_election() {
+        let latency = ConfirmationLatency::default();
+        let block = SavedBlock::new_test_instance();
+        let arrival = SystemTime::now();
+        latency.block_arrived(block.hash(), BlockSource::Bootstrap, arrival);
+
+        let status = ElectionStatus {
+            winner: Some(MaybeSavedBlock::Saved(block)),
+            election_status_type: ElectionStatusType::InactiveConfirmationHeight,
+            ..Default::default()
+        };
+        latency.election_ended(&status, arrival + Duration::from_secs(2));
+
+        let by_source = latency.by_source();
+        let bootstrap = by_source.get(&BlockSource::Bootstrap).unwrap();
+        assert_eq!(bootstrap.arrival_to_cemented.count, 1);
+        assert_eq!(bootstrap.arrival_to_cemented.sum_ms, 2000);
+        assert_eq!(bootstrap.arrival_to_election.count, 0);
+        assert!(latency.by_behavior().is_empty());
+    }
+
+    #[test]
+    fn ignore_untracked_blocks_without_election() {
+        let latency = ConfirmationLatency::default();
+        let block = SavedBlock::new_test_instance();
+        let status = ElectionStatus {
+            winner: Some(MaybeSavedBlock::Saved(block.clone())),
+            election_status_type: ElectionStatusType::InactiveConfirmationHeight,
+            ..Default::default()
+        };
+        latency.election_ended(&status, SystemTime::now());
+        assert_eq!(latency.timestamps(&block.hash()), None);
+        assert!(latency.by_source().is_empty());
+    }
+
+    #[test]
+    fn keep_first_arrival() {
+        let latency = ConfirmationLatency::default();
+        let hash = BlockHash::from(1);
+        let first = SystemTime::now();
+        latency.block_arrived(hash, BlockSource::Live, first);
+        latency.block_arrived(hash, BlockSource::Unchecked, first + Duration::from_secs(1));
+
+        let timestamps = latency.timestamps(&hash).unwrap();
+        assert_eq!(timestamps.arrival, Some(first));
+        assert_eq!(timestamps.source, Some(BlockSource::Live));
+    }
+
+    #[test]
+    fn evict_oldest_when_full() {
+        let latency = ConfirmationLatency::new(ConfirmationLatencyConfig { max_tracked: 2 });
+        let now = SystemTime::now();
+        for i in 1..=3 {
+            latency.block_arrived(BlockHash::from(i), BlockSource::Live, now);
+        }
+        assert_eq!(latency.timestamps(&BlockHash::from(1)), None);
+        assert!(latency.timestamps(&BlockHash::from(2)).is_some());
+        assert!(latency.timestamps(&BlockHash::from(3)).is_some());
+    }
+}
@@ -158,6 +158,15 @@ impl<'a> ConfirmationSolicitor<'a> {
         error
     }
 
+    /// Channels that receive a confirmation request for the given hash on the next flush
+    pub fn requested_channels(&self, hash: &BlockHash) -> Vec<ChannelId> {
+        self.requests
+            .iter()
+            .filter(|(_, requests)| requests.iter().any(|(h, _)| h == hash))
+            .map(|(channel_id, _)| *channel_id)
+            .collect()
+    }
+
     /// Dispatch bundled requests to each channel
     pub fn flush(&mut self) {
         debug_assert!(self.prepared);
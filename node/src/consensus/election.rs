@@ -3,12 +3,14 @@ use crate::{
     stats::{DetailType, StatType},
     utils::HardenedConstants,
 };
+use anyhow::anyhow;
 use rsban_core::{
     Amount, Block, BlockHash, MaybeSavedBlock, PublicKey, QualifiedRoot, Root, SavedBlock,
 };
 use std::{
     collections::HashMap,
     fmt::Debug,
+    str::FromStr,
     sync::{
         atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
         Mutex, RwLock,
@@ -56,6 +58,7 @@ impl Election {
                 election_end: SystemTime::now(),
                 block_count: 1,
                 election_status_type: super::ElectionStatusType::Ongoing,
+                behavior: Some(behavior),
                 ..Default::default()
             },
             last_votes: HashMap::from([(
@@ -69,6 +72,7 @@ impl Election {
             final_weight: Amount::zero(),
             last_vote: None,
             last_block_hash: BlockHash::zero(),
+            state_history: Vec::new(),
         };
 
         Self {
@@ -185,6 +189,8 @@ pub struct ElectionData {
     /** The last time vote for this election was generated */
     pub last_vote: Option<Instant>,
     pub last_block_hash: BlockHash,
+    /// State transitions with the time they happened
+    pub state_history: Vec<(ElectionState, SystemTime)>,
 }
 
 impl ElectionData {
@@ -213,6 +219,7 @@ impl ElectionData {
             if self.state == expected {
                 self.state = desired;
                 self.state_start = Instant::now();
+                self.state_history.push((desired, SystemTime::now()));
                 return Ok(());
             }
         }
@@ -320,7 +327,7 @@ impl From<ElectionState> for DetailType {
     }
 }
 
-#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum ElectionBehavior {
     Manual,
@@ -340,6 +347,20 @@ pub enum ElectionBehavior {
     Optimistic,
 }
 
+impl FromStr for ElectionBehavior {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(Self::Manual),
+            "priority" => Ok(Self::Priority),
+            "hinted" => Ok(Self::Hinted),
+            "optimistic" => Ok(Self::Optimistic),
+            _ => Err(anyhow!("Invalid election behavior: {}", s)),
+        }
+    }
+}
+
 impl From<ElectionBehavior> for DetailType {
     fn from(value: ElectionBehavior) -> Self {
         match value {
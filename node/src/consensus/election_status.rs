@@ -1,3 +1,4 @@
+use super::ElectionBehavior;
 use crate::stats::DetailType;
 use rsban_core::{Amount, MaybeSavedBlock};
 use std::time::{Duration, SystemTime};
@@ -53,6 +54,8 @@ pub struct ElectionStatus {
     pub election_end: SystemTime,
     pub election_duration: Duration,
     pub election_status_type: ElectionStatusType,
+    /// Behavior of the election that confirmed the block. None for inactive confirmations
+    pub behavior: Option<ElectionBehavior>,
 }
 
 impl Default for ElectionStatus {
@@ -67,6 +70,7 @@ impl Default for ElectionStatus {
             election_end: SystemTime::now(),
             election_duration: Duration::ZERO,
             election_status_type: ElectionStatusType::InactiveConfirmationHeight,
+            behavior: None,
         }
     }
 }
@@ -0,0 +1,589 @@
+use super::{ElectionBehavior, ElectionData, ElectionState};
+use rand::{thread_rng, Rng};
+use rsban_core::{
+    utils::ContainerInfo, Account, Amount, BlockHash, PublicKey, QualifiedRoot, Vote, VoteCode,
+    VoteSource,
+};
+use rsban_network::ChannelId;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+/// Elections are traced if they match one of the configured accounts or hashes,
+/// or if they are picked by the sample rate
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElectionTracerConfig {
+    pub accounts: Vec<Account>,
+    pub hashes: Vec<BlockHash>,
+    /// Fraction of all other elections that are traced (0.0 - 1.0)
+    pub sample_rate: f64,
+    /// Maximum number of finished traces that are kept
+    pub max_traces: usize,
+    /// Maximum number of events recorded per election
+    pub max_events: usize,
+}
+
+impl ElectionTracerConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.accounts.is_empty() || !self.hashes.is_empty() || self.sample_rate > 0.0
+    }
+}
+
+impl Default for ElectionTracerConfig {
+    fn default() -> Self {
+        Self {
+            accounts: Vec::new(),
+            hashes: Vec::new(),
+            sample_rate: 0.0,
+            max_traces: 1024,
+            max_events: 1024,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ElectionTraceEvent {
+    Started {
+        behavior: ElectionBehavior,
+    },
+    /// A competing block was added to the election
+    BlockAdded {
+        hash: BlockHash,
+    },
+    Vote {
+        representative: PublicKey,
+        hash: BlockHash,
+        weight: Amount,
+        is_final: bool,
+        channel_id: ChannelId,
+        source: VoteSource,
+        code: VoteCode,
+    },
+    ConfirmReq {
+        hash: BlockHash,
+        channels: Vec<ChannelId>,
+    },
+    StateChanged {
+        state: ElectionState,
+    },
+    Cemented {
+        hash: BlockHash,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElectionTraceEntry {
+    pub time: SystemTime,
+    pub event: ElectionTraceEvent,
+}
+
+/// Timeline of a single election
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElectionTrace {
+    pub root: QualifiedRoot,
+    pub account: Account,
+    /// The block the election was started for
+    pub hash: BlockHash,
+    pub behavior: ElectionBehavior,
+    pub started: SystemTime,
+    pub winner: Option<BlockHash>,
+    /// Set once the election was removed from the active elections container
+    pub final_state: Option<ElectionState>,
+    pub events: Vec<ElectionTraceEntry>,
+    /// Number of events that were not recorded because of `max_events`
+    pub dropped_events: usize,
+}
+
+impl ElectionTrace {
+    pub fn is_finished(&self) -> bool {
+        self.final_state.is_some()
+    }
+
+    fn add_event(&mut self, time: SystemTime, event: ElectionTraceEvent, max_events: usize) {
+        if self.events.len() < max_events {
+            self.events.push(ElectionTraceEntry { time, event });
+        } else {
+            self.dropped_events += 1;
+        }
+    }
+}
+
+/// Records the full timeline of selected elections, so that slow confirmations
+/// can be analyzed after the election was removed from the AEC
+pub struct ElectionTracer {
+    config: ElectionTracerConfig,
+    enabled: bool,
+    data: Mutex<ElectionTracerData>,
+}
+
+#[derive(Default)]
+struct ElectionTracerData {
+    traces: HashMap<QualifiedRoot, ElectionTrace>,
+    hashes: HashMap<BlockHash, QualifiedRoot>,
+    /// Roots of finished traces, oldest first
+    finished: VecDeque<QualifiedRoot>,
+}
+
+impl ElectionTracerData {
+    fn remove(&mut self, root: &QualifiedRoot) {
+        if let Some(trace) = self.traces.remove(root) {
+            self.hashes.retain(|_, r| r != root);
+            if trace.is_finished() {
+                self.finished.retain(|r| r != root);
+            }
+        }
+    }
+
+    fn trace_for_hash(&mut self, hash: &BlockHash) -> Option<&mut ElectionTrace> {
+        let root = self.hashes.get(hash)?;
+        self.traces.get_mut(root)
+    }
+}
+
+impl ElectionTracer {
+    pub fn new(config: ElectionTracerConfig) -> Self {
+        Self {
+            enabled: config.is_enabled(),
+            config,
+            data: Mutex::new(ElectionTracerData::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn should_trace(&self, account: &Account, hash: &BlockHash) -> bool {
+        self.config.accounts.contains(account)
+            || self.config.hashes.contains(hash)
+            || (self.config.sample_rate > 0.0
+                && thread_rng().gen::<f64>() < self.config.sample_rate)
+    }
+
+    pub fn election_started(
+        &self,
+        root: &QualifiedRoot,
+        account: Account,
+        hash: BlockHash,
+        behavior: ElectionBehavior,
+        now: SystemTime,
+    ) {
+        if !self.enabled || !self.should_trace(&account, &hash) {
+            return;
+        }
+        let mut data = self.data.lock().unwrap();
+        // A root can get a new election after the previous one was dropped
+        data.remove(root);
+        let mut trace = ElectionTrace {
+            root: root.clone(),
+            account,
+            hash,
+            behavior,
+            started: now,
+            winner: None,
+            final_state: None,
+            events: Vec::new(),
+            dropped_events: 0,
+        };
+        trace.add_event(
+            now,
+            ElectionTraceEvent::Started { behavior },
+            self.config.max_events,
+        );
+        data.traces.insert(root.clone(), trace);
+        data.hashes.insert(hash, root.clone());
+    }
+
+    pub fn is_tracing(&self, root: &QualifiedRoot) -> bool {
+        self.enabled
+            && self
+                .data
+                .lock()
+                .unwrap()
+                .traces
+                .get(root)
+                .is_some_and(|t| !t.is_finished())
+    }
+
+    pub fn block_added(&self, root: &QualifiedRoot, hash: BlockHash, now: SystemTime) {
+        if !self.enabled {
+            return;
+        }
+        let mut data = self.data.lock().unwrap();
+        let Some(trace) = data.traces.get_mut(root) else {
+            return;
+        };
+        if trace.is_finished() {
+            return;
+        }
+        trace.add_event(
+            now,
+            ElectionTraceEvent::BlockAdded { hash },
+            self.config.max_events,
+        );
+        data.hashes.insert(hash, root.clone());
+    }
+
+    pub fn vote_processed(
+        &self,
+        vote: &Vote,
+        weight: Amount,
+        channel_id: ChannelId,
+        source: VoteSource,
+        code: VoteCode,
+        now: SystemTime,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let mut data = self.data.lock().unwrap();
+        for hash in &vote.hashes {
+            if let Some(trace) = data.trace_for_hash(hash) {
+                trace.add_event(
+                    now,
+                    ElectionTraceEvent::Vote {
+                        representative: vote.voting_account,
+                        hash: *hash,
+                        weight,
+                        is_final: vote.is_final(),
+                        channel_id,
+                        source,
+                        code,
+                    },
+                    self.config.max_events,
+                );
+            }
+        }
+    }
+
+    pub fn confirm_req_sent(
+        &self,
+        root: &QualifiedRoot,
+        hash: BlockHash,
+        channels: Vec<ChannelId>,
+        now: SystemTime,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(trace) = self.data.lock().unwrap().traces.get_mut(root) {
+            trace.add_event(
+                now,
+                ElectionTraceEvent::ConfirmReq { hash, channels },
+                self.config.max_events,
+            );
+        }
+    }
+
+    /// Called when the election is removed from the AEC
+    pub fn election_stopped(&self, root: &QualifiedRoot, election: &ElectionData) {
+        if !self.enabled {
+            return;
+        }
+        let mut data = self.data.lock().unwrap();
+        let Some(trace) = data.traces.get_mut(root) else {
+            return;
+        };
+        if trace.is_finished() {
+            return;
+        }
+        for (state, time) in &election.state_history {
+            trace.add_event(
+                *time,
+                ElectionTraceEvent::StateChanged { state: *state },
+                self.config.max_events,
+            );
+        }
+        trace.events.sort_by_key(|e| e.time);
+        trace.winner = election.status.winner.as_ref().map(|w| w.hash());
+        trace.final_state = Some(election.state);
+        data.finished.push_back(root.clone());
+
+        while data.finished.len() > self.config.max_traces {
+            let oldest = data.finished.front().cloned().unwrap();
+            data.remove(&oldest);
+        }
+    }
+
+    pub fn block_cemented(&self, hash: &BlockHash, now: SystemTime) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(trace) = self.data.lock().unwrap().trace_for_hash(hash) {
+            trace.add_event(
+                now,
+                ElectionTraceEvent::Cemented { hash: *hash },
+                self.config.max_events,
+            );
+        }
+    }
+
+    /// Trace of the election for the given block, either running or finished
+    pub fn trace(&self, hash: &BlockHash) -> Option<ElectionTrace> {
+        let mut data = self.data.lock().unwrap();
+        data.trace_for_hash(hash).cloned()
+    }
+
+    /// All recorded traces, oldest first
+    pub fn traces(&self) -> Vec<ElectionTrace> {
+        let data = self.data.lock().unwrap();
+        let mut traces: Vec<_> = data.traces.values().cloned().collect();
+        traces.sort_by_key(|t| t.started);
+        traces
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        let data = self.data.lock().unwrap();
+        [
+            ("traces", data.traces.len(), 0),
+            ("finished", data.finished.len(), 0),
+            ("hashes", data.hashes.len(), 0),
+        ]
+        .into()
+    }
+}
+
+impl Default for ElectionTracer {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::Election;
+    use rsban_core::{BlockSideband, PrivateKey, SavedBlock, UnsavedBlockLatticeBuilder};
+    use std::time::Duration;
+
+    #[test]
+    fn disabled_by_default() {
+        let tracer = ElectionTracer::default();
+        assert!(!tracer.is_enabled());
+        let block = SavedBlock::new_test_instance();
+        start(&tracer, &block);
+        assert!(tracer.traces().is_empty());
+    }
+
+    #[test]
+    fn trace_matching_account() {
+        let block = SavedBlock::new_test_instance();
+        let tracer = ElectionTracer::new(ElectionTracerConfig {
+            accounts: vec![block.account()],
+            ..Default::default()
+        });
+
+        start(&tracer, &block);
+        start(
+            &tracer,
+            &SavedBlock::new_test_instance_with_key(PrivateKey::from(42)),
+        );
+
+        let traces = tracer.traces();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].hash, block.hash());
+        assert_eq!(traces[0].behavior, ElectionBehavior::Priority);
+        assert!(tracer.is_tracing(&block.qualified_root()));
+    }
+
+    #[test]
+    fn trace_all_with_full_sample_rate() {
+        let tracer = ElectionTracer::new(ElectionTracerConfig {
+            sample_rate: 1.0,
+            ..Default::default()
+        });
+        for block in test_blocks(2) {
+            start(&tracer, &block);
+        }
+        assert_eq!(tracer.traces().len(), 2);
+    }
+
+    #[test]
+    fn record_votes() {
+        let block = SavedBlock::new_test_instance();
+        let tracer = tracer_for(&block);
+        start(&tracer, &block);
+        let key = PrivateKey::from(7);
+        let vote = Vote::new_final(&key, vec![block.hash(), BlockHash::from(123)]);
+
+        tracer.vote_processed(
+            &vote,
+            Amount::raw(1000),
+            ChannelId::from(3),
+            VoteSource::Live,
+            VoteCode::Vote,
+            SystemTime::now(),
+        );
+
+        let trace = tracer.trace(&block.hash()).unwrap();
+        assert_eq!(trace.events.len(), 2);
+        assert_eq!(
+            trace.events[1].event,
+            ElectionTraceEvent::Vote {
+                representative: key.public_key(),
+                hash: block.hash(),
+                weight: Amount::raw(1000),
+                is_final: true,
+                channel_id: ChannelId::from(3),
+                source: VoteSource::Live,
+                code: VoteCode::Vote,
+            }
+        );
+    }
+
+    #[test]
+    fn record_votes_for_fork() {
+        let block = SavedBlock::new_test_instance();
+        let tracer = tracer_for(&block);
+        start(&tracer, &block);
+        let fork = BlockHash::from(999);
+        tracer.block_added(&block.qualified_root(), fork, SystemTime::now());
+        let vote = Vote::new_final(&PrivateKey::from(7), vec![fork]);
+
+        tracer.vote_processed(
+            &vote,
+            Amount::raw(1),
+            ChannelId::from(3),
+            VoteSource::Live,
+            VoteCode::Vote,
+            SystemTime::now(),
+        );
+
+        let trace = tracer.trace(&fork).unwrap();
+        assert_eq!(trace.hash, block.hash());
+        assert_eq!(trace.events.len(), 3);
+    }
+
+    #[test]
+    fn limit_events() {
+        let block = SavedBlock::new_test_instance();
+        let tracer = ElectionTracer::new(ElectionTracerConfig {
+            hashes: vec![block.hash()],
+            max_events: 2,
+            ..Default::default()
+        });
+        start(&tracer, &block);
+        for _ in 0..3 {
+            tracer.confirm_req_sent(
+                &block.qualified_root(),
+                block.hash(),
+                vec![ChannelId::from(1)],
+                SystemTime::now(),
+            );
+        }
+
+        let trace = tracer.trace(&block.hash()).unwrap();
+        assert_eq!(trace.events.len(), 2);
+        assert_eq!(trace.dropped_events, 2);
+    }
+
+    #[test]
+    fn stop_election() {
+        let block = SavedBlock::new_test_instance();
+        let tracer = tracer_for(&block);
+        let started = SystemTime::now();
+        tracer.election_started(
+            &block.qualified_root(),
+            block.account(),
+            block.hash(),
+            ElectionBehavior::Priority,
+            started,
+        );
+        let election = Election::new(
+            1,
+            block.clone(),
+            ElectionBehavior::Priority,
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+        );
+        {
+            let mut guard = election.mutex.lock().unwrap();
+            guard
+                .state_change(ElectionState::Passive, ElectionState::Active)
+                .unwrap();
+            guard
+                .state_change(ElectionState::Active, ElectionState::Confirmed)
+                .unwrap();
+            tracer.election_stopped(&block.qualified_root(), &guard);
+        }
+        tracer.block_cemented(&block.hash(), SystemTime::now() + Duration::from_secs(1));
+
+        assert!(!tracer.is_tracing(&block.qualified_root()));
+        let trace = tracer.trace(&block.hash()).unwrap();
+        assert_eq!(trace.final_state, Some(ElectionState::Confirmed));
+        assert_eq!(trace.winner, Some(block.hash()));
+        let events: Vec<_> = trace.events.iter().map(|e| e.event.clone()).collect();
+        assert_eq!(
+            events,
+            vec![
+                ElectionTraceEvent::Started {
+                    behavior: ElectionBehavior::Priority
+                },
+                ElectionTraceEvent::StateChanged {
+                    state: ElectionState::Active
+                },
+                ElectionTraceEvent::StateChanged {
+                    state: ElectionState::Confirmed
+                },
+                ElectionTraceEvent::Cemented { hash: block.hash() },
+            ]
+        );
+    }
+
+    #[test]
+    fn evict_oldest_finished_trace() {
+        let tracer = ElectionTracer::new(ElectionTracerConfig {
+            sample_rate: 1.0,
+            max_traces: 1,
+            ..Default::default()
+        });
+        let blocks = test_blocks(2);
+        let (block1, block2) = (&blocks[0], &blocks[1]);
+        for block in [block1, block2] {
+            start(&tracer, block);
+            let election = Election::new(
+                1,
+                block.clone(),
+                ElectionBehavior::Priority,
+                Box::new(|_| {}),
+                Box::new(|_| {}),
+            );
+            tracer.election_stopped(&block.qualified_root(), &election.mutex.lock().unwrap());
+        }
+
+        assert!(tracer.trace(&block1.hash()).is_none());
+        assert!(tracer.trace(&block2.hash()).is_some());
+    }
+
+    /// Blocks with different roots
+    fn test_blocks(count: usize) -> Vec<SavedBlock> {
+        let mut lattice = UnsavedBlockLatticeBuilder::new();
+        (0..count)
+            .map(|_| {
+                SavedBlock::new(
+                    lattice.genesis().send(Account::from(1), 1),
+                    BlockSideband::new_test_instance(),
+                )
+            })
+            .collect()
+    }
+
+    fn tracer_for(block: &SavedBlock) -> ElectionTracer {
+        ElectionTracer::new(ElectionTracerConfig {
+            hashes: vec![block.hash()],
+            ..Default::default()
+        })
+    }
+
+    fn start(tracer: &ElectionTracer, block: &SavedBlock) {
+        tracer.election_started(
+            &block.qualified_root(),
+            block.account(),
+            block.hash(),
+            ElectionBehavior::Priority,
+            SystemTime::now(),
+        );
+    }
+}
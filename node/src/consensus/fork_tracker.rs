@@ -0,0 +1,482 @@
+use super::ElectionStatus;
+use rsban_core::{
+    utils::ContainerInfo, Account, Amount, BlockHash, QualifiedRoot, VoteWithWeightInfo,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForkTrackerConfig {
+    /// Maximum number of unresolved forks. The oldest fork is moved to the history when exceeded
+    pub max_active: usize,
+    /// Maximum number of resolved forks that are kept
+    pub max_history: usize,
+}
+
+impl Default for ForkTrackerConfig {
+    fn default() -> Self {
+        Self {
+            max_active: 1024,
+            max_history: 1024,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkCandidate {
+    pub hash: BlockHash,
+    pub first_seen: SystemTime,
+    /// Vote weight for this block. Only known after the fork was resolved
+    pub tally: Amount,
+}
+
+/// Two or more blocks that compete for the same qualified root
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fork {
+    pub root: QualifiedRoot,
+    pub account: Account,
+    pub first_seen: SystemTime,
+    pub candidates: Vec<ForkCandidate>,
+    pub winner: Option<BlockHash>,
+    pub resolved: Option<SystemTime>,
+}
+
+impl Fork {
+    fn add_candidate(&mut self, hash: BlockHash, now: SystemTime) -> bool {
+        if self.candidates.iter().any(|c| c.hash == hash) {
+            false
+        } else {
+            self.candidates.push(ForkCandidate {
+                hash,
+                first_seen: now,
+                tally: Amount::zero(),
+            });
+            true
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForkEvent {
+    /// A new fork or an additional candidate block was seen
+    Detected,
+    /// The election of a forked root ended. The winner is only set if the
+    /// election was confirmed, not if it expired or was cancelled
+    Resolved,
+}
+
+impl ForkEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForkEvent::Detected => "detected",
+            ForkEvent::Resolved => "resolved",
+        }
+    }
+}
+
+pub type ForkCallback = Box<dyn Fn(ForkEvent, &Fork) + Send + Sync>;
+
+/// Records forks seen by the block processor and how their elections ended
+pub struct ForkTracker {
+    config: ForkTrackerConfig,
+    data: Mutex<ForkTrackerData>,
+    observers: Mutex<Vec<ForkCallback>>,
+}
+
+#[derive(Default)]
+struct ForkTrackerData {
+    active: HashMap<QualifiedRoot, Fork>,
+    /// Newest first
+    history: VecDeque<Fork>,
+}
+
+impl ForkTrackerData {
+    fn add_to_history(&mut self, fork: Fork, max_history: usize) {
+        self.history.push_front(fork);
+        self.history.truncate(max_history);
+    }
+}
+
+impl ForkTracker {
+    pub fn new(config: ForkTrackerConfig) -> Self {
+        Self {
+            config,
+            data: Mutex::new(ForkTrackerData::default()),
+            observers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn on_fork_event(&self, callback: ForkCallback) {
+        self.observers.lock().unwrap().push(callback);
+    }
+
+    /// `existing` is the block that is already known for that root, `fork` the competing block
+    pub fn fork_detected(
+        &self,
+        root: QualifiedRoot,
+        account: Account,
+        existing: BlockHash,
+        fork: BlockHash,
+        now: SystemTime,
+    ) {
+        let changed = {
+            let mut data = self.data.lock().unwrap();
+            let entry = data.active.entry(root.clone()).or_insert_with(|| Fork {
+                root,
+                account,
+                first_seen: now,
+                candidates: Vec::new(),
+                winner: None,
+                resolved: None,
+            });
+            let added_existing = entry.add_candidate(existing, now);
+            let added_fork = entry.add_candidate(fork, now);
+            let changed = if added_existing || added_fork {
+                Some(entry.clone())
+            } else {
+                None
+            };
+
+            if data.active.len() > self.config.max_active {
+                let oldest = data
+                    .active
+                    .values()
+                    .min_by_key(|f| f.first_seen)
+                    .map(|f| f.root.clone())
+                    .unwrap();
+                let evicted = data.active.remove(&oldest).unwrap();
+                data.add_to_history(evicted, self.config.max_history);
+            }
+            changed
+        };
+
+        if let Some(fork) = changed {
+            self.notify(ForkEvent::Detected, &fork);
+        }
+    }
+
+    pub fn election_ended(&self, status: &ElectionStatus, votes: &[VoteWithWeightInfo]) {
+        let Some(winner) = &status.winner else {
+            return;
+        };
+        let mut tallies: HashMap<BlockHash, Amount> = HashMap::new();
+        for vote in votes {
+            *tallies.entry(vote.hash).or_default() += vote.weight;
+        }
+        self.resolve(
+            &winner.qualified_root(),
+            Some(winner.hash()),
+            &tallies,
+            status.election_end,
+        );
+    }
+
+    /// The election of the root expired or was cancelled without being confirmed
+    pub fn election_dropped(
+        &self,
+        root: &QualifiedRoot,
+        tallies: &HashMap<BlockHash, Amount>,
+        now: SystemTime,
+    ) {
+        self.resolve(root, None, tallies, now);
+    }
+
+    fn resolve(
+        &self,
+        root: &QualifiedRoot,
+        winner: Option<BlockHash>,
+        tallies: &HashMap<BlockHash, Amount>,
+        now: SystemTime,
+    ) {
+        let resolved = {
+            let mut data = self.data.lock().unwrap();
+            let Some(mut fork) = data.active.remove(root) else {
+                return;
+            };
+
+            if let Some(winner) = winner {
+                fork.add_candidate(winner, now);
+            }
+            for candidate in &mut fork.candidates {
+                candidate.tally = tallies.get(&candidate.hash).cloned().unwrap_or_default();
+            }
+            fork.winner = winner;
+            fork.resolved = Some(now);
+            data.add_to_history(fork.clone(), self.config.max_history);
+            fork
+        };
+        self.notify(ForkEvent::Resolved, &resolved);
+    }
+
+    pub fn active(&self) -> Vec<Fork> {
+        let mut forks: Vec<_> = self.data.lock().unwrap().active.values().cloned().collect();
+        forks.sort_by_key(|f| f.first_seen);
+        forks
+    }
+
+    /// Resolved or evicted forks, newest first
+    pub fn history(&self) -> Vec<Fork> {
+        self.data.lock().unwrap().history.iter().cloned().collect()
+    }
+
+    pub fn is_forked(&self, account: &Account) -> bool {
+        self.data
+            .lock()
+            .unwrap()
+            .active
+            .values()
+            .any(|f| f.account == *account)
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        let data = self.data.lock().unwrap();
+        [
+            ("active", data.active.len(), 0),
+            ("history", data.history.len(), 0),
+        ]
+        .into()
+    }
+
+    fn notify(&self, event: ForkEvent, fork: &Fork) {
+        for observer in self.observers.lock().unwrap().iter() {
+            observer(event, fork);
+        }
+    }
+}
+
+impl Default for ForkTracker {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ElectionStatusType;
+    use rsban_core::{Block, MaybeSavedBlock, PublicKey, SavedBlock};
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[test]
+    fn empty() {
+        let tracker = ForkTracker::default();
+        assert!(tracker.active().is_empty());
+        assert!(tracker.history().is_empty());
+        assert!(!tracker.is_forked(&Account::from(1)));
+    }
+
+    #[test]
+    fn detect_fork() {
+        let tracker = ForkTracker::default();
+        let events = track_events(&tracker);
+        let root = QualifiedRoot::new_test_instance();
+        let account = Account::from(1);
+        let now = SystemTime::now();
+
+        tracker.fork_detected(
+            root.clone(),
+            account,
+            BlockHash::from(1),
+            BlockHash::from(2),
+            now,
+        );
+
+        let active = tracker.active();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].root, root);
+        assert_eq!(active[0].account, account);
+        assert_eq!(active[0].first_seen, now);
+        assert_eq!(
+            active[0]
+                .candidates
+                .iter()
+                .map(|c| c.hash)
+                .collect::<Vec<_>>(),
+            vec![BlockHash::from(1), BlockHash::from(2)]
+        );
+        assert!(tracker.is_forked(&account));
+        assert_eq!(*events.lock().unwrap(), vec![ForkEvent::Detected]);
+    }
+
+    #[test]
+    fn add_third_candidate() {
+        let tracker = ForkTracker::default();
+        let events = track_events(&tracker);
+        let root = QualifiedRoot::new_test_instance();
+        let now = SystemTime::now();
+
+        tracker.fork_detected(
+            root.clone(),
+            Account::from(1),
+            BlockHash::from(1),
+            BlockHash::from(2),
+            now,
+        );
+        tracker.fork_detected(
+            root.clone(),
+            Account::from(1),
+            BlockHash::from(1),
+            BlockHash::from(2),
+            now,
+        );
+        tracker.fork_detected(
+            root,
+            Account::from(1),
+            BlockHash::from(1),
+            BlockHash::from(3),
+            now + Duration::from_secs(1),
+        );
+
+        let active = tracker.active();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].candidates.len(), 3);
+        assert_eq!(
+            active[0].candidates[2].first_seen,
+            now + Duration::from_secs(1)
+        );
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![ForkEvent::Detected, ForkEvent::Detected]
+        );
+    }
+
+    #[test]
+    fn resolve_fork() {
+        let tracker = ForkTracker::default();
+        let events = track_events(&tracker);
+        let winner = SavedBlock::new_test_instance();
+        let loser = BlockHash::from(999);
+        let account = winner.account();
+        tracker.fork_detected(
+            winner.qualified_root(),
+            account,
+            loser,
+            winner.hash(),
+            SystemTime::now(),
+        );
+
+        let status = ElectionStatus {
+            winner: Some(MaybeSavedBlock::Saved(winner.clone())),
+            election_status_type: ElectionStatusType::ActiveConfirmedQuorum,
+            ..Default::default()
+        };
+        tracker.election_ended(
+            &status,
+            &[
+                vote(winner.hash(), Amount::raw(100)),
+                vote(winner.hash(), Amount::raw(50)),
+                vote(loser, Amount::raw(10)),
+            ],
+        );
+
+        assert!(tracker.active().is_empty());
+        assert!(!tracker.is_forked(&account));
+        let history = tracker.history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].winner, Some(winner.hash()));
+        assert_eq!(history[0].resolved, Some(status.election_end));
+        assert_eq!(history[0].candidates[0].tally, Amount::raw(10));
+        assert_eq!(history[0].candidates[1].tally, Amount::raw(150));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![ForkEvent::Detected, ForkEvent::Resolved]
+        );
+    }
+
+    #[test]
+    fn resolve_fork_when_election_was_dropped() {
+        let tracker = ForkTracker::default();
+        let events = track_events(&tracker);
+        let root = QualifiedRoot::new_test_instance();
+        let account = Account::from(1);
+        tracker.fork_detected(
+            root.clone(),
+            account,
+            BlockHash::from(1),
+            BlockHash::from(2),
+            SystemTime::now(),
+        );
+        let now = SystemTime::now() + Duration::from_secs(300);
+
+        tracker.election_dropped(&root, &[(BlockHash::from(2), Amount::raw(42))].into(), now);
+
+        assert!(tracker.active().is_empty());
+        assert!(!tracker.is_forked(&account));
+        let history = tracker.history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].winner, None);
+        assert_eq!(history[0].resolved, Some(now));
+        assert_eq!(history[0].candidates[0].tally, Amount::zero());
+        assert_eq!(history[0].candidates[1].tally, Amount::raw(42));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![ForkEvent::Detected, ForkEvent::Resolved]
+        );
+    }
+
+    #[test]
+    fn ignore_elections_without_fork() {
+        let tracker = ForkTracker::default();
+        let events = track_events(&tracker);
+        let status = ElectionStatus {
+            winner: Some(MaybeSavedBlock::Unsaved(Block::new_test_instance())),
+            ..Default::default()
+        };
+
+        tracker.election_ended(&status, &[]);
+
+        assert!(tracker.history().is_empty());
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn evict_oldest_active_fork() {
+        let tracker = ForkTracker::new(ForkTrackerConfig {
+            max_active: 2,
+            max_history: 1,
+        });
+        let now = SystemTime::now();
+
+        for i in 0..4u64 {
+            tracker.fork_detected(
+                QualifiedRoot::new(BlockHash::from(i).into(), BlockHash::from(i)),
+                Account::from(i),
+                BlockHash::from(100 + i),
+                BlockHash::from(200 + i),
+                now + Duration::from_secs(i),
+            );
+        }
+
+        let active: Vec<_> = tracker.active().iter().map(|f| f.account).collect();
+        assert_eq!(active, vec![Account::from(2), Account::from(3)]);
+        let history = tracker.history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].account, Account::from(1));
+        assert_eq!(history[0].winner, None);
+    }
+
+    fn track_events(tracker: &ForkTracker) -> Arc<Mutex<Vec<ForkEvent>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_l = events.clone();
+        tracker.on_fork_event(Box::new(move |event, _| {
+            events_l.lock().unwrap().push(event);
+        }));
+        events
+    }
+
+    fn vote(hash: BlockHash, weight: Amount) -> VoteWithWeightInfo {
+        VoteWithWeightInfo {
+            representative: PublicKey::from(1),
+            time: SystemTime::now(),
+            timestamp: 1,
+            hash,
+            weight,
+        }
+    }
+}
@@ -44,10 +44,18 @@ impl ManualScheduler {
     }
 
     pub fn push(&self, block: SavedBlock, previous_balance: Option<Amount>) {
+        self.push_with_behavior(block, previous_balance, ElectionBehavior::Manual);
+    }
+
+    /// Starts an election that is counted towards the limit of the given behavior
+    pub fn push_with_behavior(
+        &self,
+        block: SavedBlock,
+        previous_balance: Option<Amount>,
+        behavior: ElectionBehavior,
+    ) {
         let mut guard = self.mutex.lock().unwrap();
-        guard
-            .queue
-            .push_back((block, previous_balance, ElectionBehavior::Manual));
+        guard.queue.push_back((block, previous_balance, behavior));
         self.notify();
     }
 
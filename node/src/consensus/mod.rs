@@ -1,10 +1,13 @@
 mod active_elections;
 mod bootstrap_weights;
 mod bucket;
+mod confirmation_latency;
 mod confirmation_solicitor;
 mod election;
 pub(crate) mod election_schedulers;
 mod election_status;
+mod election_tracer;
+mod fork_tracker;
 mod hinted_scheduler;
 mod manual_scheduler;
 mod optimistic_scheduler;
@@ -24,9 +27,12 @@ mod vote_router;
 pub use active_elections::*;
 pub(crate) use bootstrap_weights::*;
 pub use bucket::*;
+pub use confirmation_latency::*;
 pub use confirmation_solicitor::ConfirmationSolicitor;
 pub use election::*;
 pub use election_status::{ElectionStatus, ElectionStatusType};
+pub use election_tracer::*;
+pub use fork_tracker::*;
 pub use hinted_scheduler::*;
 pub use manual_scheduler::*;
 pub use optimistic_scheduler::*;
@@ -3,7 +3,7 @@ use crate::{
     stats::{DetailType, Direction, StatType, Stats},
     NetworkParams,
 };
-use rsban_core::{utils::ContainerInfo, Account, PublicKey};
+use rsban_core::{utils::ContainerInfo, Account, Amount, PublicKey};
 use rsban_ledger::RepWeightCache;
 use std::{
     collections::HashSet,
@@ -24,6 +24,33 @@ pub enum RepTier {
     Tier3, // (> 5%) of online stake
 }
 
+impl RepTier {
+    /// Returns the tier of a representative with `weight` for the given trended online weight
+    pub fn for_weight(weight: Amount, trended: Amount) -> Self {
+        if weight > trended / 20 {
+            // 5% or above (level 3)
+            RepTier::Tier3
+        } else if weight > trended / 100 {
+            // 1% or above (level 2)
+            RepTier::Tier2
+        } else if weight > trended / 1000 {
+            // 0.1% or above (level 1)
+            RepTier::Tier1
+        } else {
+            RepTier::None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepTier::None => "none",
+            RepTier::Tier1 => "tier_1",
+            RepTier::Tier2 => "tier_2",
+            RepTier::Tier3 => "tier_3",
+        }
+    }
+}
+
 impl From<RepTier> for DetailType {
     fn from(value: RepTier) -> Self {
         match value {
@@ -189,18 +216,17 @@ impl RepTiersImpl {
             let rep_weights = self.rep_weights.read();
             reps_count = rep_weights.len();
             for (&representative, &weight) in rep_weights.iter() {
-                if weight > trended / 1000 {
-                    // 0.1% or above (level 1)
+                let tier = RepTier::for_weight(weight, trended);
+                if tier >= RepTier::Tier1 {
                     representatives_1_l.insert(representative);
-                    if weight > trended / 100 {
-                        // 1% or above (level 2)
-                        representatives_2_l.insert(representative);
-                        if weight > trended / 20 {
-                            // 5% or above (level 3)
-                            representatives_3_l.insert(representative);
-                        }
-                    }
-                } else {
+                }
+                if tier >= RepTier::Tier2 {
+                    representatives_2_l.insert(representative);
+                }
+                if tier >= RepTier::Tier3 {
+                    representatives_3_l.insert(representative);
+                }
+                if tier == RepTier::None {
                     ignored += 1;
                 }
             }
@@ -12,7 +12,7 @@ use crate::{
 
 use super::{
     election_schedulers::ElectionSchedulers, Election, ElectionData, LocalVoteHistory,
-    RecentlyConfirmedCache, TallyKey, VoteGenerators,
+    RecentlyConfirmedCache, TallyKey, VoteGenerators, VotingPolicies,
 };
 use rsban_core::{Amount, BlockHash, MaybeSavedBlock, PublicKey, VoteCode, VoteSource};
 use rsban_ledger::Ledger;
@@ -33,6 +33,7 @@ pub struct VoteApplier {
     node_config: NodeConfig,
     history: Arc<LocalVoteHistory>,
     wallets: Arc<Wallets>,
+    voting_policies: Arc<VotingPolicies>,
     recently_confirmed: Arc<RecentlyConfirmedCache>,
     confirming_set: Arc<ConfirmingSet>,
     workers: Arc<dyn ThreadPool>,
@@ -50,6 +51,7 @@ impl VoteApplier {
         node_config: NodeConfig,
         history: Arc<LocalVoteHistory>,
         wallets: Arc<Wallets>,
+        voting_policies: Arc<VotingPolicies>,
         recently_confirmed: Arc<RecentlyConfirmedCache>,
         confirming_set: Arc<ConfirmingSet>,
         workers: Arc<dyn ThreadPool>,
@@ -64,6 +66,7 @@ impl VoteApplier {
             node_config,
             history,
             wallets,
+            voting_policies,
             recently_confirmed,
             confirming_set,
             workers,
@@ -128,13 +131,19 @@ impl VoteApplier {
         result
     }
 
+    /// Whether a local representative from a wallet or a key file votes
+    pub fn is_voting(&self) -> bool {
+        self.voting_policies
+            .is_voting(self.wallets.voting_reps_count())
+    }
+
     pub fn remove_votes(
         &self,
         election: &Election,
         guard: &mut MutexGuard<ElectionData>,
         hash: &BlockHash,
     ) {
-        if self.node_config.enable_voting && self.wallets.voting_reps_count() > 0 {
+        if self.is_voting() {
             // Remove votes from election
             let list_generated_votes = self.history.votes(&election.root, hash, false);
             for vote in list_generated_votes {
@@ -265,10 +274,7 @@ impl VoteApplierExt for Arc<VoteApplier> {
         }
 
         if self.have_quorum(&tally) {
-            if !election.is_quorum.swap(true, Ordering::SeqCst)
-                && self.node_config.enable_voting
-                && self.wallets.voting_reps_count() > 0
-            {
+            if !election.is_quorum.swap(true, Ordering::SeqCst) && self.is_voting() {
                 self.vote_generators
                     .generate_final_vote(&election.root, &status_winner_hash);
             }
@@ -285,6 +291,10 @@ impl VoteApplierExt for Arc<VoteApplier> {
 
         if just_confirmed {
             election_lock.update_status_to_confirmed(election);
+            let confirmed_at = election_lock.status.election_end;
+            election_lock
+                .state_history
+                .push((ElectionState::Confirmed, confirmed_at));
             let status = election_lock.status.clone();
 
             self.recently_confirmed.put(
@@ -0,0 +1,285 @@
+use crate::utils::{CancellationToken, Runnable};
+use rsban_core::{utils::ContainerInfo, Account, BlockHash, QualifiedRoot, Root};
+use rsban_ledger::{Ledger, Writer};
+use rsban_store_lmdb::Transaction;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::{debug, info};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FinalVoteCleanupConfig {
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Final votes are removed once their account is cemented at least this many blocks past the voted block
+    pub min_depth: u64,
+    pub batch_size: usize,
+}
+
+impl Default for FinalVoteCleanupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(60 * 5),
+            min_depth: 1,
+            batch_size: 1024,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalVoteEntry {
+    pub qualified_root: QualifiedRoot,
+    pub hash: BlockHash,
+    /// How many blocks the account is cemented past the voted block
+    pub cemented_depth: Option<u64>,
+}
+
+/// Removes final votes for roots that can't be contested anymore
+pub struct FinalVoteCleanup {
+    ledger: Arc<Ledger>,
+    stopped: AtomicBool,
+    total_deleted: AtomicU64,
+}
+
+impl FinalVoteCleanup {
+    pub fn new(ledger: Arc<Ledger>) -> Self {
+        Self {
+            ledger,
+            stopped: AtomicBool::new(false),
+            total_deleted: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.ledger.store.final_vote.count(&self.ledger.read_txn())
+    }
+
+    pub fn entries(&self, root: &Root) -> Vec<FinalVoteEntry> {
+        let tx = self.ledger.read_txn();
+        let mut result = Vec::new();
+        let mut it = self
+            .ledger
+            .store
+            .final_vote
+            .begin_at_root(&tx, &QualifiedRoot::new(*root, BlockHash::zero()));
+        while let Some((qualified_root, hash)) = it.current() {
+            if qualified_root.root != *root {
+                break;
+            }
+            result.push(FinalVoteEntry {
+                qualified_root: qualified_root.clone(),
+                hash: *hash,
+                cemented_depth: self.cemented_depth(&tx, qualified_root, hash),
+            });
+            it.next();
+        }
+        result
+    }
+
+    /// Returns how many blocks the account of the voted block is cemented past it.
+    /// Returns None if the voted block isn't cemented or its position in the ledger is unknown
+    pub fn cemented_depth(
+        &self,
+        tx: &dyn Transaction,
+        qualified_root: &QualifiedRoot,
+        hash: &BlockHash,
+    ) -> Option<u64> {
+        let (account, height) = self.position(tx, qualified_root, hash)?;
+        let conf_height = self.ledger.get_confirmation_height(tx, &account)?;
+        conf_height.height.checked_sub(height)
+    }
+
+    fn position(
+        &self,
+        tx: &dyn Transaction,
+        qualified_root: &QualifiedRoot,
+        hash: &BlockHash,
+    ) -> Option<(Account, u64)> {
+        if let Some(block) = self.ledger.any().get_block(tx, hash) {
+            return Some((block.account(), block.height()));
+        }
+
+        // The voted block lost the election, so use the position of the block that won
+        if qualified_root.previous.is_zero() {
+            Some((qualified_root.root.into(), 1))
+        } else {
+            let previous = self.ledger.any().get_block(tx, &qualified_root.previous)?;
+            Some((previous.account(), previous.height() + 1))
+        }
+    }
+
+    /// Deletes all final votes whose account is cemented at least `min_depth` blocks past the voted block.
+    /// Returns the number of deleted entries
+    pub fn prune(&self, min_depth: u64, batch_size: usize) -> u64 {
+        let mut deleted = 0;
+        let mut last_read = None;
+        let mut finished = false;
+        while !finished && !self.stopped.load(Ordering::SeqCst) {
+            let targets;
+            (targets, finished) = self.collect_targets(&mut last_read, min_depth, batch_size);
+            if targets.is_empty() {
+                continue;
+            }
+
+            let _guard = self.ledger.write_queue.wait(Writer::VotingFinal);
+            let mut tx = self.ledger.rw_txn();
+            for qualified_root in &targets {
+                self.ledger
+                    .store
+                    .final_vote
+                    .del_qualified_root(&mut tx, qualified_root);
+            }
+            deleted += targets.len() as u64;
+            debug!("Deleted final votes: {}", deleted);
+        }
+
+        self.total_deleted.fetch_add(deleted, Ordering::Relaxed);
+        deleted
+    }
+
+    fn collect_targets(
+        &self,
+        last_read: &mut Option<QualifiedRoot>,
+        min_depth: u64,
+        batch_size: usize,
+    ) -> (Vec<QualifiedRoot>, bool) {
+        let tx = self.ledger.read_txn();
+        let mut targets = Vec::new();
+        let mut it = match last_read {
+            Some(last) => {
+                let mut it = self.ledger.store.final_vote.begin_at_root(&tx, last);
+                if it.current().is_some_and(|(k, _)| k == last) {
+                    it.next();
+                }
+                it
+            }
+            None => self.ledger.store.final_vote.begin(&tx),
+        };
+
+        let mut read = 0;
+        while let Some((qualified_root, hash)) = it.current() {
+            if self
+                .cemented_depth(&tx, qualified_root, hash)
+                .is_some_and(|depth| depth >= min_depth)
+            {
+                targets.push(qualified_root.clone());
+            }
+            read += 1;
+            if read >= batch_size {
+                *last_read = Some(qualified_root.clone());
+                return (targets, false);
+            }
+            it.next();
+        }
+        (targets, true)
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        [(
+            "total_deleted",
+            self.total_deleted.load(Ordering::Relaxed) as usize,
+            0,
+        )]
+        .into()
+    }
+}
+
+pub struct FinalVoteCleanupRunner {
+    cleanup: Arc<FinalVoteCleanup>,
+    config: FinalVoteCleanupConfig,
+}
+
+impl FinalVoteCleanupRunner {
+    pub fn new(cleanup: Arc<FinalVoteCleanup>, config: FinalVoteCleanupConfig) -> Self {
+        Self { cleanup, config }
+    }
+}
+
+impl Runnable for FinalVoteCleanupRunner {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        let deleted = self
+            .cleanup
+            .prune(self.config.min_depth, self.config.batch_size);
+        if deleted > 0 {
+            info!("Removed {} stale final votes", deleted);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::{ConfirmationHeightInfo, SavedAccountChain};
+
+    #[test]
+    fn cemented_depth() {
+        let mut chain = SavedAccountChain::genesis();
+        chain.add_state();
+        chain.add_state();
+        let cleanup = create_cleanup(&chain, 2);
+        let tx = cleanup.ledger.read_txn();
+
+        let depth = |height: u64| {
+            let block = chain.block(height);
+            cleanup.cemented_depth(&tx, &block.qualified_root(), &block.hash())
+        };
+
+        assert_eq!(depth(1), Some(1));
+        assert_eq!(depth(2), Some(0));
+        assert_eq!(depth(3), None);
+    }
+
+    #[test]
+    fn cemented_depth_of_lost_fork() {
+        let mut chain = SavedAccountChain::genesis();
+        chain.add_state();
+        chain.add_state();
+        let cleanup = create_cleanup(&chain, 3);
+        let tx = cleanup.ledger.read_txn();
+
+        let winner = chain.block(2);
+        assert_eq!(
+            cleanup.cemented_depth(&tx, &winner.qualified_root(), &BlockHash::from(999)),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn cemented_depth_of_unknown_root() {
+        let chain = SavedAccountChain::genesis();
+        let cleanup = create_cleanup(&chain, 1);
+        let tx = cleanup.ledger.read_txn();
+
+        assert_eq!(
+            cleanup.cemented_depth(
+                &tx,
+                &QualifiedRoot::new(Root::from(1), BlockHash::from(2)),
+                &BlockHash::from(3)
+            ),
+            None
+        );
+    }
+
+    fn create_cleanup(chain: &SavedAccountChain, cemented_height: u64) -> FinalVoteCleanup {
+        let ledger = Ledger::new_null_builder()
+            .blocks(chain.blocks())
+            .confirmation_height(
+                &chain.account(),
+                &ConfirmationHeightInfo {
+                    height: cemented_height,
+                    frontier: chain.block(cemented_height).hash(),
+                },
+            )
+            .finish();
+        FinalVoteCleanup::new(Arc::new(ledger))
+    }
+}
@@ -1,11 +1,17 @@
+mod final_vote_cleanup;
 mod local_vote_history;
+mod persistent_vote_history;
 mod request_aggregator;
 mod request_aggregator_impl;
 mod vote_generator;
 mod vote_generators;
 mod vote_spacing;
+mod voting_policies;
 
+pub use final_vote_cleanup::*;
 pub use local_vote_history::*;
+pub use persistent_vote_history::*;
 pub use request_aggregator::*;
 pub use vote_generators::*;
 pub use vote_spacing::VoteSpacing;
+pub use voting_policies::*;
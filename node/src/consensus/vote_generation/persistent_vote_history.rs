@@ -0,0 +1,265 @@
+use crate::utils::{CancellationToken, Runnable};
+use rsban_core::{utils::ContainerInfo, BlockHash, Root};
+use rsban_ledger::{Ledger, Writer};
+use std::{
+    collections::HashMap,
+    mem::size_of,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+use tracing::info;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistentVoteHistoryConfig {
+    pub enabled: bool,
+    /// How long a generated non-final vote prevents votes for other blocks on the same root
+    pub window: Duration,
+}
+
+impl Default for PersistentVoteHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Durable record of the non-final votes generated by the local representatives.
+/// Votes that were generated before a restart are reloaded at startup, so that the
+/// node never votes for a different block on the same root within the configured window.
+/// The vote generators write each batch of generated votes in a single transaction,
+/// before the votes are broadcast.
+pub struct PersistentVoteHistory {
+    config: PersistentVoteHistoryConfig,
+    ledger: Arc<Ledger>,
+    data: Mutex<PersistentVoteHistoryData>,
+}
+
+struct PersistentVoteHistoryData {
+    /// Votes generated before the last restart
+    previous_votes: HashMap<Root, (BlockHash, SystemTime)>,
+}
+
+impl PersistentVoteHistory {
+    pub const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(config: PersistentVoteHistoryConfig, ledger: Arc<Ledger>) -> Self {
+        let mut previous_votes = HashMap::new();
+        if config.enabled {
+            let now = SystemTime::now();
+            let mut tx = ledger.rw_txn();
+            ledger
+                .store
+                .local_vote
+                .delete_before(&mut tx, cutoff(now, config.window));
+            previous_votes.extend(ledger.store.local_vote.iter(&tx));
+            if !previous_votes.is_empty() {
+                info!(
+                    "Loaded {} local votes from the vote history",
+                    previous_votes.len()
+                );
+            }
+        }
+        Self::with_previous_votes(config, ledger, previous_votes)
+    }
+
+    fn with_previous_votes(
+        config: PersistentVoteHistoryConfig,
+        ledger: Arc<Ledger>,
+        previous_votes: HashMap<Root, (BlockHash, SystemTime)>,
+    ) -> Self {
+        Self {
+            config,
+            ledger,
+            data: Mutex::new(PersistentVoteHistoryData { previous_votes }),
+        }
+    }
+
+    /// Returns false if the node voted for a different block on that root before
+    /// it was restarted and the vote is still within the window
+    pub fn votable(&self, root: &Root, hash: &BlockHash, now: SystemTime) -> bool {
+        if !self.config.enabled {
+            return true;
+        }
+        let data = self.data.lock().unwrap();
+        match data.previous_votes.get(root) {
+            Some((voted, time)) => voted == hash || *time < cutoff(now, self.config.window),
+            None => true,
+        }
+    }
+
+    /// Writes the votes that were just generated, so that they are respected after a restart.
+    /// The transaction is committed before this returns, so the votes may be broadcast afterwards
+    pub fn record(&self, votes: &[(Root, BlockHash)], now: SystemTime) {
+        if !self.config.enabled || votes.is_empty() {
+            return;
+        }
+        let _guard = self.ledger.write_queue.wait(Writer::LocalVotes);
+        let mut tx = self.ledger.rw_txn();
+        for (root, hash) in votes {
+            self.ledger.store.local_vote.put(&mut tx, root, hash, now);
+        }
+        tx.commit();
+    }
+
+    /// Deletes the votes which are older than the window
+    pub fn cleanup(&self, now: SystemTime) {
+        if !self.config.enabled {
+            return;
+        }
+        let cutoff = cutoff(now, self.config.window);
+        self.data
+            .lock()
+            .unwrap()
+            .previous_votes
+            .retain(|_, (_, time)| *time >= cutoff);
+
+        let _guard = self.ledger.write_queue.wait(Writer::LocalVotes);
+        let mut tx = self.ledger.rw_txn();
+        self.ledger.store.local_vote.delete_before(&mut tx, cutoff);
+        tx.commit();
+    }
+
+    pub fn previous_votes(&self) -> usize {
+        self.data.lock().unwrap().previous_votes.len()
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        let entry_size = size_of::<Root>() + size_of::<BlockHash>() + size_of::<SystemTime>();
+        [("previous_votes", self.previous_votes(), entry_size)].into()
+    }
+}
+
+/// Periodically deletes the expired votes of `PersistentVoteHistory`
+pub struct PersistentVoteHistoryCleanup {
+    history: Arc<PersistentVoteHistory>,
+}
+
+impl PersistentVoteHistoryCleanup {
+    pub fn new(history: Arc<PersistentVoteHistory>) -> Self {
+        Self { history }
+    }
+}
+
+impl Runnable for PersistentVoteHistoryCleanup {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.history.cleanup(SystemTime::now());
+    }
+}
+
+fn cutoff(now: SystemTime, window: Duration) -> SystemTime {
+    now.checked_sub(window).unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn votable_when_empty() {
+        let history = create_history(HashMap::new());
+        assert!(history.votable(&Root::from(1), &BlockHash::from(2), SystemTime::now()));
+    }
+
+    #[test]
+    fn previous_vote_for_same_hash_is_votable() {
+        let now = SystemTime::now();
+        let history = create_history([(Root::from(1), (BlockHash::from(2), now))].into());
+        assert!(history.votable(&Root::from(1), &BlockHash::from(2), now));
+        assert!(history.votable(&Root::from(3), &BlockHash::from(4), now));
+    }
+
+    #[test]
+    fn previous_vote_for_different_hash_is_not_votable_within_window() {
+        let now = SystemTime::now();
+        let history = create_history([(Root::from(1), (BlockHash::from(2), now))].into());
+        let window = PersistentVoteHistoryConfig::default().window;
+
+        assert!(!history.votable(
+            &Root::from(1),
+            &BlockHash::from(3),
+            now + window - Duration::from_secs(1)
+        ));
+        assert!(history.votable(
+            &Root::from(1),
+            &BlockHash::from(3),
+            now + window + Duration::from_secs(1)
+        ));
+    }
+
+    #[test]
+    fn persist_votes() {
+        let ledger = Arc::new(Ledger::new_null());
+        let put_tracker = ledger.store.local_vote.track_puts();
+        let history = PersistentVoteHistory::new(Default::default(), ledger);
+        let now = SystemTime::now();
+
+        history.record(
+            &[
+                (Root::from(1), BlockHash::from(2)),
+                (Root::from(3), BlockHash::from(4)),
+            ],
+            now,
+        );
+        assert_eq!(
+            put_tracker.output(),
+            vec![
+                (Root::from(1), BlockHash::from(2)),
+                (Root::from(3), BlockHash::from(4))
+            ]
+        );
+    }
+
+    #[test]
+    fn votes_of_current_session_dont_block() {
+        let history = create_history(HashMap::new());
+        let now = SystemTime::now();
+        history.record(&[(Root::from(1), BlockHash::from(2))], now);
+        assert!(history.votable(&Root::from(1), &BlockHash::from(3), now));
+    }
+
+    #[test]
+    fn disabled() {
+        let ledger = Arc::new(Ledger::new_null());
+        let put_tracker = ledger.store.local_vote.track_puts();
+        let now = SystemTime::now();
+        let history = PersistentVoteHistory::with_previous_votes(
+            PersistentVoteHistoryConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ledger,
+            [(Root::from(1), (BlockHash::from(2), now))].into(),
+        );
+
+        history.record(&[(Root::from(1), BlockHash::from(2))], now);
+        history.cleanup(now + Duration::from_secs(60 * 60 * 24));
+
+        assert!(history.votable(&Root::from(1), &BlockHash::from(3), now));
+        assert!(put_tracker.output().is_empty());
+    }
+
+    #[test]
+    fn remove_expired_previous_votes() {
+        let now = SystemTime::now();
+        let window = PersistentVoteHistoryConfig::default().window;
+        let history = create_history([(Root::from(1), (BlockHash::from(2), now))].into());
+
+        history.cleanup(now + window);
+        assert_eq!(history.previous_votes(), 1);
+
+        history.cleanup(now + window + Duration::from_secs(1));
+        assert_eq!(history.previous_votes(), 0);
+    }
+
+    fn create_history(
+        previous_votes: HashMap<Root, (BlockHash, SystemTime)>,
+    ) -> PersistentVoteHistory {
+        PersistentVoteHistory::with_previous_votes(
+            Default::default(),
+            Arc::new(Ledger::new_null()),
+            previous_votes,
+        )
+    }
+}
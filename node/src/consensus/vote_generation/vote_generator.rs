@@ -1,4 +1,4 @@
-use super::{LocalVoteHistory, VoteSpacing};
+use super::{LocalVoteHistory, PersistentVoteHistory, VoteSpacing, VotingPolicies};
 use crate::{
     consensus::VoteBroadcaster,
     stats::{DetailType, Direction, Sample, StatType, Stats},
@@ -22,7 +22,7 @@ use std::{
         Arc, Condvar, Mutex, MutexGuard,
     },
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 pub(crate) struct VoteGenerator {
@@ -41,6 +41,8 @@ impl VoteGenerator {
         ledger: Arc<Ledger>,
         wallets: Arc<Wallets>,
         history: Arc<LocalVoteHistory>,
+        persistent_history: Option<Arc<PersistentVoteHistory>>,
+        voting_policies: Arc<VotingPolicies>,
         is_final: bool,
         stats: Arc<Stats>,
         message_publisher: MessagePublisher,
@@ -53,6 +55,8 @@ impl VoteGenerator {
             ledger: Arc::clone(&ledger),
             message_publisher: Mutex::new(message_publisher),
             history,
+            persistent_history,
+            voting_policies,
             wallets,
             condition: Condvar::new(),
             queues: Mutex::new(Queues::default()),
@@ -177,6 +181,8 @@ struct SharedState {
     ledger: Arc<Ledger>,
     wallets: Arc<Wallets>,
     history: Arc<LocalVoteHistory>,
+    persistent_history: Option<Arc<PersistentVoteHistory>>,
+    voting_policies: Arc<VotingPolicies>,
     message_publisher: Mutex<MessagePublisher>,
     is_final: bool,
     condition: Condvar,
@@ -234,12 +240,17 @@ impl SharedState {
             let spacing = self.spacing.lock().unwrap();
             while let Some((root, hash)) = queues.candidates.pop_front() {
                 if !roots.contains(&root) {
-                    if spacing.votable(&root, &hash) {
-                        roots.push(root);
-                        hashes.push(hash);
-                    } else {
+                    if !spacing.votable(&root, &hash) {
                         self.stats
                             .inc(StatType::VoteGenerator, DetailType::GeneratorSpacing);
+                    } else if !self.persistent_votable(&root, &hash) {
+                        self.stats.inc(
+                            StatType::VoteGenerator,
+                            DetailType::GeneratorHistoryConflict,
+                        );
+                    } else {
+                        roots.push(root);
+                        hashes.push(hash);
                     }
                 }
                 if hashes.len() == VoteGenerator::MAX_HASHES {
@@ -277,7 +288,10 @@ impl SharedState {
     {
         debug_assert_eq!(hashes.len(), roots.len());
         let mut votes = Vec::new();
-        self.wallets.foreach_representative(|keys| {
+        let voters = self
+            .voting_policies
+            .voters(self.wallets.representative_keys(), self.is_final);
+        for keys in &voters {
             let timestamp = if self.is_final {
                 Vote::TIMESTAMP_MAX
             } else {
@@ -294,7 +308,16 @@ impl SharedState {
                 duration,
                 hashes.clone(),
             )));
-        });
+        }
+
+        // The votes are written before they are broadcast, so that a crash can't make the
+        // node vote for a different block on the same root after a restart
+        if let Some(persistent_history) = &self.persistent_history {
+            if !votes.is_empty() {
+                let voted: Vec<_> = roots.iter().cloned().zip(hashes.iter().cloned()).collect();
+                persistent_history.record(&voted, SystemTime::now());
+            }
+        }
 
         for vote in votes {
             {
@@ -308,6 +331,12 @@ impl SharedState {
         }
     }
 
+    fn persistent_votable(&self, root: &Root, hash: &BlockHash) -> bool {
+        self.persistent_history
+            .as_ref()
+            .is_none_or(|h| h.votable(root, hash, SystemTime::now()))
+    }
+
     fn reply(&self, request: (Vec<(Root, BlockHash)>, ChannelId)) {
         let mut i = request.0.iter().peekable();
         while i.peek().is_some() && !self.stopped.load(Ordering::SeqCst) {
@@ -320,12 +349,17 @@ impl SharedState {
                         break;
                     };
                     if !roots.contains(root) {
-                        if spacing.votable(root, hash) {
-                            roots.push(*root);
-                            hashes.push(*hash);
-                        } else {
+                        if !spacing.votable(root, hash) {
                             self.stats
                                 .inc(StatType::VoteGenerator, DetailType::GeneratorSpacing);
+                        } else if !self.persistent_votable(root, hash) {
+                            self.stats.inc(
+                                StatType::VoteGenerator,
+                                DetailType::GeneratorHistoryConflict,
+                            );
+                        } else {
+                            roots.push(*root);
+                            hashes.push(*hash);
                         }
                     }
                 }
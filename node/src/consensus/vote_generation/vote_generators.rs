@@ -1,4 +1,6 @@
-use super::{vote_generator::VoteGenerator, LocalVoteHistory};
+use super::{
+    vote_generator::VoteGenerator, LocalVoteHistory, PersistentVoteHistory, VotingPolicies,
+};
 use crate::{
     config::NodeConfig, consensus::VoteBroadcaster, stats::Stats, transport::MessagePublisher,
     wallets::Wallets, NetworkParams,
@@ -18,6 +20,8 @@ impl VoteGenerators {
         ledger: Arc<Ledger>,
         wallets: Arc<Wallets>,
         history: Arc<LocalVoteHistory>,
+        persistent_history: Arc<PersistentVoteHistory>,
+        voting_policies: Arc<VotingPolicies>,
         stats: Arc<Stats>,
         config: &NodeConfig,
         network_params: &NetworkParams,
@@ -28,6 +32,8 @@ impl VoteGenerators {
             ledger.clone(),
             wallets.clone(),
             history.clone(),
+            Some(persistent_history),
+            voting_policies.clone(),
             false, //none-final
             stats.clone(),
             message_publisher.clone(),
@@ -41,6 +47,8 @@ impl VoteGenerators {
             ledger,
             wallets,
             history,
+            None,
+            voting_policies,
             true, //final
             stats,
             message_publisher.clone(),
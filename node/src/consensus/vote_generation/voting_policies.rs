@@ -0,0 +1,391 @@
+use crate::stats::{DetailType, StatType, Stats};
+use anyhow::{anyhow, Context};
+use rsban_core::{utils::ContainerInfo, Account, Amount, PrivateKey, PublicKey};
+use rsban_ledger::Ledger;
+use rsban_network::bandwidth_limiter::RateLimiter;
+use rsban_nullable_fs::NullableFilesystem;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Voting behaviour of a single local representative
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepVotingPolicy {
+    pub representative: Account,
+    pub enabled: bool,
+    /// Whether the representative generates final votes
+    pub final_votes: bool,
+    /// Maximum number of votes the representative signs per second. 0 means unlimited
+    pub max_votes_per_second: usize,
+    /// File with the private key of the representative, for representatives that aren't stored
+    /// in a wallet. Relative paths are resolved against the data path
+    pub key_file: Option<PathBuf>,
+}
+
+impl RepVotingPolicy {
+    pub fn new(representative: Account) -> Self {
+        Self {
+            representative,
+            enabled: true,
+            final_votes: true,
+            max_votes_per_second: 0,
+            key_file: None,
+        }
+    }
+}
+
+struct PolicyEntry {
+    policy: RepVotingPolicy,
+    limiter: Option<RateLimiter>,
+}
+
+/// Applies the configured per representative policies to the keys that sign votes.
+/// Representatives without a policy vote without restrictions
+pub struct VotingPolicies {
+    enable_voting: bool,
+    /// Minimum voting weight of a representative whose key was loaded from a key file
+    vote_minimum: Amount,
+    ledger: Arc<Ledger>,
+    stats: Arc<Stats>,
+    policies: HashMap<PublicKey, PolicyEntry>,
+    /// Signing keys that were loaded from key files
+    external_keys: Vec<PrivateKey>,
+}
+
+impl VotingPolicies {
+    /// `external_keys` are the keys that were loaded with `load_vote_keys`
+    pub fn new(
+        policies: &[RepVotingPolicy],
+        external_keys: Vec<PrivateKey>,
+        enable_voting: bool,
+        vote_minimum: Amount,
+        ledger: Arc<Ledger>,
+        stats: Arc<Stats>,
+    ) -> Self {
+        let policies = policies
+            .iter()
+            .map(|policy| {
+                let limiter = (policy.max_votes_per_second > 0)
+                    .then(|| RateLimiter::new(1.0, policy.max_votes_per_second));
+                (
+                    policy.representative.into(),
+                    PolicyEntry {
+                        policy: policy.clone(),
+                        limiter,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            enable_voting,
+            vote_minimum,
+            ledger,
+            stats,
+            policies,
+            external_keys,
+        }
+    }
+
+    /// Whether this node votes at all. `wallet_voting_reps` is the number of
+    /// representatives in the unlocked wallets that have the minimum voting weight
+    pub fn is_voting(&self, wallet_voting_reps: u64) -> bool {
+        self.enable_voting
+            && (wallet_voting_reps > 0
+                || self
+                    .external_keys
+                    .iter()
+                    .any(|key| self.has_vote_minimum(&key.public_key())))
+    }
+
+    /// Returns the keys that may sign the next vote. `wallet_keys` are the
+    /// voting representatives found in the unlocked wallets
+    pub fn voters(&self, wallet_keys: Vec<PrivateKey>, is_final: bool) -> Vec<PrivateKey> {
+        let mut keys = wallet_keys;
+        if self.enable_voting {
+            for key in &self.external_keys {
+                let pub_key = key.public_key();
+                if !keys.iter().any(|k| k.public_key() == pub_key)
+                    && self.has_vote_minimum(&pub_key)
+                {
+                    keys.push(key.clone());
+                }
+            }
+        }
+
+        keys.retain(|key| self.may_vote(&key.public_key(), is_final));
+        keys
+    }
+
+    fn has_vote_minimum(&self, representative: &PublicKey) -> bool {
+        self.ledger.weight(representative) >= self.vote_minimum
+    }
+
+    fn may_vote(&self, representative: &PublicKey, is_final: bool) -> bool {
+        let Some(entry) = self.policies.get(representative) else {
+            return true;
+        };
+
+        if !entry.policy.enabled || (is_final && !entry.policy.final_votes) {
+            self.stats
+                .inc(StatType::VoteGenerator, DetailType::GeneratorPolicyDisabled);
+            return false;
+        }
+
+        if let Some(limiter) = &entry.limiter {
+            if !limiter.should_pass(1) {
+                self.stats
+                    .inc(StatType::VoteGenerator, DetailType::GeneratorRateLimited);
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        [
+            ("policies", self.policies.len(), 0),
+            ("external_keys", self.external_keys.len(), 0),
+        ]
+        .into()
+    }
+}
+
+/// Loads the keys of all representatives whose policy has a key file.
+/// Fails if a key file can't be read or contains the key of another representative
+pub fn load_vote_keys(
+    policies: &[RepVotingPolicy],
+    data_path: &Path,
+    fs: &NullableFilesystem,
+) -> anyhow::Result<Vec<PrivateKey>> {
+    let mut keys = Vec::new();
+    for policy in policies {
+        if let Some(key_file) = &policy.key_file {
+            let key = load_vote_key(fs, &data_path.join(key_file))?;
+            if key.account() != policy.representative {
+                return Err(anyhow!(
+                    "Vote key file {:?} doesn't contain the key of representative {}",
+                    key_file,
+                    policy.representative.encode_account()
+                ));
+            }
+            keys.push(key);
+        }
+    }
+    Ok(keys)
+}
+
+/// Reads the private key of a representative from the first line of a key file
+fn load_vote_key(fs: &NullableFilesystem, file_path: &Path) -> anyhow::Result<PrivateKey> {
+    info!("Reading vote key from: {:?}", file_path);
+
+    let content = fs
+        .read_to_string(file_path)
+        .context(format!("Could not read vote key file {:?}", file_path))?;
+
+    let first_line = content.lines().next().unwrap_or("");
+    PrivateKey::from_hex_str(first_line.trim()).context(format!(
+        "Could not decode vote key from file {:?}",
+        file_path
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::Direction;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn no_policy_votes_unrestricted() {
+        let policies = create_policies(&[]);
+        let key = PrivateKey::from(1);
+        assert_eq!(
+            voters(&policies, vec![key.clone()], true),
+            vec![key.public_key()]
+        );
+    }
+
+    #[test]
+    fn disabled_representative_doesnt_vote() {
+        let key = PrivateKey::from(1);
+        let other = PrivateKey::from(2);
+        let policies = create_policies(&[RepVotingPolicy {
+            enabled: false,
+            ..RepVotingPolicy::new(key.account())
+        }]);
+
+        assert_eq!(
+            voters(&policies, vec![key, other.clone()], false),
+            vec![other.public_key()]
+        );
+        assert_eq!(
+            policies.stats.count(
+                StatType::VoteGenerator,
+                DetailType::GeneratorPolicyDisabled,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn exclude_from_final_votes() {
+        let key = PrivateKey::from(1);
+        let policies = create_policies(&[RepVotingPolicy {
+            final_votes: false,
+            ..RepVotingPolicy::new(key.account())
+        }]);
+
+        assert_eq!(
+            voters(&policies, vec![key.clone()], false),
+            vec![key.public_key()]
+        );
+        assert!(voters(&policies, vec![key], true).is_empty());
+    }
+
+    #[test]
+    fn rate_limit_votes() {
+        let key = PrivateKey::from(1);
+        let policies = create_policies(&[RepVotingPolicy {
+            max_votes_per_second: 2,
+            ..RepVotingPolicy::new(key.account())
+        }]);
+
+        assert_eq!(voters(&policies, vec![key.clone()], false).len(), 1);
+        assert_eq!(voters(&policies, vec![key.clone()], false).len(), 1);
+        assert!(voters(&policies, vec![key], false).is_empty());
+        assert_eq!(
+            policies.stats.count(
+                StatType::VoteGenerator,
+                DetailType::GeneratorRateLimited,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn vote_with_key_from_key_file() {
+        let key = PrivateKey::from(1);
+        let fs = fs_with_key_file(format!("{}\n", key.raw_key().encode_hex()));
+        let ledger = Arc::new(Ledger::new_null());
+        let rep_policies = [RepVotingPolicy {
+            key_file: Some("rep.key".into()),
+            ..RepVotingPolicy::new(key.account())
+        }];
+        let keys = load_vote_keys(&rep_policies, Path::new("/data"), &fs).unwrap();
+        let policies = VotingPolicies::new(
+            &rep_policies,
+            keys,
+            true,
+            Amount::nano(1000),
+            ledger.clone(),
+            Arc::new(Stats::default()),
+        );
+
+        // keys without voting weight don't vote
+        assert!(voters(&policies, vec![], false).is_empty());
+        assert!(!policies.is_voting(0));
+
+        // keys below the vote minimum don't vote
+        ledger.rep_weights.set(key.public_key(), Amount::nano(999));
+        assert!(voters(&policies, vec![], false).is_empty());
+        assert!(!policies.is_voting(0));
+
+        ledger.rep_weights.set(key.public_key(), Amount::nano(1000));
+        assert_eq!(voters(&policies, vec![], false), vec![key.public_key()]);
+        assert!(policies.is_voting(0));
+        // a key that is also stored in a wallet signs only once
+        assert_eq!(
+            voters(&policies, vec![key.clone()], false),
+            vec![key.public_key()]
+        );
+    }
+
+    #[test]
+    fn is_voting_with_wallet_reps() {
+        let policies = create_policies(&[]);
+        assert!(!policies.is_voting(0));
+        assert!(policies.is_voting(1));
+
+        let disabled = VotingPolicies::new(
+            &[],
+            Vec::new(),
+            false,
+            Amount::nano(1000),
+            Arc::new(Ledger::new_null()),
+            Arc::new(Stats::default()),
+        );
+        assert!(!disabled.is_voting(1));
+    }
+
+    #[test]
+    fn key_file_for_wrong_representative() {
+        let fs = fs_with_key_file(format!("{}\n", PrivateKey::from(1).raw_key().encode_hex()));
+        let Err(err) = load_vote_keys(
+            &[RepVotingPolicy {
+                key_file: Some("rep.key".into()),
+                ..RepVotingPolicy::new(PrivateKey::from(2).account())
+            }],
+            Path::new("/data"),
+            &fs,
+        ) else {
+            panic!("should fail")
+        };
+        assert!(err.to_string().starts_with("Vote key file \"rep.key\""));
+    }
+
+    #[test]
+    fn fail_if_key_file_cannot_be_read() {
+        let fs = NullableFilesystem::null_builder()
+            .read_to_string_fails(
+                "/data/rep.key",
+                std::io::Error::new(ErrorKind::PermissionDenied, ""),
+            )
+            .finish();
+        let Err(err) = load_vote_keys(
+            &[RepVotingPolicy {
+                key_file: Some("rep.key".into()),
+                ..RepVotingPolicy::new(Account::from(1))
+            }],
+            Path::new("/data"),
+            &fs,
+        ) else {
+            panic!("should fail")
+        };
+        assert_eq!(
+            err.to_string(),
+            "Could not read vote key file \"/data/rep.key\""
+        );
+    }
+
+    fn create_policies(policies: &[RepVotingPolicy]) -> VotingPolicies {
+        VotingPolicies::new(
+            policies,
+            Vec::new(),
+            true,
+            Amount::nano(1000),
+            Arc::new(Ledger::new_null()),
+            Arc::new(Stats::default()),
+        )
+    }
+
+    fn voters(policies: &VotingPolicies, keys: Vec<PrivateKey>, is_final: bool) -> Vec<PublicKey> {
+        policies
+            .voters(keys, is_final)
+            .iter()
+            .map(|k| k.public_key())
+            .collect()
+    }
+
+    fn fs_with_key_file(contents: String) -> NullableFilesystem {
+        NullableFilesystem::null_builder()
+            .read_to_string("/data/rep.key", contents)
+            .finish()
+    }
+}
@@ -58,8 +58,8 @@ impl Monitor {
         );
 
         let channels = self.network.read().unwrap().channels_info();
-        info!("Peers: {} (realtime: {} | bootstrap: {} | inbound connections: {} | outbound connections: {})",
-            channels.total, channels.realtime, channels.bootstrap, channels.inbound, channels.outbound);
+        info!("Peers: {} (realtime: {} | bootstrap: {} | inbound connections: {} | outbound connections: {} | encrypted: {} | plaintext: {})",
+            channels.total, channels.realtime, channels.bootstrap, channels.inbound, channels.outbound, channels.encrypted, channels.plaintext);
 
         {
             let (delta, online, peered) = {
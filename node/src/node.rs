@@ -11,25 +11,29 @@ use crate::{
     config::{GlobalConfig, NodeConfig, NodeFlags},
     consensus::{
         election_schedulers::ElectionSchedulers, get_bootstrap_weights, log_bootstrap_weights,
-        ActiveElections, ActiveElectionsExt, ElectionStatusType, LocalVoteHistory,
-        ProcessLiveDispatcher, ProcessLiveDispatcherExt, RecentlyConfirmedCache, RepTiers,
-        RequestAggregator, RequestAggregatorCleanup, VoteApplier, VoteBroadcaster, VoteCache,
-        VoteCacheProcessor, VoteGenerators, VoteProcessor, VoteProcessorExt, VoteProcessorQueue,
-        VoteProcessorQueueCleanup, VoteRouter,
+        ActiveElections, ActiveElectionsExt, ConfirmationLatency, ElectionStatusType,
+        ElectionTracer, FinalVoteCleanup, FinalVoteCleanupRunner, ForkTracker, LocalVoteHistory,
+        PersistentVoteHistory, PersistentVoteHistoryCleanup, ProcessLiveDispatcher,
+        ProcessLiveDispatcherExt, RecentlyConfirmedCache, RepTiers, RequestAggregator,
+        RequestAggregatorCleanup, VoteApplier, VoteBroadcaster, VoteCache, VoteCacheProcessor,
+        VoteGenerators, VoteProcessor, VoteProcessorExt, VoteProcessorQueue,
+        VoteProcessorQueueCleanup, VoteRouter, VotingPolicies,
     },
     monitor::Monitor,
     node_id_key_file::NodeIdKeyFile,
     pruning::{LedgerPruning, LedgerPruningExt},
-    representatives::{OnlineReps, OnlineRepsCleanup, RepCrawler, RepCrawlerExt},
+    representatives::{
+        OnlineReps, OnlineRepsCleanup, RepCrawler, RepCrawlerExt, RepStats, RepStatsSampler,
+    },
     stats::{
         adapters::{LedgerStats, NetworkStats},
         DetailType, Direction, StatType, Stats,
     },
     transport::{
         InboundMessageQueue, InboundMessageQueueCleanup, KeepaliveFactory, LatestKeepalives,
-        LatestKeepalivesCleanup, MessageProcessor, MessagePublisher, NanoResponseServerSpawner,
-        NetworkFilter, NetworkThreads, PeerCacheConnector, PeerCacheUpdater,
-        RealtimeMessageHandler, SynCookies,
+        LatestKeepalivesCleanup, MessageCapture, MessageProcessor, MessagePublisher,
+        NanoResponseServerSpawner, NetworkFilter, NetworkThreads, PeerCacheConnector,
+        PeerCacheUpdater, PortMapping, PortMappingRefresher, RealtimeMessageHandler, SynCookies,
     },
     utils::{
         LongRunningTransactionLogger, ThreadPool, ThreadPoolImpl, TimerThread, TxnTrackingConfig,
@@ -48,10 +52,10 @@ use rsban_core::{
 use rsban_ledger::{BlockStatus, Ledger, RepWeightCache};
 use rsban_messages::{ConfirmAck, Message, Publish};
 use rsban_network::{
-    ChannelId, DeadChannelCleanup, DropPolicy, Network, NetworkCleanup, NetworkInfo, PeerConnector,
-    TcpListener, TcpListenerExt, TrafficType,
+    ChannelId, DeadChannelCleanup, DropPolicy, IpSubnet, Network, NetworkCleanup, NetworkInfo,
+    PeerConnector, TcpListener, TcpListenerExt, TrafficType,
 };
-use rsban_nullable_clock::{SteadyClock, SystemTimeFactory};
+use rsban_nullable_clock::{SteadyClock, SystemTimeFactory, Timestamp};
 use rsban_nullable_http_client::{HttpClient, Url};
 use rsban_output_tracker::OutputListenerMt;
 use rsban_store_lmdb::{
@@ -99,6 +103,11 @@ pub struct Node {
     pub rep_tiers: Arc<RepTiers>,
     pub vote_processor_queue: Arc<VoteProcessorQueue>,
     pub history: Arc<LocalVoteHistory>,
+    pub persistent_vote_history: Arc<PersistentVoteHistory>,
+    persistent_vote_history_cleanup: TimerThread<PersistentVoteHistoryCleanup>,
+    pub voting_policies: Arc<VotingPolicies>,
+    pub final_vote_cleanup: Arc<FinalVoteCleanup>,
+    final_vote_cleanup_runner: TimerThread<FinalVoteCleanupRunner>,
     pub confirming_set: Arc<ConfirmingSet>,
     pub vote_cache: Arc<Mutex<VoteCache>>,
     pub block_processor: Arc<BlockProcessor>,
@@ -126,6 +135,14 @@ pub struct Node {
     peer_cache_connector: TimerThread<PeerCacheConnector>,
     pub inbound_message_queue: Arc<InboundMessageQueue>,
     monitor: TimerThread<Monitor>,
+    pub rep_stats: Arc<RepStats>,
+    rep_stats_sampler: TimerThread<RepStatsSampler>,
+    pub fork_tracker: Arc<ForkTracker>,
+    pub election_tracer: Arc<ElectionTracer>,
+    pub confirmation_latency: Arc<ConfirmationLatency>,
+    pub port_mapping: Arc<PortMapping>,
+    port_mapping_refresher: TimerThread<PortMappingRefresher>,
+    pub message_capture: Option<Arc<MessageCapture>>,
     stopped: AtomicBool,
     pub network_filter: Arc<NetworkFilter>,
     pub message_publisher: Arc<Mutex<MessagePublisher>>, // TODO remove this. It is needed right now
@@ -141,6 +158,9 @@ pub(crate) struct NodeArgs {
     pub flags: NodeFlags,
     pub work: Arc<WorkPoolImpl>,
     pub callbacks: NodeCallbacks,
+    pub steady_clock: Arc<SteadyClock>,
+    /// Keys of the representatives whose voting policy has a key file
+    pub vote_keys: Vec<PrivateKey>,
 }
 
 impl NodeArgs {
@@ -155,6 +175,8 @@ impl NodeArgs {
             flags: Default::default(),
             callbacks: Default::default(),
             work: Arc::new(WorkPoolImpl::new_null(123)),
+            steady_clock: Arc::new(SteadyClock::default()),
+            vote_keys: Vec::new(),
         }
     }
 }
@@ -188,7 +210,7 @@ impl Node {
         let work = args.work;
         // Time relative to the start of the node. This makes time exlicit and enables us to
         // write time relevant unit tests with ease.
-        let steady_clock = Arc::new(SteadyClock::default());
+        let steady_clock = args.steady_clock;
 
         let network_label = network_params.network.get_current_network_as_string();
         let global_config = GlobalConfig {
@@ -273,6 +295,16 @@ impl Node {
         ));
 
         let network_info = Arc::new(RwLock::new(NetworkInfo::new(global_config.into())));
+        load_peer_bans(
+            &store,
+            &mut network_info.write().unwrap(),
+            steady_clock.now(),
+        );
+        load_peer_reputations(
+            &store,
+            &mut network_info.write().unwrap(),
+            steady_clock.now(),
+        );
 
         let network_observer = Arc::new(NetworkStats::new(stats.clone()));
 
@@ -299,12 +331,22 @@ impl Node {
 
         dead_channel_cleanup.add_step(NetworkCleanup::new(network.clone()));
 
+        let message_capture = config.message_capture.enabled.then(|| {
+            Arc::new(MessageCapture::new(
+                &config.message_capture,
+                &application_path,
+                network_info.clone(),
+            ))
+        });
+
+        let callbacks = args.callbacks;
+
         let mut inbound_message_queue =
             InboundMessageQueue::new(config.message_processor.max_queue, stats.clone());
-        if let Some(cb) = args.callbacks.on_inbound {
+        if let Some(cb) = callbacks.on_inbound {
             inbound_message_queue.set_inbound_callback(cb);
         }
-        if let Some(cb) = args.callbacks.on_inbound_dropped {
+        if let Some(cb) = callbacks.on_inbound_dropped {
             inbound_message_queue.set_inbound_dropped_callback(cb);
         }
         let inbound_message_queue = Arc::new(inbound_message_queue);
@@ -346,9 +388,13 @@ impl Node {
             network_params.network.protocol_info(),
         );
 
-        if let Some(callback) = &args.callbacks.on_publish {
+        if let Some(callback) = &callbacks.on_publish {
             message_publisher.set_published_callback(callback.clone());
         }
+        // Inbound messages are captured by the response servers as they are read
+        if let Some(capture) = &message_capture {
+            message_publisher.set_message_capture(capture.clone());
+        }
 
         let telemetry = Arc::new(Telemetry::new(
             telemetry_config,
@@ -388,6 +434,18 @@ impl Node {
         dead_channel_cleanup.add_step(VoteProcessorQueueCleanup::new(vote_processor_queue.clone()));
 
         let history = Arc::new(LocalVoteHistory::new(network_params.voting.max_cache));
+        let persistent_vote_history = Arc::new(PersistentVoteHistory::new(
+            config.persistent_vote_history.clone(),
+            ledger.clone(),
+        ));
+        let voting_policies = Arc::new(VotingPolicies::new(
+            &config.voting_policies,
+            args.vote_keys,
+            config.enable_voting,
+            config.vote_minimum,
+            ledger.clone(),
+            stats.clone(),
+        ));
 
         let confirming_set = Arc::new(ConfirmingSet::new(
             config.confirming_set.clone(),
@@ -463,6 +521,8 @@ impl Node {
             ledger.clone(),
             wallets.clone(),
             history.clone(),
+            persistent_vote_history.clone(),
+            voting_policies.clone(),
             stats.clone(),
             &config,
             &network_params,
@@ -480,6 +540,7 @@ impl Node {
             config.clone(),
             history.clone(),
             wallets.clone(),
+            voting_policies.clone(),
             recently_confirmed.clone(),
             confirming_set.clone(),
             election_workers.clone(),
@@ -492,8 +553,7 @@ impl Node {
             rep_weights.clone(),
         ));
 
-        let on_vote = args
-            .callbacks
+        let on_vote = callbacks
             .on_vote
             .unwrap_or_else(|| Box::new(|_, _, _, _| {}));
 
@@ -511,14 +571,14 @@ impl Node {
             config.vote_processor.clone(),
         ));
 
-        let on_election_end = args
-            .callbacks
+        let on_election_end = callbacks
             .on_election_end
             .unwrap_or_else(|| Box::new(|_, _, _, _, _, _| {}));
 
+        let election_tracer = Arc::new(ElectionTracer::new(config.election_tracer.clone()));
+
         let active_elections = Arc::new(ActiveElections::new(
             network_params.clone(),
-            wallets.clone(),
             config.clone(),
             ledger.clone(),
             confirming_set.clone(),
@@ -537,6 +597,7 @@ impl Node {
             vote_cache_processor.clone(),
             steady_clock.clone(),
             message_publisher.clone(),
+            election_tracer.clone(),
         ));
 
         active_elections.initialize();
@@ -568,9 +629,12 @@ impl Node {
             512,
         );
 
-        if let Some(callback) = &args.callbacks.on_publish {
+        if let Some(callback) = &callbacks.on_publish {
             bootstrap_publisher.set_published_callback(callback.clone());
         }
+        if let Some(capture) = &message_capture {
+            bootstrap_publisher.set_message_capture(capture.clone());
+        }
 
         let bootstrap_initiator = Arc::new(BootstrapInitiator::new(
             global_config.into(),
@@ -608,6 +672,7 @@ impl Node {
             syn_cookies: syn_cookies.clone(),
             latest_keepalives: latest_keepalives.clone(),
             network_filter: network_filter.clone(),
+            message_capture: message_capture.clone(),
         });
 
         let peer_connector = Arc::new(PeerConnector::new(
@@ -694,7 +759,7 @@ impl Node {
             network_filter.clone(),
             block_processor.clone(),
             config.clone(),
-            wallets.clone(),
+            vote_applier.clone(),
             request_aggregator.clone(),
             vote_processor_queue.clone(),
             telemetry.clone(),
@@ -702,9 +767,17 @@ impl Node {
             ascendboot.clone(),
         ));
 
+        let port_mapping = Arc::new(PortMapping::new(
+            config.port_mapping.clone(),
+            &network_params.portmapping,
+            network_info.clone(),
+            stats.clone(),
+        ));
+
         let keepalive_factory = Arc::new(KeepaliveFactory {
             network: network_info.clone(),
             config: config.clone(),
+            port_mapping: port_mapping.clone(),
         });
 
         let network_threads = Arc::new(Mutex::new(NetworkThreads::new(
@@ -930,6 +1003,90 @@ impl Node {
             }
         }));
 
+        let rep_stats = Arc::new(RepStats::new(ledger.clone(), config.rep_stats.clone()));
+        if config.rep_stats.enabled {
+            let rep_stats = rep_stats.clone();
+            active_elections.on_election_ended(Box::new(move |status, votes, _, _, _, _| {
+                rep_stats.election_ended(status, votes)
+            }));
+        }
+
+        let fork_tracker = Arc::new(ForkTracker::new(config.fork_tracker.clone()));
+        let fork_tracker_l = fork_tracker.clone();
+        let ledger_l = ledger.clone();
+        block_processor.on_block_processed(Box::new(move |status, context| {
+            if status != BlockStatus::Fork {
+                return;
+            }
+            let block = context.block.lock().unwrap().clone();
+            let root = block.qualified_root();
+            let tx = ledger_l.read_txn();
+            let Some(existing) = ledger_l.any().block_successor_by_qualified_root(&tx, &root)
+            else {
+                return;
+            };
+            // Forks of confirmed blocks can't win an election anymore
+            if ledger_l.confirmed().block_exists_or_pruned(&tx, &existing) {
+                return;
+            }
+            let Some(account) = ledger_l
+                .any()
+                .get_block(&tx, &existing)
+                .map(|b| b.account())
+            else {
+                return;
+            };
+            fork_tracker_l.fork_detected(root, account, existing, block.hash(), SystemTime::now());
+        }));
+        if election_tracer.is_enabled() {
+            let tracer = election_tracer.clone();
+            let rep_weights_l = rep_weights.clone();
+            vote_processor.add_vote_processed_callback(Box::new(
+                move |vote, channel_id, source, code| {
+                    let weight = rep_weights_l.weight(&vote.voting_account);
+                    tracer.vote_processed(
+                        vote,
+                        weight,
+                        channel_id,
+                        source,
+                        code,
+                        SystemTime::now(),
+                    );
+                },
+            ));
+
+            let tracer = election_tracer.clone();
+            confirming_set.on_cemented(Box::new(move |block| {
+                tracer.block_cemented(&block.hash(), SystemTime::now());
+            }));
+        }
+
+        let fork_tracker_l = fork_tracker.clone();
+        active_elections.on_election_ended(Box::new(move |status, votes, _, _, _, _| {
+            fork_tracker_l.election_ended(status, votes)
+        }));
+        let fork_tracker_l = fork_tracker.clone();
+        active_elections.on_election_dropped(Box::new(move |election| {
+            let tallies = election.mutex.lock().unwrap().last_tally.clone();
+            fork_tracker_l.election_dropped(&election.qualified_root, &tallies, SystemTime::now())
+        }));
+
+        let confirmation_latency = Arc::new(ConfirmationLatency::new(
+            config.confirmation_latency.clone(),
+        ));
+        let confirmation_latency_l = confirmation_latency.clone();
+        block_processor.on_block_processed(Box::new(move |status, context| {
+            if status == BlockStatus::Progress {
+                let hash = context.block.lock().unwrap().hash();
+                let arrival = SystemTime::now() - context.arrival.elapsed();
+                confirmation_latency_l.block_arrived(hash, context.source, arrival);
+            }
+        }));
+        let confirmation_latency_l = confirmation_latency.clone();
+        active_elections.on_election_ended(Box::new(move |status, _, _, _, _, _| {
+            confirmation_latency_l.election_ended(status, SystemTime::now())
+        }));
+
         if !config.callback_address.is_empty() {
             let tokio = runtime.clone();
             let stats = stats.clone();
@@ -1013,6 +1170,7 @@ impl Node {
             network_info.clone(),
             ledger.clone(),
             time_factory,
+            steady_clock.clone(),
             stats.clone(),
             if network_params.network.is_dev_network() {
                 Duration::from_secs(10)
@@ -1023,6 +1181,7 @@ impl Node {
 
         let peer_cache_connector = PeerCacheConnector::new(
             ledger.clone(),
+            network_info.clone(),
             peer_connector.clone(),
             stats.clone(),
             network_params.network.merge_period,
@@ -1043,16 +1202,47 @@ impl Node {
                 online_reps.clone(),
                 active_elections.clone(),
             ),
-        );
+        )
+        .with_clock(steady_clock.clone());
+
+        let final_vote_cleanup = Arc::new(FinalVoteCleanup::new(ledger.clone()));
+        let final_vote_cleanup_runner = TimerThread::new(
+            "Final vote cleanup",
+            FinalVoteCleanupRunner::new(
+                final_vote_cleanup.clone(),
+                config.final_vote_cleanup.clone(),
+            ),
+        )
+        .with_clock(steady_clock.clone());
+
+        let persistent_vote_history_cleanup = TimerThread::new(
+            "Vote history",
+            PersistentVoteHistoryCleanup::new(persistent_vote_history.clone()),
+        )
+        .with_clock(steady_clock.clone());
+
+        let rep_stats_sampler = TimerThread::new(
+            "Rep stats",
+            RepStatsSampler::new(rep_stats.clone(), online_reps.clone()),
+        )
+        .with_clock(steady_clock.clone());
+
+        let peer_cache_updater =
+            TimerThread::new("Peer history", peer_cache_updater).with_clock(steady_clock.clone());
+        let peer_cache_connector =
+            TimerThread::new_run_immedately("Net reachout", peer_cache_connector)
+                .with_clock(steady_clock.clone());
+        let port_mapping_refresher = TimerThread::new_run_immedately(
+            "Port mapping",
+            PortMappingRefresher::new(port_mapping.clone()),
+        )
+        .with_clock(steady_clock.clone());
 
         Self {
             is_nulled,
             steady_clock,
-            peer_cache_updater: TimerThread::new("Peer history", peer_cache_updater),
-            peer_cache_connector: TimerThread::new_run_immedately(
-                "Net reachout",
-                peer_cache_connector,
-            ),
+            peer_cache_updater,
+            peer_cache_connector,
             ongoing_bootstrap,
             peer_connector,
             node_id,
@@ -1082,6 +1272,11 @@ impl Node {
             vote_router,
             vote_processor_queue,
             history,
+            persistent_vote_history,
+            persistent_vote_history_cleanup,
+            voting_policies,
+            final_vote_cleanup,
+            final_vote_cleanup_runner,
             confirming_set,
             vote_cache,
             block_processor,
@@ -1104,6 +1299,14 @@ impl Node {
             message_processor,
             inbound_message_queue,
             monitor,
+            rep_stats_sampler,
+            rep_stats,
+            fork_tracker,
+            election_tracer,
+            confirmation_latency,
+            port_mapping_refresher,
+            port_mapping,
+            message_capture,
             message_publisher: message_publisher_l,
             network_filter,
             stopped: AtomicBool::new(false),
@@ -1140,7 +1343,23 @@ impl Node {
             .node("rep_crawler", self.rep_crawler.container_info())
             .node("block_processor", self.block_processor.container_info())
             .node("online_reps", online_reps)
+            .node("rep_stats", self.rep_stats.container_info())
+            .node("fork_tracker", self.fork_tracker.container_info())
+            .node("election_tracer", self.election_tracer.container_info())
+            .node(
+                "confirmation_latency",
+                self.confirmation_latency.container_info(),
+            )
             .node("history", self.history.container_info())
+            .node(
+                "persistent_vote_history",
+                self.persistent_vote_history.container_info(),
+            )
+            .node("voting_policies", self.voting_policies.container_info())
+            .node(
+                "final_vote_cleanup",
+                self.final_vote_cleanup.container_info(),
+            )
             .node("confirming_set", self.confirming_set.container_info())
             .node(
                 "request_aggregator",
@@ -1188,6 +1407,45 @@ impl Node {
         }
     }
 
+    /// Bans the subnet and closes all channels from within it. `None` bans it forever.
+    /// Returns the number of closed channels.
+    pub fn ban_peer(&self, subnet: IpSubnet, duration: Option<Duration>, persist: bool) -> usize {
+        let until = match duration {
+            Some(duration) => self.steady_clock.now() + duration,
+            None => Timestamp::MAX,
+        };
+        if persist {
+            let mut tx = self.ledger.rw_txn();
+            self.ledger.store.peer_ban.put(
+                &mut tx,
+                subnet.network(),
+                subnet.prefix_len(),
+                duration.map(|d| SystemTime::now() + d),
+            );
+        }
+        self.network_info.write().unwrap().ban(subnet, until)
+    }
+
+    /// Lifts a (persisted) ban. Returns false if the subnet wasn't excluded
+    pub fn unban_peer(&self, subnet: &IpSubnet) -> bool {
+        let mut tx = self.ledger.rw_txn();
+        let persisted = self
+            .ledger
+            .store
+            .peer_ban
+            .iter(&tx)
+            .any(|(network, prefix_len, _)| IpSubnet::new(network, prefix_len) == *subnet);
+        if persisted {
+            self.ledger
+                .store
+                .peer_ban
+                .del(&mut tx, subnet.network(), subnet.prefix_len());
+        }
+        drop(tx);
+        let unbanned = self.network_info.write().unwrap().unban(subnet);
+        unbanned || persisted
+    }
+
     pub fn is_stopped(&self) -> bool {
         self.stopped.load(Ordering::SeqCst)
     }
@@ -1447,6 +1705,27 @@ impl NodeExt for Arc<Node> {
         if self.config.monitor.enabled {
             self.monitor.start(self.config.monitor.interval);
         }
+
+        if self.config.rep_stats.enabled {
+            self.rep_stats_sampler
+                .start(self.config.rep_stats.sample_interval);
+        }
+
+        if self.config.persistent_vote_history.enabled {
+            self.persistent_vote_history_cleanup
+                .start(PersistentVoteHistory::CLEANUP_INTERVAL);
+        }
+
+        if self.config.final_vote_cleanup.enabled {
+            self.final_vote_cleanup_runner
+                .start(self.config.final_vote_cleanup.interval);
+        }
+
+        if self.config.port_mapping.enabled {
+            self.port_mapping_refresher.start(Duration::from_secs(
+                self.network_params.portmapping.health_check_period_s as u64,
+            ));
+        }
     }
 
     fn stop(&self) {
@@ -1470,6 +1749,8 @@ impl NodeExt for Arc<Node> {
         self.ledger_pruning.stop();
         self.peer_cache_connector.stop();
         self.peer_cache_updater.stop();
+        self.port_mapping_refresher.stop();
+        self.port_mapping.remove();
         // Cancels ongoing work generation tasks, which may be blocking other threads
         // No tasks may wait for work generation in I/O threads, or termination signal capturing will be unable to call node::stop()
         self.distributed_work.stop();
@@ -1487,6 +1768,7 @@ impl NodeExt for Arc<Node> {
         self.election_schedulers.stop();
         self.active.stop();
         self.vote_generators.stop();
+        self.persistent_vote_history_cleanup.stop();
         self.confirming_set.stop();
         self.telemetry.stop();
         self.bootstrap_server.stop();
@@ -1498,6 +1780,12 @@ impl NodeExt for Arc<Node> {
         self.message_processor.lock().unwrap().stop();
         self.network_threads.lock().unwrap().stop(); // Stop network last to avoid killing in-use sockets
         self.monitor.stop();
+        self.rep_stats_sampler.stop();
+        self.final_vote_cleanup.stop();
+        self.final_vote_cleanup_runner.stop();
+        if let Some(capture) = &self.message_capture {
+            capture.flush();
+        }
 
         // work pool is not stopped on purpose due to testing setup
     }
@@ -1591,6 +1879,42 @@ impl NodeExt for Arc<Node> {
     }
 }
 
+fn load_peer_bans(store: &LmdbStore, network_info: &mut NetworkInfo, now: Timestamp) {
+    let mut tx = store.tx_begin_write();
+    let system_now = SystemTime::now();
+    let mut expired = Vec::new();
+    for (network, prefix_len, until) in store.peer_ban.iter(&tx) {
+        let until = match until {
+            Some(until) => match until.duration_since(system_now) {
+                Ok(remaining) => now + remaining,
+                Err(_) => {
+                    expired.push((network, prefix_len));
+                    continue;
+                }
+            },
+            None => Timestamp::MAX,
+        };
+        network_info.ban(IpSubnet::new(network, prefix_len), until);
+    }
+    for (network, prefix_len) in expired {
+        store.peer_ban.del(&mut tx, network, prefix_len);
+    }
+}
+
+fn load_peer_reputations(store: &LmdbStore, network_info: &mut NetworkInfo, now: Timestamp) {
+    let tx = store.tx_begin_read();
+    let system_now = SystemTime::now();
+    for (ip, reputation) in store.peer_reputation.iter(&tx) {
+        if let Some(exclusion) = &reputation.exclusion {
+            // Expired exclusions are not restored
+            if let Ok(remaining) = exclusion.until.duration_since(system_now) {
+                network_info.restore_exclusion(ip, exclusion.score, now + remaining);
+            }
+        }
+        network_info.restore_reputation(ip, reputation);
+    }
+}
+
 fn make_store(
     path: &Path,
     add_db_postfix: bool,
@@ -1680,6 +2004,23 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn start_rep_stats_sampler() {
+        let node = TestNode::new().await;
+        let start_tracker = node.rep_stats_sampler.track_start();
+
+        node.start();
+
+        assert_eq!(
+            start_tracker.output(),
+            vec![TimerStartEvent {
+                thread_name: "Rep stats".to_string(),
+                interval: node.config.rep_stats.sample_interval,
+                run_immediately: false
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn stop_node() {
         let node = TestNode::new().await;
@@ -1697,6 +2038,10 @@ mod tests {
             false,
             "peer_cache_connector running"
         );
+        assert!(
+            !node.rep_stats_sampler.is_running(),
+            "rep_stats_sampler running"
+        );
     }
 
     struct TestNode {
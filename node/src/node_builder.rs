@@ -1,15 +1,18 @@
 use crate::{
     config::{get_node_toml_config_path, DaemonConfig, DaemonToml, NodeConfig, NodeFlags},
-    consensus::{ElectionEndCallback, ElectionStatus, VoteProcessedCallback2},
+    consensus::{load_vote_keys, ElectionEndCallback, ElectionStatus, VoteProcessedCallback2},
     transport::MessageCallback,
     working_path_for, NetworkParams, Node, NodeArgs,
 };
+use anyhow::Context;
 use rsban_core::{
     utils::get_cpu_count, work::WorkPoolImpl, Account, Amount, Networks, Vote, VoteCode,
     VoteSource, VoteWithWeightInfo,
 };
 use rsban_messages::Message;
 use rsban_network::ChannelId;
+use rsban_nullable_clock::SteadyClock;
+use rsban_nullable_fs::NullableFilesystem;
 use std::{path::PathBuf, sync::Arc, time::Duration};
 
 #[derive(Default)]
@@ -91,6 +94,7 @@ pub struct NodeBuilder {
     flags: Option<NodeFlags>,
     work: Option<Arc<WorkPoolImpl>>,
     callbacks: Option<NodeCallbacks>,
+    steady_clock: Option<Arc<SteadyClock>>,
 }
 
 impl NodeBuilder {
@@ -104,6 +108,7 @@ impl NodeBuilder {
             flags: None,
             work: None,
             callbacks: None,
+            steady_clock: None,
         }
     }
 
@@ -142,6 +147,12 @@ impl NodeBuilder {
         self
     }
 
+    /// The clock of the node. A nulled clock lets the node run on a virtual time
+    pub fn steady_clock(mut self, clock: Arc<SteadyClock>) -> Self {
+        self.steady_clock = Some(clock);
+        self
+    }
+
     pub fn get_data_path(&self) -> anyhow::Result<PathBuf> {
         match &self.data_path {
             Some(path) => Ok(path.clone()),
@@ -184,6 +195,12 @@ impl NodeBuilder {
         });
 
         let callbacks = self.callbacks.unwrap_or_default();
+        let vote_keys = load_vote_keys(
+            &config.voting_policies,
+            &data_path,
+            &NullableFilesystem::default(),
+        )
+        .context("Could not load voting policies")?;
 
         let args = NodeArgs {
             runtime,
@@ -193,6 +210,8 @@ impl NodeBuilder {
             flags,
             work,
             callbacks,
+            steady_clock: self.steady_clock.unwrap_or_default(),
+            vote_keys,
         };
 
         Ok(Node::new_with_args(args))
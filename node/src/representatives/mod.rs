@@ -1,7 +1,11 @@
 mod online_reps;
 mod online_weight_sampler;
+mod quorum_simulator;
 mod rep_crawler;
+mod rep_stats;
 
 pub use online_reps::*;
 pub use online_weight_sampler::OnlineWeightSampler;
+pub use quorum_simulator::*;
 pub use rep_crawler::*;
+pub use rep_stats::*;
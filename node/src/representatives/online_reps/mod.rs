@@ -112,15 +112,11 @@ impl OnlineReps {
 
     /// Returns the quorum required for confirmation
     pub fn quorum_delta(&self) -> Amount {
-        // Using a larger container to ensure maximum precision
-        let weight = max(
-            max(self.online_weight, self.trended_weight),
+        calculate_quorum_delta(
+            self.online_weight,
+            self.trended_weight,
             self.online_weight_minimum,
-        );
-
-        let delta =
-            U256::from(weight.number()) * U256::from(ONLINE_WEIGHT_QUORUM) / U256::from(100);
-        Amount::raw(delta.as_u128())
+        )
     }
 
     pub fn on_rep_request(&mut self, channel_id: ChannelId, now: Timestamp) {
@@ -137,6 +133,46 @@ impl OnlineReps {
             .map(|rep| rep.last_request.elapsed(now))
     }
 
+    /// Records the response of a peered representative to a rep crawler query
+    pub fn rep_responded(
+        &mut self,
+        rep_account: &PublicKey,
+        latency: Option<Duration>,
+        now: Timestamp,
+    ) {
+        self.peered_reps.modify(rep_account, |rep| {
+            rep.last_response = Some(now);
+            if latency.is_some() {
+                rep.latency = latency;
+            }
+        });
+    }
+
+    /// Channels of peered principal representatives that didn't answer their last query
+    pub fn unresponsive_principal_channels(&self) -> Vec<ChannelId> {
+        let min_weight = self.minimum_principal_weight();
+        let mut channels: Vec<_> = self
+            .peered_reps
+            .iter()
+            .filter(|rep| {
+                rep.is_unresponsive() && self.rep_weights.weight(&rep.account) >= min_weight
+            })
+            .map(|rep| rep.channel_id)
+            .collect();
+        channels.sort();
+        channels.dedup();
+        channels
+    }
+
+    pub fn peered_rep(&self, rep_account: &PublicKey) -> Option<&PeeredRep> {
+        self.peered_reps.get(rep_account)
+    }
+
+    /// Returns when the last vote of the representative was observed
+    pub fn last_vote(&self, rep_account: &PublicKey) -> Option<Timestamp> {
+        self.online_reps.last_vote(rep_account)
+    }
+
     /// List of online representatives, both the currently sampling ones and the ones observed in the previous sampling period
     pub fn online_reps(&self) -> impl Iterator<Item = &PublicKey> {
         self.online_reps.iter()
@@ -225,6 +261,19 @@ impl OnlineReps {
     }
 }
 
+/// Returns the quorum required for confirmation for the given online and trended weight
+pub fn calculate_quorum_delta(
+    online_weight: Amount,
+    trended_weight: Amount,
+    online_weight_minimum: Amount,
+) -> Amount {
+    // Using a larger container to ensure maximum precision
+    let weight = max(max(online_weight, trended_weight), online_weight_minimum);
+
+    let delta = U256::from(weight.number()) * U256::from(ONLINE_WEIGHT_QUORUM) / U256::from(100);
+    Amount::raw(delta.as_u128())
+}
+
 impl Default for OnlineReps {
     fn default() -> Self {
         Self::builder().finish()
@@ -380,4 +429,50 @@ mod tests {
 
         assert_eq!(online_reps.online_weight(), Amount::nano(600_000));
     }
+
+    #[test]
+    fn record_rep_response() {
+        let now = Timestamp::new_test_instance();
+        let rep_account = PublicKey::from(42);
+        let channel_id = ChannelId::from(1);
+        let mut online_reps = OnlineReps::default();
+        online_reps.vote_observed_directly(rep_account, channel_id, now);
+
+        online_reps.on_rep_request(channel_id, now + Duration::from_secs(1));
+        online_reps.rep_responded(
+            &rep_account,
+            Some(Duration::from_millis(300)),
+            now + Duration::from_secs(2),
+        );
+
+        let rep = online_reps.peered_rep(&rep_account).unwrap();
+        assert_eq!(rep.last_response, Some(now + Duration::from_secs(2)));
+        assert_eq!(rep.latency, Some(Duration::from_millis(300)));
+        assert!(!rep.is_unresponsive());
+    }
+
+    #[test]
+    fn unresponsive_principal_channels() {
+        let now = Timestamp::new_test_instance();
+        let weights = Arc::new(RepWeightCache::new());
+        let mut online_reps = OnlineReps::builder().rep_weights(weights.clone()).finish();
+        let principal = PublicKey::from(1);
+        let non_principal = PublicKey::from(2);
+        weights.set(principal, Amount::nano(100_000));
+        weights.set(non_principal, Amount::nano(1));
+        online_reps.vote_observed_directly(principal, ChannelId::from(1), now);
+        online_reps.vote_observed_directly(non_principal, ChannelId::from(2), now);
+        online_reps.rep_responded(&principal, None, now);
+        online_reps.rep_responded(&non_principal, None, now);
+        assert!(online_reps.unresponsive_principal_channels().is_empty());
+
+        let later = now + Duration::from_secs(60);
+        online_reps.on_rep_request(ChannelId::from(1), later);
+        online_reps.on_rep_request(ChannelId::from(2), later);
+
+        assert_eq!(
+            online_reps.unresponsive_principal_channels(),
+            vec![ChannelId::from(1)]
+        );
+    }
 }
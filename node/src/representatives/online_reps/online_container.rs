@@ -22,6 +22,11 @@ impl OnlineContainer {
         self.by_account.keys()
     }
 
+    /// Returns when the last vote of the representative was observed
+    pub fn last_vote(&self, rep: &PublicKey) -> Option<Timestamp> {
+        self.by_account.get(rep).cloned()
+    }
+
     /// Returns `true` if it was a new insert and `false` if an entry for that account was already present
     pub fn insert(&mut self, rep: PublicKey, now: Timestamp) -> bool {
         let new_insert = if let Some(time) = self.by_account.get_mut(&rep) {
@@ -70,6 +70,16 @@ impl PeeredContainer {
         }
     }
 
+    pub fn get(&self, account: &PublicKey) -> Option<&PeeredRep> {
+        self.by_account.get(account)
+    }
+
+    pub fn modify(&mut self, account: &PublicKey, modify: impl FnOnce(&mut PeeredRep)) {
+        if let Some(rep) = self.by_account.get_mut(account) {
+            modify(rep);
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &PeeredRep> {
         self.by_account.values()
     }
@@ -1,6 +1,7 @@
 use rsban_core::PublicKey;
 use rsban_network::ChannelId;
 use rsban_nullable_clock::Timestamp;
+use std::time::Duration;
 
 /// A representative to which we have a direct connection
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -8,6 +9,10 @@ pub struct PeeredRep {
     pub account: PublicKey,
     pub channel_id: ChannelId,
     pub last_request: Timestamp,
+    /// When the representative last answered a rep crawler query
+    pub last_response: Option<Timestamp>,
+    /// Time between the last answered query and its response
+    pub latency: Option<Duration>,
 }
 
 impl PeeredRep {
@@ -16,6 +21,16 @@ impl PeeredRep {
             account,
             channel_id,
             last_request,
+            last_response: None,
+            latency: None,
+        }
+    }
+
+    /// The representative didn't answer the last rep crawler query
+    pub fn is_unresponsive(&self) -> bool {
+        match self.last_response {
+            Some(last_response) => last_response < self.last_request,
+            None => true,
         }
     }
 }
@@ -0,0 +1,272 @@
+use super::{calculate_quorum_delta, OnlineReps};
+use crate::consensus::RepTier;
+use anyhow::bail;
+use rsban_core::{Amount, PublicKey};
+use rsban_ledger::RepWeightCache;
+use std::{
+    cmp::{max, Reverse},
+    collections::{HashMap, HashSet},
+};
+
+/// A hypothetical change of the representative weights or of their online status
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WeightChange {
+    /// Delegators of `from` move `amount` of weight to `to`
+    Move {
+        from: PublicKey,
+        to: PublicKey,
+        amount: Amount,
+    },
+    Offline(PublicKey),
+    Online(PublicKey),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedRep {
+    pub representative: PublicKey,
+    pub weight: Amount,
+    pub online: bool,
+    pub tier: RepTier,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuorumSnapshot {
+    pub online_weight: Amount,
+    pub trended_weight: Amount,
+    pub quorum_delta: Amount,
+    /// Principal representatives in descending order of weight
+    pub principal_reps: Vec<SimulatedRep>,
+}
+
+/// Calculates the online weight, quorum delta and representative tiers
+/// that would result from hypothetical weight changes.
+/// The trended weight is a long term average and isn't affected by the changes.
+#[derive(Clone)]
+pub struct QuorumSimulator {
+    weights: HashMap<PublicKey, Amount>,
+    online: HashSet<PublicKey>,
+    trended_weight: Amount,
+    online_weight_minimum: Amount,
+}
+
+impl QuorumSimulator {
+    pub fn new(
+        weights: HashMap<PublicKey, Amount>,
+        online: HashSet<PublicKey>,
+        trended_weight: Amount,
+        online_weight_minimum: Amount,
+    ) -> Self {
+        Self {
+            weights,
+            online,
+            trended_weight,
+            online_weight_minimum,
+        }
+    }
+
+    pub fn from_online_reps(online_reps: &OnlineReps, rep_weights: &RepWeightCache) -> Self {
+        Self::new(
+            rep_weights.read().clone(),
+            online_reps.online_reps().cloned().collect(),
+            online_reps.trended_weight(),
+            online_reps.online_weight_minimum(),
+        )
+    }
+
+    pub fn apply(&mut self, change: &WeightChange) -> anyhow::Result<()> {
+        match change {
+            WeightChange::Move { from, to, amount } => {
+                let from_weight = self.weight(from);
+                if from_weight < *amount {
+                    bail!("Representative has insufficient weight");
+                }
+                self.weights.insert(*from, from_weight - *amount);
+                let to_weight = self.weight(to);
+                self.weights.insert(*to, to_weight + *amount);
+            }
+            WeightChange::Offline(rep) => {
+                self.online.remove(rep);
+            }
+            WeightChange::Online(rep) => {
+                self.online.insert(*rep);
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies all changes and returns the quorum before and after them
+    pub fn simulate(
+        &self,
+        changes: &[WeightChange],
+    ) -> anyhow::Result<(QuorumSnapshot, QuorumSnapshot)> {
+        let mut simulated = self.clone();
+        for change in changes {
+            simulated.apply(change)?;
+        }
+        Ok((self.snapshot(), simulated.snapshot()))
+    }
+
+    pub fn snapshot(&self) -> QuorumSnapshot {
+        let online_weight = self
+            .online
+            .iter()
+            .map(|rep| self.weight(rep))
+            .fold(Amount::zero(), |sum, weight| sum + weight);
+
+        let trended_or_minimum = max(self.trended_weight, self.online_weight_minimum);
+        let mut principal_reps: Vec<_> = self
+            .weights
+            .iter()
+            .filter_map(|(rep, weight)| {
+                let tier = RepTier::for_weight(*weight, trended_or_minimum);
+                (tier != RepTier::None).then(|| SimulatedRep {
+                    representative: *rep,
+                    weight: *weight,
+                    online: self.online.contains(rep),
+                    tier,
+                })
+            })
+            .collect();
+        principal_reps.sort_by_key(|rep| (Reverse(rep.weight), rep.representative));
+
+        QuorumSnapshot {
+            online_weight,
+            trended_weight: self.trended_weight,
+            quorum_delta: calculate_quorum_delta(
+                online_weight,
+                self.trended_weight,
+                self.online_weight_minimum,
+            ),
+            principal_reps,
+        }
+    }
+
+    fn weight(&self, rep: &PublicKey) -> Amount {
+        self.weights.get(rep).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot() {
+        let simulator = create_simulator();
+        let snapshot = simulator.snapshot();
+
+        assert_eq!(snapshot.online_weight, Amount::nano(900));
+        assert_eq!(snapshot.trended_weight, Amount::nano(1000));
+        assert_eq!(snapshot.quorum_delta, Amount::nano(670));
+        assert_eq!(
+            snapshot.principal_reps,
+            vec![
+                SimulatedRep {
+                    representative: PublicKey::from(1),
+                    weight: Amount::nano(600),
+                    online: true,
+                    tier: RepTier::Tier3
+                },
+                SimulatedRep {
+                    representative: PublicKey::from(2),
+                    weight: Amount::nano(300),
+                    online: true,
+                    tier: RepTier::Tier3
+                },
+                SimulatedRep {
+                    representative: PublicKey::from(4),
+                    weight: Amount::nano(200),
+                    online: false,
+                    tier: RepTier::Tier3
+                },
+                SimulatedRep {
+                    representative: PublicKey::from(3),
+                    weight: Amount::nano(20),
+                    online: false,
+                    tier: RepTier::Tier2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn move_weight() {
+        let simulator = create_simulator();
+
+        let (before, after) = simulator
+            .simulate(&[WeightChange::Move {
+                from: PublicKey::from(1),
+                to: PublicKey::from(3),
+                amount: Amount::nano(200),
+            }])
+            .unwrap();
+
+        assert_eq!(before.online_weight, Amount::nano(900));
+        // the weight moved to an offline rep
+        assert_eq!(after.online_weight, Amount::nano(700));
+        assert_eq!(after.principal_reps[0].weight, Amount::nano(400));
+        assert_eq!(after.principal_reps[2].representative, PublicKey::from(3));
+        assert_eq!(after.principal_reps[2].weight, Amount::nano(220));
+        assert_eq!(after.principal_reps[2].tier, RepTier::Tier3);
+    }
+
+    #[test]
+    fn move_more_weight_than_available() {
+        let simulator = create_simulator();
+        let result = simulator.simulate(&[WeightChange::Move {
+            from: PublicKey::from(3),
+            to: PublicKey::from(1),
+            amount: Amount::nano(21),
+        }]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rep_goes_offline() {
+        let simulator = create_simulator();
+
+        let (_, after) = simulator
+            .simulate(&[WeightChange::Offline(PublicKey::from(1))])
+            .unwrap();
+
+        assert_eq!(after.online_weight, Amount::nano(300));
+        // the trended weight still determines the quorum
+        assert_eq!(after.quorum_delta, Amount::nano(670));
+        assert!(!after.principal_reps[0].online);
+    }
+
+    #[test]
+    fn online_weight_above_trended_increases_quorum() {
+        let simulator = create_simulator();
+
+        let (_, after) = simulator
+            .simulate(&[
+                WeightChange::Online(PublicKey::from(3)),
+                WeightChange::Move {
+                    from: PublicKey::from(4),
+                    to: PublicKey::from(2),
+                    amount: Amount::nano(180),
+                },
+            ])
+            .unwrap();
+
+        assert_eq!(after.online_weight, Amount::nano(1100));
+        assert_eq!(after.quorum_delta, Amount::nano(737));
+    }
+
+    fn create_simulator() -> QuorumSimulator {
+        QuorumSimulator::new(
+            [
+                (PublicKey::from(1), Amount::nano(600)),
+                (PublicKey::from(2), Amount::nano(300)),
+                (PublicKey::from(3), Amount::nano(20)),
+                (PublicKey::from(4), Amount::nano(200)),
+                (PublicKey::from(5), Amount::nano(1)),
+            ]
+            .into(),
+            [PublicKey::from(1), PublicKey::from(2)].into(),
+            Amount::nano(1000),
+            Amount::nano(100),
+        )
+    }
+}
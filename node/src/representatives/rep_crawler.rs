@@ -131,13 +131,14 @@ impl RepCrawler {
                 self.stats
                     .inc_dir(StatType::RepCrawler, DetailType::Response, Direction::In);
 
+                let latency = query.time.elapsed();
                 self.stats.sample(
                     Sample::RepResponseTime,
-                    query.time.elapsed().as_millis() as i64,
+                    latency.as_millis() as i64,
                     (0, query_timeout.as_millis() as i64),
                 );
 
-                responses.push_back((channel_id, Arc::clone(&vote)));
+                responses.push_back((channel_id, Arc::clone(&vote), Some(latency)));
                 query.replies += 1;
                 self.condition.notify_all();
                 processed = true;
@@ -196,7 +197,7 @@ impl RepCrawler {
     pub fn force_process(&self, vote: Arc<Vote>, channel_id: ChannelId) {
         assert!(self.network_params.network.is_dev_network());
         let mut guard = self.rep_crawler_impl.lock().unwrap();
-        guard.responses.push_back((channel_id, vote));
+        guard.responses.push_back((channel_id, vote, None));
     }
 
     // Only for tests
@@ -270,17 +271,30 @@ impl RepCrawler {
                     AGGRESSIVE_COUNT
                 };
 
-                /* include channels with ephemeral remote ports */
-                let random_peers = self
-                    .network_info
-                    .read()
+                let unresponsive = self
+                    .online_reps
+                    .lock()
                     .unwrap()
-                    .random_realtime_channels(required_peer_count, 0);
+                    .unresponsive_principal_channels();
+
+                let (unresponsive_peers, random_peers) = {
+                    let network_info = self.network_info.read().unwrap();
+                    let unresponsive_peers: Vec<_> = unresponsive
+                        .iter()
+                        .filter_map(|channel_id| network_info.get(*channel_id).cloned())
+                        .collect();
+                    /* include channels with ephemeral remote ports */
+                    let random_peers =
+                        network_info.random_realtime_channels(required_peer_count, 0);
+                    (unresponsive_peers, random_peers)
+                };
 
                 guard = self.rep_crawler_impl.lock().unwrap();
                 let targets = guard.prepare_crawl_targets(
                     sufficient_weight,
+                    unresponsive_peers,
                     random_peers,
+                    required_peer_count,
                     self.steady_clock.now(),
                 );
                 drop(guard);
@@ -303,7 +317,7 @@ impl RepCrawler {
         );
 
         // TODO: Is it really faster to repeatedly lock/unlock the mutex for each response?
-        for (channel_id, vote) in responses {
+        for (channel_id, vote, latency) in responses {
             if channel_id == ChannelId::LOOPBACK {
                 debug!("Ignoring vote from loopback channel");
                 continue;
@@ -319,11 +333,14 @@ impl RepCrawler {
                 continue;
             }
 
-            let result = self.online_reps.lock().unwrap().vote_observed_directly(
-                vote.voting_account,
-                channel_id,
-                self.steady_clock.now(),
-            );
+            let now = self.steady_clock.now();
+            let result = {
+                let mut online_reps = self.online_reps.lock().unwrap();
+                let result =
+                    online_reps.vote_observed_directly(vote.voting_account, channel_id, now);
+                online_reps.rep_responded(&vote.voting_account, latency, now);
+                result
+            };
 
             match result {
                 InsertResult::Inserted => {
@@ -483,7 +500,8 @@ struct RepCrawlerImpl {
     query_timeout: Duration,
     stopped: bool,
     last_query: Option<Instant>,
-    responses: BoundedVecDeque<(ChannelId, Arc<Vote>)>,
+    /// Votes that answered a query, together with the response latency
+    responses: BoundedVecDeque<(ChannelId, Arc<Vote>, Option<Duration>)>,
     is_dev_network: bool,
 }
 
@@ -495,10 +513,15 @@ impl RepCrawlerImpl {
         }
     }
 
+    /// Returns the channels to query. Channels of principal representatives that didn't answer
+    /// their last query come first, followed by channels whose representatives are still unknown.
+    /// At most `max_targets` channels are returned
     fn prepare_crawl_targets(
         &self,
         sufficient_weight: bool,
-        mut random_peers: Vec<Arc<ChannelInfo>>,
+        unresponsive_peers: Vec<Arc<ChannelInfo>>,
+        random_peers: Vec<Arc<ChannelInfo>>,
+        max_targets: usize,
         now: Timestamp,
     ) -> Vec<Arc<ChannelInfo>> {
         // TODO: Make these values configurable
@@ -521,12 +544,30 @@ impl RepCrawlerImpl {
             Direction::In,
         );
 
-        random_peers.retain(|channel| {
-            let elapsed = self
-                .online_reps
-                .lock()
-                .unwrap()
-                .last_request_elapsed(channel.channel_id(), now);
+        let online_reps = self.online_reps.lock().unwrap();
+        let prioritized = unresponsive_peers.len();
+        let mut targets = unresponsive_peers;
+        let mut known_reps = Vec::new();
+        for channel in random_peers {
+            if targets[..prioritized]
+                .iter()
+                .any(|c| c.channel_id() == channel.channel_id())
+            {
+                continue;
+            }
+            if online_reps
+                .last_request_elapsed(channel.channel_id(), now)
+                .is_none()
+            {
+                targets.push(channel);
+            } else {
+                known_reps.push(channel);
+            }
+        }
+        targets.extend(known_reps);
+
+        targets.retain(|channel| {
+            let elapsed = online_reps.last_request_elapsed(channel.channel_id(), now);
 
             match elapsed {
                 Some(last_request_elapsed) => {
@@ -545,7 +586,8 @@ impl RepCrawlerImpl {
             }
         });
 
-        random_peers
+        targets.truncate(max_targets);
+        targets
     }
 
     fn track_rep_request(
@@ -724,3 +766,50 @@ impl RepCrawlerExt for Arc<RepCrawler> {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_network::ChannelDirection;
+
+    #[test]
+    fn cap_crawl_targets_including_prioritized_channels() {
+        let crawler = RepCrawlerImpl {
+            queries: OrderedQueries::new(),
+            online_reps: Arc::new(Mutex::new(OnlineReps::builder().finish())),
+            stats: Arc::new(Stats::default()),
+            query_timeout: Duration::from_secs(60),
+            stopped: false,
+            last_query: None,
+            responses: BoundedVecDeque::new(RepCrawler::MAX_RESPONSES),
+            is_dev_network: true,
+        };
+        let channels: Vec<_> = (1..=6).map(test_channel).collect();
+
+        let targets = crawler.prepare_crawl_targets(
+            true,
+            channels[..3].to_vec(),
+            channels[3..].to_vec(),
+            4,
+            Timestamp::new_test_instance(),
+        );
+
+        let target_ids: Vec<_> = targets.iter().map(|c| c.channel_id()).collect();
+        assert_eq!(
+            target_ids,
+            [1, 2, 3, 4].map(ChannelId::from).to_vec(),
+            "prioritized channels must come first"
+        );
+    }
+
+    fn test_channel(id: usize) -> Arc<ChannelInfo> {
+        Arc::new(ChannelInfo::new(
+            ChannelId::from(id),
+            NULL_ENDPOINT,
+            NULL_ENDPOINT,
+            ChannelDirection::Outbound,
+            u8::MAX,
+            Timestamp::new_test_instance(),
+        ))
+    }
+}
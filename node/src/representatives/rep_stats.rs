@@ -0,0 +1,538 @@
+use super::OnlineReps;
+use crate::{
+    consensus::{ElectionStatus, ElectionStatusType},
+    utils::{CancellationToken, Runnable},
+};
+use rsban_core::{
+    utils::ContainerInfo, PublicKey, RepStatsBucket, RepStatsKey, VoteWithWeightInfo,
+};
+use rsban_ledger::Ledger;
+use rsban_store_lmdb::LmdbWriteTransaction;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepStatsConfig {
+    pub enabled: bool,
+    /// How often the online status of the representatives is sampled
+    pub sample_interval: Duration,
+    /// Length of one history bucket
+    pub bucket_duration: Duration,
+    /// Number of buckets that are kept per representative. Older buckets are deleted
+    pub max_buckets: usize,
+}
+
+impl Default for RepStatsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_interval: Duration::from_secs(60),
+            bucket_duration: Duration::from_secs(60 * 60),
+            max_buckets: 24 * 30,
+        }
+    }
+}
+
+/// Aggregated statistics of a representative over the whole retained history
+#[derive(Default, PartialEq, Eq, Debug, Clone)]
+pub struct RepStatsSummary {
+    pub totals: RepStatsBucket,
+    /// Buckets ordered by start time (seconds since epoch)
+    pub history: Vec<(u64, RepStatsBucket)>,
+}
+
+impl RepStatsSummary {
+    /// Fraction of samples in which the representative was online
+    pub fn uptime(&self) -> f64 {
+        ratio(self.totals.online_samples, self.totals.samples)
+    }
+
+    /// Fraction of confirmed elections the representative voted in
+    pub fn participation(&self) -> f64 {
+        ratio(self.totals.votes, self.totals.elections)
+    }
+
+    /// Fraction of confirmed elections the representative sent a final vote for
+    pub fn final_participation(&self) -> f64 {
+        ratio(self.totals.final_votes, self.totals.elections)
+    }
+
+    /// Average time between the start of an election and the arrival of the vote
+    pub fn average_vote_latency(&self) -> Duration {
+        if self.totals.votes == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_millis(self.totals.vote_latency_ms / self.totals.votes as u64)
+        }
+    }
+}
+
+fn ratio(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Records per representative how often it was online, how many confirmed elections
+/// it voted in and how fast its votes arrived. The data is aggregated in time buckets,
+/// which are persisted in the ledger database. Only representatives with activity in
+/// a bucket get a stored bucket. The global counters of each bucket are stored under
+/// [`GLOBAL_COUNTERS`], so that the buckets in which a representative was inactive
+/// can be derived from them.
+pub struct RepStats {
+    ledger: Arc<Ledger>,
+    config: RepStatsConfig,
+    current: Mutex<CurrentBucket>,
+}
+
+/// Key under which the global counters of each bucket are stored.
+/// The zero key can't vote, so it never collides with a representative
+pub const GLOBAL_COUNTERS: PublicKey = PublicKey::zero();
+
+#[derive(Default)]
+struct CurrentBucket {
+    start: u64,
+    /// Global counters. They are the same for all representatives
+    samples: u32,
+    elections: u32,
+    /// Representatives with activity in this bucket
+    reps: HashMap<PublicKey, RepStatsBucket>,
+}
+
+impl CurrentBucket {
+    fn global(&self) -> RepStatsBucket {
+        RepStatsBucket {
+            samples: self.samples,
+            elections: self.elections,
+            ..Default::default()
+        }
+    }
+}
+
+impl RepStats {
+    pub fn new(ledger: Arc<Ledger>, config: RepStatsConfig) -> Self {
+        Self {
+            ledger,
+            config,
+            current: Mutex::new(CurrentBucket::default()),
+        }
+    }
+
+    pub fn election_ended(&self, status: &ElectionStatus, votes: &[VoteWithWeightInfo]) {
+        if !matches!(
+            status.election_status_type,
+            ElectionStatusType::ActiveConfirmedQuorum
+                | ElectionStatusType::ActiveConfirmationHeight
+        ) {
+            return;
+        }
+
+        let election_start = status
+            .election_end
+            .checked_sub(status.election_duration)
+            .unwrap_or(status.election_end);
+
+        let mut current = self.current.lock().unwrap();
+        current.elections += 1;
+        for vote in votes {
+            let latency = vote.time.duration_since(election_start).unwrap_or_default();
+            let bucket = current.reps.entry(vote.representative).or_default();
+            bucket.votes += 1;
+            if vote.timestamp == u64::MAX {
+                bucket.final_votes += 1;
+            }
+            bucket.vote_latency_ms += latency.as_millis() as u64;
+        }
+    }
+
+    /// Records which representatives are online right now and persists the current bucket
+    pub fn sample<'a>(
+        &self,
+        online_reps: impl IntoIterator<Item = &'a PublicKey>,
+        now: SystemTime,
+    ) {
+        let bucket_start = self.bucket_start(now);
+        let mut current = self.current.lock().unwrap();
+        let mut tx = self.ledger.rw_txn();
+
+        if current.start != bucket_start {
+            if current.start != 0 {
+                self.persist(&mut tx, &current);
+            }
+            self.roll_over(&mut tx, &mut current, bucket_start);
+        }
+
+        current.samples += 1;
+        for rep in online_reps {
+            current.reps.entry(*rep).or_default().online_samples += 1;
+        }
+        self.persist(&mut tx, &current);
+    }
+
+    pub fn summary(&self, representative: &PublicKey) -> RepStatsSummary {
+        let (mut rep_buckets, mut global_buckets) = {
+            let tx = self.ledger.read_txn();
+            let store = &self.ledger.store.rep_stats;
+            let rep_buckets: BTreeMap<u64, RepStatsBucket> = store
+                .iter_rep(&tx, *representative)
+                .map(|(key, bucket)| (key.bucket_start, bucket))
+                .collect();
+            let global_buckets: BTreeMap<u64, RepStatsBucket> = store
+                .iter_rep(&tx, GLOBAL_COUNTERS)
+                .map(|(key, bucket)| (key.bucket_start, bucket))
+                .collect();
+            (rep_buckets, global_buckets)
+        };
+
+        {
+            let current = self.current.lock().unwrap();
+            if current.start != 0 {
+                global_buckets.insert(current.start, current.global());
+                if let Some(bucket) = Self::bucket_for(&current, representative) {
+                    rep_buckets.insert(current.start, bucket);
+                }
+            }
+        }
+
+        // The history starts with the first bucket in which the representative was active.
+        // In later buckets without activity it was offline and didn't vote
+        let Some(first) = rep_buckets.keys().next().cloned() else {
+            return RepStatsSummary::default();
+        };
+        let starts: BTreeSet<u64> = rep_buckets
+            .keys()
+            .chain(global_buckets.range(first..).map(|(start, _)| start))
+            .cloned()
+            .collect();
+        let history: Vec<(u64, RepStatsBucket)> = starts
+            .into_iter()
+            .map(|start| {
+                let bucket = rep_buckets
+                    .remove(&start)
+                    .or_else(|| global_buckets.remove(&start))
+                    .unwrap_or_default();
+                (start, bucket)
+            })
+            .collect();
+
+        let mut totals = RepStatsBucket::default();
+        for (_, bucket) in &history {
+            totals.merge(bucket);
+        }
+        RepStatsSummary { totals, history }
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        let current = self.current.lock().unwrap();
+        [("reps", current.reps.len(), 0)].into()
+    }
+
+    fn bucket_start(&self, now: SystemTime) -> u64 {
+        let bucket_secs = self.config.bucket_duration.as_secs().max(1);
+        let now_secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        now_secs - now_secs % bucket_secs
+    }
+
+    fn bucket_for(current: &CurrentBucket, rep: &PublicKey) -> Option<RepStatsBucket> {
+        let mut bucket = current.reps.get(rep)?.clone();
+        bucket.samples = current.samples;
+        bucket.elections = current.elections;
+        Some(bucket)
+    }
+
+    fn persist(&self, tx: &mut LmdbWriteTransaction, current: &CurrentBucket) {
+        let store = &self.ledger.store.rep_stats;
+        store.put(
+            tx,
+            &RepStatsKey::new(GLOBAL_COUNTERS, current.start),
+            &current.global(),
+        );
+        for rep in current.reps.keys() {
+            if let Some(bucket) = Self::bucket_for(current, rep) {
+                store.put(tx, &RepStatsKey::new(*rep, current.start), &bucket);
+            }
+        }
+    }
+
+    fn roll_over(
+        &self,
+        tx: &mut LmdbWriteTransaction,
+        current: &mut CurrentBucket,
+        bucket_start: u64,
+    ) {
+        let retention = self.config.bucket_duration.as_secs() * self.config.max_buckets as u64;
+        let cutoff = bucket_start.saturating_sub(retention);
+        let store = &self.ledger.store.rep_stats;
+        store.delete_before(tx, cutoff);
+
+        let mut samples = 0;
+        let mut elections = 0;
+        let mut reps = HashMap::new();
+
+        if current.start == 0 {
+            // The node may have been restarted during this bucket
+            if let Some(global) = store.get(tx, &RepStatsKey::new(GLOBAL_COUNTERS, bucket_start)) {
+                samples = global.samples;
+                elections = global.elections;
+                reps = store
+                    .iter(tx)
+                    .filter(|(key, _)| {
+                        key.bucket_start == bucket_start && key.representative != GLOBAL_COUNTERS
+                    })
+                    .map(|(key, bucket)| (key.representative, bucket))
+                    .collect();
+            }
+
+            // Elections that ended before the first sample are added to the first bucket
+            elections += current.elections;
+            for (rep, bucket) in &current.reps {
+                let merged: &mut RepStatsBucket = reps.entry(*rep).or_default();
+                merged.merge(bucket);
+            }
+        }
+
+        *current = CurrentBucket {
+            start: bucket_start,
+            samples,
+            elections,
+            reps,
+        };
+    }
+}
+
+/// Periodically samples which representatives are online
+pub struct RepStatsSampler {
+    rep_stats: Arc<RepStats>,
+    online_reps: Arc<Mutex<OnlineReps>>,
+}
+
+impl RepStatsSampler {
+    pub fn new(rep_stats: Arc<RepStats>, online_reps: Arc<Mutex<OnlineReps>>) -> Self {
+        Self {
+            rep_stats,
+            online_reps,
+        }
+    }
+}
+
+impl Runnable for RepStatsSampler {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        let online: Vec<PublicKey> = self
+            .online_reps
+            .lock()
+            .unwrap()
+            .online_reps()
+            .cloned()
+            .collect();
+        self.rep_stats.sample(&online, SystemTime::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::{Amount, BlockHash};
+
+    #[test]
+    fn empty() {
+        let rep_stats = RepStats::new(Arc::new(Ledger::new_null()), RepStatsConfig::default());
+        let summary = rep_stats.summary(&PublicKey::from(1));
+        assert_eq!(summary, RepStatsSummary::default());
+        assert_eq!(summary.uptime(), 0.0);
+    }
+
+    #[test]
+    fn sample_online_status() {
+        let rep_stats = RepStats::new(Arc::new(Ledger::new_null()), RepStatsConfig::default());
+        let rep1 = PublicKey::from(1);
+        let rep2 = PublicKey::from(2);
+        let now = UNIX_EPOCH + Duration::from_secs(3600 * 1000);
+
+        rep_stats.sample([&rep1, &rep2], now);
+        rep_stats.sample([&rep1], now + Duration::from_secs(60));
+
+        let summary = rep_stats.summary(&rep2);
+        assert_eq!(summary.totals.samples, 2);
+        assert_eq!(summary.totals.online_samples, 1);
+        assert_eq!(summary.uptime(), 0.5);
+        assert_eq!(summary.history.len(), 1);
+        assert_eq!(summary.history[0].0, 3600 * 1000);
+        assert_eq!(rep_stats.summary(&rep1).uptime(), 1.0);
+    }
+
+    #[test]
+    fn persist_sample() {
+        let ledger = Arc::new(Ledger::new_null());
+        let put_tracker = ledger.store.rep_stats.track_puts();
+        let rep_stats = RepStats::new(ledger, RepStatsConfig::default());
+        let rep = PublicKey::from(1);
+
+        rep_stats.sample([&rep], UNIX_EPOCH + Duration::from_secs(3600 * 1000 + 5));
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![
+                (
+                    RepStatsKey::new(GLOBAL_COUNTERS, 3600 * 1000),
+                    RepStatsBucket {
+                        samples: 1,
+                        ..Default::default()
+                    }
+                ),
+                (
+                    RepStatsKey::new(rep, 3600 * 1000),
+                    RepStatsBucket {
+                        samples: 1,
+                        online_samples: 1,
+                        ..Default::default()
+                    }
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn persist_only_reps_with_activity() {
+        let ledger = Arc::new(Ledger::new_null());
+        let put_tracker = ledger.store.rep_stats.track_puts();
+        let config = RepStatsConfig::default();
+        let rep_stats = RepStats::new(ledger, config.clone());
+        let rep1 = PublicKey::from(1);
+        let rep2 = PublicKey::from(2);
+        let now = UNIX_EPOCH + Duration::from_secs(3600 * 1000);
+        rep_stats.sample([&rep1], now);
+        let puts_before = put_tracker.output().len();
+
+        rep_stats.sample([&rep2], now + config.bucket_duration);
+
+        // The previous bucket is persisted once more, then the new bucket without rep1
+        let puts: Vec<_> = put_tracker.output()[puts_before..]
+            .iter()
+            .map(|(key, _)| (key.representative, key.bucket_start))
+            .collect();
+        assert_eq!(
+            puts,
+            vec![
+                (GLOBAL_COUNTERS, 3600 * 1000),
+                (rep1, 3600 * 1000),
+                (GLOBAL_COUNTERS, 3600 * 1001),
+                (rep2, 3600 * 1001),
+            ]
+        );
+    }
+
+    #[test]
+    fn record_vote_participation() {
+        let rep_stats = RepStats::new(Arc::new(Ledger::new_null()), RepStatsConfig::default());
+        let rep1 = PublicKey::from(1);
+        let rep2 = PublicKey::from(2);
+        let election_end = UNIX_EPOCH + Duration::from_secs(3600 * 1000);
+        let status = ElectionStatus {
+            election_end,
+            election_duration: Duration::from_millis(500),
+            election_status_type: ElectionStatusType::ActiveConfirmedQuorum,
+            ..Default::default()
+        };
+        let election_start = election_end - Duration::from_millis(500);
+
+        rep_stats.sample([&rep1, &rep2], election_end);
+        rep_stats.election_ended(
+            &status,
+            &[
+                vote(rep1, election_start + Duration::from_millis(100), u64::MAX),
+                vote(rep2, election_start + Duration::from_millis(300), 1),
+            ],
+        );
+        rep_stats.election_ended(
+            &status,
+            &[vote(
+                rep1,
+                election_start + Duration::from_millis(200),
+                u64::MAX,
+            )],
+        );
+
+        let summary = rep_stats.summary(&rep1);
+        assert_eq!(summary.totals.elections, 2);
+        assert_eq!(summary.totals.votes, 2);
+        assert_eq!(summary.totals.final_votes, 2);
+        assert_eq!(summary.participation(), 1.0);
+        assert_eq!(summary.average_vote_latency(), Duration::from_millis(150));
+
+        let summary = rep_stats.summary(&rep2);
+        assert_eq!(summary.participation(), 0.5);
+        assert_eq!(summary.final_participation(), 0.0);
+        assert_eq!(summary.average_vote_latency(), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn ignore_unconfirmed_elections() {
+        let rep_stats = RepStats::new(Arc::new(Ledger::new_null()), RepStatsConfig::default());
+        let rep = PublicKey::from(1);
+        let status = ElectionStatus {
+            election_status_type: ElectionStatusType::Stopped,
+            ..Default::default()
+        };
+
+        rep_stats.sample([&rep], SystemTime::now());
+        rep_stats.election_ended(&status, &[vote(rep, SystemTime::now(), 1)]);
+
+        assert_eq!(rep_stats.summary(&rep).totals.elections, 0);
+    }
+
+    #[test]
+    fn keep_elections_that_ended_before_first_sample() {
+        let rep_stats = RepStats::new(Arc::new(Ledger::new_null()), RepStatsConfig::default());
+        let rep = PublicKey::from(1);
+        let status = ElectionStatus {
+            election_status_type: ElectionStatusType::ActiveConfirmedQuorum,
+            ..Default::default()
+        };
+
+        rep_stats.election_ended(&status, &[vote(rep, SystemTime::now(), u64::MAX)]);
+        rep_stats.sample([&rep], SystemTime::now());
+
+        let summary = rep_stats.summary(&rep);
+        assert_eq!(summary.totals.elections, 1);
+        assert_eq!(summary.totals.votes, 1);
+        assert_eq!(summary.totals.final_votes, 1);
+        assert_eq!(summary.totals.online_samples, 1);
+    }
+
+    #[test]
+    fn new_bucket() {
+        let config = RepStatsConfig::default();
+        let rep_stats = RepStats::new(Arc::new(Ledger::new_null()), config.clone());
+        let rep = PublicKey::from(1);
+        let now = UNIX_EPOCH + Duration::from_secs(3600 * 1000);
+
+        rep_stats.sample([&rep], now);
+        rep_stats.sample([&rep], now + config.bucket_duration);
+
+        // The first bucket only lives in the nulled store, so only the current one is returned
+        let summary = rep_stats.summary(&rep);
+        assert_eq!(summary.history.len(), 1);
+        assert_eq!(summary.history[0].0, 3600 * 1001);
+        assert_eq!(summary.totals.samples, 1);
+        assert_eq!(summary.totals.online_samples, 1);
+        assert_eq!(
+            rep_stats.summary(&PublicKey::from(2)),
+            RepStatsSummary::default()
+        );
+    }
+
+    fn vote(representative: PublicKey, time: SystemTime, timestamp: u64) -> VoteWithWeightInfo {
+        VoteWithWeightInfo {
+            representative,
+            time,
+            timestamp,
+            hash: BlockHash::from(1),
+            weight: Amount::zero(),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::stats::{DetailType, Direction, Sample, StatType, Stats};
+use rsban_messages::PayloadCompression;
+
+impl Stats {
+    /// Records how much a bootstrap payload shrank because of compression
+    pub fn payload_compressed(&self, dir: Direction, compression: &PayloadCompression) {
+        self.inc_dir(StatType::BootstrapCompression, DetailType::Compressed, dir);
+        self.add_dir(
+            StatType::BootstrapCompression,
+            DetailType::UncompressedBytes,
+            dir,
+            compression.uncompressed as u64,
+        );
+        self.add_dir(
+            StatType::BootstrapCompression,
+            DetailType::CompressedBytes,
+            dir,
+            compression.compressed as u64,
+        );
+        self.sample(
+            Sample::BootstrapCompressionRatio,
+            compression.ratio_percent(),
+            (0, 100),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_compressed() {
+        let stats = Stats::default();
+        let compression = PayloadCompression {
+            uncompressed: 1000,
+            compressed: 400,
+        };
+
+        stats.payload_compressed(Direction::Out, &compression);
+
+        assert_eq!(
+            stats.count(
+                StatType::BootstrapCompression,
+                DetailType::Compressed,
+                Direction::Out
+            ),
+            1
+        );
+        assert_eq!(
+            stats.count(
+                StatType::BootstrapCompression,
+                DetailType::UncompressedBytes,
+                Direction::Out
+            ),
+            1000
+        );
+        assert_eq!(
+            stats.count(
+                StatType::BootstrapCompression,
+                DetailType::CompressedBytes,
+                Direction::Out
+            ),
+            400
+        );
+        assert_eq!(stats.samples(Sample::BootstrapCompressionRatio), [40]);
+    }
+}
@@ -1,3 +1,4 @@
+mod compression_stats;
 mod ledger_stats;
 mod network_stats;
 mod parse_message_error;
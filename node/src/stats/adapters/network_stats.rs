@@ -181,6 +181,17 @@ impl NetworkObserver for NetworkStats {
                     ?direction,
                     "Already connected to that peer, unable to open new connection");
             }
+            NetworkError::NodeIdNotAllowed => {
+                self.0.inc_dir(
+                    StatType::TcpListenerRejected,
+                    DetailType::NodeIdNotAllowed,
+                    direction.into(),
+                );
+                debug!(
+                    %peer,
+                    ?direction,
+                    "No node is allowed in private network mode, unable to open new connection");
+            }
         }
     }
 
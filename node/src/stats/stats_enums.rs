@@ -82,6 +82,8 @@ pub enum StatType {
     RepTiers,
     SynCookies,
     PeerHistory,
+    PortMapping,
+    BootstrapCompression,
     MessageProcessor,
     MessageProcessorOverfill,
     MessageProcessorType,
@@ -314,6 +316,8 @@ pub enum DetailType {
     ChannelAccepted,
     ChannelRejected,
     ChannelDuplicate,
+    ChannelEncrypted,
+    ChannelPlaintext,
 
     // tcp_server
     Handshake,
@@ -370,6 +374,9 @@ pub enum DetailType {
     GeneratorReplies,
     GeneratorRepliesDiscarded,
     GeneratorSpacing,
+    GeneratorHistoryConflict,
+    GeneratorPolicyDisabled,
+    GeneratorRateLimited,
 
     // hinting
     MissingBlock,
@@ -418,6 +425,21 @@ pub enum DetailType {
     InvalidNodeId,
     MissingCookie,
     InvalidGenesis,
+    NodeIdNotAllowed,
+    EncryptionRequired,
+    InvalidEphemeralKey,
+
+    // bootstrap compression
+    Compressed,
+    CompressedBytes,
+    UncompressedBytes,
+
+    // port mapping
+    MappingAdded,
+    MappingRefreshed,
+    MappingRemoved,
+    MappingFailed,
+    GatewayNotFound,
 
     // bootstrap ascending
     MissingTag,
@@ -551,6 +573,7 @@ impl Direction {
 pub enum Sample {
     ActiveElectionDuration,
     BootstrapTagDuration,
+    BootstrapCompressionRatio,
     RepResponseTime,
     VoteGeneratorFinalHashes,
     VoteGeneratorHashes,
@@ -1,14 +1,20 @@
 use num_traits::FromPrimitive;
-use rsban_core::{serialized_block_size, utils::BufferReader, Block, BlockType};
+use rsban_core::{
+    serialized_block_size,
+    utils::{BufferReader, Stream},
+    Block, BlockType,
+};
+use rsban_messages::{CompressedPayload, PayloadCompression};
 use rsban_network::AsyncBufferReader;
 
 pub async fn read_block(input: &impl AsyncBufferReader) -> anyhow::Result<Option<Block>> {
     let mut buf = [0; 1];
     input.read(&mut buf, 1).await?;
-    received_type(buf[0], input).await
+    read_block_of_type(buf[0], input).await
 }
 
-async fn received_type(
+/// Reads the rest of a block whose type byte was already read
+pub async fn read_block_of_type(
     block_type_byte: u8,
     input: &impl AsyncBufferReader,
 ) -> anyhow::Result<Option<Block>> {
@@ -25,3 +31,40 @@ async fn received_type(
         }
     }
 }
+
+/// Reads one frame of a compressed bulk pull response. A frame contains a batch of
+/// blocks. `None` marks the end of the response.
+pub async fn read_compressed_blocks(
+    input: &impl AsyncBufferReader,
+) -> anyhow::Result<(Vec<Option<Block>>, PayloadCompression)> {
+    let mut header = [0; CompressedPayload::HEADER_SIZE];
+    input
+        .read(&mut header, CompressedPayload::HEADER_SIZE)
+        .await?;
+    let data_len = CompressedPayload::data_len(&header);
+    let mut frame = vec![0; header.len() + data_len];
+    frame[..header.len()].copy_from_slice(&header);
+    input.read(&mut frame[header.len()..], data_len).await?;
+
+    let compressed = CompressedPayload::deserialize(&mut BufferReader::new(&frame))?;
+    let uncompressed = compressed.decompress()?;
+    let mut stream = BufferReader::new(&uncompressed);
+    let mut blocks = Vec::new();
+    while !stream.remaining().is_empty() {
+        let block_type_byte = stream.read_u8()?;
+        match BlockType::from_u8(block_type_byte) {
+            None | Some(BlockType::Invalid) => bail!("Invalid block type: {block_type_byte}"),
+            Some(BlockType::NotABlock) => {
+                blocks.push(None);
+                break;
+            }
+            Some(block_type) => {
+                blocks.push(Some(Block::deserialize_block_type(
+                    block_type,
+                    &mut stream,
+                )?));
+            }
+        }
+    }
+    Ok((blocks, compressed.compression()))
+}
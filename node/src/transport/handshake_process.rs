@@ -2,15 +2,17 @@ use super::SynCookies;
 use crate::stats::{DetailType, Direction, StatType, Stats};
 use rsban_core::{utils::TEST_ENDPOINT_1, BlockHash, NodeId, PrivateKey};
 use rsban_messages::{
-    Message, MessageSerializer, NodeIdHandshake, NodeIdHandshakeQuery, NodeIdHandshakeResponse,
-    ProtocolInfo,
+    Cookie, Message, MessageSerializer, NodeIdHandshake, NodeIdHandshakeQuery,
+    NodeIdHandshakeResponse, ProtocolInfo,
+};
+use rsban_network::{
+    handshake_transcript, Channel, EphemeralKey, NetworkInfo, TrafficType, TransportEncryption,
 };
-use rsban_network::{Channel, TrafficType};
 use std::{
     net::SocketAddrV6,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex, RwLock,
     },
 };
 use tracing::{debug, warn};
@@ -32,6 +34,11 @@ pub(crate) struct HandshakeProcess {
     handshake_received: AtomicBool,
     remote_endpoint: SocketAddrV6,
     protocol: ProtocolInfo,
+    network_info: Arc<RwLock<NetworkInfo>>,
+    /// Our X25519 key which was sent as query cookie
+    ephemeral_key: Mutex<Option<EphemeralKey>>,
+    /// The query cookie of the peer, if the peer supports encryption
+    peer_ephemeral_key: Mutex<Option<Cookie>>,
 }
 
 impl HandshakeProcess {
@@ -42,6 +49,7 @@ impl HandshakeProcess {
         stats: Arc<Stats>,
         remote_endpoint: SocketAddrV6,
         protocol: ProtocolInfo,
+        network_info: Arc<RwLock<NetworkInfo>>,
     ) -> Self {
         Self {
             genesis_hash,
@@ -51,6 +59,9 @@ impl HandshakeProcess {
             handshake_received: AtomicBool::new(false),
             remote_endpoint,
             protocol,
+            network_info,
+            ephemeral_key: Mutex::new(None),
+            peer_ephemeral_key: Mutex::new(None),
         }
     }
 
@@ -64,6 +75,9 @@ impl HandshakeProcess {
             handshake_received: AtomicBool::new(false),
             remote_endpoint: TEST_ENDPOINT_1,
             protocol: ProtocolInfo::default(),
+            network_info: Arc::new(RwLock::new(NetworkInfo::new_test_instance())),
+            ephemeral_key: Mutex::new(None),
+            peer_ephemeral_key: Mutex::new(None),
         }
     }
 
@@ -81,6 +95,8 @@ impl HandshakeProcess {
             query,
             response: None,
             is_v2: true,
+            encryption: self.transport_encryption().is_enabled(),
+            compression: self.bootstrap_compression(),
         });
 
         debug!("Initiating handshake query ({})", endpoint);
@@ -161,7 +177,15 @@ impl HandshakeProcess {
             log_type, self.remote_endpoint
         );
 
+        if message.compression && self.bootstrap_compression() {
+            channel.info.set_supports_compression();
+        }
+
         if let Some(query) = message.query.clone() {
+            if message.encryption {
+                *self.peer_ephemeral_key.lock().unwrap() = Some(query.cookie);
+            }
+
             // Send response + our own query
             if self
                 .send_response(&query, message.is_v2, channel)
@@ -174,7 +198,10 @@ impl HandshakeProcess {
             // Fall through and continue handshake
         }
         if let Some(response) = &message.response {
-            match self.verify_response(response, &self.remote_endpoint) {
+            let verified = self
+                .verify_response(response, message.encryption, &self.remote_endpoint)
+                .and_then(|()| self.start_encryption(channel));
+            match verified {
                 Ok(()) => {
                     self.stats
                         .inc_dir(StatType::Handshake, DetailType::Ok, Direction::In);
@@ -187,6 +214,30 @@ impl HandshakeProcess {
                     );
                     return HandshakeStatus::AbortOwnNodeId;
                 }
+                Err(HandshakeResponseError::NodeIdNotAllowed) => {
+                    self.stats.inc_dir(
+                        StatType::Handshake,
+                        DetailType::NodeIdNotAllowed,
+                        Direction::In,
+                    );
+                    debug!(
+                        "Node {} is not allowed in this private network. Closing channel ({})",
+                        response.node_id, self.remote_endpoint
+                    );
+                    return HandshakeStatus::Abort;
+                }
+                Err(HandshakeResponseError::EncryptionRequired) => {
+                    self.stats.inc_dir(
+                        StatType::Handshake,
+                        DetailType::EncryptionRequired,
+                        Direction::In,
+                    );
+                    debug!(
+                        "Node {} doesn't support encrypted transport. Closing channel ({})",
+                        response.node_id, self.remote_endpoint
+                    );
+                    return HandshakeStatus::Abort;
+                }
                 Err(e) => {
                     self.stats
                         .inc_dir(StatType::Handshake, e.into(), Direction::In);
@@ -212,13 +263,22 @@ impl HandshakeProcess {
         v2: bool,
         channel: &Channel,
     ) -> anyhow::Result<()> {
-        let response = self.prepare_response(query, v2);
+        // Our own query comes first, because the response signs our ephemeral key
         let own_query = self.prepare_query(&self.remote_endpoint);
+        let encryption = self.ephemeral_key.lock().unwrap().is_some();
+        let response = match self.transcript(|own, peer| handshake_transcript(peer, own)) {
+            Some(transcript) => {
+                self.prepare_response(&NodeIdHandshakeQuery { cookie: transcript }, v2)
+            }
+            None => self.prepare_response(query, v2),
+        };
 
         let handshake_response = Message::NodeIdHandshake(NodeIdHandshake {
             is_v2: own_query.is_some() || response.v2.is_some(),
             query: own_query,
             response: Some(response),
+            encryption,
+            compression: self.bootstrap_compression(),
         });
 
         debug!("Responding to handshake ({})", self.remote_endpoint);
@@ -254,6 +314,7 @@ impl HandshakeProcess {
     fn verify_response(
         &self,
         response: &NodeIdHandshakeResponse,
+        peer_supports_encryption: bool,
         remote_endpoint: &SocketAddrV6,
     ) -> Result<(), HandshakeResponseError> {
         // Prevent connection with ourselves
@@ -268,14 +329,30 @@ impl HandshakeProcess {
             }
         }
 
-        let Some(cookie) = self.syn_cookies.cookie(remote_endpoint) else {
+        let Some(mut cookie) = self.syn_cookies.cookie(remote_endpoint) else {
             return Err(HandshakeResponseError::MissingCookie);
         };
 
+        if peer_supports_encryption {
+            if let Some(transcript) = self.transcript(handshake_transcript) {
+                cookie = transcript;
+            }
+        }
+
         if response.validate(&cookie).is_err() {
             return Err(HandshakeResponseError::InvalidSignature);
         }
 
+        // Only checked after the signature, because the node id could be forged otherwise
+        if !self
+            .network_info
+            .read()
+            .unwrap()
+            .is_node_id_allowed(&response.node_id)
+        {
+            return Err(HandshakeResponseError::NodeIdNotAllowed);
+        }
+
         Ok(())
     }
 
@@ -295,9 +372,58 @@ impl HandshakeProcess {
         &self,
         remote_endpoint: &SocketAddrV6,
     ) -> Option<NodeIdHandshakeQuery> {
-        self.syn_cookies
-            .assign(remote_endpoint)
-            .map(|cookie| NodeIdHandshakeQuery { cookie })
+        if !self.transport_encryption().is_enabled() {
+            return self
+                .syn_cookies
+                .assign(remote_endpoint)
+                .map(|cookie| NodeIdHandshakeQuery { cookie });
+        }
+
+        // The ephemeral public key is used as cookie, so that the peer signs it
+        let key = EphemeralKey::new_random();
+        let cookie = self
+            .syn_cookies
+            .assign_cookie(remote_endpoint, key.public_key())?;
+        *self.ephemeral_key.lock().unwrap() = Some(key);
+        Some(NodeIdHandshakeQuery { cookie })
+    }
+
+    fn bootstrap_compression(&self) -> bool {
+        self.network_info.read().unwrap().bootstrap_compression()
+    }
+
+    fn transport_encryption(&self) -> TransportEncryption {
+        self.network_info.read().unwrap().transport_encryption()
+    }
+
+    /// Calls `f` with our and the peer's ephemeral public key if both nodes support encryption
+    fn transcript(&self, f: impl FnOnce(&Cookie, &Cookie) -> Cookie) -> Option<Cookie> {
+        let own = self.ephemeral_key.lock().unwrap().as_ref()?.public_key();
+        let peer = (*self.peer_ephemeral_key.lock().unwrap())?;
+        Some(f(&own, &peer))
+    }
+
+    /// Enables encryption after a successful handshake if both nodes support it
+    fn start_encryption(&self, channel: &Channel) -> Result<(), HandshakeResponseError> {
+        let mode = self.transport_encryption();
+        let own_key = self.ephemeral_key.lock().unwrap().take();
+        let peer_key = self.peer_ephemeral_key.lock().unwrap().take();
+        match (own_key, peer_key) {
+            (Some(own_key), Some(peer_key)) => {
+                let keys = own_key
+                    .session_keys(&peer_key)
+                    .ok_or(HandshakeResponseError::InvalidEphemeralKey)?;
+                channel
+                    .enable_encryption(keys)
+                    .map_err(|_| HandshakeResponseError::InvalidEphemeralKey)?;
+                debug!("Encrypted transport enabled ({})", self.remote_endpoint);
+                Ok(())
+            }
+            _ if mode == TransportEncryption::Required => {
+                Err(HandshakeResponseError::EncryptionRequired)
+            }
+            _ => Ok(()),
+        }
     }
 }
 
@@ -308,6 +434,11 @@ enum HandshakeResponseError {
     InvalidGenesis,
     MissingCookie,
     InvalidSignature,
+    /// Private network mode is enabled and the node id isn't in the allowlist
+    NodeIdNotAllowed,
+    /// Encryption is required, but the peer doesn't support it
+    EncryptionRequired,
+    InvalidEphemeralKey,
 }
 
 impl From<HandshakeResponseError> for DetailType {
@@ -317,6 +448,9 @@ impl From<HandshakeResponseError> for DetailType {
             HandshakeResponseError::InvalidGenesis => Self::InvalidGenesis,
             HandshakeResponseError::MissingCookie => Self::MissingCookie,
             HandshakeResponseError::InvalidSignature => Self::InvalidSignature,
+            HandshakeResponseError::NodeIdNotAllowed => Self::NodeIdNotAllowed,
+            HandshakeResponseError::EncryptionRequired => Self::EncryptionRequired,
+            HandshakeResponseError::InvalidEphemeralKey => Self::InvalidEphemeralKey,
         }
     }
 }
@@ -0,0 +1,550 @@
+use super::{InboundMessageQueue, MessageDeserializer, NetworkFilter, VecBufferReader};
+use rsban_core::{utils::NULL_ENDPOINT, work::WorkThresholds};
+use rsban_messages::{Message, ParseMessageError, ProtocolInfo};
+use rsban_network::{ChannelDirection, ChannelId, ChannelInfo, NetworkInfo};
+use rsban_nullable_clock::{SteadyClock, SystemTimeFactory};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    net::{Ipv6Addr, SocketAddrV6},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, UNIX_EPOCH},
+};
+use tracing::warn;
+
+const FILE_MAGIC: &[u8; 6] = b"RSBCAP";
+const FILE_VERSION: u16 = 1;
+const FILE_HEADER_SIZE: u64 = 8;
+const RECORD_HEADER_SIZE: usize = 8 + 8 + 1 + 16 + 2 + 4;
+pub const CAPTURE_FILE_NAME: &str = "capture.bin";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageCaptureConfig {
+    pub enabled: bool,
+    /// Directory of the capture files. Defaults to "capture" in the data path
+    pub path: Option<PathBuf>,
+    /// The capture file is rotated when it grows beyond this size
+    pub max_file_size: u64,
+    /// Number of capture files which are kept, including the current one
+    pub max_files: usize,
+}
+
+impl Default for MessageCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_file_size: 64 * 1024 * 1024,
+            max_files: 8,
+        }
+    }
+}
+
+/// A message which was received or sent by the node
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    pub channel_id: ChannelId,
+    pub peer_addr: SocketAddrV6,
+    pub direction: ChannelDirection,
+    /// The serialized message including its header
+    pub bytes: Vec<u8>,
+}
+
+impl CaptureRecord {
+    pub fn serialized_size(&self) -> usize {
+        RECORD_HEADER_SIZE + self.bytes.len()
+    }
+
+    pub fn serialize(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&self.timestamp.to_le_bytes())?;
+        writer.write_all(&(self.channel_id.as_usize() as u64).to_le_bytes())?;
+        writer.write_all(&[match self.direction {
+            ChannelDirection::Inbound => 0,
+            ChannelDirection::Outbound => 1,
+        }])?;
+        writer.write_all(&self.peer_addr.ip().octets())?;
+        writer.write_all(&self.peer_addr.port().to_le_bytes())?;
+        writer.write_all(&(self.bytes.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.bytes)
+    }
+
+    /// Returns `None` at the end of the stream
+    pub fn deserialize(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut header = [0; RECORD_HEADER_SIZE];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        let timestamp = u64::from_le_bytes(header[0..8].try_into().unwrap());
+        let channel_id = u64::from_le_bytes(header[8..16].try_into().unwrap());
+        let direction = match header[16] {
+            0 => ChannelDirection::Inbound,
+            1 => ChannelDirection::Outbound,
+            _ => return Err(io::Error::new(ErrorKind::InvalidData, "invalid direction")),
+        };
+        let ip: [u8; 16] = header[17..33].try_into().unwrap();
+        let port = u16::from_le_bytes(header[33..35].try_into().unwrap());
+        let len = u32::from_le_bytes(header[35..39].try_into().unwrap()) as usize;
+        if len > Message::MAX_MESSAGE_SIZE {
+            return Err(io::Error::new(ErrorKind::InvalidData, "record too big"));
+        }
+        let mut bytes = vec![0; len];
+        reader.read_exact(&mut bytes)?;
+        Ok(Some(Self {
+            timestamp,
+            channel_id: ChannelId::from(channel_id as usize),
+            peer_addr: SocketAddrV6::new(Ipv6Addr::from(ip), port, 0, 0),
+            direction,
+            bytes,
+        }))
+    }
+
+    /// Parses the message bytes like a message which was received from the network
+    pub async fn decode(
+        &self,
+        protocol_info: ProtocolInfo,
+        work_thresholds: WorkThresholds,
+    ) -> Result<Message, ParseMessageError> {
+        // Every record gets a fresh filter, because duplicates are expected in captures
+        let mut deserializer = MessageDeserializer::new(
+            protocol_info,
+            work_thresholds,
+            Arc::new(NetworkFilter::new(1)),
+            VecBufferReader::new(self.bytes.clone()),
+        );
+        deserializer.read().await.map(|m| m.message)
+    }
+}
+
+/// Writes capture records into a file which is rotated when it reaches its maximum size.
+/// Rotated files get the suffix ".1", ".2" and so on, where ".1" is the most recent one.
+pub struct CaptureWriter {
+    dir: PathBuf,
+    max_file_size: u64,
+    max_files: usize,
+    file: Option<BufWriter<File>>,
+    file_size: u64,
+}
+
+impl CaptureWriter {
+    pub fn new(dir: impl Into<PathBuf>, max_file_size: u64, max_files: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            max_file_size,
+            max_files: max_files.max(1),
+            file: None,
+            file_size: 0,
+        }
+    }
+
+    pub fn current_file(&self) -> PathBuf {
+        self.dir.join(CAPTURE_FILE_NAME)
+    }
+
+    pub fn write(&mut self, record: &CaptureRecord) -> io::Result<()> {
+        let record_size = record.serialized_size() as u64;
+        if self.file.is_some() && self.file_size + record_size > self.max_file_size {
+            self.rotate()?;
+        }
+        if self.file.is_none() {
+            self.open()?;
+        }
+        let file = self.file.as_mut().unwrap();
+        record.serialize(file)?;
+        self.file_size += record_size;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn open(&mut self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut file = BufWriter::new(File::create(self.current_file())?);
+        file.write_all(FILE_MAGIC)?;
+        file.write_all(&FILE_VERSION.to_le_bytes())?;
+        self.file = Some(file);
+        self.file_size = FILE_HEADER_SIZE;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        if self.max_files == 1 {
+            // The current file is truncated when it is opened again
+            return Ok(());
+        }
+        let current = self.current_file();
+        let _ = fs::remove_file(rotated_file(&current, self.max_files - 1));
+        for i in (1..self.max_files - 1).rev() {
+            let from = rotated_file(&current, i);
+            if from.exists() {
+                fs::rename(from, rotated_file(&current, i + 1))?;
+            }
+        }
+        fs::rename(&current, rotated_file(&current, 1))?;
+        Ok(())
+    }
+}
+
+fn rotated_file(current: &Path, index: usize) -> PathBuf {
+    let mut name = current.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Reads the records of a single capture file
+pub struct CaptureReader<R: Read> {
+    reader: R,
+}
+
+impl CaptureReader<BufReader<File>> {
+    pub fn open(file: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(file)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; FILE_HEADER_SIZE as usize];
+        reader.read_exact(&mut header)?;
+        if &header[..6] != FILE_MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not a message capture file",
+            ));
+        }
+        let version = u16::from_le_bytes([header[6], header[7]]);
+        if version != FILE_VERSION {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("unsupported capture file version {}", version),
+            ));
+        }
+        Ok(Self { reader })
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = io::Result<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        CaptureRecord::deserialize(&mut self.reader).transpose()
+    }
+}
+
+/// Writes all inbound and sent messages of the node into capture files.
+/// Inbound messages are captured as they were read from the channel, including
+/// duplicates and messages which were dropped later. Outbound messages are
+/// captured as they were written to the channel, if the send succeeded
+pub struct MessageCapture {
+    network_info: Arc<RwLock<NetworkInfo>>,
+    time_factory: SystemTimeFactory,
+    state: Mutex<CaptureState>,
+}
+
+struct CaptureState {
+    writer: CaptureWriter,
+    failed: bool,
+}
+
+impl MessageCapture {
+    pub fn new(
+        config: &MessageCaptureConfig,
+        data_path: &Path,
+        network_info: Arc<RwLock<NetworkInfo>>,
+    ) -> Self {
+        let dir = config
+            .path
+            .clone()
+            .unwrap_or_else(|| data_path.join("capture"));
+        Self {
+            network_info,
+            time_factory: SystemTimeFactory::default(),
+            state: Mutex::new(CaptureState {
+                writer: CaptureWriter::new(dir, config.max_file_size, config.max_files),
+                failed: false,
+            }),
+        }
+    }
+
+    /// Captures a message as it was read from or written to the channel
+    pub fn capture_raw(&self, channel_id: ChannelId, bytes: Vec<u8>, direction: ChannelDirection) {
+        let peer_addr = self
+            .network_info
+            .read()
+            .unwrap()
+            .get(channel_id)
+            .map(|c| c.peer_addr())
+            .unwrap_or_else(|| SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0));
+
+        let timestamp = self
+            .time_factory
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_millis() as u64;
+
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        let record = CaptureRecord {
+            timestamp,
+            channel_id,
+            peer_addr,
+            direction,
+            bytes,
+        };
+        if let Err(e) = state.writer.write(&record) {
+            // Log only once, so that a full disk doesn't flood the log
+            if !state.failed {
+                warn!("Could not write message capture: {:?}", e);
+                state.failed = true;
+            }
+        } else {
+            state.failed = false;
+        }
+    }
+
+    pub fn flush(&self) {
+        if let Err(e) = self.state.lock().unwrap().writer.flush() {
+            warn!("Could not flush message capture: {:?}", e);
+        }
+    }
+}
+
+impl Drop for MessageCapture {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Feeds captured inbound messages into the inbound message queue,
+/// as if they were received from the network
+pub struct CaptureReplayer {
+    queue: Arc<InboundMessageQueue>,
+    protocol_info: ProtocolInfo,
+    work_thresholds: WorkThresholds,
+    clock: Arc<SteadyClock>,
+    channels: HashMap<ChannelId, Arc<ChannelInfo>>,
+}
+
+impl CaptureReplayer {
+    const MAX_QUEUE_RETRIES: usize = 1000;
+
+    pub fn new(
+        queue: Arc<InboundMessageQueue>,
+        protocol_info: ProtocolInfo,
+        work_thresholds: WorkThresholds,
+        clock: Arc<SteadyClock>,
+    ) -> Self {
+        Self {
+            queue,
+            protocol_info,
+            work_thresholds,
+            clock,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Returns false if the record was not replayed, because it is an outbound
+    /// message, a handshake or bootstrap message or because the queue stayed full
+    pub async fn replay(&mut self, record: &CaptureRecord) -> Result<bool, ParseMessageError> {
+        if record.direction != ChannelDirection::Inbound {
+            return Ok(false);
+        }
+        let message = record
+            .decode(self.protocol_info, self.work_thresholds.clone())
+            .await?;
+        if matches!(
+            message,
+            Message::NodeIdHandshake(_)
+                | Message::BulkPull(_)
+                | Message::BulkPullAccount(_)
+                | Message::BulkPush
+        ) {
+            // Only realtime messages are put into the inbound queue
+            return Ok(false);
+        }
+
+        let channel = self
+            .channels
+            .entry(record.channel_id)
+            .or_insert_with(|| {
+                Arc::new(ChannelInfo::new(
+                    record.channel_id,
+                    NULL_ENDPOINT,
+                    record.peer_addr,
+                    ChannelDirection::Inbound,
+                    self.protocol_info.version_using,
+                    self.clock.now(),
+                ))
+            })
+            .clone();
+
+        for _ in 0..Self::MAX_QUEUE_RETRIES {
+            if self.queue.put(message.clone(), channel.clone()) {
+                return Ok(true);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stats::Stats, transport::MessagePublisher, unique_path};
+    use rsban_messages::{Keepalive, MessageSerializer};
+    use rsban_network::{DropPolicy, TrafficType};
+    use std::io::Cursor;
+
+    #[test]
+    fn serialize_record() {
+        let record = test_record(vec![1, 2, 3]);
+        let mut buffer = Vec::new();
+        record.serialize(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), record.serialized_size());
+
+        let mut reader = Cursor::new(buffer);
+        let deserialized = CaptureRecord::deserialize(&mut reader).unwrap();
+        assert_eq!(deserialized, Some(record));
+        assert_eq!(CaptureRecord::deserialize(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn write_and_read_file() {
+        let dir = unique_path().unwrap();
+        let mut writer = CaptureWriter::new(&dir, 1024 * 1024, 2);
+        writer.write(&test_record(vec![1])).unwrap();
+        writer.write(&test_record(vec![2])).unwrap();
+        writer.flush().unwrap();
+
+        let records: Vec<_> = CaptureReader::open(writer.current_file())
+            .unwrap()
+            .map(|r| r.unwrap().bytes)
+            .collect();
+        assert_eq!(records, vec![vec![1], vec![2]]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rotate_files() {
+        let dir = unique_path().unwrap();
+        let record_size = test_record(vec![0; 10]).serialized_size() as u64;
+        let mut writer = CaptureWriter::new(&dir, FILE_HEADER_SIZE + record_size, 3);
+        for i in 0..5 {
+            writer.write(&test_record(vec![i; 10])).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let first_byte = |file: PathBuf| {
+            CaptureReader::open(file)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+                .bytes[0]
+        };
+        let current = writer.current_file();
+        assert_eq!(first_byte(current.clone()), 4);
+        assert_eq!(first_byte(rotated_file(&current, 1)), 3);
+        assert_eq!(first_byte(rotated_file(&current, 2)), 2);
+        assert!(!rotated_file(&current, 3).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_file() {
+        assert!(CaptureReader::new(Cursor::new(b"foobarfoobar".to_vec())).is_err());
+    }
+
+    #[tokio::test]
+    async fn decode_record() {
+        let protocol = ProtocolInfo::default();
+        let message = Message::Keepalive(Keepalive::new_test_instance());
+        let bytes = MessageSerializer::new(protocol)
+            .serialize(&message)
+            .to_vec();
+
+        let decoded = test_record(bytes)
+            .decode(protocol, WorkThresholds::new(0, 0, 0))
+            .await
+            .unwrap();
+
+        assert_eq!(decoded, message);
+    }
+
+    #[tokio::test]
+    async fn replay_inbound_messages() {
+        let protocol = ProtocolInfo::default();
+        let queue = Arc::new(InboundMessageQueue::new(10, Arc::new(Stats::default())));
+        let mut replayer = CaptureReplayer::new(
+            queue.clone(),
+            protocol,
+            WorkThresholds::new(0, 0, 0),
+            Arc::new(SteadyClock::new_null()),
+        );
+        let bytes = MessageSerializer::new(protocol)
+            .serialize(&Message::Keepalive(Keepalive::new_test_instance()))
+            .to_vec();
+        let inbound = test_record(bytes);
+        let outbound = CaptureRecord {
+            direction: ChannelDirection::Outbound,
+            ..inbound.clone()
+        };
+
+        assert_eq!(replayer.replay(&inbound).await, Ok(true));
+        assert_eq!(replayer.replay(&outbound).await, Ok(false));
+        assert_eq!(queue.size(), 1);
+    }
+
+    #[tokio::test]
+    async fn dropped_messages_are_not_captured() {
+        let dir = unique_path().unwrap();
+        let config = MessageCaptureConfig {
+            enabled: true,
+            path: Some(dir.clone()),
+            ..Default::default()
+        };
+        let capture = Arc::new(MessageCapture::new(
+            &config,
+            &dir,
+            Arc::new(RwLock::new(NetworkInfo::new_test_instance())),
+        ));
+        let mut publisher = MessagePublisher::new_null(tokio::runtime::Handle::current());
+        publisher.set_message_capture(capture.clone());
+
+        let sent = publisher.try_send(
+            ChannelId::from(42),
+            &Message::Keepalive(Keepalive::new_test_instance()),
+            DropPolicy::CanDrop,
+            TrafficType::Generic,
+        );
+        capture.flush();
+
+        assert!(!sent);
+        assert!(!dir.join(CAPTURE_FILE_NAME).exists());
+    }
+
+    fn test_record(bytes: Vec<u8>) -> CaptureRecord {
+        CaptureRecord {
+            timestamp: 1_700_000_000_000,
+            channel_id: ChannelId::from(7),
+            peer_addr: "[::ffff:10.0.0.1]:7071".parse().unwrap(),
+            direction: ChannelDirection::Inbound,
+            bytes,
+        }
+    }
+}
@@ -10,6 +10,8 @@ pub struct MessageDeserializer<T: AsyncBufferReader + Send> {
     protocol_info: ProtocolInfo,
     read_buffer: Vec<u8>,
     buffer_reader: T,
+    last_header: Option<MessageHeader>,
+    last_header_bytes: [u8; MessageHeader::SERIALIZED_SIZE],
 }
 
 impl<T: AsyncBufferReader + Send> MessageDeserializer<T> {
@@ -25,10 +27,28 @@ impl<T: AsyncBufferReader + Send> MessageDeserializer<T> {
             buffer_reader,
             work_thresholds,
             network_filter,
+            last_header: None,
+            last_header_bytes: [0; MessageHeader::SERIALIZED_SIZE],
         }
     }
 
+    /// Header of the last message that was read completely, even if its
+    /// payload was rejected afterwards
+    pub fn last_header(&self) -> Option<&MessageHeader> {
+        self.last_header.as_ref()
+    }
+
+    /// The raw bytes of the last message that was read completely, including its header.
+    /// Duplicate and invalid messages are included
+    pub fn last_message_bytes(&self) -> Option<Vec<u8>> {
+        let header = self.last_header.as_ref()?;
+        let mut bytes = self.last_header_bytes.to_vec();
+        bytes.extend_from_slice(&self.read_buffer[..header.payload_length()]);
+        Some(bytes)
+    }
+
     pub async fn read(&mut self) -> Result<DeserializedMessage, ParseMessageError> {
+        self.last_header = None;
         self.buffer_reader
             .read(&mut self.read_buffer, MessageHeader::SERIALIZED_SIZE)
             .await
@@ -38,24 +58,25 @@ impl<T: AsyncBufferReader + Send> MessageDeserializer<T> {
     }
 
     async fn received_header(&mut self) -> Result<DeserializedMessage, ParseMessageError> {
+        self.last_header_bytes
+            .copy_from_slice(&self.read_buffer[..MessageHeader::SERIALIZED_SIZE]);
         let header = {
-            let header_bytes = &self.read_buffer[..MessageHeader::SERIALIZED_SIZE];
+            let header_bytes = &self.last_header_bytes;
             let mut stream = BufferReader::new(header_bytes);
             MessageHeader::deserialize(&mut stream).map_err(|_| ParseMessageError::InvalidHeader)?
         };
 
         validate_header(&header, &self.protocol_info)?;
         let payload_size = header.payload_length();
-        if payload_size == 0 {
+        if payload_size > 0 {
             // Payload size will be 0 for `bulk_push` & `telemetry_req` message type
-            self.parse_message(header, 0)
-        } else {
             self.buffer_reader
                 .read(&mut self.read_buffer, payload_size)
                 .await
                 .map_err(|e| ParseMessageError::Other(e.to_string()))?;
-            self.parse_message(header, payload_size)
         }
+        self.last_header = Some(header.clone());
+        self.parse_message(header, payload_size)
     }
 
     fn parse_message(
@@ -184,4 +205,49 @@ mod tests {
 
         assert_eq!(error, ParseMessageError::DuplicateConfirmAckMessage);
     }
+
+    #[tokio::test]
+    async fn raw_bytes_of_duplicate_message() {
+        let protocol = ProtocolInfo::default();
+        let message = Message::Publish(Publish::new_test_instance());
+        let mut serializer = MessageSerializer::new(protocol);
+        let serialized = serializer.serialize(&message).to_vec();
+        let mut buffer = serialized.clone();
+        buffer.extend_from_slice(&serialized);
+        let reader = VecBufferReader::new(buffer);
+
+        let mut deserializer = MessageDeserializer::new(
+            protocol,
+            WorkThresholds::new(0, 0, 0),
+            Arc::new(NetworkFilter::default()),
+            reader,
+        );
+
+        deserializer.read().await.unwrap();
+        deserializer.read().await.unwrap_err();
+
+        assert_eq!(deserializer.last_message_bytes(), Some(serialized));
+    }
+
+    #[tokio::test]
+    async fn failed_header_read_resets_last_header() {
+        let protocol = ProtocolInfo::default();
+        let message = Message::Publish(Publish::new_test_instance());
+        let mut serializer = MessageSerializer::new(protocol);
+        let reader = VecBufferReader::new(serializer.serialize(&message).to_vec());
+
+        let mut deserializer = MessageDeserializer::new(
+            protocol,
+            WorkThresholds::new(0, 0, 0),
+            Arc::new(NetworkFilter::default()),
+            reader,
+        );
+
+        deserializer.read().await.unwrap();
+        assert!(deserializer.last_header().is_some());
+        deserializer.read().await.unwrap_err();
+
+        assert!(deserializer.last_header().is_none());
+        assert_eq!(deserializer.last_message_bytes(), None);
+    }
 }
@@ -1,9 +1,10 @@
+use super::MessageCapture;
 use crate::{
     representatives::OnlineReps,
     stats::{Direction, StatType, Stats},
 };
-use rsban_messages::{Message, MessageSerializer, ProtocolInfo};
-use rsban_network::{ChannelId, ChannelInfo, DropPolicy, Network, TrafficType};
+use rsban_messages::{Message, MessageSerializer, PayloadCompression, ProtocolInfo};
+use rsban_network::{ChannelDirection, ChannelId, ChannelInfo, DropPolicy, Network, TrafficType};
 use std::sync::{Arc, Mutex};
 use tracing::trace;
 
@@ -17,6 +18,7 @@ pub struct MessagePublisher {
     stats: Arc<Stats>,
     message_serializer: MessageSerializer,
     published_callback: Option<MessageCallback>,
+    message_capture: Option<Arc<MessageCapture>>,
 }
 
 impl MessagePublisher {
@@ -32,6 +34,7 @@ impl MessagePublisher {
             stats,
             message_serializer: MessageSerializer::new(protocol_info),
             published_callback: None,
+            message_capture: None,
         }
     }
 
@@ -48,6 +51,7 @@ impl MessagePublisher {
             stats,
             message_serializer: MessageSerializer::new_with_buffer_size(protocol_info, buffer_size),
             published_callback: None,
+            message_capture: None,
         }
     }
 
@@ -55,6 +59,11 @@ impl MessagePublisher {
         self.published_callback = Some(callback);
     }
 
+    /// Captures the bytes of every message that was written to a channel
+    pub fn set_message_capture(&mut self, capture: Arc<MessageCapture>) {
+        self.message_capture = Some(capture);
+    }
+
     pub(crate) fn new_null(handle: tokio::runtime::Handle) -> Self {
         Self::new(
             Arc::new(Mutex::new(OnlineReps::default())),
@@ -71,7 +80,13 @@ impl MessagePublisher {
         drop_policy: DropPolicy,
         traffic_type: TrafficType,
     ) -> bool {
-        let buffer = self.message_serializer.serialize(message);
+        let (buffer, compression) = serialize_for_channel(
+            &mut self.message_serializer,
+            &self.network,
+            channel_id,
+            message,
+            traffic_type,
+        );
         let sent = try_send_serialized_message(
             &self.network,
             &self.stats,
@@ -81,6 +96,12 @@ impl MessagePublisher {
             drop_policy,
             traffic_type,
         );
+        if sent {
+            capture_sent(self.message_capture.as_deref(), channel_id, buffer);
+            if let Some(compression) = compression {
+                self.stats.payload_compressed(Direction::Out, &compression);
+            }
+        }
 
         if let Some(callback) = &self.published_callback {
             callback(channel_id, message);
@@ -95,10 +116,21 @@ impl MessagePublisher {
         message: &Message,
         traffic_type: TrafficType,
     ) -> anyhow::Result<()> {
-        let buffer = self.message_serializer.serialize(message);
+        let (buffer, compression) = serialize_for_channel(
+            &mut self.message_serializer,
+            &self.network,
+            channel_id,
+            message,
+            traffic_type,
+        );
         self.network
             .send_buffer(channel_id, &buffer, traffic_type)
             .await?;
+        add_message_out(&self.network, channel_id, message, buffer.len());
+        capture_sent(self.message_capture.as_deref(), channel_id, buffer);
+        if let Some(compression) = compression {
+            self.stats.payload_compressed(Direction::Out, &compression);
+        }
         self.stats
             .inc_dir_aggregate(StatType::Message, message.into(), Direction::Out);
         trace!(%channel_id, message = ?message, "Message sent");
@@ -154,7 +186,7 @@ impl MessagePublisher {
             .random_fanout_realtime(scale);
 
         for channel in channels {
-            try_send_serialized_message(
+            let sent = try_send_serialized_message(
                 &self.network,
                 &self.stats,
                 channel.channel_id(),
@@ -163,10 +195,41 @@ impl MessagePublisher {
                 drop_policy,
                 TrafficType::Generic,
             );
+            if sent {
+                capture_sent(
+                    self.message_capture.as_deref(),
+                    channel.channel_id(),
+                    buffer,
+                );
+            }
         }
     }
 }
 
+/// Bootstrap responses are compressed if the peer negotiated compression in the handshake
+fn serialize_for_channel<'a>(
+    serializer: &'a mut MessageSerializer,
+    network: &Network,
+    channel_id: ChannelId,
+    message: &Message,
+    traffic_type: TrafficType,
+) -> (&'a [u8], Option<PayloadCompression>) {
+    let supports_compression = matches!(traffic_type, TrafficType::Bootstrap)
+        && network
+            .info
+            .read()
+            .unwrap()
+            .get(channel_id)
+            .map(|c| c.supports_compression())
+            .unwrap_or(false);
+
+    if supports_compression {
+        serializer.serialize_compressed(message)
+    } else {
+        (serializer.serialize(message), None)
+    }
+}
+
 fn try_send_serialized_message(
     network: &Network,
     stats: &Stats,
@@ -179,6 +242,7 @@ fn try_send_serialized_message(
     let sent = network.try_send_buffer(channel_id, buffer, drop_policy, traffic_type);
 
     if sent {
+        add_message_out(network, channel_id, message, buffer.len());
         stats.inc_dir_aggregate(StatType::Message, message.into(), Direction::Out);
         trace!(%channel_id, message = ?message, "Message sent");
     } else {
@@ -189,3 +253,16 @@ fn try_send_serialized_message(
 
     sent
 }
+
+/// The buffer is captured as it was written, so compressed messages stay compressed
+fn capture_sent(capture: Option<&MessageCapture>, channel_id: ChannelId, buffer: &[u8]) {
+    if let Some(capture) = capture {
+        capture.capture_raw(channel_id, buffer.to_vec(), ChannelDirection::Outbound);
+    }
+}
+
+fn add_message_out(network: &Network, channel_id: ChannelId, message: &Message, size: usize) {
+    if let Some(channel) = network.info.read().unwrap().get(channel_id) {
+        channel.add_message_out(message.message_type().as_str(), size);
+    }
+}
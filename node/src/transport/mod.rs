@@ -3,24 +3,29 @@ mod fair_queue;
 mod handshake_process;
 mod inbound_message_queue;
 mod latest_keepalives;
+mod message_capture;
 mod message_deserializer;
 mod message_processor;
 mod message_publisher;
+mod natpmp;
 mod network_filter;
 mod network_threads;
 mod peer_cache_connector;
 mod peer_cache_updater;
+mod port_mapping;
 mod realtime_message_handler;
 mod response_server;
 mod response_server_spawner;
 mod syn_cookies;
+mod upnp;
 mod vec_buffer_reader;
 
-pub use block_deserializer::read_block;
+pub use block_deserializer::{read_block, read_block_of_type, read_compressed_blocks};
 pub use fair_queue::*;
 pub(crate) use handshake_process::*;
 pub use inbound_message_queue::*;
 pub use latest_keepalives::*;
+pub use message_capture::*;
 pub use message_deserializer::MessageDeserializer;
 pub use message_processor::*;
 pub use message_publisher::*;
@@ -28,6 +33,7 @@ pub use network_filter::NetworkFilter;
 pub(crate) use network_threads::*;
 pub use peer_cache_connector::*;
 pub use peer_cache_updater::*;
+pub use port_mapping::*;
 pub use realtime_message_handler::RealtimeMessageHandler;
 pub use response_server::*;
 pub use response_server_spawner::*;
@@ -0,0 +1,138 @@
+use anyhow::{anyhow, bail};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+pub const NATPMP_PORT: u16 = 5351;
+
+const VERSION: u8 = 0;
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+const OP_MAP_TCP: u8 = 2;
+const RESPONSE_FLAG: u8 = 128;
+
+/// NAT-PMP (RFC 6886) client, which is used for gateways that don't speak UPnP
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NatPmpGateway {
+    pub address: SocketAddr,
+    pub timeout: Duration,
+}
+
+impl NatPmpGateway {
+    pub fn new(address: SocketAddr, timeout: Duration) -> Self {
+        Self { address, timeout }
+    }
+
+    pub fn external_ip(&self) -> anyhow::Result<Ipv4Addr> {
+        let response = self.request(&[VERSION, OP_EXTERNAL_ADDRESS], 12)?;
+        Ok(Ipv4Addr::new(
+            response[8],
+            response[9],
+            response[10],
+            response[11],
+        ))
+    }
+
+    /// Returns the external port which was assigned by the gateway
+    pub fn add_port_mapping(
+        &self,
+        external_port: u16,
+        internal_port: u16,
+        lease_duration: Duration,
+    ) -> anyhow::Result<u16> {
+        let response = self.map(external_port, internal_port, lease_duration)?;
+        Ok(u16::from_be_bytes([response[10], response[11]]))
+    }
+
+    pub fn delete_port_mapping(&self, internal_port: u16) -> anyhow::Result<()> {
+        self.map(0, internal_port, Duration::ZERO)?;
+        Ok(())
+    }
+
+    fn map(
+        &self,
+        external_port: u16,
+        internal_port: u16,
+        lease_duration: Duration,
+    ) -> anyhow::Result<Vec<u8>> {
+        let lifetime = u32::try_from(lease_duration.as_secs()).unwrap_or(u32::MAX);
+        let mut request = vec![VERSION, OP_MAP_TCP, 0, 0];
+        request.extend_from_slice(&internal_port.to_be_bytes());
+        request.extend_from_slice(&external_port.to_be_bytes());
+        request.extend_from_slice(&lifetime.to_be_bytes());
+        self.request(&request, 16)
+    }
+
+    fn request(&self, request: &[u8], response_len: usize) -> anyhow::Result<Vec<u8>> {
+        let local: IpAddr = if self.address.ip().is_loopback() {
+            self.address.ip()
+        } else {
+            Ipv4Addr::UNSPECIFIED.into()
+        };
+        let socket = UdpSocket::bind(SocketAddr::new(local, 0))?;
+        socket.set_read_timeout(Some(self.timeout))?;
+        socket.send_to(request, self.address)?;
+
+        let mut buffer = [0; 16];
+        let (len, _) = socket
+            .recv_from(&mut buffer)
+            .map_err(|_| anyhow!("no NAT-PMP response from {}", self.address))?;
+        if len < response_len || buffer[0] != VERSION || buffer[1] != request[1] | RESPONSE_FLAG {
+            bail!("invalid NAT-PMP response");
+        }
+        let result_code = u16::from_be_bytes([buffer[2], buffer[3]]);
+        if result_code != 0 {
+            bail!("NAT-PMP request failed with result code {}", result_code);
+        }
+        Ok(buffer[..len].to_vec())
+    }
+}
+
+/// Reads the IPv4 default gateway from the routing table
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    parse_default_gateway(&routes)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    None
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut columns = line.split_whitespace();
+        let destination = columns.nth(1)?;
+        let gateway = columns.next()?;
+        if destination != "00000000" {
+            return None;
+        }
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        // The routing table contains the address in host byte order
+        Some(Ipv4Addr::from(gateway.to_le_bytes()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_routing_table() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                      eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n\
+                      eth0\t00000000\t0101A8C0\t0003\t0\t0\t0\t00000000\n";
+        assert_eq!(
+            parse_default_gateway(routes),
+            Some(Ipv4Addr::new(192, 168, 1, 1))
+        );
+    }
+
+    #[test]
+    fn no_default_route() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n";
+        assert_eq!(parse_default_gateway(routes), None);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{LatestKeepalives, MessagePublisher, NetworkFilter, SynCookies};
+use super::{LatestKeepalives, MessagePublisher, NetworkFilter, PortMapping, SynCookies};
 use crate::{
     config::{NodeConfig, NodeFlags},
     stats::{DetailType, StatType, Stats},
@@ -186,6 +186,7 @@ impl CleanupLoop {
 pub struct KeepaliveFactory {
     pub network: Arc<RwLock<NetworkInfo>>,
     pub config: NodeConfig,
+    pub port_mapping: Arc<PortMapping>,
 }
 
 impl KeepaliveFactory {
@@ -209,16 +210,10 @@ impl KeepaliveFactory {
                 0,
             );
         } else {
-            // TODO Read external address from port_mapping!
-            //let external_address  node.port_mapping.external_address ());
-            let external_address = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0);
-            if !external_address.ip().is_unspecified() {
-                result.peers[0] =
-                    SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, network.listening_port(), 0, 0);
+            result.peers[0] =
+                SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, network.listening_port(), 0, 0);
+            if let Some(external_address) = self.port_mapping.external_address() {
                 result.peers[1] = external_address;
-            } else {
-                result.peers[0] =
-                    SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, network.listening_port(), 0, 0);
             }
         }
         result
@@ -4,13 +4,19 @@ use crate::{
     utils::{CancellationToken, Runnable},
 };
 use rsban_ledger::Ledger;
-use rsban_network::PeerConnector;
-use std::{net::SocketAddrV6, sync::Arc, time::Duration};
+use rsban_network::{NetworkInfo, PeerConnector};
+use std::{
+    net::SocketAddrV6,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 use tracing::info;
 
-// Tries to connect to peers that are stored in the peer cache
+// Tries to connect to peers that are stored in the peer cache.
+// Peers with a good reputation are tried first.
 pub struct PeerCacheConnector {
     ledger: Arc<Ledger>,
+    network_info: Arc<RwLock<NetworkInfo>>,
     peer_connector: Arc<PeerConnector>,
     stats: Arc<Stats>,
     first_run: bool,
@@ -21,12 +27,14 @@ pub struct PeerCacheConnector {
 impl PeerCacheConnector {
     pub fn new(
         ledger: Arc<Ledger>,
+        network_info: Arc<RwLock<NetworkInfo>>,
         peer_connector: Arc<PeerConnector>,
         stats: Arc<Stats>,
         reach_out_delay: Duration,
     ) -> Self {
         Self {
             ledger,
+            network_info,
             peer_connector,
             stats,
             first_run: true,
@@ -36,12 +44,18 @@ impl PeerCacheConnector {
 
     fn load_peers_from_cache(&self) -> Vec<SocketAddrV6> {
         let tx = self.ledger.read_txn();
-        self.ledger
+        let mut peers: Vec<_> = self
+            .ledger
             .store
             .peer
             .iter(&tx)
             .map(|(peer, _)| peer)
-            .collect()
+            .collect();
+        self.network_info
+            .read()
+            .unwrap()
+            .sort_by_reputation(&mut peers);
+        peers
     }
 }
 
@@ -72,7 +86,10 @@ impl Runnable for PeerCacheConnector {
 mod tests {
     use super::*;
     use crate::stats::Direction;
-    use rsban_core::utils::{parse_endpoint, TEST_ENDPOINT_1, TEST_ENDPOINT_2, TEST_ENDPOINT_3};
+    use rsban_core::{
+        utils::{parse_endpoint, TEST_ENDPOINT_1, TEST_ENDPOINT_2, TEST_ENDPOINT_3},
+        PeerReputation,
+    };
     use rsban_output_tracker::OutputTrackerMt;
     use std::time::UNIX_EPOCH;
     use tracing_test::traced_test;
@@ -95,6 +112,37 @@ mod tests {
         assert_eq!(merged_peers, [peer1, peer2]);
     }
 
+    #[tokio::test]
+    async fn connect_to_reliable_peers_first() {
+        let mut network_info = NetworkInfo::new_test_instance();
+        network_info.restore_reputation(
+            *TEST_ENDPOINT_3.ip(),
+            PeerReputation {
+                uptime: Duration::from_secs(3600),
+                ..Default::default()
+            },
+        );
+        network_info.restore_reputation(
+            *TEST_ENDPOINT_1.ip(),
+            PeerReputation {
+                misbehavior_score: 1,
+                ..Default::default()
+            },
+        );
+        let (mut connector, merge_tracker, _) = create_test_connector_with(
+            [TEST_ENDPOINT_1, TEST_ENDPOINT_2, TEST_ENDPOINT_3],
+            network_info,
+        )
+        .await;
+
+        connector.run(&CancellationToken::new_null());
+
+        assert_eq!(
+            merge_tracker.output(),
+            [TEST_ENDPOINT_3, TEST_ENDPOINT_2, TEST_ENDPOINT_1]
+        );
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn log_initial_peers() {
@@ -191,13 +239,29 @@ mod tests {
         PeerCacheConnector,
         Arc<OutputTrackerMt<SocketAddrV6>>,
         Arc<Stats>,
+    ) {
+        create_test_connector_with(cached_peers, NetworkInfo::new_test_instance()).await
+    }
+
+    async fn create_test_connector_with(
+        cached_peers: impl IntoIterator<Item = SocketAddrV6>,
+        network_info: NetworkInfo,
+    ) -> (
+        PeerCacheConnector,
+        Arc<OutputTrackerMt<SocketAddrV6>>,
+        Arc<Stats>,
     ) {
         let ledger = ledger_with_peers(cached_peers);
         let peer_connector = Arc::new(PeerConnector::new_null(tokio::runtime::Handle::current()));
         let merge_tracker = peer_connector.track_connections();
         let stats = Arc::new(Stats::default());
-        let connector =
-            PeerCacheConnector::new(ledger, peer_connector, stats.clone(), REACHOUT_DELAY);
+        let connector = PeerCacheConnector::new(
+            ledger,
+            Arc::new(RwLock::new(network_info)),
+            peer_connector,
+            stats.clone(),
+            REACHOUT_DELAY,
+        );
         (connector, merge_tracker, stats)
     }
 
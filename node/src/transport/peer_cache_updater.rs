@@ -2,9 +2,10 @@ use crate::{
     stats::{DetailType, StatType, Stats},
     utils::{CancellationToken, Runnable},
 };
+use rsban_core::MisbehaviorExclusion;
 use rsban_ledger::Ledger;
 use rsban_network::{ChannelInfo, NetworkInfo};
-use rsban_nullable_clock::SystemTimeFactory;
+use rsban_nullable_clock::{SteadyClock, SystemTimeFactory, Timestamp};
 use rsban_store_lmdb::LmdbWriteTransaction;
 use std::{
     net::SocketAddrV6,
@@ -13,14 +14,16 @@ use std::{
 };
 use tracing::debug;
 
-/// Writes a snapshot of the current peers to the database,
+/// Writes a snapshot of the current peers and their reputation to the database,
 /// so that we can reconnect to them when the node is restarted
 pub struct PeerCacheUpdater {
     network_info: Arc<RwLock<NetworkInfo>>,
     ledger: Arc<Ledger>,
     time_factory: SystemTimeFactory,
+    clock: Arc<SteadyClock>,
     stats: Arc<Stats>,
     erase_cutoff: Duration,
+    last_reputation_update: Option<Timestamp>,
 }
 
 impl PeerCacheUpdater {
@@ -28,6 +31,7 @@ impl PeerCacheUpdater {
         network_info: Arc<RwLock<NetworkInfo>>,
         ledger: Arc<Ledger>,
         time_factory: SystemTimeFactory,
+        clock: Arc<SteadyClock>,
         stats: Arc<Stats>,
         erase_cutoff: Duration,
     ) -> Self {
@@ -35,8 +39,10 @@ impl PeerCacheUpdater {
             network_info,
             ledger,
             time_factory,
+            clock,
             stats,
             erase_cutoff,
+            last_reputation_update: None,
         }
     }
 
@@ -72,6 +78,49 @@ impl PeerCacheUpdater {
         }
     }
 
+    fn save_reputations(&mut self, tx: &mut LmdbWriteTransaction) {
+        let now = self.clock.now();
+        let elapsed = match self.last_reputation_update {
+            Some(last) => last.elapsed(now),
+            None => Duration::ZERO,
+        };
+        self.last_reputation_update = Some(now);
+
+        let network_info = self.network_info.read().unwrap();
+        let (changed, removed) = network_info.update_reputations(elapsed);
+
+        // The end of an exclusion is persisted as system time, so that it
+        // is still valid after a restart
+        let system_now = self.time_factory.now();
+        for (ip, mut reputation) in changed {
+            reputation.exclusion =
+                network_info
+                    .misbehavior_exclusion(&ip, now)
+                    .map(|(score, until)| MisbehaviorExclusion {
+                        score,
+                        until: system_now + (until - now),
+                    });
+            self.ledger.store.peer_reputation.put(tx, ip, &reputation);
+        }
+        for ip in removed {
+            self.ledger.store.peer_reputation.del(tx, ip);
+        }
+    }
+
+    fn delete_expired_bans(&self, tx: &mut LmdbWriteTransaction) {
+        let expired = self
+            .network_info
+            .write()
+            .unwrap()
+            .take_expired_bans(self.clock.now());
+        for subnet in expired {
+            self.ledger
+                .store
+                .peer_ban
+                .del(tx, subnet.network(), subnet.prefix_len());
+        }
+    }
+
     fn get_old_peers(&self, tx: &LmdbWriteTransaction) -> Vec<SocketAddrV6> {
         let cutoff = self.time_factory.now() - self.erase_cutoff;
         let now = self.time_factory.now();
@@ -96,6 +145,8 @@ impl Runnable for PeerCacheUpdater {
         let mut tx = self.ledger.rw_txn();
         self.save_peers(&mut tx);
         self.delete_old_peers(&mut tx);
+        self.save_reputations(&mut tx);
+        self.delete_expired_bans(&mut tx);
     }
 }
 
@@ -106,8 +157,7 @@ mod tests {
     use rsban_core::utils::{
         new_test_timestamp, NULL_ENDPOINT, TEST_ENDPOINT_1, TEST_ENDPOINT_2, TEST_ENDPOINT_3,
     };
-    use rsban_network::{ChannelDirection, ChannelMode};
-    use rsban_nullable_clock::Timestamp;
+    use rsban_network::{ChannelDirection, ChannelMode, IpSubnet};
     use std::{net::SocketAddrV6, time::SystemTime};
     use tracing_test::traced_test;
 
@@ -272,15 +322,68 @@ mod tests {
         );
     }
 
-    async fn run_peer_history(
-        now: SystemTime,
-        open_channels: Vec<SocketAddrV6>,
-        already_stored: Vec<(SocketAddrV6, SystemTime)>,
-    ) -> (
-        Vec<(SocketAddrV6, SystemTime)>,
-        Vec<SocketAddrV6>,
-        Arc<Stats>,
-    ) {
+    #[tokio::test]
+    async fn save_reputation_of_connected_peers() {
+        let network = create_network(vec![TEST_ENDPOINT_1, TEST_ENDPOINT_2]);
+        let ledger = Arc::new(Ledger::new_null_builder().finish());
+        let reputation_tracker = ledger.store.peer_reputation.track_puts();
+        let mut peer_history = create_peer_history(network, ledger, new_test_timestamp());
+
+        peer_history.run(&CancellationToken::new());
+
+        let mut saved: Vec<_> = reputation_tracker
+            .output()
+            .into_iter()
+            .map(|(ip, _)| ip)
+            .collect();
+        saved.sort();
+        assert_eq!(saved, vec![*TEST_ENDPOINT_1.ip(), *TEST_ENDPOINT_2.ip()]);
+    }
+
+    #[tokio::test]
+    async fn save_end_of_exclusion_as_system_time() {
+        let mut network = create_network(vec![TEST_ENDPOINT_1]);
+        network.restore_exclusion(
+            *TEST_ENDPOINT_1.ip(),
+            3,
+            Timestamp::new_test_instance() + Duration::from_secs(60),
+        );
+        let ledger = Arc::new(Ledger::new_null_builder().finish());
+        let reputation_tracker = ledger.store.peer_reputation.track_puts();
+        let now = new_test_timestamp();
+        let mut peer_history = create_peer_history(network, ledger, now);
+
+        peer_history.run(&CancellationToken::new());
+
+        let saved = reputation_tracker.output();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(
+            saved[0].1.exclusion,
+            Some(MisbehaviorExclusion {
+                score: 3,
+                until: now + Duration::from_secs(60)
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_expired_bans() {
+        let mut network = create_network(Vec::new());
+        let subnet = IpSubnet::host(*TEST_ENDPOINT_1.ip());
+        network.ban(subnet, Timestamp::new_test_instance());
+        let ledger = Arc::new(Ledger::new_null_builder().finish());
+        let delete_tracker = ledger.store.peer_ban.track_deletions();
+        let mut peer_history = create_peer_history(network, ledger, new_test_timestamp());
+
+        peer_history.run(&CancellationToken::new());
+
+        assert_eq!(
+            delete_tracker.output(),
+            vec![(subnet.network(), subnet.prefix_len())]
+        );
+    }
+
+    fn create_network(open_channels: Vec<SocketAddrV6>) -> NetworkInfo {
         let mut network = NetworkInfo::new_test_instance();
         for endpoint in open_channels {
             let channel = network
@@ -294,6 +397,34 @@ mod tests {
                 .unwrap();
             channel.set_mode(ChannelMode::Realtime);
         }
+        network
+    }
+
+    fn create_peer_history(
+        network: NetworkInfo,
+        ledger: Arc<Ledger>,
+        now: SystemTime,
+    ) -> PeerCacheUpdater {
+        PeerCacheUpdater::new(
+            Arc::new(RwLock::new(network)),
+            ledger,
+            SystemTimeFactory::new_null_with(now),
+            Arc::new(SteadyClock::new_null()),
+            Arc::new(Stats::default()),
+            Duration::from_secs(60 * 60),
+        )
+    }
+
+    async fn run_peer_history(
+        now: SystemTime,
+        open_channels: Vec<SocketAddrV6>,
+        already_stored: Vec<(SocketAddrV6, SystemTime)>,
+    ) -> (
+        Vec<(SocketAddrV6, SystemTime)>,
+        Vec<SocketAddrV6>,
+        Arc<Stats>,
+    ) {
+        let network = create_network(open_channels);
         let ledger = Arc::new(Ledger::new_null_builder().peers(already_stored).finish());
         let time_factory = SystemTimeFactory::new_null_with(now);
         let stats = Arc::new(Stats::default());
@@ -304,6 +435,7 @@ mod tests {
             Arc::new(RwLock::new(network)),
             ledger,
             time_factory,
+            Arc::new(SteadyClock::new_null()),
             Arc::clone(&stats),
             erase_cutoff,
         );
@@ -0,0 +1,611 @@
+use super::{
+    natpmp::{default_gateway, NatPmpGateway, NATPMP_PORT},
+    upnp::{self, SSDP_MULTICAST_ADDRESS},
+};
+use crate::{
+    stats::{DetailType, StatType, Stats},
+    utils::{CancellationToken, Runnable},
+    PortmappingConstants,
+};
+use anyhow::bail;
+use rsban_network::NetworkInfo;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV6},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+const MAPPING_DESCRIPTION: &str = "Banano Node";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortMappingConfig {
+    pub enabled: bool,
+    /// SSDP search requests are sent to this address
+    pub discovery_address: SocketAddr,
+    pub discovery_timeout: Duration,
+    /// NAT-PMP is used if no UPnP gateway was found. If not set, the default gateway is used
+    pub natpmp_gateway: Option<SocketAddr>,
+}
+
+impl Default for PortMappingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            discovery_address: SSDP_MULTICAST_ADDRESS.into(),
+            discovery_timeout: Duration::from_secs(3),
+            natpmp_gateway: None,
+        }
+    }
+}
+
+/// Maps the TCP listening port on the internet gateway, so that nodes behind
+/// a NAT can accept incoming connections
+pub struct PortMapping {
+    config: PortMappingConfig,
+    lease_duration: Duration,
+    health_check_period: Duration,
+    network_info: Arc<RwLock<NetworkInfo>>,
+    stats: Arc<Stats>,
+    mapping: Mutex<Option<Mapping>>,
+    external_address: Mutex<Option<SocketAddrV6>>,
+}
+
+impl PortMapping {
+    pub fn new(
+        config: PortMappingConfig,
+        constants: &PortmappingConstants,
+        network_info: Arc<RwLock<NetworkInfo>>,
+        stats: Arc<Stats>,
+    ) -> Self {
+        Self {
+            config,
+            lease_duration: Duration::from_secs(constants.lease_duration_s as u64),
+            health_check_period: Duration::from_secs(constants.health_check_period_s as u64),
+            network_info,
+            stats,
+            mapping: Mutex::new(None),
+            external_address: Mutex::new(None),
+        }
+    }
+
+    /// The endpoint under which this node is reachable from the internet
+    pub fn external_address(&self) -> Option<SocketAddrV6> {
+        *self.external_address.lock().unwrap()
+    }
+
+    /// Adds the port mapping if it doesn't exist yet and renews the lease before it expires
+    pub fn refresh(&self) {
+        let port = self.network_info.read().unwrap().listening_port();
+        if port == 0 {
+            return;
+        }
+
+        let mut mapping = self.mapping.lock().unwrap();
+        if let Some(current) = mapping.as_ref() {
+            if current.internal_port == port && !self.lease_expiring(current) {
+                match current.gateway.external_ip() {
+                    Ok(ip) if ip.to_ipv6_mapped() == *current.external_address.ip() => return,
+                    Ok(ip) => info!("External IP address changed to {}", ip),
+                    Err(e) => warn!("Port mapping health check failed: {:?}", e),
+                }
+            }
+        }
+
+        let is_refresh = mapping.is_some();
+        let gateway = match mapping.take() {
+            Some(previous) => {
+                if previous.internal_port != port {
+                    // The old mapping would otherwise stay on the gateway until its lease expires
+                    self.delete_mapping(&previous);
+                }
+                previous.gateway
+            }
+            None => match self.discover_gateway() {
+                Some(gateway) => gateway,
+                None => {
+                    self.stats
+                        .inc(StatType::PortMapping, DetailType::GatewayNotFound);
+                    *self.external_address.lock().unwrap() = None;
+                    return;
+                }
+            },
+        };
+
+        match self.add_mapping(&gateway, port) {
+            Ok(external_address) => {
+                if is_refresh {
+                    self.stats
+                        .inc(StatType::PortMapping, DetailType::MappingRefreshed);
+                    debug!("Refreshed port mapping {}", external_address);
+                } else {
+                    self.stats
+                        .inc(StatType::PortMapping, DetailType::MappingAdded);
+                    info!(
+                        "Mapped port {} to external endpoint {} via {}",
+                        port,
+                        external_address,
+                        gateway.protocol()
+                    );
+                }
+                *self.external_address.lock().unwrap() = Some(external_address);
+                *mapping = Some(Mapping {
+                    gateway,
+                    internal_port: port,
+                    external_address,
+                    refreshed: Instant::now(),
+                });
+            }
+            Err(e) => {
+                // The gateway will be discovered again on the next refresh
+                self.stats
+                    .inc(StatType::PortMapping, DetailType::MappingFailed);
+                warn!("Could not map port {}: {:?}", port, e);
+                *self.external_address.lock().unwrap() = None;
+            }
+        }
+    }
+
+    /// Removes the port mapping from the gateway
+    pub fn remove(&self) {
+        let Some(mapping) = self.mapping.lock().unwrap().take() else {
+            return;
+        };
+        *self.external_address.lock().unwrap() = None;
+        self.delete_mapping(&mapping);
+    }
+
+    fn delete_mapping(&self, mapping: &Mapping) {
+        match mapping
+            .gateway
+            .delete_mapping(mapping.internal_port, mapping.external_address.port())
+        {
+            Ok(()) => {
+                self.stats
+                    .inc(StatType::PortMapping, DetailType::MappingRemoved);
+                info!("Removed port mapping {}", mapping.external_address);
+            }
+            Err(e) => warn!("Could not remove port mapping: {:?}", e),
+        }
+    }
+
+    fn lease_expiring(&self, mapping: &Mapping) -> bool {
+        mapping.refreshed.elapsed() + self.health_check_period * 2 >= self.lease_duration
+    }
+
+    fn discover_gateway(&self) -> Option<MappingGateway> {
+        match upnp::discover_gateway(self.config.discovery_address, self.config.discovery_timeout) {
+            Ok(gateway) => {
+                debug!("Found UPnP gateway: {}", gateway.control_url);
+                return Some(MappingGateway::Upnp(gateway));
+            }
+            Err(e) => debug!("UPnP discovery failed: {:?}", e),
+        }
+
+        let address = self
+            .config
+            .natpmp_gateway
+            .or_else(|| default_gateway().map(|ip| SocketAddr::new(IpAddr::V4(ip), NATPMP_PORT)))?;
+        let gateway = NatPmpGateway::new(address, self.config.discovery_timeout);
+        match gateway.external_ip() {
+            Ok(_) => {
+                debug!("Found NAT-PMP gateway: {}", address);
+                Some(MappingGateway::NatPmp(gateway))
+            }
+            Err(e) => {
+                debug!("NAT-PMP discovery failed: {:?}", e);
+                None
+            }
+        }
+    }
+
+    fn add_mapping(&self, gateway: &MappingGateway, port: u16) -> anyhow::Result<SocketAddrV6> {
+        let external_port = gateway.add_mapping(port, self.lease_duration)?;
+        let external_ip = gateway.external_ip()?;
+        Ok(SocketAddrV6::new(
+            external_ip.to_ipv6_mapped(),
+            external_port,
+            0,
+            0,
+        ))
+    }
+}
+
+struct Mapping {
+    gateway: MappingGateway,
+    internal_port: u16,
+    external_address: SocketAddrV6,
+    refreshed: Instant,
+}
+
+enum MappingGateway {
+    Upnp(upnp::Gateway),
+    NatPmp(NatPmpGateway),
+}
+
+impl MappingGateway {
+    fn protocol(&self) -> &'static str {
+        match self {
+            MappingGateway::Upnp(_) => "UPnP",
+            MappingGateway::NatPmp(_) => "NAT-PMP",
+        }
+    }
+
+    fn external_ip(&self) -> anyhow::Result<Ipv4Addr> {
+        match self {
+            MappingGateway::Upnp(gateway) => match gateway.external_ip()? {
+                IpAddr::V4(ip) => Ok(ip),
+                IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                    Some(ip) => Ok(ip),
+                    None => bail!("gateway returned an IPv6 address"),
+                },
+            },
+            MappingGateway::NatPmp(gateway) => gateway.external_ip(),
+        }
+    }
+
+    /// Returns the external port
+    fn add_mapping(&self, port: u16, lease_duration: Duration) -> anyhow::Result<u16> {
+        match self {
+            MappingGateway::Upnp(gateway) => {
+                gateway.add_port_mapping(port, port, lease_duration, MAPPING_DESCRIPTION)?;
+                Ok(port)
+            }
+            MappingGateway::NatPmp(gateway) => gateway.add_port_mapping(port, port, lease_duration),
+        }
+    }
+
+    fn delete_mapping(&self, internal_port: u16, external_port: u16) -> anyhow::Result<()> {
+        match self {
+            MappingGateway::Upnp(gateway) => gateway.delete_port_mapping(external_port),
+            MappingGateway::NatPmp(gateway) => gateway.delete_port_mapping(internal_port),
+        }
+    }
+}
+
+/// Periodically checks the port mapping and renews its lease
+pub struct PortMappingRefresher {
+    port_mapping: Arc<PortMapping>,
+}
+
+impl PortMappingRefresher {
+    pub fn new(port_mapping: Arc<PortMapping>) -> Self {
+        Self { port_mapping }
+    }
+}
+
+impl Runnable for PortMappingRefresher {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.port_mapping.refresh();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stats::Direction, DEV_NETWORK_PARAMS};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, UdpSocket},
+        thread,
+    };
+
+    const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(203, 0, 113, 7);
+    const LISTENING_PORT: u16 = 7071;
+
+    #[test]
+    fn map_port_via_upnp() {
+        let igd = MockIgd::start();
+        let port_mapping = create_port_mapping(PortMappingConfig {
+            discovery_address: igd.ssdp_address,
+            ..test_config()
+        });
+
+        port_mapping.refresh();
+
+        assert_eq!(
+            port_mapping.external_address(),
+            Some(SocketAddrV6::new(
+                EXTERNAL_IP.to_ipv6_mapped(),
+                LISTENING_PORT,
+                0,
+                0
+            ))
+        );
+        let actions = igd.actions();
+        assert!(actions.contains(&"AddPortMapping".to_string()));
+        assert!(igd
+            .requests()
+            .iter()
+            .any(|r| r.contains("<NewInternalPort>7071</NewInternalPort>")
+                && r.contains("<NewLeaseDuration>1787</NewLeaseDuration>")));
+        assert_eq!(
+            port_mapping.stats.count(
+                StatType::PortMapping,
+                DetailType::MappingAdded,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn health_check_does_not_add_mapping_again() {
+        let igd = MockIgd::start();
+        let port_mapping = create_port_mapping(PortMappingConfig {
+            discovery_address: igd.ssdp_address,
+            ..test_config()
+        });
+
+        port_mapping.refresh();
+        port_mapping.refresh();
+
+        let add_count = igd
+            .actions()
+            .iter()
+            .filter(|a| *a == "AddPortMapping")
+            .count();
+        assert_eq!(add_count, 1);
+    }
+
+    #[test]
+    fn remove_mapping() {
+        let igd = MockIgd::start();
+        let port_mapping = create_port_mapping(PortMappingConfig {
+            discovery_address: igd.ssdp_address,
+            ..test_config()
+        });
+        port_mapping.refresh();
+
+        port_mapping.remove();
+
+        assert_eq!(port_mapping.external_address(), None);
+        assert!(igd.actions().contains(&"DeletePortMapping".to_string()));
+    }
+
+    #[test]
+    fn remove_old_mapping_when_listening_port_changes() {
+        let igd = MockIgd::start();
+        let port_mapping = create_port_mapping(PortMappingConfig {
+            discovery_address: igd.ssdp_address,
+            ..test_config()
+        });
+        port_mapping.refresh();
+
+        port_mapping
+            .network_info
+            .write()
+            .unwrap()
+            .set_listening_port(LISTENING_PORT + 1);
+        port_mapping.refresh();
+
+        let requests = igd.requests();
+        let delete_index = requests
+            .iter()
+            .position(|r| {
+                r.contains("#DeletePortMapping\"")
+                    && r.contains("<NewExternalPort>7071</NewExternalPort>")
+            })
+            .expect("old mapping not deleted");
+        let add_index = requests
+            .iter()
+            .position(|r| {
+                r.contains("#AddPortMapping\"")
+                    && r.contains("<NewInternalPort>7072</NewInternalPort>")
+            })
+            .expect("new port not mapped");
+        assert!(delete_index < add_index);
+        assert_eq!(
+            port_mapping.external_address(),
+            Some(SocketAddrV6::new(
+                EXTERNAL_IP.to_ipv6_mapped(),
+                LISTENING_PORT + 1,
+                0,
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn map_port_via_natpmp() {
+        let natpmp_address = start_mock_natpmp();
+        let port_mapping = create_port_mapping(PortMappingConfig {
+            discovery_address: unused_udp_address(),
+            natpmp_gateway: Some(natpmp_address),
+            ..test_config()
+        });
+
+        port_mapping.refresh();
+
+        assert_eq!(
+            port_mapping.external_address(),
+            Some(SocketAddrV6::new(EXTERNAL_IP.to_ipv6_mapped(), 17071, 0, 0))
+        );
+    }
+
+    #[test]
+    fn no_gateway_found() {
+        let port_mapping = create_port_mapping(PortMappingConfig {
+            discovery_address: unused_udp_address(),
+            natpmp_gateway: Some(unused_udp_address()),
+            ..test_config()
+        });
+
+        port_mapping.refresh();
+
+        assert_eq!(port_mapping.external_address(), None);
+        assert_eq!(
+            port_mapping.stats.count(
+                StatType::PortMapping,
+                DetailType::GatewayNotFound,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    fn test_config() -> PortMappingConfig {
+        PortMappingConfig {
+            discovery_timeout: Duration::from_millis(500),
+            ..Default::default()
+        }
+    }
+
+    fn create_port_mapping(config: PortMappingConfig) -> PortMapping {
+        let mut network_info = NetworkInfo::new_test_instance();
+        network_info.set_listening_port(LISTENING_PORT);
+        PortMapping::new(
+            config,
+            &DEV_NETWORK_PARAMS.portmapping,
+            Arc::new(RwLock::new(network_info)),
+            Arc::new(Stats::default()),
+        )
+    }
+
+    fn unused_udp_address() -> SocketAddr {
+        UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    /// Answers SSDP searches and SOAP requests like an internet gateway device
+    struct MockIgd {
+        ssdp_address: SocketAddr,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockIgd {
+        fn start() -> Self {
+            let http = TcpListener::bind("127.0.0.1:0").unwrap();
+            let http_address = http.local_addr().unwrap();
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let requests_l = requests.clone();
+            thread::spawn(move || {
+                for stream in http.incoming() {
+                    let Ok(stream) = stream else { break };
+                    Self::handle_http(stream, &requests_l);
+                }
+            });
+
+            let ssdp = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let ssdp_address = ssdp.local_addr().unwrap();
+            thread::spawn(move || {
+                let mut buffer = [0; 2048];
+                while let Ok((len, sender)) = ssdp.recv_from(&mut buffer) {
+                    if !buffer[..len].starts_with(b"M-SEARCH") {
+                        continue;
+                    }
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\n\
+                         ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+                         LOCATION: http://{}/rootDesc.xml\r\n\r\n",
+                        http_address
+                    );
+                    let _ = ssdp.send_to(response.as_bytes(), sender);
+                }
+            });
+
+            Self {
+                ssdp_address,
+                requests,
+            }
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn actions(&self) -> Vec<String> {
+            self.requests()
+                .iter()
+                .filter_map(|r| {
+                    let start = r.find("SOAPAction: \"")? + 13;
+                    let action = &r[start..];
+                    let action = &action[action.find('#')? + 1..action.find('"')?];
+                    Some(action.to_string())
+                })
+                .collect()
+        }
+
+        fn handle_http(mut stream: std::net::TcpStream, requests: &Mutex<Vec<String>>) {
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    return;
+                }
+                if let Some(len) = line.strip_prefix("Content-Length: ") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body));
+
+            let response_body = if request.starts_with("GET /rootDesc.xml") {
+                DEVICE_DESCRIPTION.to_string()
+            } else if request.contains("#GetExternalIPAddress\"") {
+                format!(
+                    "<?xml version=\"1.0\"?><s:Envelope><s:Body>\
+                     <u:GetExternalIPAddressResponse>\
+                     <NewExternalIPAddress>{}</NewExternalIPAddress>\
+                     </u:GetExternalIPAddressResponse></s:Body></s:Envelope>",
+                    EXTERNAL_IP
+                )
+            } else {
+                "<?xml version=\"1.0\"?><s:Envelope><s:Body></s:Body></s:Envelope>".to_string()
+            };
+            requests.lock().unwrap().push(request);
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}",
+                response_body.len(),
+                response_body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    }
+
+    const DEVICE_DESCRIPTION: &str = "<?xml version=\"1.0\"?>\
+        <root xmlns=\"urn:schemas-upnp-org:device-1-0\"><device>\
+        <deviceType>urn:schemas-upnp-org:device:InternetGatewayDevice:1</deviceType>\
+        <deviceList><device><deviceList><device>\
+        <serviceList>\
+        <service><serviceType>urn:schemas-upnp-org:service:WANCommonInterfaceConfig:1</serviceType>\
+        <controlURL>/ctl/CommonIfCfg</controlURL></service>\
+        <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+        <controlURL>/ctl/IPConn</controlURL></service>\
+        </serviceList></device></deviceList></device></deviceList>\
+        </device></root>";
+
+    /// Answers NAT-PMP requests and maps to the internal port + 10000
+    fn start_mock_natpmp() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = socket.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut buffer = [0; 16];
+            while let Ok((len, sender)) = socket.recv_from(&mut buffer) {
+                let mut response = vec![0, buffer[1] | 128, 0, 0, 0, 0, 0, 1];
+                if buffer[1] == 0 && len == 2 {
+                    response.extend_from_slice(&EXTERNAL_IP.octets());
+                } else if buffer[1] == 2 && len == 12 {
+                    let internal_port = u16::from_be_bytes([buffer[4], buffer[5]]);
+                    response.extend_from_slice(&buffer[4..6]);
+                    response.extend_from_slice(&(internal_port + 10000).to_be_bytes());
+                    response.extend_from_slice(&buffer[8..12]);
+                } else {
+                    continue;
+                }
+                let _ = socket.send_to(&response, sender);
+            }
+        });
+        address
+    }
+}
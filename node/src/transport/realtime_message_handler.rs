@@ -3,9 +3,8 @@ use crate::{
     block_processing::{BlockProcessor, BlockSource},
     bootstrap::{BootstrapAscending, BootstrapServer},
     config::NodeConfig,
-    consensus::{RequestAggregator, VoteProcessorQueue},
+    consensus::{RequestAggregator, VoteApplier, VoteProcessorQueue},
     stats::{DetailType, Direction, StatType, Stats},
-    wallets::Wallets,
     Telemetry,
 };
 use rsban_core::VoteSource;
@@ -24,7 +23,7 @@ pub struct RealtimeMessageHandler {
     network_info: Arc<RwLock<NetworkInfo>>,
     block_processor: Arc<BlockProcessor>,
     config: NodeConfig,
-    wallets: Arc<Wallets>,
+    vote_applier: Arc<VoteApplier>,
     request_aggregator: Arc<RequestAggregator>,
     vote_processor_queue: Arc<VoteProcessorQueue>,
     telemetry: Arc<Telemetry>,
@@ -39,7 +38,7 @@ impl RealtimeMessageHandler {
         network_filter: Arc<NetworkFilter>,
         block_processor: Arc<BlockProcessor>,
         config: NodeConfig,
-        wallets: Arc<Wallets>,
+        vote_applier: Arc<VoteApplier>,
         request_aggregator: Arc<RequestAggregator>,
         vote_processor_queue: Arc<VoteProcessorQueue>,
         telemetry: Arc<Telemetry>,
@@ -52,7 +51,7 @@ impl RealtimeMessageHandler {
             network_filter,
             block_processor,
             config,
-            wallets,
+            vote_applier,
             request_aggregator,
             vote_processor_queue,
             telemetry,
@@ -107,7 +106,7 @@ impl RealtimeMessageHandler {
             Message::ConfirmReq(req) => {
                 // Don't load nodes with disabled voting
                 // TODO: This check should be cached somewhere
-                if self.config.enable_voting && self.wallets.voting_reps_count() > 0 {
+                if self.vote_applier.is_voting() {
                     self.request_aggregator
                         .request(req.roots_hashes, channel.channel_id());
                 }
@@ -1,6 +1,6 @@
 use super::{
-    HandshakeProcess, HandshakeStatus, InboundMessageQueue, LatestKeepalives, MessageDeserializer,
-    NetworkFilter, SynCookies,
+    HandshakeProcess, HandshakeStatus, InboundMessageQueue, LatestKeepalives, MessageCapture,
+    MessageDeserializer, NetworkFilter, SynCookies,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -17,7 +17,7 @@ use async_trait::async_trait;
 use rsban_core::{NodeId, PrivateKey};
 use rsban_ledger::Ledger;
 use rsban_messages::*;
-use rsban_network::{Channel, ChannelMode, ChannelReader, NetworkInfo};
+use rsban_network::{Channel, ChannelDirection, ChannelMode, ChannelReader, NetworkInfo};
 use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
 use std::{
     net::SocketAddrV6,
@@ -88,6 +88,7 @@ pub struct ResponseServer {
     bootstrap_initiator: Weak<BootstrapInitiator>,
     latest_keepalives: Arc<Mutex<LatestKeepalives>>,
     flags: NodeFlags,
+    pub message_capture: Option<Arc<MessageCapture>>,
 }
 
 static NEXT_UNIQUE_ID: AtomicUsize = AtomicUsize::new(0);
@@ -114,7 +115,7 @@ impl ResponseServer {
         let network_constants = network_params.network.clone();
         let remote_endpoint = channel.info.peer_addr();
         Self {
-            network_info,
+            network_info: network_info.clone(),
             inbound_queue,
             channel,
             disable_bootstrap_listener: false,
@@ -128,6 +129,7 @@ impl ResponseServer {
                 stats.clone(),
                 remote_endpoint,
                 network_constants.protocol_info(),
+                network_info,
             ),
             network_params,
             unique_id: NEXT_UNIQUE_ID.fetch_add(1, Ordering::Relaxed),
@@ -143,6 +145,7 @@ impl ResponseServer {
             bootstrap_initiator: Arc::downgrade(&bootstrap_initiator),
             flags,
             latest_keepalives,
+            message_capture: None,
         }
     }
 
@@ -299,6 +302,14 @@ impl ResponseServerExt for Arc<ResponseServer> {
 
             self.stats
                 .inc(StatType::TcpChannels, DetailType::ChannelAccepted);
+            self.stats.inc(
+                StatType::TcpChannels,
+                if self.channel.info.is_encrypted() {
+                    DetailType::ChannelEncrypted
+                } else {
+                    DetailType::ChannelPlaintext
+                },
+            );
 
             debug!(
                 "Switched to realtime mode (addr: {}, node_id: {})",
@@ -341,7 +352,24 @@ impl ResponseServerExt for Arc<ResponseServer> {
                 break;
             }
 
-            let result = match message_deserializer.read().await {
+            let read_result = message_deserializer.read().await;
+            if let Some(header) = message_deserializer.last_header() {
+                self.channel.info.add_message_in(
+                    header.message_type.as_str(),
+                    MessageHeader::SERIALIZED_SIZE + header.payload_length(),
+                );
+            }
+            if let Some(capture) = &self.message_capture {
+                if let Some(bytes) = message_deserializer.last_message_bytes() {
+                    capture.capture_raw(
+                        self.channel.channel_id(),
+                        bytes,
+                        ChannelDirection::Inbound,
+                    );
+                }
+            }
+
+            let result = match read_result {
                 Ok(msg) => {
                     if first_message {
                         // TODO: if version using changes => peer misbehaved!
@@ -567,6 +595,8 @@ impl ResponseServerExt for Arc<ResponseServer> {
                     self.ledger.clone(),
                     self.workers.clone(),
                     self.tokio.clone(),
+                    self.stats.clone(),
+                    self.network_info.read().unwrap().bootstrap_compression(),
                 );
                 self.workers.push_task(Box::new(move || {
                     bulk_pull_server.send_next();
@@ -1,6 +1,6 @@
 use super::{
-    InboundMessageQueue, LatestKeepalives, MessagePublisher, NetworkFilter, ResponseServer,
-    ResponseServerExt, SynCookies,
+    InboundMessageQueue, LatestKeepalives, MessageCapture, MessagePublisher, NetworkFilter,
+    ResponseServer, ResponseServerExt, SynCookies,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -33,6 +33,7 @@ pub struct NanoResponseServerSpawner {
     pub(crate) network_params: NetworkParams,
     pub(crate) syn_cookies: Arc<SynCookies>,
     pub(crate) latest_keepalives: Arc<Mutex<LatestKeepalives>>,
+    pub(crate) message_capture: Option<Arc<MessageCapture>>,
 }
 
 impl NanoResponseServerSpawner {
@@ -77,6 +78,7 @@ impl NanoResponseServerSpawner {
             syn_cookies: Arc::new(SynCookies::new(1)),
             latest_keepalives: Arc::new(Mutex::new(LatestKeepalives::default())),
             network_filter,
+            message_capture: None,
         }
     }
 
@@ -88,7 +90,7 @@ impl NanoResponseServerSpawner {
     }
 
     fn spawn_response_server(&self, channel: Arc<Channel>) -> Arc<ResponseServer> {
-        let server = Arc::new(ResponseServer::new(
+        let mut server = ResponseServer::new(
             self.network.clone(),
             self.inbound_queue.clone(),
             channel,
@@ -105,7 +107,9 @@ impl NanoResponseServerSpawner {
             self.bootstrap_initiator.clone(),
             self.node_flags.clone(),
             self.latest_keepalives.clone(),
-        ));
+        );
+        server.message_capture = self.message_capture.clone();
+        let server = Arc::new(server);
 
         let server_l = server.clone();
         self.tokio.spawn(async move { server_l.run().await });
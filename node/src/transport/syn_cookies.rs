@@ -30,6 +30,11 @@ impl SynCookies {
     /// Returns `None` if the IP is rate capped on syn cookie requests,
     /// or if the endpoint already has a syn cookie query
     pub fn assign(&self, endpoint: &SocketAddrV6) -> Option<Cookie> {
+        self.assign_cookie(endpoint, rand::thread_rng().gen::<Cookie>())
+    }
+
+    /// Like `assign`, but uses the given cookie instead of a random one
+    pub fn assign_cookie(&self, endpoint: &SocketAddrV6, cookie: Cookie) -> Option<Cookie> {
         let ip_addr = endpoint.ip();
         let mut lock = self.data.lock().unwrap();
 
@@ -40,7 +45,6 @@ impl SynCookies {
         let ip_cookies = lock.cookies_per_ip.entry(*ip_addr).or_default();
         if *ip_cookies < self.max_cookies_per_ip {
             *ip_cookies += 1;
-            let cookie = rand::thread_rng().gen::<Cookie>();
             lock.cookies.insert(
                 *endpoint,
                 SynCookieInfo {
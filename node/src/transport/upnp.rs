@@ -0,0 +1,368 @@
+use anyhow::{anyhow, bail, Context};
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, TcpStream, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+pub const SSDP_MULTICAST_ADDRESS: SocketAddrV4 =
+    SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900);
+
+const IGD_SERVICE_TYPES: [&str; 3] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An internet gateway device which was found via SSDP
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gateway {
+    pub control_url: String,
+    pub service_type: String,
+    /// Our address in the network of the gateway
+    pub local_ip: IpAddr,
+}
+
+impl Gateway {
+    pub fn external_ip(&self) -> anyhow::Result<IpAddr> {
+        let response = self.soap_request("GetExternalIPAddress", "")?;
+        let ip = xml_value(&response, "NewExternalIPAddress")
+            .ok_or_else(|| anyhow!("no external IP in gateway response"))?;
+        ip.trim().parse().context("invalid external IP")
+    }
+
+    pub fn add_port_mapping(
+        &self,
+        external_port: u16,
+        internal_port: u16,
+        lease_duration: Duration,
+        description: &str,
+    ) -> anyhow::Result<()> {
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost>\
+             <NewExternalPort>{}</NewExternalPort>\
+             <NewProtocol>TCP</NewProtocol>\
+             <NewInternalPort>{}</NewInternalPort>\
+             <NewInternalClient>{}</NewInternalClient>\
+             <NewEnabled>1</NewEnabled>\
+             <NewPortMappingDescription>{}</NewPortMappingDescription>\
+             <NewLeaseDuration>{}</NewLeaseDuration>",
+            external_port,
+            internal_port,
+            self.local_ip,
+            description,
+            lease_duration.as_secs()
+        );
+        self.soap_request("AddPortMapping", &args)?;
+        Ok(())
+    }
+
+    pub fn delete_port_mapping(&self, external_port: u16) -> anyhow::Result<()> {
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost>\
+             <NewExternalPort>{}</NewExternalPort>\
+             <NewProtocol>TCP</NewProtocol>",
+            external_port
+        );
+        self.soap_request("DeletePortMapping", &args)?;
+        Ok(())
+    }
+
+    fn soap_request(&self, action: &str, args: &str) -> anyhow::Result<String> {
+        let body = format!(
+            "<?xml version=\"1.0\"?>\r\n\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{service}\">{args}</u:{action}></s:Body>\
+             </s:Envelope>\r\n",
+            action = action,
+            service = self.service_type,
+            args = args
+        );
+        let headers = format!(
+            "Content-Type: text/xml; charset=\"utf-8\"\r\nSOAPAction: \"{}#{}\"\r\n",
+            self.service_type, action
+        );
+        let response = http_request("POST", &self.control_url, &headers, &body)?;
+        if response.status != 200 {
+            let error = xml_value(&response.body, "errorDescription")
+                .or_else(|| xml_value(&response.body, "errorCode"))
+                .unwrap_or_default();
+            bail!(
+                "{} failed with HTTP status {} {}",
+                action,
+                response.status,
+                error
+            );
+        }
+        Ok(response.body)
+    }
+}
+
+/// Searches an internet gateway device via SSDP. `search_address` is the SSDP
+/// multicast address in production
+pub fn discover_gateway(search_address: SocketAddr, timeout: Duration) -> anyhow::Result<Gateway> {
+    let socket = UdpSocket::bind(SocketAddr::new(unspecified_like(&search_address), 0))?;
+    let request = format!(
+        "M-SEARCH * HTTP/1.1\r\n\
+         HOST: {}\r\n\
+         MAN: \"ssdp:discover\"\r\n\
+         MX: 2\r\n\
+         ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\r\n",
+        search_address
+    );
+    socket.send_to(request.as_bytes(), search_address)?;
+
+    let deadline = Instant::now() + timeout;
+    let mut buffer = [0; 2048];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            bail!("no internet gateway device found");
+        }
+        socket.set_read_timeout(Some(remaining))?;
+        let len = match socket.recv_from(&mut buffer) {
+            Ok((len, _)) => len,
+            Err(_) => bail!("no internet gateway device found"),
+        };
+        let response = String::from_utf8_lossy(&buffer[..len]);
+        if let Some(location) = header_value(&response, "location") {
+            // Not every responder is a usable gateway, so keep searching on errors
+            if let Ok(gateway) = read_description(location) {
+                return Ok(gateway);
+            }
+        }
+    }
+}
+
+fn unspecified_like(address: &SocketAddr) -> IpAddr {
+    if address.ip().is_loopback() {
+        address.ip()
+    } else {
+        Ipv4Addr::UNSPECIFIED.into()
+    }
+}
+
+fn read_description(location: &str) -> anyhow::Result<Gateway> {
+    let response = http_request("GET", location, "", "")?;
+    if response.status != 200 {
+        bail!("could not read device description: {}", response.status);
+    }
+
+    let base_url = xml_value(&response.body, "URLBase")
+        .map(|url| url.trim().to_owned())
+        .unwrap_or_else(|| base_url(location).to_owned());
+
+    for service in xml_blocks(&response.body, "service") {
+        let Some(service_type) = xml_value(service, "serviceType") else {
+            continue;
+        };
+        let service_type = service_type.trim();
+        if !IGD_SERVICE_TYPES.contains(&service_type) {
+            continue;
+        }
+        let Some(control_url) = xml_value(service, "controlURL") else {
+            continue;
+        };
+        let control_url = control_url.trim();
+        let control_url = if control_url.starts_with("http://") {
+            control_url.to_owned()
+        } else {
+            format!(
+                "{}/{}",
+                base_url.trim_end_matches('/'),
+                control_url.trim_start_matches('/')
+            )
+        };
+        return Ok(Gateway {
+            control_url,
+            service_type: service_type.to_owned(),
+            local_ip: response.local_ip,
+        });
+    }
+    bail!("device has no WAN connection service")
+}
+
+struct HttpResponse {
+    status: u16,
+    body: String,
+    local_ip: IpAddr,
+}
+
+/// Minimal HTTP/1.1 client, which is good enough to talk to gateways
+fn http_request(
+    method: &str,
+    url: &str,
+    headers: &str,
+    body: &str,
+) -> anyhow::Result<HttpResponse> {
+    let without_scheme = url
+        .strip_prefix("http://")
+        .ok_or_else(|| anyhow!("unsupported URL: {}", url))?;
+    let (host, path) = match without_scheme.find('/') {
+        Some(i) => (&without_scheme[..i], &without_scheme[i..]),
+        None => (without_scheme, "/"),
+    };
+    let host_with_port = if host.contains(':') {
+        host.to_owned()
+    } else {
+        format!("{}:80", host)
+    };
+    let address = host_with_port
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow!("could not resolve {}", host))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, IO_TIMEOUT)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let local_ip = stream.local_addr()?.ip();
+
+    let request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n{}\r\n{}",
+        method,
+        path,
+        host,
+        body.len(),
+        headers,
+        body
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    let response = String::from_utf8_lossy(&response);
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| anyhow!("invalid HTTP response"))?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| anyhow!("invalid HTTP status line"))?;
+    let body = match header_value(head, "transfer-encoding") {
+        Some(encoding) if encoding.eq_ignore_ascii_case("chunked") => decode_chunked(body)?,
+        _ => body.to_owned(),
+    };
+
+    Ok(HttpResponse {
+        status,
+        body,
+        local_ip,
+    })
+}
+
+fn decode_chunked(mut input: &str) -> anyhow::Result<String> {
+    let mut result = String::new();
+    loop {
+        let (size_line, rest) = input
+            .split_once("\r\n")
+            .ok_or_else(|| anyhow!("invalid chunk"))?;
+        let size = usize::from_str_radix(size_line.split(';').next().unwrap().trim(), 16)?;
+        if size == 0 {
+            return Ok(result);
+        }
+        if rest.len() < size {
+            bail!("truncated chunk");
+        }
+        result.push_str(&rest[..size]);
+        input = rest[size..].trim_start_matches("\r\n");
+    }
+}
+
+fn header_value<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim().eq_ignore_ascii_case(name) {
+            Some(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
+fn base_url(url: &str) -> &str {
+    let after_scheme = url.find("://").map(|i| i + 3).unwrap_or(0);
+    match url[after_scheme..].find('/') {
+        Some(i) => &url[..after_scheme + i],
+        None => url,
+    }
+}
+
+/// Returns the text of the first element with the given name. Namespace prefixes are ignored
+fn xml_value<'a>(xml: &'a str, name: &'a str) -> Option<&'a str> {
+    xml_blocks(xml, name).next()
+}
+
+/// Returns the content of all elements with the given name
+fn xml_blocks<'a>(xml: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = xml;
+    std::iter::from_fn(move || loop {
+        let start = rest.find('<')?;
+        rest = &rest[start + 1..];
+        let end = rest.find('>')?;
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        let tag_name = tag.split_whitespace().next().unwrap_or_default();
+        let local_name = tag_name.rsplit(':').next().unwrap_or_default();
+        if local_name != name || tag.starts_with('/') || tag.ends_with('/') {
+            continue;
+        }
+        let closing = format!("</{}>", tag_name);
+        let content_end = rest.find(&closing)?;
+        let content = &rest[..content_end];
+        rest = &rest[content_end + closing.len()..];
+        return Some(content);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_xml_value() {
+        let xml = "<s:Body><u:Response><NewExternalIPAddress>1.2.3.4</NewExternalIPAddress></u:Response></s:Body>";
+        assert_eq!(xml_value(xml, "NewExternalIPAddress"), Some("1.2.3.4"));
+        assert!(xml_value(xml, "Response").is_some());
+        assert_eq!(xml_value(xml, "Missing"), None);
+    }
+
+    #[test]
+    fn parse_multiple_blocks() {
+        let xml = "<serviceList><service><serviceType>a</serviceType></service>\
+                   <service><serviceType>b</serviceType></service></serviceList>";
+        let types: Vec<_> = xml_blocks(xml, "service")
+            .filter_map(|s| xml_value(s, "serviceType"))
+            .collect();
+        assert_eq!(types, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn parse_header() {
+        let head = "HTTP/1.1 200 OK\r\nLOCATION: http://192.168.1.1:5000/desc.xml\r\nST: foo";
+        assert_eq!(
+            header_value(head, "location"),
+            Some("http://192.168.1.1:5000/desc.xml")
+        );
+    }
+
+    #[test]
+    fn get_base_url() {
+        assert_eq!(
+            base_url("http://192.168.1.1:5000/desc.xml"),
+            "http://192.168.1.1:5000"
+        );
+        assert_eq!(base_url("http://192.168.1.1"), "http://192.168.1.1");
+    }
+
+    #[test]
+    fn chunked_body() {
+        assert_eq!(
+            decode_chunked("5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n").unwrap(),
+            "hello world"
+        );
+    }
+}
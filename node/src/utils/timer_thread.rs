@@ -7,6 +7,7 @@ use std::{
     time::Duration,
 };
 
+use rsban_nullable_clock::SteadyClock;
 use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
 
 // Runs a task periodically in it's own thread
@@ -17,6 +18,7 @@ pub struct TimerThread<T: Runnable + 'static> {
     cancel_token: CancellationToken,
     run_immediately: bool,
     start_listener: OutputListenerMt<TimerStartEvent>,
+    clock: Option<Arc<SteadyClock>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -35,6 +37,7 @@ impl<T: Runnable> TimerThread<T> {
             cancel_token: CancellationToken::new(),
             run_immediately: false,
             start_listener: OutputListenerMt::new(),
+            clock: None,
         }
     }
 
@@ -46,9 +49,17 @@ impl<T: Runnable> TimerThread<T> {
             cancel_token: CancellationToken::new(),
             run_immediately: true,
             start_listener: OutputListenerMt::new(),
+            clock: None,
         }
     }
 
+    /// If the clock is nulled, the interval is measured on that clock instead
+    /// of the real time, so that the timer follows a virtual time
+    pub fn with_clock(mut self, clock: Arc<SteadyClock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
     pub fn is_running(&self) -> bool {
         self.thread.lock().unwrap().is_some()
     }
@@ -73,6 +84,7 @@ impl<T: Runnable> TimerThread<T> {
 
         let cancel_token = self.cancel_token.clone();
         let run_immediately = self.run_immediately;
+        let clock = self.clock.clone().filter(|c| c.is_nulled());
         let handle = std::thread::Builder::new()
             .name(self.thread_name.clone())
             .spawn(move || {
@@ -80,8 +92,13 @@ impl<T: Runnable> TimerThread<T> {
                     task.run(&cancel_token);
                 }
 
-                while !cancel_token.wait_for_cancellation(interval) {
-                    task.run(&cancel_token);
+                match clock {
+                    Some(clock) => run_on_clock(&mut task, &cancel_token, &clock, interval),
+                    None => {
+                        while !cancel_token.wait_for_cancellation(interval) {
+                            task.run(&cancel_token);
+                        }
+                    }
                 }
             })
             .unwrap();
@@ -104,6 +121,22 @@ impl<T: Runnable> Drop for TimerThread<T> {
     }
 }
 
+fn run_on_clock(
+    task: &mut impl Runnable,
+    cancel_token: &CancellationToken,
+    clock: &SteadyClock,
+    interval: Duration,
+) {
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+    let mut next_run = clock.now() + interval;
+    while !cancel_token.wait_for_cancellation(POLL_INTERVAL) {
+        if clock.now() >= next_run {
+            task.run(cancel_token);
+            next_run = clock.now() + interval;
+        }
+    }
+}
+
 pub trait Runnable: Send {
     fn run(&mut self, cancel_token: &CancellationToken);
 }
@@ -239,6 +272,7 @@ impl CancellationTokenStub {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::AtomicUsize;
 
     #[test]
     fn can_be_nulled() {
@@ -263,6 +297,33 @@ mod tests {
         assert_eq!(token.is_cancelled(), true);
     }
 
+    #[test]
+    fn run_on_nulled_clock() {
+        let clock = Arc::new(SteadyClock::new_null());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let timer = TimerThread::new("test", CountingTask(runs.clone())).with_clock(clock.clone());
+        timer.start(Duration::from_secs(60));
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        clock.advance(Duration::from_secs(60));
+        let start = std::time::Instant::now();
+        while runs.load(Ordering::SeqCst) == 0 && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        timer.stop();
+    }
+
+    struct CountingTask(Arc<AtomicUsize>);
+
+    impl Runnable for CountingTask {
+        fn run(&mut self, _cancel_token: &CancellationToken) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
     #[test]
     fn can_track_waits() {
         let token = CancellationToken::new_null();
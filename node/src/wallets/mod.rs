@@ -1,9 +1,17 @@
+mod receive_policy;
+mod send_id;
+mod spending_policy;
 mod wallet;
 mod wallet_action_thread;
+mod wallet_backup;
 mod wallet_representatives;
 mod wallets;
 
+pub use receive_policy::*;
+pub use send_id::*;
+pub use spending_policy::*;
 pub use wallet::*;
 pub use wallet_action_thread::*;
+pub use wallet_backup::*;
 pub use wallet_representatives::*;
 pub use wallets::*;
@@ -0,0 +1,469 @@
+use rsban_core::{Account, Amount, BlockHash, WalletId};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Controls which receivable blocks a wallet receives automatically
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceivePolicy {
+    /// Overrides the node wide `receive_minimum` for this wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receive_minimum: Option<Amount>,
+    #[serde(default = "default_auto_receive")]
+    pub auto_receive: bool,
+    /// If not empty, only sends from these accounts are received automatically
+    #[serde(default)]
+    pub allowed_senders: Vec<Account>,
+    #[serde(default)]
+    pub denied_senders: Vec<Account>,
+    /// Upper bound of the total amount that is received automatically within one hour
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_receive_per_hour: Option<Amount>,
+}
+
+fn default_auto_receive() -> bool {
+    true
+}
+
+impl Default for ReceivePolicy {
+    fn default() -> Self {
+        Self {
+            receive_minimum: None,
+            auto_receive: true,
+            allowed_senders: Vec::new(),
+            denied_senders: Vec::new(),
+            max_receive_per_hour: None,
+        }
+    }
+}
+
+impl ReceivePolicy {
+    pub fn receive_minimum_or(&self, default_minimum: Amount) -> Amount {
+        self.receive_minimum.unwrap_or(default_minimum)
+    }
+
+    fn sender_allowed(&self, sender: &Account) -> bool {
+        if self.denied_senders.contains(sender) {
+            return false;
+        }
+        self.allowed_senders.is_empty() || self.allowed_senders.contains(sender)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiveDecision {
+    Receive,
+    Disabled,
+    BelowMinimum,
+    SenderNotAllowed,
+    HourlyLimitReached,
+}
+
+impl ReceiveDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReceiveDecision::Receive => "receive",
+            ReceiveDecision::Disabled => "auto receive disabled",
+            ReceiveDecision::BelowMinimum => "below receive minimum",
+            ReceiveDecision::SenderNotAllowed => "sender not allowed",
+            ReceiveDecision::HourlyLimitReached => "hourly receive limit reached",
+        }
+    }
+}
+
+/// The receive policies of all wallets and the amounts which were
+/// received automatically during the last hour, including receives which are still queued.
+/// The amounts are only tracked for wallets with an hourly limit
+#[derive(Default)]
+pub struct ReceivePolicies {
+    policies: HashMap<WalletId, ReceivePolicy>,
+    received: HashMap<WalletId, VecDeque<(Instant, Amount)>>,
+}
+
+impl ReceivePolicies {
+    const RATE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+    pub fn get(&self, wallet_id: &WalletId) -> ReceivePolicy {
+        self.policies.get(wallet_id).cloned().unwrap_or_default()
+    }
+
+    pub fn insert(&mut self, wallet_id: WalletId, policy: ReceivePolicy) {
+        if policy.max_receive_per_hour.is_none() {
+            self.received.remove(&wallet_id);
+        }
+        if policy == ReceivePolicy::default() {
+            self.policies.remove(&wallet_id);
+        } else {
+            self.policies.insert(wallet_id, policy);
+        }
+    }
+
+    pub fn remove(&mut self, wallet_id: &WalletId) {
+        self.policies.remove(wallet_id);
+        self.received.remove(wallet_id);
+    }
+
+    /// Total amount received automatically by the wallet within the last hour.
+    /// Receives which are still queued are included
+    pub fn received_last_hour(&mut self, wallet_id: &WalletId, now: Instant) -> Amount {
+        match self.received.get_mut(wallet_id) {
+            Some(received) => {
+                Self::purge_old(received, now);
+                received
+                    .iter()
+                    .fold(Amount::zero(), |sum, (_, amount)| sum.wrapping_add(*amount))
+            }
+            None => Amount::zero(),
+        }
+    }
+
+    /// Gives back the amount which `auto_receive` reserved for a receive that failed
+    pub fn release_receive(&mut self, wallet_id: &WalletId, amount: Amount) {
+        if let Some(received) = self.received.get_mut(wallet_id) {
+            if let Some(index) = received.iter().rposition(|(_, a)| *a == amount) {
+                received.remove(index);
+            }
+        }
+    }
+
+    /// Decides whether a receivable block should be received automatically. If so and the
+    /// wallet has an hourly limit, the amount is reserved in the limit right away, so that
+    /// receives which are queued at the same time can't exceed the limit together
+    pub fn auto_receive(
+        &mut self,
+        wallet_id: &WalletId,
+        sender: &Account,
+        amount: Amount,
+        default_minimum: Amount,
+        now: Instant,
+    ) -> ReceiveDecision {
+        self.purge_expired(now);
+        let decision = self.decide(wallet_id, sender, amount, default_minimum, now);
+        let has_hourly_limit = self
+            .policies
+            .get(wallet_id)
+            .is_some_and(|p| p.max_receive_per_hour.is_some());
+        if decision == ReceiveDecision::Receive && has_hourly_limit {
+            self.received
+                .entry(*wallet_id)
+                .or_default()
+                .push_back((now, amount));
+        }
+        decision
+    }
+
+    /// Drops the reservations which are older than one hour
+    fn purge_expired(&mut self, now: Instant) {
+        self.received.retain(|_, received| {
+            Self::purge_old(received, now);
+            !received.is_empty()
+        });
+    }
+
+    fn decide(
+        &mut self,
+        wallet_id: &WalletId,
+        sender: &Account,
+        amount: Amount,
+        default_minimum: Amount,
+        now: Instant,
+    ) -> ReceiveDecision {
+        let Some(policy) = self.policies.get(wallet_id).cloned() else {
+            return if amount < default_minimum {
+                ReceiveDecision::BelowMinimum
+            } else {
+                ReceiveDecision::Receive
+            };
+        };
+
+        if !policy.auto_receive {
+            return ReceiveDecision::Disabled;
+        }
+        if amount < policy.receive_minimum_or(default_minimum) {
+            return ReceiveDecision::BelowMinimum;
+        }
+        if !policy.sender_allowed(sender) {
+            return ReceiveDecision::SenderNotAllowed;
+        }
+        if let Some(max) = policy.max_receive_per_hour {
+            let received = self.received_last_hour(wallet_id, now);
+            if amount > max || received > max - amount {
+                return ReceiveDecision::HourlyLimitReached;
+            }
+        }
+        ReceiveDecision::Receive
+    }
+
+    fn purge_old(received: &mut VecDeque<(Instant, Amount)>, now: Instant) {
+        while let Some((time, _)) = received.front() {
+            if now.duration_since(*time) < Self::RATE_WINDOW {
+                break;
+            }
+            received.pop_front();
+        }
+    }
+}
+
+/// A confirmed send to a watch-only account of a wallet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchReceive {
+    pub wallet_id: WalletId,
+    pub account: Account,
+    pub hash: BlockHash,
+    pub source: Account,
+    pub amount: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMUM: Amount = Amount::raw(100);
+
+    #[test]
+    fn default_policy_uses_node_minimum() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        let now = Instant::now();
+        assert_eq!(
+            policies.auto_receive(&wallet, &Account::from(2), Amount::raw(99), MINIMUM, now),
+            ReceiveDecision::BelowMinimum
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &Account::from(2), MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+    }
+
+    #[test]
+    fn wallet_minimum_overrides_node_minimum() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                receive_minimum: Some(Amount::raw(1)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            policies.auto_receive(
+                &wallet,
+                &Account::from(2),
+                Amount::raw(1),
+                MINIMUM,
+                Instant::now()
+            ),
+            ReceiveDecision::Receive
+        );
+    }
+
+    #[test]
+    fn auto_receive_disabled() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                auto_receive: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &Account::from(2), MINIMUM, MINIMUM, Instant::now()),
+            ReceiveDecision::Disabled
+        );
+    }
+
+    #[test]
+    fn allowed_and_denied_senders() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                allowed_senders: vec![Account::from(2), Account::from(3)],
+                denied_senders: vec![Account::from(3)],
+                ..Default::default()
+            },
+        );
+        let now = Instant::now();
+        assert_eq!(
+            policies.auto_receive(&wallet, &Account::from(2), MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &Account::from(3), MINIMUM, MINIMUM, now),
+            ReceiveDecision::SenderNotAllowed
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &Account::from(4), MINIMUM, MINIMUM, now),
+            ReceiveDecision::SenderNotAllowed
+        );
+    }
+
+    #[test]
+    fn hourly_limit() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                max_receive_per_hour: Some(Amount::raw(250)),
+                ..Default::default()
+            },
+        );
+        let sender = Account::from(2);
+        let now = Instant::now();
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+        assert_eq!(policies.received_last_hour(&wallet, now), MINIMUM);
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::HourlyLimitReached
+        );
+        assert_eq!(policies.received_last_hour(&wallet, now), Amount::raw(200));
+
+        let later = now + Duration::from_secs(60 * 60);
+        assert_eq!(policies.received_last_hour(&wallet, later), Amount::zero());
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, later),
+            ReceiveDecision::Receive
+        );
+    }
+
+    #[test]
+    fn queued_receives_count_towards_hourly_limit() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                max_receive_per_hour: Some(Amount::raw(250)),
+                ..Default::default()
+            },
+        );
+        let sender = Account::from(2);
+        let now = Instant::now();
+        let decisions: Vec<_> = (0..3)
+            .map(|_| policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now))
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![
+                ReceiveDecision::Receive,
+                ReceiveDecision::Receive,
+                ReceiveDecision::HourlyLimitReached
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_receive_releases_reservation() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                max_receive_per_hour: Some(Amount::raw(150)),
+                ..Default::default()
+            },
+        );
+        let sender = Account::from(2);
+        let now = Instant::now();
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::HourlyLimitReached
+        );
+        policies.release_receive(&wallet, MINIMUM);
+        assert_eq!(policies.received_last_hour(&wallet, now), Amount::zero());
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+    }
+
+    #[test]
+    fn no_reservations_without_hourly_limit() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        let sender = Account::from(2);
+        let now = Instant::now();
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                receive_minimum: Some(Amount::raw(1)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now),
+            ReceiveDecision::Receive
+        );
+        assert!(policies.received.is_empty());
+    }
+
+    #[test]
+    fn expired_reservations_are_dropped() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                max_receive_per_hour: Some(Amount::raw(250)),
+                ..Default::default()
+            },
+        );
+        let sender = Account::from(2);
+        let now = Instant::now();
+        policies.auto_receive(&wallet, &sender, MINIMUM, MINIMUM, now);
+        assert_eq!(policies.received.len(), 1);
+
+        let later = now + Duration::from_secs(60 * 60);
+        policies.auto_receive(&WalletId::from(3), &sender, MINIMUM, MINIMUM, later);
+        assert!(policies.received.is_empty());
+    }
+
+    #[test]
+    fn inserting_default_policy_removes_it() {
+        let mut policies = ReceivePolicies::default();
+        let wallet = WalletId::from(1);
+        policies.insert(
+            wallet,
+            ReceivePolicy {
+                auto_receive: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(policies.policies.len(), 1);
+        policies.insert(wallet, ReceivePolicy::default());
+        assert_eq!(policies.policies.len(), 0);
+    }
+
+    #[test]
+    fn serialize_default_policy() {
+        let json = serde_json::to_string(&ReceivePolicy::default()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"auto_receive":true,"allowed_senders":[],"denied_senders":[]}"#
+        );
+        let policy: ReceivePolicy = serde_json::from_str("{}").unwrap();
+        assert_eq!(policy, ReceivePolicy::default());
+    }
+}
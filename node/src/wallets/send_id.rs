@@ -0,0 +1,170 @@
+use rsban_core::{BlockHash, WalletId};
+
+/// What is stored for each send id in the `send_action_ids` database
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendIdRecord {
+    pub wallet_id: WalletId,
+    pub hash: BlockHash,
+    /// Seconds since epoch at which the send id was registered
+    pub created: u64,
+    /// The block was refused by the block processor or its work couldn't be generated
+    pub rejected: bool,
+}
+
+impl SendIdRecord {
+    /// How long a block which isn't in the ledger yet is considered to be still in process.
+    /// It may wait in the block processor queue or for a dependency
+    pub const PENDING_GRACE_PERIOD_SECS: u64 = 10 * 60;
+    const SERIALIZED_SIZE: usize = 32 + 32 + 8 + 1;
+    /// Records written before the rejected flag existed
+    const SERIALIZED_SIZE_WITHOUT_REJECTED: usize = 32 + 32 + 8;
+
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut buffer = [0; Self::SERIALIZED_SIZE];
+        buffer[..32].copy_from_slice(self.hash.as_bytes());
+        buffer[32..64].copy_from_slice(self.wallet_id.as_bytes());
+        buffer[64..72].copy_from_slice(&self.created.to_be_bytes());
+        buffer[72] = self.rejected as u8;
+        buffer
+    }
+
+    /// Status of a send id whose block is not in the ledger
+    pub fn status_without_block(&self, now: u64) -> SendIdStatus {
+        if !self.rejected && now < self.created.saturating_add(Self::PENDING_GRACE_PERIOD_SECS) {
+            SendIdStatus::Pending
+        } else {
+            SendIdStatus::Failed
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() == 32 {
+            // Entries written by older node versions only contain the block hash
+            return Some(Self {
+                wallet_id: WalletId::zero(),
+                hash: BlockHash::from_slice(bytes)?,
+                created: 0,
+                rejected: false,
+            });
+        }
+
+        if bytes.len() != Self::SERIALIZED_SIZE
+            && bytes.len() != Self::SERIALIZED_SIZE_WITHOUT_REJECTED
+        {
+            return None;
+        }
+
+        Some(Self {
+            hash: BlockHash::from_slice(&bytes[..32])?,
+            wallet_id: WalletId::from_slice(&bytes[32..64])?,
+            created: u64::from_be_bytes(bytes[64..72].try_into().ok()?),
+            rejected: bytes.get(72).is_some_and(|b| *b != 0),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SendIdStatus {
+    /// The block isn't in the ledger yet, but it may still be added. The send id
+    /// can't be used for another block
+    Pending,
+    /// The block is in the ledger but not confirmed yet
+    Published,
+    Confirmed,
+    /// The block was rejected or didn't make it into the ledger within the grace period.
+    /// The send id can be used again
+    Failed,
+}
+
+impl SendIdStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SendIdStatus::Pending => "pending",
+            SendIdStatus::Published => "published",
+            SendIdStatus::Confirmed => "confirmed",
+            SendIdStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendIdInfo {
+    pub id: String,
+    pub record: SendIdRecord,
+    pub status: SendIdStatus,
+    /// Local timestamp of the send block, if it is in the ledger
+    pub local_timestamp: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let record = SendIdRecord {
+            wallet_id: WalletId::from(1),
+            hash: BlockHash::from(2),
+            created: 3,
+            rejected: true,
+        };
+        let bytes = record.to_bytes();
+        assert_eq!(SendIdRecord::from_bytes(&bytes), Some(record));
+    }
+
+    #[test]
+    fn deserialize_entry_without_rejected_flag() {
+        let record = SendIdRecord {
+            wallet_id: WalletId::from(1),
+            hash: BlockHash::from(2),
+            created: 3,
+            rejected: false,
+        };
+        let bytes = record.to_bytes();
+        assert_eq!(SendIdRecord::from_bytes(&bytes[..72]), Some(record));
+    }
+
+    #[test]
+    fn pending_within_grace_period() {
+        let record = SendIdRecord {
+            wallet_id: WalletId::from(1),
+            hash: BlockHash::from(2),
+            created: 1000,
+            rejected: false,
+        };
+        let end_of_grace = 1000 + SendIdRecord::PENDING_GRACE_PERIOD_SECS;
+        assert_eq!(
+            record.status_without_block(end_of_grace - 1),
+            SendIdStatus::Pending
+        );
+        assert_eq!(
+            record.status_without_block(end_of_grace),
+            SendIdStatus::Failed
+        );
+    }
+
+    #[test]
+    fn rejected_block_fails_immediately() {
+        let record = SendIdRecord {
+            wallet_id: WalletId::from(1),
+            hash: BlockHash::from(2),
+            created: 1000,
+            rejected: true,
+        };
+        assert_eq!(record.status_without_block(1000), SendIdStatus::Failed);
+    }
+
+    #[test]
+    fn deserialize_legacy_entry() {
+        let hash = BlockHash::from(42);
+        let record = SendIdRecord::from_bytes(hash.as_bytes()).unwrap();
+        assert_eq!(record.hash, hash);
+        assert_eq!(record.wallet_id, WalletId::zero());
+        assert_eq!(record.created, 0);
+    }
+
+    #[test]
+    fn deserialize_invalid_length() {
+        assert_eq!(SendIdRecord::from_bytes(&[1, 2, 3]), None);
+    }
+}
@@ -0,0 +1,282 @@
+use rand::{thread_rng, Rng};
+use rsban_core::{Account, Amount, BlockHash, KeyDerivationFunction, RawKey, WalletId};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// Length of the rolling window of the daily spending limit
+pub const SPENDING_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendingLimit {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_transaction: Option<Amount>,
+    /// Maximum total amount sent within any 24 hours
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<Amount>,
+}
+
+impl SpendingLimit {
+    pub fn is_unlimited(&self) -> bool {
+        self.per_transaction.is_none() && self.daily.is_none()
+    }
+
+    fn check(&self, amount: Amount, spent_last_day: Amount) -> Result<(), SpendingLimitExceeded> {
+        if let Some(max) = self.per_transaction {
+            if amount > max {
+                return Err(SpendingLimitExceeded::PerTransaction);
+            }
+        }
+        if let Some(max) = self.daily {
+            if amount > max || spent_last_day > max - amount {
+                return Err(SpendingLimitExceeded::Daily);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpendingLimitExceeded {
+    PerTransaction,
+    Daily,
+}
+
+impl SpendingLimitExceeded {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpendingLimitExceeded::PerTransaction => "Amount exceeds the per transaction limit",
+            SpendingLimitExceeded::Daily => "Amount exceeds the daily spending limit",
+        }
+    }
+}
+
+impl fmt::Display for SpendingLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::error::Error for SpendingLimitExceeded {}
+
+/// The approval password is stored as salted argon2 hash
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalKey {
+    salt: RawKey,
+    hash: RawKey,
+}
+
+impl ApprovalKey {
+    pub fn new(kdf: &KeyDerivationFunction, password: &str) -> Self {
+        let salt = RawKey::from_bytes(thread_rng().gen());
+        Self {
+            salt,
+            hash: kdf.hash_password(password, salt.as_bytes()),
+        }
+    }
+
+    pub fn verify(&self, kdf: &KeyDerivationFunction, password: &str) -> bool {
+        kdf.hash_password(password, self.salt.as_bytes()) == self.hash
+    }
+}
+
+/// Spending limits of a wallet and of its accounts
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendingPolicy {
+    /// Applies to the sum of all accounts of the wallet
+    #[serde(default)]
+    pub wallet: SpendingLimit,
+    #[serde(default)]
+    pub accounts: BTreeMap<Account, SpendingLimit>,
+    /// If set, sends are queued until they are approved with the approval password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalKey>,
+}
+
+impl SpendingPolicy {
+    pub fn account_limit(&self, account: &Account) -> SpendingLimit {
+        self.accounts.get(account).copied().unwrap_or_default()
+    }
+
+    pub fn set_limit(&mut self, account: Option<Account>, limit: SpendingLimit) {
+        match account {
+            Some(account) if limit.is_unlimited() => {
+                self.accounts.remove(&account);
+            }
+            Some(account) => {
+                self.accounts.insert(account, limit);
+            }
+            None => self.wallet = limit,
+        }
+    }
+
+    pub fn requires_approval(&self) -> bool {
+        self.approval.is_some()
+    }
+
+    /// `spent_by_account` and `spent_by_wallet` are the amounts sent within the last 24 hours
+    pub fn check(
+        &self,
+        source: &Account,
+        amount: Amount,
+        spent_by_account: Amount,
+        spent_by_wallet: Amount,
+    ) -> Result<(), SpendingLimitExceeded> {
+        self.account_limit(source).check(amount, spent_by_account)?;
+        self.wallet.check(amount, spent_by_wallet)
+    }
+}
+
+/// A send which waits for approval
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSend {
+    pub id: String,
+    pub wallet_id: WalletId,
+    pub source: Account,
+    pub destination: Account,
+    pub amount: Amount,
+    pub work: u64,
+    pub send_id: Option<String>,
+    /// Seconds since epoch
+    pub created: u64,
+}
+
+/// Outcome of a send request of a wallet which requires send approval
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SendRequest {
+    /// The send waits for approval
+    Pending(PendingSend),
+    /// The send id was already used for this block
+    Sent(BlockHash),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_by_default() {
+        let policy = SpendingPolicy::default();
+        assert_eq!(
+            policy.check(&Account::from(1), Amount::MAX, Amount::MAX, Amount::MAX),
+            Ok(())
+        );
+        assert!(!policy.requires_approval());
+    }
+
+    #[test]
+    fn per_transaction_limit() {
+        let mut policy = SpendingPolicy::default();
+        policy.set_limit(
+            None,
+            SpendingLimit {
+                per_transaction: Some(Amount::raw(100)),
+                daily: None,
+            },
+        );
+        let account = Account::from(1);
+        assert_eq!(
+            policy.check(&account, Amount::raw(100), Amount::zero(), Amount::zero()),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&account, Amount::raw(101), Amount::zero(), Amount::zero()),
+            Err(SpendingLimitExceeded::PerTransaction)
+        );
+    }
+
+    #[test]
+    fn daily_limit() {
+        let mut policy = SpendingPolicy::default();
+        let account = Account::from(1);
+        policy.set_limit(
+            Some(account),
+            SpendingLimit {
+                per_transaction: None,
+                daily: Some(Amount::raw(100)),
+            },
+        );
+        assert_eq!(
+            policy.check(&account, Amount::raw(40), Amount::raw(60), Amount::raw(60)),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&account, Amount::raw(41), Amount::raw(60), Amount::raw(60)),
+            Err(SpendingLimitExceeded::Daily)
+        );
+        // Other accounts of the wallet aren't affected
+        assert_eq!(
+            policy.check(
+                &Account::from(2),
+                Amount::raw(41),
+                Amount::raw(60),
+                Amount::raw(60)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn wallet_limit_applies_to_all_accounts() {
+        let mut policy = SpendingPolicy::default();
+        policy.set_limit(
+            None,
+            SpendingLimit {
+                per_transaction: None,
+                daily: Some(Amount::raw(100)),
+            },
+        );
+        assert_eq!(
+            policy.check(
+                &Account::from(2),
+                Amount::raw(50),
+                Amount::zero(),
+                Amount::raw(60)
+            ),
+            Err(SpendingLimitExceeded::Daily)
+        );
+    }
+
+    #[test]
+    fn removing_account_limit() {
+        let mut policy = SpendingPolicy::default();
+        let account = Account::from(1);
+        policy.set_limit(
+            Some(account),
+            SpendingLimit {
+                per_transaction: Some(Amount::raw(1)),
+                daily: None,
+            },
+        );
+        policy.set_limit(Some(account), SpendingLimit::default());
+        assert!(policy.accounts.is_empty());
+    }
+
+    #[test]
+    fn approval_key() {
+        let kdf = KeyDerivationFunction::new(8);
+        let key = ApprovalKey::new(&kdf, "second factor");
+        assert!(key.verify(&kdf, "second factor"));
+        assert!(!key.verify(&kdf, "wrong"));
+    }
+
+    #[test]
+    fn serialize_policy() {
+        let mut policy = SpendingPolicy::default();
+        policy.set_limit(
+            Some(Account::zero()),
+            SpendingLimit {
+                per_transaction: Some(Amount::raw(1)),
+                daily: None,
+            },
+        );
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(
+            json,
+            r#"{"wallet":{},"accounts":{"ban_1111111111111111111111111111111111111111111111111111hifc8npp":{"per_transaction":"1"}}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<SpendingPolicy>(&json).unwrap(),
+            policy
+        );
+    }
+}
@@ -1,15 +1,18 @@
 use anyhow::Context;
-use rsban_core::{work::WorkThresholds, KeyDerivationFunction, PrivateKey, PublicKey, Root};
+use rsban_core::{
+    work::WorkThresholds, KeyDerivationFunction, PrivateKey, PublicKey, Root, WalletId,
+};
 use rsban_ledger::Ledger;
 use rsban_store_lmdb::{LmdbWalletStore, LmdbWriteTransaction, Transaction};
 use std::{
     collections::HashSet,
-    path::Path,
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 use tracing::warn;
 
 pub struct Wallet {
+    pub id: WalletId,
     pub representatives: Mutex<HashSet<PublicKey>>,
     pub store: Arc<LmdbWalletStore>,
     ledger: Arc<Ledger>,
@@ -18,18 +21,20 @@ pub struct Wallet {
 
 impl Wallet {
     pub fn new(
+        id: WalletId,
         ledger: Arc<Ledger>,
         work_thresholds: WorkThresholds,
         txn: &mut LmdbWriteTransaction,
         fanout: usize,
         kdf: KeyDerivationFunction,
         representative: PublicKey,
-        wallet_path: &Path,
     ) -> anyhow::Result<Self> {
+        let wallet_path = PathBuf::from(id.encode_hex());
         let store = LmdbWalletStore::new(fanout, kdf, txn, &representative, &wallet_path)
             .context("could not create wallet store")?;
 
         Ok(Self {
+            id,
             representatives: Mutex::new(HashSet::new()),
             store: Arc::new(store),
             ledger,
@@ -38,18 +43,20 @@ impl Wallet {
     }
 
     pub fn new_from_json(
+        id: WalletId,
         ledger: Arc<Ledger>,
         work_thresholds: WorkThresholds,
         txn: &mut LmdbWriteTransaction,
         fanout: usize,
         kdf: KeyDerivationFunction,
-        wallet_path: &Path,
         json: &str,
     ) -> anyhow::Result<Self> {
+        let wallet_path = PathBuf::from(id.encode_hex());
         let store = LmdbWalletStore::new_from_json(fanout, kdf, txn, &wallet_path, json)
             .context("could not create wallet store")?;
 
         Ok(Self {
+            id,
             representatives: Mutex::new(HashSet::new()),
             store: Arc::new(store),
             ledger,
@@ -0,0 +1,263 @@
+use aes::Aes256;
+use anyhow::{anyhow, bail};
+use blake2::{
+    digest::{consts::U32, Mac},
+    Blake2bMac,
+};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use rand::{thread_rng, Rng};
+use rsban_core::{Account, DerivationScheme, KeyDerivationFunction, PublicKey, RawKey, Slip10Key};
+use rsban_store_lmdb::{KeyType, LmdbWalletStore, LmdbWriteTransaction, Transaction};
+use serde::{Deserialize, Serialize};
+
+type Aes256Ctr = ctr::Ctr64BE<Aes256>;
+type Blake2bMac256 = Blake2bMac<U32>;
+
+/// Everything that is needed to recreate a wallet
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletBackup {
+    /// The legacy seed, or the root key at m/44'/198' of BIP44 wallets
+    pub seed: RawKey,
+    pub derivation: DerivationScheme,
+    /// Only present for BIP44 wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_code: Option<RawKey>,
+    pub deterministic_index: u32,
+    /// Indices of the deterministic accounts which are in the wallet
+    pub deterministic_accounts: Vec<u32>,
+    pub representative: PublicKey,
+    pub adhoc_keys: Vec<RawKey>,
+    pub watch_accounts: Vec<Account>,
+}
+
+impl WalletBackup {
+    /// Magic bytes at the start of every backup file
+    pub const MAGIC: [u8; 8] = *b"BANWBKUP";
+    pub const VERSION: u8 = 1;
+    /// Kdf work of every backup that is written and the highest kdf work that is accepted
+    /// in a backup file. It is the kdf work of the live network (64 MiB of argon2 memory),
+    /// so backups are as hard to brute force as the live wallets, and a crafted file can't
+    /// stall the node
+    pub const MAX_KDF_WORK: u32 = 64 * 1024;
+    /// Lowest memory cost argon2 accepts
+    const MIN_KDF_WORK: u32 = 8;
+    const HEADER_SIZE: usize = 8 + 1 + 4 + 32 + 16;
+    const MAC_SIZE: usize = 32;
+
+    /// Reads the backup content from an unlocked wallet store
+    pub fn from_store(store: &LmdbWalletStore, txn: &dyn Transaction) -> anyhow::Result<Self> {
+        if !store.valid_password(txn) {
+            bail!("wallet is locked");
+        }
+
+        let derivation = store.derivation_scheme(txn);
+        let (seed, chain_code) = match derivation {
+            DerivationScheme::Legacy => (store.seed(txn), None),
+            DerivationScheme::Bip44 => {
+                let root = store.bip44_root(txn);
+                (root.key, Some(root.chain_code))
+            }
+        };
+        let mut backup = Self {
+            seed,
+            derivation,
+            chain_code,
+            deterministic_index: store.deterministic_index_get(txn),
+            deterministic_accounts: Vec::new(),
+            representative: store.representative(txn),
+            adhoc_keys: Vec::new(),
+            watch_accounts: Vec::new(),
+        };
+
+        let mut it = store.begin(txn);
+        while let Some((pub_key, value)) = it.current() {
+            match LmdbWalletStore::key_type(value) {
+                KeyType::Deterministic => backup
+                    .deterministic_accounts
+                    .push(value.key.number().low_u32()),
+                KeyType::Adhoc => backup.adhoc_keys.push(store.fetch(txn, pub_key)?),
+                _ if value.key.is_zero() => backup.watch_accounts.push((*pub_key).into()),
+                _ => bail!(
+                    "unknown key type for {}",
+                    Account::from(pub_key).encode_account()
+                ),
+            }
+            it.next();
+        }
+
+        Ok(backup)
+    }
+
+    /// Writes the backup content into an unlocked and empty wallet store
+    pub fn restore(
+        &self,
+        store: &LmdbWalletStore,
+        txn: &mut LmdbWriteTransaction,
+    ) -> anyhow::Result<()> {
+        match (self.derivation, self.chain_code) {
+            (DerivationScheme::Bip44, Some(chain_code)) => store.set_bip44_seed(
+                txn,
+                &Slip10Key {
+                    key: self.seed,
+                    chain_code,
+                },
+            ),
+            (DerivationScheme::Bip44, None) => bail!("BIP44 wallet backup without chain code"),
+            (DerivationScheme::Legacy, _) => store.set_seed(txn, &self.seed),
+        }
+
+        for index in &self.deterministic_accounts {
+            store.deterministic_insert_at(txn, *index);
+        }
+        store.deterministic_index_set(txn, self.deterministic_index);
+
+        for key in &self.adhoc_keys {
+            store.insert_adhoc(txn, key);
+        }
+        for account in &self.watch_accounts {
+            // Watch accounts have been validated when they were inserted into the source wallet
+            let _ = store.insert_watch(txn, &account.into());
+        }
+
+        store.representative_set(txn, &self.representative);
+        Ok(())
+    }
+
+    /// Serializes and encrypts the backup.
+    ///
+    /// File format:
+    /// magic (8) | version (1) | kdf work (4) | salt (32) | iv (16) | ciphertext | mac (32)
+    ///
+    /// The ciphertext is the JSON encoded backup, encrypted with AES-256-CTR. The MAC is
+    /// a keyed Blake2b over everything that precedes it.
+    pub fn encrypt(&self, password: &str) -> Vec<u8> {
+        self.encrypt_with_kdf_work(password, Self::MAX_KDF_WORK)
+    }
+
+    fn encrypt_with_kdf_work(&self, password: &str, kdf_work: u32) -> Vec<u8> {
+        let salt: [u8; 32] = thread_rng().gen();
+        let iv: [u8; 16] = thread_rng().gen();
+        let (encryption_key, mac_key) = Self::derive_keys(password, &salt, kdf_work);
+
+        let mut result = Vec::with_capacity(Self::HEADER_SIZE + 1024);
+        result.extend_from_slice(&Self::MAGIC);
+        result.push(Self::VERSION);
+        result.extend_from_slice(&kdf_work.to_be_bytes());
+        result.extend_from_slice(&salt);
+        result.extend_from_slice(&iv);
+
+        let mut payload = serde_json::to_vec(self).unwrap();
+        Aes256Ctr::new(&encryption_key.into(), &iv.into()).apply_keystream(&mut payload);
+        result.extend_from_slice(&payload);
+
+        let mut mac = Blake2bMac256::new_from_slice(&mac_key).unwrap();
+        mac.update(&result);
+        result.extend_from_slice(&mac.finalize().into_bytes());
+        result
+    }
+
+    pub fn decrypt(bytes: &[u8], password: &str) -> anyhow::Result<Self> {
+        if bytes.len() < Self::HEADER_SIZE + Self::MAC_SIZE || bytes[..8] != Self::MAGIC {
+            bail!("not a wallet backup");
+        }
+
+        let version = bytes[8];
+        if version != Self::VERSION {
+            bail!("unsupported wallet backup version {}", version);
+        }
+
+        let kdf_work = u32::from_be_bytes(bytes[9..13].try_into().unwrap());
+        if !(Self::MIN_KDF_WORK..=Self::MAX_KDF_WORK).contains(&kdf_work) {
+            bail!("invalid kdf work {} in wallet backup", kdf_work);
+        }
+        let salt: [u8; 32] = bytes[13..45].try_into().unwrap();
+        let iv: [u8; 16] = bytes[45..61].try_into().unwrap();
+        let (encryption_key, mac_key) = Self::derive_keys(password, &salt, kdf_work);
+
+        let (content, tag) = bytes.split_at(bytes.len() - Self::MAC_SIZE);
+        let mut mac = Blake2bMac256::new_from_slice(&mac_key).unwrap();
+        mac.update(content);
+        mac.verify_slice(tag)
+            .map_err(|_| anyhow!("invalid password or corrupted wallet backup"))?;
+
+        let mut payload = content[Self::HEADER_SIZE..].to_vec();
+        Aes256Ctr::new(&encryption_key.into(), &iv.into()).apply_keystream(&mut payload);
+        Ok(serde_json::from_slice(&payload)?)
+    }
+
+    fn derive_keys(password: &str, salt: &[u8; 32], kdf_work: u32) -> ([u8; 32], [u8; 32]) {
+        let master = KeyDerivationFunction::new(kdf_work).hash_password(password, salt);
+        let derive = |purpose: &[u8]| -> [u8; 32] {
+            let mut mac = Blake2bMac256::new_from_slice(master.as_bytes()).unwrap();
+            mac.update(purpose);
+            mac.finalize().into_bytes().into()
+        };
+        (derive(b"encryption"), derive(b"authentication"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let backup = test_backup();
+        let encrypted = backup.encrypt_with_kdf_work("secret", 8);
+        assert_eq!(encrypted[..8], WalletBackup::MAGIC);
+        assert_eq!(WalletBackup::decrypt(&encrypted, "secret").unwrap(), backup);
+    }
+
+    #[test]
+    fn wrong_password() {
+        let encrypted = test_backup().encrypt_with_kdf_work("secret", 8);
+        assert!(WalletBackup::decrypt(&encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    fn tampered_backup() {
+        let mut encrypted = test_backup().encrypt_with_kdf_work("secret", 8);
+        let i = encrypted.len() - 40;
+        encrypted[i] ^= 1;
+        assert!(WalletBackup::decrypt(&encrypted, "secret").is_err());
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut encrypted = test_backup().encrypt_with_kdf_work("secret", 8);
+        encrypted[8] = 2;
+        let err = WalletBackup::decrypt(&encrypted, "secret").unwrap_err();
+        assert_eq!(err.to_string(), "unsupported wallet backup version 2");
+    }
+
+    #[test]
+    fn reject_excessive_kdf_work() {
+        let mut encrypted = test_backup().encrypt_with_kdf_work("secret", 8);
+        let kdf_work = WalletBackup::MAX_KDF_WORK + 1;
+        encrypted[9..13].copy_from_slice(&kdf_work.to_be_bytes());
+        let err = WalletBackup::decrypt(&encrypted, "secret").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("invalid kdf work {} in wallet backup", kdf_work)
+        );
+    }
+
+    #[test]
+    fn encrypt_with_strong_kdf_work() {
+        let encrypted = test_backup().encrypt("secret");
+        assert_eq!(encrypted[9..13], WalletBackup::MAX_KDF_WORK.to_be_bytes());
+    }
+
+    fn test_backup() -> WalletBackup {
+        WalletBackup {
+            seed: RawKey::from(1),
+            derivation: DerivationScheme::Bip44,
+            chain_code: Some(RawKey::from(2)),
+            deterministic_index: 3,
+            deterministic_accounts: vec![0, 2],
+            representative: PublicKey::from(4),
+            adhoc_keys: vec![RawKey::from(5)],
+            watch_accounts: vec![Account::from(6)],
+        }
+    }
+}
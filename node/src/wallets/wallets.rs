@@ -1,4 +1,9 @@
-use super::{Wallet, WalletActionThread, WalletRepresentatives};
+use super::{
+    ApprovalKey, PendingSend, ReceiveDecision, ReceivePolicies, ReceivePolicy, SendIdInfo,
+    SendIdRecord, SendIdStatus, SendRequest, SpendingLimit, SpendingLimitExceeded, SpendingPolicy,
+    Wallet, WalletActionThread, WalletBackup, WalletRepresentatives, WatchReceive,
+    SPENDING_WINDOW_SECS,
+};
 use crate::{
     block_processing::{BlockProcessor, BlockSource},
     cementation::{ConfirmingSet, ConfirmingSetConfig},
@@ -12,10 +17,11 @@ use crate::{
 };
 use rand::{thread_rng, Rng};
 use rsban_core::{
-    utils::{get_env_or_default_string, ContainerInfo},
+    utils::{get_env_or_default_string, seconds_since_epoch, ContainerInfo},
     work::{WorkPoolImpl, WorkThresholds},
-    Account, Amount, Block, BlockDetails, BlockHash, Epoch, KeyDerivationFunction, Link, NoValue,
-    PendingKey, PrivateKey, PublicKey, RawKey, Root, SavedBlock, StateBlockArgs, WalletId,
+    Account, Amount, Block, BlockDetails, BlockHash, DerivationScheme, Epoch,
+    KeyDerivationFunction, Link, NoValue, PendingKey, PrivateKey, PublicKey, RawKey, Root,
+    SavedBlock, Slip10Key, StateBlockArgs, WalletId,
 };
 use rsban_ledger::{Ledger, RepWeightCache};
 use rsban_messages::{Message, Publish};
@@ -33,7 +39,7 @@ use std::{
     mem::size_of,
     os::unix::fs::PermissionsExt,
     path::{Path, PathBuf},
-    sync::{Arc, Condvar, Mutex},
+    sync::{mpsc, Arc, Condvar, Mutex},
     time::{Duration, Instant},
 };
 use tracing::{info, warn};
@@ -47,6 +53,8 @@ pub enum WalletsError {
     AccountNotFound,
     InvalidPassword,
     BadPublicKey,
+    /// The legacy seed isn't used by wallets with BIP44 derivation
+    Bip44Wallet,
 }
 
 impl WalletsError {
@@ -59,6 +67,7 @@ impl WalletsError {
             WalletsError::AccountNotFound => "Account not found",
             WalletsError::InvalidPassword => "Invalid password",
             WalletsError::BadPublicKey => "Bad public key",
+            WalletsError::Bip44Wallet => "Not supported by BIP44 wallets",
         }
     }
 }
@@ -71,6 +80,8 @@ impl fmt::Display for WalletsError {
 
 impl std::error::Error for WalletsError {}
 
+pub type WatchReceiveCallback = Box<dyn Fn(&WatchReceive) + Send + Sync>;
+
 pub type WalletsIterator<'txn> = BinaryDbIterator<'txn, [u8; 64], NoValue>;
 
 pub enum PreparedSend {
@@ -78,9 +89,24 @@ pub enum PreparedSend {
     New(Block, BlockDetails),
 }
 
+/// The block of a wallet action was not added to the ledger and won't be added later
+#[derive(Debug)]
+struct BlockRejected(String);
+
+impl fmt::Display for BlockRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BlockRejected {}
+
 pub struct Wallets {
     db: Option<LmdbDatabase>,
     send_action_ids_handle: Option<LmdbDatabase>,
+    receive_policies_handle: Option<LmdbDatabase>,
+    spending_policies_handle: Option<LmdbDatabase>,
+    pending_sends_handle: Option<LmdbDatabase>,
     env: Arc<LmdbEnv>,
     pub mutex: Mutex<HashMap<WalletId, Arc<Wallet>>>,
     node_config: NodeConfig,
@@ -99,6 +125,13 @@ pub struct Wallets {
     start_election: Mutex<Option<Box<dyn Fn(SavedBlock) + Send + Sync>>>,
     confirming_set: Arc<ConfirmingSet>,
     message_publisher: Mutex<MessagePublisher>,
+    receive_policies: Arc<Mutex<ReceivePolicies>>,
+    watch_receive_observers: Mutex<Vec<WatchReceiveCallback>>,
+    spending_policies: Mutex<HashMap<WalletId, SpendingPolicy>>,
+    /// Sends which wait for approval
+    pending_sends: Mutex<HashMap<String, PendingSend>>,
+    /// Prevents that the same pending send is approved twice
+    send_approval_mutex: Mutex<()>,
 }
 
 impl Wallets {
@@ -148,6 +181,9 @@ impl Wallets {
         Self {
             db: None,
             send_action_ids_handle: None,
+            receive_policies_handle: None,
+            spending_policies_handle: None,
+            pending_sends_handle: None,
             mutex: Mutex::new(HashMap::new()),
             env,
             node_config: node_config.clone(),
@@ -169,6 +205,11 @@ impl Wallets {
             start_election: Mutex::new(None),
             confirming_set,
             message_publisher: Mutex::new(message_publisher),
+            receive_policies: Arc::new(Mutex::new(ReceivePolicies::default())),
+            watch_receive_observers: Mutex::new(Vec::new()),
+            spending_policies: Mutex::new(HashMap::new()),
+            pending_sends: Mutex::new(HashMap::new()),
+            send_approval_mutex: Mutex::new(()),
         }
     }
 
@@ -184,6 +225,11 @@ impl Wallets {
         *self.start_election.lock().unwrap() = Some(callback);
     }
 
+    /// Called when a watch-only account of a wallet receives a confirmed send
+    pub fn on_watch_receive(&self, callback: WatchReceiveCallback) {
+        self.watch_receive_observers.lock().unwrap().push(callback);
+    }
+
     pub fn initialize(&mut self) -> anyhow::Result<()> {
         let mut txn = self.env.tx_begin_write();
         self.db = Some(unsafe { txn.rw_txn_mut().create_db(None, DatabaseFlags::empty())? });
@@ -191,21 +237,35 @@ impl Wallets {
             txn.rw_txn_mut()
                 .create_db(Some("send_action_ids"), DatabaseFlags::empty())?
         });
+        self.receive_policies_handle = Some(unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("receive_policies"), DatabaseFlags::empty())?
+        });
+        self.load_receive_policies(&txn);
+        self.spending_policies_handle = Some(unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("spending_policies"), DatabaseFlags::empty())?
+        });
+        self.load_spending_policies(&txn);
+        self.pending_sends_handle = Some(unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("pending_sends"), DatabaseFlags::empty())?
+        });
+        self.load_pending_sends(&txn);
         {
             let mut guard = self.mutex.lock().unwrap();
             let wallet_ids = self.get_wallet_ids(&txn);
             for id in wallet_ids {
                 assert!(!guard.contains_key(&id));
                 let representative = self.node_config.random_representative();
-                let text = PathBuf::from(id.encode_hex());
                 let wallet = Wallet::new(
+                    id,
                     self.ledger.clone(),
                     self.work_thresholds.clone(),
                     &mut txn,
                     self.node_config.password_fanout as usize,
                     self.kdf.clone(),
                     representative,
-                    &text,
                 )?;
 
                 guard.insert(id, Arc::new(wallet));
@@ -264,46 +324,690 @@ impl Wallets {
         wallet_ids
     }
 
-    pub fn get_block_hash(
+    pub fn get_send_id(
         &self,
         txn: &dyn Transaction,
         id: &str,
-    ) -> anyhow::Result<Option<BlockHash>> {
+    ) -> anyhow::Result<Option<SendIdRecord>> {
         match txn.get(self.send_action_ids_handle.unwrap(), id.as_bytes()) {
             Ok(bytes) => Ok(Some(
-                BlockHash::from_slice(bytes).ok_or_else(|| anyhow!("invalid block hash"))?,
+                SendIdRecord::from_bytes(bytes).ok_or_else(|| anyhow!("invalid send id record"))?,
             )),
             Err(rsban_nullable_lmdb::Error::NotFound) => Ok(None),
             Err(e) => Err(e.into()),
         }
     }
 
-    pub fn set_block_hash(
+    pub fn set_send_id(
         &self,
         txn: &mut LmdbWriteTransaction,
         id: &str,
-        hash: &BlockHash,
+        record: &SendIdRecord,
     ) -> anyhow::Result<()> {
         txn.rw_txn_mut().put(
             self.send_action_ids_handle.unwrap(),
             id.as_bytes(),
-            hash.as_bytes(),
+            &record.to_bytes(),
             WriteFlags::empty(),
         )?;
         Ok(())
     }
 
+    pub fn send_id_info(&self, id: &str) -> anyhow::Result<Option<SendIdInfo>> {
+        let tx = self.env.tx_begin_read();
+        Ok(self
+            .get_send_id(&tx, id)?
+            .map(|record| self.to_send_id_info(id.to_owned(), record)))
+    }
+
+    pub fn send_ids_of_wallet(
+        &self,
+        wallet_id: &WalletId,
+    ) -> Result<Vec<SendIdInfo>, WalletsError> {
+        if !self.wallet_exists(wallet_id) {
+            return Err(WalletsError::WalletNotFound);
+        }
+        let tx = self.env.tx_begin_read();
+        let mut result = Vec::new();
+        for (id, record) in self.iter_send_ids(&tx) {
+            if record.wallet_id == *wallet_id {
+                result.push(self.to_send_id_info(id, record));
+            }
+        }
+        Ok(result)
+    }
+
+    fn iter_send_ids(&self, txn: &dyn Transaction) -> Vec<(String, SendIdRecord)> {
+        let mut cursor = txn
+            .open_ro_cursor(self.send_action_ids_handle.unwrap())
+            .expect("Could not read send ids");
+        cursor
+            .iter_start()
+            .filter_map(|i| {
+                let (k, v) = i.ok()?;
+                let id = std::str::from_utf8(k).ok()?.to_owned();
+                Some((id, SendIdRecord::from_bytes(v)?))
+            })
+            .collect()
+    }
+
+    fn to_send_id_info(&self, id: String, record: SendIdRecord) -> SendIdInfo {
+        let block_tx = self.ledger.read_txn();
+        let block = self.ledger.any().get_block(&block_tx, &record.hash);
+        let status = if self
+            .ledger
+            .confirmed()
+            .block_exists_or_pruned(&block_tx, &record.hash)
+        {
+            SendIdStatus::Confirmed
+        } else if block.is_some() {
+            SendIdStatus::Published
+        } else {
+            record.status_without_block(seconds_since_epoch())
+        };
+
+        SendIdInfo {
+            id,
+            record,
+            status,
+            local_timestamp: block.map(|b| b.timestamp()),
+        }
+    }
+
+    /// Deletes all send ids which were created before the given time (seconds since epoch).
+    /// Entries written by older node versions don't have a creation time and are kept.
+    pub fn purge_send_ids(&self, created_before: u64) -> usize {
+        let mut tx = self.env.tx_begin_write();
+        let expired: Vec<String> = self
+            .iter_send_ids(&tx)
+            .into_iter()
+            .filter(|(_, record)| record.created != 0 && record.created < created_before)
+            .map(|(id, _)| id)
+            .collect();
+
+        for id in &expired {
+            tx.delete(self.send_action_ids_handle.unwrap(), id.as_bytes(), None)
+                .unwrap();
+        }
+        expired.len()
+    }
+
+    /// Allows to reuse the send id if its block was rejected
+    fn mark_rejected_send_id(&self, result: &anyhow::Result<SavedBlock>, id: Option<&str>) {
+        let (Err(e), Some(id)) = (result, id) else {
+            return;
+        };
+        if !e.is::<BlockRejected>() {
+            return;
+        }
+        let mut tx = self.env.tx_begin_write();
+        if let Ok(Some(mut record)) = self.get_send_id(&tx, id) {
+            record.rejected = true;
+            let _ = self.set_send_id(&mut tx, id, &record);
+        }
+    }
+
     pub fn clear_send_ids(&self) {
         let mut tx = self.env.tx_begin_write();
         tx.clear_db(self.send_action_ids_handle.unwrap()).unwrap();
     }
 
+    fn load_receive_policies(&self, txn: &dyn Transaction) {
+        let mut cursor = txn
+            .open_ro_cursor(self.receive_policies_handle.unwrap())
+            .expect("Could not read receive policies");
+        let mut policies = self.receive_policies.lock().unwrap();
+        for (key, value) in cursor.iter_start().filter_map(|i| i.ok()) {
+            let Some(wallet_id) = WalletId::from_slice(key) else {
+                continue;
+            };
+            match serde_json::from_slice::<ReceivePolicy>(value) {
+                Ok(policy) => policies.insert(wallet_id, policy),
+                Err(e) => warn!("Invalid receive policy for wallet {}: {:?}", wallet_id, e),
+            }
+        }
+    }
+
+    pub fn receive_policy(&self, wallet_id: &WalletId) -> Result<ReceivePolicy, WalletsError> {
+        if !self.wallet_exists(wallet_id) {
+            return Err(WalletsError::WalletNotFound);
+        }
+        Ok(self.receive_policies.lock().unwrap().get(wallet_id))
+    }
+
+    pub fn set_receive_policy(
+        &self,
+        wallet_id: &WalletId,
+        policy: ReceivePolicy,
+    ) -> Result<(), WalletsError> {
+        if !self.wallet_exists(wallet_id) {
+            return Err(WalletsError::WalletNotFound);
+        }
+        let mut tx = self.env.tx_begin_write();
+        if policy == ReceivePolicy::default() {
+            let _ = tx.delete(
+                self.receive_policies_handle.unwrap(),
+                wallet_id.as_bytes(),
+                None,
+            );
+        } else {
+            tx.rw_txn_mut()
+                .put(
+                    self.receive_policies_handle.unwrap(),
+                    wallet_id.as_bytes(),
+                    &serde_json::to_vec(&policy).unwrap(),
+                    WriteFlags::empty(),
+                )
+                .map_err(|_| WalletsError::Generic)?;
+        }
+        self.receive_policies
+            .lock()
+            .unwrap()
+            .insert(*wallet_id, policy);
+        Ok(())
+    }
+
+    /// The minimum amount of a receivable block which this wallet receives
+    pub fn receive_minimum(&self, wallet_id: &WalletId) -> Amount {
+        self.receive_policies
+            .lock()
+            .unwrap()
+            .get(wallet_id)
+            .receive_minimum_or(self.node_config.receive_minimum)
+    }
+
+    /// Amount which the wallet received automatically within the last hour
+    pub fn received_last_hour(&self, wallet_id: &WalletId) -> Amount {
+        self.receive_policies
+            .lock()
+            .unwrap()
+            .received_last_hour(wallet_id, Instant::now())
+    }
+
+    fn auto_receive_allowed(
+        &self,
+        wallet_id: &WalletId,
+        hash: &BlockHash,
+        source: &Account,
+        amount: Amount,
+    ) -> bool {
+        let decision = self.receive_policies.lock().unwrap().auto_receive(
+            wallet_id,
+            source,
+            amount,
+            self.node_config.receive_minimum,
+            Instant::now(),
+        );
+        if decision != ReceiveDecision::Receive {
+            info!(
+                "Not receiving block {} automatically: {}",
+                hash,
+                decision.as_str()
+            );
+        }
+        decision == ReceiveDecision::Receive
+    }
+
+    /// Creates the completion action of an automatic receive. The amount was reserved
+    /// in the hourly limit when the receive was allowed and is released if the receive fails
+    fn release_failed_auto_receive(
+        &self,
+        wallet_id: WalletId,
+        amount: Amount,
+    ) -> Box<dyn Fn(Option<SavedBlock>) + Send + Sync> {
+        let receive_policies = self.receive_policies.clone();
+        Box::new(move |block| {
+            if block.is_none() {
+                receive_policies
+                    .lock()
+                    .unwrap()
+                    .release_receive(&wallet_id, amount);
+            }
+        })
+    }
+
+    fn notify_watch_receive(&self, event: &WatchReceive) {
+        for observer in self.watch_receive_observers.lock().unwrap().iter() {
+            observer(event);
+        }
+    }
+
+    fn load_spending_policies(&self, txn: &dyn Transaction) {
+        let mut cursor = txn
+            .open_ro_cursor(self.spending_policies_handle.unwrap())
+            .expect("Could not read spending policies");
+        let mut policies = self.spending_policies.lock().unwrap();
+        for (key, value) in cursor.iter_start().filter_map(|i| i.ok()) {
+            let Some(wallet_id) = WalletId::from_slice(key) else {
+                continue;
+            };
+            match serde_json::from_slice::<SpendingPolicy>(value) {
+                Ok(policy) => {
+                    policies.insert(wallet_id, policy);
+                }
+                Err(e) => warn!("Invalid spending policy for wallet {}: {:?}", wallet_id, e),
+            }
+        }
+    }
+
+    fn load_pending_sends(&self, txn: &dyn Transaction) {
+        let mut cursor = txn
+            .open_ro_cursor(self.pending_sends_handle.unwrap())
+            .expect("Could not read pending sends");
+        let mut pending_sends = self.pending_sends.lock().unwrap();
+        for (key, value) in cursor.iter_start().filter_map(|i| i.ok()) {
+            match serde_json::from_slice::<PendingSend>(value) {
+                Ok(send) => {
+                    pending_sends.insert(send.id.clone(), send);
+                }
+                Err(e) => warn!(
+                    "Invalid pending send {}: {:?}",
+                    String::from_utf8_lossy(key),
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Removes the pending sends which match `filter` from memory and from the wallet store
+    fn remove_pending_sends(
+        &self,
+        tx: &mut LmdbWriteTransaction,
+        filter: impl Fn(&PendingSend) -> bool,
+    ) {
+        self.pending_sends.lock().unwrap().retain(|id, send| {
+            if filter(send) {
+                let _ = tx.delete(self.pending_sends_handle.unwrap(), id.as_bytes(), None);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    pub fn spending_policy(&self, wallet_id: &WalletId) -> Result<SpendingPolicy, WalletsError> {
+        if !self.wallet_exists(wallet_id) {
+            return Err(WalletsError::WalletNotFound);
+        }
+        Ok(self
+            .spending_policies
+            .lock()
+            .unwrap()
+            .get(wallet_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn save_spending_policy(
+        &self,
+        wallet_id: &WalletId,
+        policy: SpendingPolicy,
+    ) -> Result<(), WalletsError> {
+        let mut tx = self.env.tx_begin_write();
+        let mut policies = self.spending_policies.lock().unwrap();
+        if policy == SpendingPolicy::default() {
+            let _ = tx.delete(
+                self.spending_policies_handle.unwrap(),
+                wallet_id.as_bytes(),
+                None,
+            );
+            policies.remove(wallet_id);
+        } else {
+            tx.rw_txn_mut()
+                .put(
+                    self.spending_policies_handle.unwrap(),
+                    wallet_id.as_bytes(),
+                    &serde_json::to_vec(&policy).unwrap(),
+                    WriteFlags::empty(),
+                )
+                .map_err(|_| WalletsError::Generic)?;
+            policies.insert(*wallet_id, policy);
+        }
+        Ok(())
+    }
+
+    /// Sets the limit of a single account or, if `account` is `None`, of the whole wallet.
+    /// If send approval is enabled, the approval password is required.
+    pub fn set_spending_limit(
+        &self,
+        wallet_id: &WalletId,
+        account: Option<Account>,
+        limit: SpendingLimit,
+        approval_password: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if let Some(account) = account {
+            let guard = self.mutex.lock().unwrap();
+            let wallet = Self::get_wallet(&guard, wallet_id)?;
+            let tx = self.env.tx_begin_read();
+            if !wallet.store.exists(&tx, &account.into()) {
+                return Err(WalletsError::AccountNotFound.into());
+            }
+        }
+        let mut policy = self.spending_policy(wallet_id)?;
+        if let Some(key) = &policy.approval {
+            if !key.verify(&self.kdf, approval_password.unwrap_or_default()) {
+                bail!("Invalid approval password");
+            }
+        }
+        policy.set_limit(account, limit);
+        self.save_spending_policy(wallet_id, policy)?;
+        Ok(())
+    }
+
+    /// From now on sends of this wallet have to be approved with `approval_password`,
+    /// which must differ from the wallet password
+    pub fn enable_send_approval(
+        &self,
+        wallet_id: &WalletId,
+        approval_password: &str,
+    ) -> anyhow::Result<()> {
+        {
+            let guard = self.mutex.lock().unwrap();
+            let wallet = Self::get_wallet(&guard, wallet_id)?;
+            let tx = self.env.tx_begin_read();
+            if !wallet.store.valid_password(&tx) {
+                return Err(WalletsError::WalletLocked.into());
+            }
+            if wallet.store.derive_key(&tx, approval_password) == wallet.store.password() {
+                bail!("Approval password must differ from the wallet password");
+            }
+        }
+        let mut policy = self.spending_policy(wallet_id)?;
+        if policy.requires_approval() {
+            bail!("Send approval is already enabled");
+        }
+        policy.approval = Some(ApprovalKey::new(&self.kdf, approval_password));
+        self.save_spending_policy(wallet_id, policy)?;
+        Ok(())
+    }
+
+    /// Disables send approval and rejects all sends which wait for approval
+    pub fn disable_send_approval(
+        &self,
+        wallet_id: &WalletId,
+        approval_password: &str,
+    ) -> anyhow::Result<()> {
+        let mut policy = self.spending_policy(wallet_id)?;
+        let Some(key) = policy.approval.take() else {
+            bail!("Send approval is not enabled");
+        };
+        if !key.verify(&self.kdf, approval_password) {
+            bail!("Invalid approval password");
+        }
+        self.save_spending_policy(wallet_id, policy)?;
+        let mut tx = self.env.tx_begin_write();
+        self.remove_pending_sends(&mut tx, |send| send.wallet_id == *wallet_id);
+        Ok(())
+    }
+
+    pub fn requires_send_approval(&self, wallet_id: &WalletId) -> bool {
+        self.spending_policies
+            .lock()
+            .unwrap()
+            .get(wallet_id)
+            .map(|p| p.requires_approval())
+            .unwrap_or_default()
+    }
+
+    /// Amount sent by the account within the last 24 hours
+    fn spent_since(&self, tx: &dyn Transaction, account: &Account, cutoff: u64) -> Amount {
+        let any = self.ledger.any();
+        let mut spent = Amount::zero();
+        let mut current = any.account_head(tx, account);
+        while let Some(hash) = current {
+            let Some(block) = any.get_block(tx, &hash) else {
+                break;
+            };
+            if block.timestamp() < cutoff {
+                break;
+            }
+            if block.is_send() {
+                spent = spent.wrapping_add(any.block_amount_for(tx, &block).unwrap_or_default());
+            }
+            current = Some(block.previous()).filter(|h| !h.is_zero());
+        }
+        spent
+    }
+
+    fn spending_window_start() -> u64 {
+        seconds_since_epoch().saturating_sub(SPENDING_WINDOW_SECS)
+    }
+
+    /// Amount sent within the last 24 hours by the given account or,
+    /// if `account` is `None`, by all accounts of the wallet
+    pub fn spent_last_day(
+        &self,
+        wallet_id: &WalletId,
+        account: Option<Account>,
+    ) -> Result<Amount, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        let accounts = match account {
+            Some(account) => vec![account],
+            None => wallet.store.accounts(&tx),
+        };
+        let block_tx = self.ledger.read_txn();
+        let cutoff = Self::spending_window_start();
+        Ok(accounts.iter().fold(Amount::zero(), |sum, account| {
+            sum.wrapping_add(self.spent_since(&block_tx, account, cutoff))
+        }))
+    }
+
+    fn check_spending_policy(
+        &self,
+        tx: &dyn Transaction,
+        wallet: &Wallet,
+        source: &Account,
+        amount: Amount,
+    ) -> Result<(), SpendingLimitExceeded> {
+        let Some(policy) = self
+            .spending_policies
+            .lock()
+            .unwrap()
+            .get(&wallet.id)
+            .cloned()
+        else {
+            return Ok(());
+        };
+        if policy.wallet.is_unlimited() && policy.accounts.is_empty() {
+            return Ok(());
+        }
+        let block_tx = self.ledger.read_txn();
+        let cutoff = Self::spending_window_start();
+        let spent_by_account = self.spent_since(&block_tx, source, cutoff);
+        let spent_by_wallet = if policy.wallet.daily.is_some() {
+            wallet
+                .store
+                .accounts(tx)
+                .iter()
+                .fold(Amount::zero(), |sum, account| {
+                    sum.wrapping_add(self.spent_since(&block_tx, account, cutoff))
+                })
+        } else {
+            Amount::zero()
+        };
+        policy.check(source, amount, spent_by_account, spent_by_wallet)
+    }
+
+    /// Queues a send until it is approved with `approve_send`. Repeating a request with
+    /// the same send id returns the pending send or the block that was already sent
+    pub fn request_send(
+        &self,
+        wallet_id: &WalletId,
+        source: Account,
+        destination: Account,
+        amount: Amount,
+        work: u64,
+        send_id: Option<String>,
+    ) -> anyhow::Result<SendRequest> {
+        if !self.requires_send_approval(wallet_id) {
+            bail!("Send approval is not enabled");
+        }
+        {
+            let guard = self.mutex.lock().unwrap();
+            let wallet = Self::get_wallet(&guard, wallet_id)?;
+            let tx = self.env.tx_begin_read();
+            if !wallet.store.valid_password(&tx) {
+                return Err(WalletsError::WalletLocked.into());
+            }
+            if !wallet.store.exists(&tx, &source.into()) {
+                return Err(WalletsError::AccountNotFound.into());
+            }
+            if let Some(id) = &send_id {
+                if let Some(record) = self.get_send_id(&tx, id)? {
+                    if self
+                        .ledger
+                        .any()
+                        .block_exists(&self.ledger.read_txn(), &record.hash)
+                    {
+                        return Ok(SendRequest::Sent(record.hash));
+                    }
+                }
+            }
+            self.check_spending_policy(&tx, wallet, &source, amount)?;
+        }
+        // The write transaction is opened before locking the pending sends, like in `destroy`
+        let mut tx = self.env.tx_begin_write();
+        let mut pending_sends = self.pending_sends.lock().unwrap();
+        if send_id.is_some() {
+            if let Some(existing) = pending_sends
+                .values()
+                .find(|send| send.wallet_id == *wallet_id && send.send_id == send_id)
+            {
+                return Ok(SendRequest::Pending(existing.clone()));
+            }
+        }
+        let pending = PendingSend {
+            id: format!("{:016X}", thread_rng().gen::<u64>()),
+            wallet_id: *wallet_id,
+            source,
+            destination,
+            amount,
+            work,
+            send_id,
+            created: seconds_since_epoch(),
+        };
+        tx.rw_txn_mut().put(
+            self.pending_sends_handle.unwrap(),
+            pending.id.as_bytes(),
+            &serde_json::to_vec(&pending).unwrap(),
+            WriteFlags::empty(),
+        )?;
+        pending_sends.insert(pending.id.clone(), pending.clone());
+        Ok(SendRequest::Pending(pending))
+    }
+
+    /// Sends of the wallet which wait for approval, oldest first
+    pub fn pending_sends(&self, wallet_id: &WalletId) -> Result<Vec<PendingSend>, WalletsError> {
+        if !self.wallet_exists(wallet_id) {
+            return Err(WalletsError::WalletNotFound);
+        }
+        let mut result: Vec<_> = self
+            .pending_sends
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|send| send.wallet_id == *wallet_id)
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| a.created.cmp(&b.created).then_with(|| a.id.cmp(&b.id)));
+        Ok(result)
+    }
+
+    /// Drops a send which waits for approval. Requires the approval password
+    pub fn reject_send(
+        &self,
+        wallet_id: &WalletId,
+        id: &str,
+        approval_password: &str,
+    ) -> anyhow::Result<()> {
+        self.verify_approval_password(wallet_id, approval_password)?;
+        self.pending_send(wallet_id, id)?;
+        let mut tx = self.env.tx_begin_write();
+        self.remove_pending_sends(&mut tx, |send| send.id == id);
+        Ok(())
+    }
+
+    fn verify_approval_password(
+        &self,
+        wallet_id: &WalletId,
+        approval_password: &str,
+    ) -> anyhow::Result<()> {
+        let policy = self.spending_policy(wallet_id)?;
+        let Some(key) = &policy.approval else {
+            bail!("Send approval is not enabled");
+        };
+        if !key.verify(&self.kdf, approval_password) {
+            bail!("Invalid approval password");
+        }
+        Ok(())
+    }
+
+    /// Revealing or signing with the keys of a wallet, moving its accounts to another wallet
+    /// and destroying it bypass the spending policy of the wallet. If send approval is enabled,
+    /// this requires the approval password. Wallets which only have spending limits refuse it
+    /// until the limits are removed.
+    pub fn verify_key_access(
+        &self,
+        wallet_id: &WalletId,
+        approval_password: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let policy = self.spending_policy(wallet_id)?;
+        match &policy.approval {
+            Some(key) => match approval_password {
+                Some(password) if key.verify(&self.kdf, password) => Ok(()),
+                Some(_) => bail!("Invalid approval password"),
+                None => bail!("Approval password required"),
+            },
+            None if policy != SpendingPolicy::default() => {
+                bail!("Not allowed while the wallet has spending limits")
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn pending_send(&self, wallet_id: &WalletId, id: &str) -> anyhow::Result<PendingSend> {
+        match self.pending_sends.lock().unwrap().get(id) {
+            Some(send) if send.wallet_id == *wallet_id => Ok(send.clone()),
+            _ => bail!("Pending send not found"),
+        }
+    }
+
+    fn prepare_send_action(
+        &self,
+        wallet: &Arc<Wallet>,
+        source: Account,
+        account: Account,
+        amount: Amount,
+        work: u64,
+        id: Option<String>,
+    ) -> anyhow::Result<PreparedSend> {
+        match id {
+            Some(id) => {
+                let mut tx = self.env.tx_begin_write();
+                self.prepare_send_with_id(&mut tx, &id, wallet, source, account, amount, work)
+            }
+            None => {
+                let tx = self.env.tx_begin_read();
+                self.prepare_send(&tx, wallet, source, account, amount, work)
+            }
+        }
+    }
+
     pub fn foreach_representative<F>(&self, mut action: F)
     where
         F: FnMut(&PrivateKey),
     {
+        for keys in self.representative_keys() {
+            action(&keys);
+        }
+    }
+
+    /// Returns the keys of all voting representatives in unlocked wallets
+    pub fn representative_keys(&self) -> Vec<PrivateKey> {
+        let mut action_accounts_l: Vec<PrivateKey> = Vec::new();
         if self.node_config.enable_voting {
-            let mut action_accounts_l: Vec<PrivateKey> = Vec::new();
             {
                 let transaction_l = self.env.tx_begin_read();
                 let ledger_txn = self.ledger.read_txn();
@@ -336,10 +1040,8 @@ impl Wallets {
                     }
                 }
             }
-            for keys in action_accounts_l {
-                action(&keys);
-            }
         }
+        action_accounts_l
     }
 
     pub fn work_cache_blocking2(
@@ -488,16 +1190,15 @@ impl Wallets {
         for id in wallet_ids {
             // New wallet
             if !guard.contains_key(&id) {
-                let text = PathBuf::from(id.encode_hex());
                 let representative = self.node_config.random_representative();
                 if let Ok(wallet) = Wallet::new(
+                    id,
                     Arc::clone(&self.ledger),
                     self.work_thresholds.clone(),
                     &mut tx,
                     self.node_config.password_fanout as usize,
                     self.kdf.clone(),
                     representative,
-                    &text,
                 ) {
                     guard.insert(id, Arc::new(wallet));
                 }
@@ -528,6 +1229,11 @@ impl Wallets {
         let _action_guard = self.wallet_actions.lock_safe();
         let wallet = guard.remove(id).unwrap();
         wallet.store.destroy(&mut tx);
+        let _ = tx.delete(self.receive_policies_handle.unwrap(), id.as_bytes(), None);
+        self.receive_policies.lock().unwrap().remove(id);
+        let _ = tx.delete(self.spending_policies_handle.unwrap(), id.as_bytes(), None);
+        self.spending_policies.lock().unwrap().remove(id);
+        self.remove_pending_sends(&mut tx, |send| send.wallet_id == *id);
     }
 
     pub fn remove_key(
@@ -597,6 +1303,23 @@ impl Wallets {
         Ok(())
     }
 
+    /// Creates a password encrypted backup of a single wallet. See `WalletBackup` for the format
+    pub fn backup_wallet(
+        &self,
+        wallet_id: &WalletId,
+        password: &str,
+    ) -> Result<Vec<u8>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+        let backup =
+            WalletBackup::from_store(&wallet.store, &tx).map_err(|_| WalletsError::Generic)?;
+        Ok(backup.encrypt(password))
+    }
+
     pub fn deterministic_index_get(&self, wallet_id: &WalletId) -> Result<u32, WalletsError> {
         let guard = self.mutex.lock().unwrap();
         let wallet = Self::get_wallet(&guard, wallet_id)?;
@@ -626,6 +1349,7 @@ impl Wallets {
         if balance.is_zero() || balance < amount {
             bail!("insufficient balance");
         }
+        self.check_spending_policy(tx, wallet, &source, amount)?;
 
         let info = self.ledger.account_info(&block_tx, &source).unwrap();
         let prv_key_raw = wallet.store.fetch(tx, &source.into()).unwrap();
@@ -661,10 +1385,18 @@ impl Wallets {
     ) -> anyhow::Result<PreparedSend> {
         let block_tx = self.ledger.read_txn();
 
-        let block = match self.get_block_hash(tx, id)? {
-            Some(hash) => Some(self.ledger.any().get_block(&block_tx, &hash).unwrap()),
+        // A send id whose block failed can be reused. If the block may still be added
+        // to the ledger, reusing the id could fork the account or send twice
+        let record = self.get_send_id(tx, id)?;
+        let block = match &record {
+            Some(record) => self.ledger.any().get_block(&block_tx, &record.hash),
             None => None,
         };
+        if let (Some(record), None) = (&record, &block) {
+            if record.status_without_block(seconds_since_epoch()) == SendIdStatus::Pending {
+                bail!("send id {} is still being processed", id);
+            }
+        }
 
         if let Some(block) = block {
             let msg = Message::Publish(Publish::new_forward(block.clone().into()));
@@ -687,6 +1419,7 @@ impl Wallets {
             if balance.is_zero() || balance < amount {
                 bail!("insufficient balance");
             }
+            self.check_spending_policy(tx, wallet, &source, amount)?;
 
             let info = self.ledger.account_info(&block_tx, &source).unwrap();
             let prv_key_raw = wallet.store.fetch(tx, &source.into()).unwrap();
@@ -707,7 +1440,13 @@ impl Wallets {
             }
             .into();
             let details = BlockDetails::new(info.epoch, true, false, false);
-            self.set_block_hash(tx, id, &state_block.hash())?;
+            let record = SendIdRecord {
+                wallet_id: wallet.id,
+                hash: state_block.hash(),
+                created: seconds_since_epoch(),
+                rejected: false,
+            };
+            self.set_send_id(tx, id, &record)?;
             Ok(PreparedSend::New(state_block, details))
         }
     }
@@ -788,12 +1527,12 @@ impl Wallets {
         let _guard = self.mutex.lock().unwrap();
         let mut tx = self.env.tx_begin_write();
         let _wallet = Wallet::new_from_json(
+            wallet_id,
             Arc::clone(&self.ledger),
             self.work_thresholds.clone(),
             &mut tx,
             self.node_config.password_fanout as usize,
             self.kdf.clone(),
-            &PathBuf::from(wallet_id.to_string()),
             json,
         )?;
         Ok(())
@@ -835,6 +1574,9 @@ impl Wallets {
         if !wallet.store.valid_password(&tx) {
             return Err(WalletsError::WalletLocked);
         }
+        if wallet.store.derivation_scheme(&tx) == DerivationScheme::Bip44 {
+            return Err(WalletsError::Bip44Wallet);
+        }
         Ok(wallet.store.seed(&tx))
     }
 
@@ -954,6 +1696,7 @@ pub trait WalletsExt {
     ) -> anyhow::Result<SavedBlock>;
 
     fn ongoing_compute_reps(&self);
+    fn ongoing_send_id_cleanup(&self);
 
     fn change_seed(
         &self,
@@ -970,6 +1713,31 @@ pub trait WalletsExt {
         count: u32,
     ) -> PublicKey;
 
+    /// Like `change_seed`, but derives the accounts along the BIP44 path below `root`
+    fn change_bip44_seed(
+        &self,
+        wallet_id: WalletId,
+        root: &Slip10Key,
+        count: u32,
+    ) -> Result<(u32, Account), WalletsError>;
+
+    fn restore_deterministic_accounts(
+        &self,
+        wallet: &Arc<Wallet>,
+        tx: &mut LmdbWriteTransaction,
+        count: u32,
+    ) -> PublicKey;
+
+    /// Restores a backup created by `backup_wallet` into a new wallet with an empty password
+    /// Creates a new wallet from an encrypted backup. The new wallet is protected by
+    /// `wallet_password`
+    fn restore_wallet(
+        &self,
+        backup: &[u8],
+        backup_password: &str,
+        wallet_password: &str,
+    ) -> anyhow::Result<WalletId>;
+
     fn send_action(
         &self,
         wallet: &Arc<Wallet>,
@@ -992,6 +1760,14 @@ pub trait WalletsExt {
         id: Option<String>,
     ) -> Result<SavedBlock, WalletsError>;
 
+    /// Builds and publishes a send which was queued by `Wallets::request_send`
+    fn approve_send(
+        &self,
+        wallet_id: &WalletId,
+        id: &str,
+        approval_password: &str,
+    ) -> anyhow::Result<SavedBlock>;
+
     fn change_action(
         &self,
         wallet: &Arc<Wallet>,
@@ -1102,10 +1878,26 @@ pub trait WalletsExt {
         id: Option<String>,
     ) -> BlockHash;
 
+    /// Like `send_sync`, but returns why the send failed. The spending limits are checked
+    /// under the wallet transaction in which the send block is built
+    fn try_send_sync(
+        &self,
+        wallet_id: WalletId,
+        source: Account,
+        account: Account,
+        amount: Amount,
+        work: u64,
+        generate_work: bool,
+        id: Option<String>,
+    ) -> anyhow::Result<BlockHash>;
+
+    /// Receives the confirmed receivable blocks of the wallet. The receive policy of the
+    /// wallet is only applied if `apply_policy` is set, explicit searches ignore it
     fn search_receivable(
         &self,
         wallet: &Arc<Wallet>,
         wallet_tx: &dyn Transaction,
+        apply_policy: bool,
     ) -> Result<(), ()>;
 
     fn receive_confirmed(&self, hash: BlockHash, destinaton: Account);
@@ -1340,13 +2132,13 @@ impl WalletsExt for Arc<Wallets> {
             );
             self.distributed_work
                 .make_blocking_block(&mut block, required_difficulty)
-                .ok_or_else(|| anyhow!("no work generated"))?;
+                .ok_or_else(|| BlockRejected("no work generated".to_string()))?;
         }
         let arc_block = Arc::new(block.clone());
         let saved_block = self
             .block_processor
             .add_blocking(arc_block.clone(), BlockSource::Local)?
-            .map_err(|s| anyhow!("block processor failed: {:?}", s))?;
+            .map_err(|s| BlockRejected(format!("block processor failed: {:?}", s)))?;
 
         if generate_work {
             // Pregenerate work for next block based on the block just created
@@ -1376,14 +2168,92 @@ impl WalletsExt for Arc<Wallets> {
         );
     }
 
+    fn ongoing_send_id_cleanup(&self) {
+        let expiry = self.node_config.send_id_expiry;
+        let created_before = seconds_since_epoch().saturating_sub(expiry.as_secs());
+        let purged = self.purge_send_ids(created_before);
+        if purged > 0 {
+            info!("Purged {} expired send ids", purged);
+        }
+
+        let cleanup_interval = if self.network_params.network.is_dev_network() {
+            Duration::from_secs(1)
+        } else {
+            Duration::from_secs(60 * 60)
+        };
+
+        let self_l = Arc::clone(self);
+        self.workers.add_delayed_task(
+            cleanup_interval,
+            Box::new(move || {
+                self_l.ongoing_send_id_cleanup();
+            }),
+        );
+    }
+
     fn change_seed_wallet(
         &self,
         wallet: &Arc<Wallet>,
         tx: &mut LmdbWriteTransaction,
         prv_key: &RawKey,
-        mut count: u32,
+        count: u32,
     ) -> PublicKey {
         wallet.store.set_seed(tx, prv_key);
+        self.restore_deterministic_accounts(wallet, tx, count)
+    }
+
+    fn change_bip44_seed(
+        &self,
+        wallet_id: WalletId,
+        root: &Slip10Key,
+        count: u32,
+    ) -> Result<(u32, Account), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Wallets::get_wallet(&guard, &wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+        wallet.store.set_bip44_seed(&mut tx, root);
+        let last_account = self.restore_deterministic_accounts(wallet, &mut tx, count);
+        let restored_count = wallet.store.deterministic_index_get(&tx);
+        Ok((restored_count, last_account.into()))
+    }
+
+    fn restore_wallet(
+        &self,
+        backup: &[u8],
+        backup_password: &str,
+        wallet_password: &str,
+    ) -> anyhow::Result<WalletId> {
+        if wallet_password.is_empty() {
+            bail!("a password for the restored wallet is required");
+        }
+        let backup = WalletBackup::decrypt(backup, backup_password)?;
+        let wallet_id = WalletId::random();
+        self.create(wallet_id);
+        let result = (|| -> anyhow::Result<()> {
+            let guard = self.mutex.lock().unwrap();
+            let wallet = Wallets::get_wallet(&guard, &wallet_id)?;
+            let mut tx = self.env.tx_begin_write();
+            backup.restore(&wallet.store, &mut tx)?;
+            wallet.store.rekey(&mut tx, wallet_password)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            self.destroy(&wallet_id);
+            return Err(e);
+        }
+        self.compute_reps();
+        Ok(wallet_id)
+    }
+
+    fn restore_deterministic_accounts(
+        &self,
+        wallet: &Arc<Wallet>,
+        tx: &mut LmdbWriteTransaction,
+        mut count: u32,
+    ) -> PublicKey {
         let mut account = self.deterministic_insert(wallet, tx, true);
         if count == 0 {
             count = wallet.deterministic_check(tx, 0);
@@ -1407,6 +2277,9 @@ impl WalletsExt for Arc<Wallets> {
         if !wallet.store.valid_password(&tx) {
             return Err(WalletsError::WalletLocked);
         }
+        if wallet.store.derivation_scheme(&tx) == DerivationScheme::Bip44 {
+            return Err(WalletsError::Bip44Wallet);
+        }
         let first_account = self.change_seed_wallet(wallet, &mut tx, prv_key, count);
         let restored_count = wallet.store.deterministic_index_get(&tx);
         Ok((restored_count, first_account.into()))
@@ -1438,25 +2311,65 @@ impl WalletsExt for Arc<Wallets> {
         generate_work: bool,
         id: Option<String>,
     ) -> anyhow::Result<SavedBlock> {
-        let result = match id {
-            Some(id) => {
-                let mut tx = self.env.tx_begin_write();
-                self.prepare_send_with_id(&mut tx, &id, wallet, source, account, amount, work)?
-            }
-            None => {
-                let tx = self.env.tx_begin_read();
-                self.prepare_send(&tx, wallet, source, account, amount, work)?
-            }
-        };
-
-        match result {
+        if self.requires_send_approval(&wallet.id) {
+            bail!("Send requires approval");
+        }
+        match self.prepare_send_action(wallet, source, account, amount, work, id.clone())? {
             PreparedSend::Cached(block) => Ok(block),
             PreparedSend::New(block, details) => {
-                self.action_complete(Arc::clone(wallet), block, source, generate_work, &details)
+                let result = self.action_complete(
+                    Arc::clone(wallet),
+                    block,
+                    source,
+                    generate_work,
+                    &details,
+                );
+                self.mark_rejected_send_id(&result, id.as_deref());
+                result
             }
         }
     }
 
+    fn approve_send(
+        &self,
+        wallet_id: &WalletId,
+        id: &str,
+        approval_password: &str,
+    ) -> anyhow::Result<SavedBlock> {
+        let wallet = {
+            let guard = self.mutex.lock().unwrap();
+            Arc::clone(Wallets::get_wallet(&guard, wallet_id)?)
+        };
+        self.verify_approval_password(wallet_id, approval_password)?;
+        if !wallet.store.valid_password(&self.env.tx_begin_read()) {
+            return Err(WalletsError::WalletLocked.into());
+        }
+        let _approval_guard = self.send_approval_mutex.lock().unwrap();
+        // The send stays pending until its block was built, so that it can be approved again
+        // if building the block fails
+        let send = self.pending_send(wallet_id, id)?;
+        let prepared = self.prepare_send_action(
+            &wallet,
+            send.source,
+            send.destination,
+            send.amount,
+            send.work,
+            send.send_id.clone(),
+        )?;
+        let block = match prepared {
+            PreparedSend::Cached(block) => block,
+            PreparedSend::New(block, details) => {
+                let result =
+                    self.action_complete(wallet, block, send.source, send.work == 0, &details);
+                self.mark_rejected_send_id(&result, send.send_id.as_deref());
+                result?
+            }
+        };
+        let mut tx = self.env.tx_begin_write();
+        self.remove_pending_sends(&mut tx, |send| send.id == id);
+        Ok(block)
+    }
+
     fn change_action(
         &self,
         wallet: &Arc<Wallet>,
@@ -1530,7 +2443,7 @@ impl WalletsExt for Arc<Wallets> {
         mut work: u64,
         generate_work: bool,
     ) -> Option<SavedBlock> {
-        if amount < self.node_config.receive_minimum {
+        if amount < self.receive_minimum(&wallet.id) {
             warn!(
                 "Not receiving block {} due to minimum receive threshold",
                 send_hash
@@ -1807,10 +2720,49 @@ impl WalletsExt for Arc<Wallets> {
         guard.1
     }
 
+    fn try_send_sync(
+        &self,
+        wallet_id: WalletId,
+        source: Account,
+        account: Account,
+        amount: Amount,
+        work: u64,
+        generate_work: bool,
+        id: Option<String>,
+    ) -> anyhow::Result<BlockHash> {
+        let wallet = {
+            let guard = self.mutex.lock().unwrap();
+            Arc::clone(Wallets::get_wallet(&guard, &wallet_id)?)
+        };
+        let (tx, rx) = mpsc::sync_channel(1);
+        let self_l = Arc::clone(self);
+        self.wallet_actions.queue_wallet_action(
+            HIGH_PRIORITY,
+            wallet,
+            Box::new(move |wallet| {
+                let result = self_l
+                    .send_action(
+                        &wallet,
+                        source,
+                        account,
+                        amount,
+                        work,
+                        generate_work,
+                        id.clone(),
+                    )
+                    .map(|block| block.hash());
+                let _ = tx.send(result);
+            }),
+        );
+        rx.recv()
+            .unwrap_or_else(|_| Err(anyhow!("send was not executed")))
+    }
+
     fn search_receivable(
         &self,
         wallet: &Arc<Wallet>,
         wallet_tx: &dyn Transaction,
+        apply_policy: bool,
     ) -> Result<(), ()> {
         if !wallet.store.valid_password(wallet_tx) {
             info!("Stopping search, wallet is locked");
@@ -1831,7 +2783,7 @@ impl WalletsExt for Arc<Wallets> {
                 ) {
                     let hash = key.send_block_hash;
                     let amount = info.amount;
-                    if self.node_config.receive_minimum <= amount {
+                    if self.receive_minimum(&wallet.id) <= amount {
                         info!(
                             "Found a receivable block {} for account {}",
                             hash,
@@ -1842,18 +2794,33 @@ impl WalletsExt for Arc<Wallets> {
                             .confirmed()
                             .block_exists_or_pruned(&block_tx, &hash)
                         {
-                            let representative = wallet.store.representative(wallet_tx);
-                            // Receive confirmed block
-                            self.receive_async_wallet(
-                                Arc::clone(wallet),
-                                hash,
-                                representative,
-                                amount,
-                                account.into(),
-                                Box::new(|_| {}),
-                                0,
-                                true,
-                            );
+                            if !apply_policy
+                                || self.auto_receive_allowed(
+                                    &wallet.id,
+                                    &hash,
+                                    &info.source,
+                                    amount,
+                                )
+                            {
+                                let action: Box<dyn Fn(Option<SavedBlock>) + Send + Sync> =
+                                    if apply_policy {
+                                        self.release_failed_auto_receive(wallet.id, amount)
+                                    } else {
+                                        Box::new(|_| {})
+                                    };
+                                let representative = wallet.store.representative(wallet_tx);
+                                // Receive confirmed block
+                                self.receive_async_wallet(
+                                    Arc::clone(wallet),
+                                    hash,
+                                    representative,
+                                    amount,
+                                    account.into(),
+                                    action,
+                                    0,
+                                    true,
+                                );
+                            }
                         } else if !self.confirming_set.contains(&hash) {
                             let block = self.ledger.any().get_block(&block_tx, &hash);
                             if let Some(block) = block {
@@ -1882,7 +2849,7 @@ impl WalletsExt for Arc<Wallets> {
             (self.env.tx_begin_read(), guard.clone())
         };
 
-        for (_id, wallet) in wallets {
+        for (id, wallet) in wallets {
             if wallet.store.exists(&wallet_tx, &destination.into()) {
                 let representative = wallet.store.representative(&wallet_tx);
                 let pending = self
@@ -1891,13 +2858,31 @@ impl WalletsExt for Arc<Wallets> {
                     .get_pending(&self.ledger.read_txn(), &PendingKey::new(destination, hash));
                 if let Some(pending) = pending {
                     let amount = pending.amount;
+                    let watch_only = wallet
+                        .store
+                        .entry_get_raw(&wallet_tx, &destination.into())
+                        .key
+                        .is_zero();
+                    if watch_only {
+                        self.notify_watch_receive(&WatchReceive {
+                            wallet_id: id,
+                            account: destination,
+                            hash,
+                            source: pending.source,
+                            amount,
+                        });
+                        continue;
+                    }
+                    if !self.auto_receive_allowed(&id, &hash, &pending.source, amount) {
+                        continue;
+                    }
                     self.receive_async_wallet(
                         wallet,
                         hash,
                         representative,
                         amount,
                         destination,
-                        Box::new(|_| {}),
+                        self.release_failed_auto_receive(id, amount),
                         0,
                         true,
                     );
@@ -1921,7 +2906,7 @@ impl WalletsExt for Arc<Wallets> {
         let wallets = self.mutex.lock().unwrap().clone();
         let wallet_tx = self.env.tx_begin_read();
         for (_, wallet) in wallets {
-            let _ = self.search_receivable(&wallet, &wallet_tx);
+            let _ = self.search_receivable(&wallet, &wallet_tx, true);
         }
     }
 
@@ -1930,7 +2915,7 @@ impl WalletsExt for Arc<Wallets> {
         if let Some(wallet) = guard.get(&wallet_id) {
             let tx = self.env.tx_begin_read();
             if wallet.store.valid_password(&tx) {
-                let _ = self.search_receivable(wallet, &tx);
+                let _ = self.search_receivable(wallet, &tx, false);
                 Ok(())
             } else {
                 Err(WalletsError::WalletLocked)
@@ -1966,7 +2951,7 @@ impl WalletsExt for Arc<Wallets> {
                 Box::new(move |wallet| {
                     // Wallets must survive node lifetime
                     let tx = self_l.env.tx_begin_read();
-                    let _ = self_l.search_receivable(&wallet, &tx);
+                    let _ = self_l.search_receivable(&wallet, &tx, true);
                 }),
             );
             Ok(())
@@ -1992,13 +2977,13 @@ impl WalletsExt for Arc<Wallets> {
         let wallet = {
             let mut tx = self.env.tx_begin_write();
             let Ok(wallet) = Wallet::new(
+                wallet_id,
                 Arc::clone(&self.ledger),
                 self.work_thresholds.clone(),
                 &mut tx,
                 self.node_config.password_fanout as usize,
                 self.kdf.clone(),
                 self.node_config.random_representative(),
-                &PathBuf::from(wallet_id.to_string()),
             ) else {
                 return;
             };
@@ -2155,6 +3140,9 @@ impl WalletsExt for Arc<Wallets> {
         if self.node_config.enable_voting {
             self.ongoing_compute_reps();
         }
+        if !self.node_config.send_id_expiry.is_zero() {
+            self.ongoing_send_id_cleanup();
+        }
     }
 }
 
@@ -837,6 +837,105 @@ mod bootstrap_processor {
         );
     }
 
+    #[test]
+    fn bootstrap_processor_process_one_compressed() {
+        let mut system = System::new();
+        let mut config = System::default_config_without_backlog_population();
+        config.enable_voting = false;
+        config.bootstrap_compression = true;
+        let mut flags = NodeFlags::new();
+        flags.disable_bootstrap_bulk_push_client = true;
+        let node0 = system
+            .build_node()
+            .config(config.clone())
+            .flags(flags.clone())
+            .finish();
+
+        let mut lattice = UnsavedBlockLatticeBuilder::new();
+        let send = lattice.genesis().send(&*DEV_GENESIS_KEY, Amount::raw(100));
+        node0.process(send.clone()).unwrap();
+
+        flags.disable_rep_crawler = true;
+        flags.disable_ascending_bootstrap = true;
+        config.peering_port = Some(get_available_port());
+        let node1 = system
+            .build_node()
+            .config(config)
+            .flags(flags)
+            .disconnected()
+            .finish();
+
+        node1
+            .peer_connector
+            .connect_to(node0.tcp_listener.local_address());
+        node1
+            .bootstrap_initiator
+            .bootstrap2(node0.tcp_listener.local_address(), "".into());
+
+        assert_timely(Duration::from_secs(10), || node1.block_exists(&send.hash()));
+        assert!(
+            node0.stats.count(
+                StatType::BootstrapCompression,
+                DetailType::Compressed,
+                Direction::Out
+            ) > 0
+        );
+        assert!(
+            node1.stats.count(
+                StatType::BootstrapCompression,
+                DetailType::Compressed,
+                Direction::In
+            ) > 0
+        );
+    }
+
+    #[test]
+    fn bootstrap_processor_process_one_compression_disabled_on_server() {
+        let mut system = System::new();
+        let mut config = System::default_config_without_backlog_population();
+        config.enable_voting = false;
+        config.bootstrap_compression = false;
+        let mut flags = NodeFlags::new();
+        flags.disable_bootstrap_bulk_push_client = true;
+        let node0 = system
+            .build_node()
+            .config(config.clone())
+            .flags(flags.clone())
+            .finish();
+
+        let mut lattice = UnsavedBlockLatticeBuilder::new();
+        let send = lattice.genesis().send(&*DEV_GENESIS_KEY, Amount::raw(100));
+        node0.process(send.clone()).unwrap();
+
+        flags.disable_rep_crawler = true;
+        flags.disable_ascending_bootstrap = true;
+        config.bootstrap_compression = true;
+        config.peering_port = Some(get_available_port());
+        let node1 = system
+            .build_node()
+            .config(config)
+            .flags(flags)
+            .disconnected()
+            .finish();
+
+        node1
+            .peer_connector
+            .connect_to(node0.tcp_listener.local_address());
+        node1
+            .bootstrap_initiator
+            .bootstrap2(node0.tcp_listener.local_address(), "".into());
+
+        assert_timely(Duration::from_secs(10), || node1.block_exists(&send.hash()));
+        assert_eq!(
+            node1.stats.count(
+                StatType::BootstrapCompression,
+                DetailType::Compressed,
+                Direction::In
+            ),
+            0
+        );
+    }
+
     #[test]
     fn bootstrap_processor_lazy_hash() {
         let mut system = System::new();
@@ -1288,6 +1387,7 @@ mod bulk_pull {
             end: 2.into(),
             count: 0,
             ascending: false,
+            compression: false,
         };
 
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
@@ -1305,6 +1405,7 @@ mod bulk_pull {
             end: BlockHash::zero(),
             count: 0,
             ascending: false,
+            compression: false,
         };
 
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
@@ -1322,6 +1423,7 @@ mod bulk_pull {
             end: 1.into(),
             count: 0,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         assert_eq!(node.latest(&DEV_GENESIS_ACCOUNT), pull_server.current());
@@ -1366,6 +1468,7 @@ mod bulk_pull {
             end: *DEV_GENESIS_HASH,
             count: 0,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         assert_eq!(pull_server.current(), pull_server.request().end);
@@ -1380,6 +1483,7 @@ mod bulk_pull {
             end: *DEV_GENESIS_HASH,
             count: 0,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         assert_eq!(pull_server.get_next(), None);
@@ -1394,6 +1498,7 @@ mod bulk_pull {
             end: 0.into(),
             count: 0,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         let block = pull_server.get_next().unwrap();
@@ -1416,6 +1521,7 @@ mod bulk_pull {
             end: 0.into(),
             count: 0,
             ascending: true,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         let block_out1 = pull_server.get_next().unwrap();
@@ -1438,6 +1544,7 @@ mod bulk_pull {
             end: 0.into(),
             count: 0,
             ascending: true,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         let block_out1 = pull_server.get_next().unwrap();
@@ -1462,6 +1569,7 @@ mod bulk_pull {
             end: block1.hash(),
             count: 0,
             ascending: true,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         let block_out1 = pull_server.get_next().unwrap();
@@ -1479,6 +1587,7 @@ mod bulk_pull {
             end: 0.into(),
             count: 0,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         let block_out1 = pull_server.get_next().unwrap();
@@ -1496,6 +1605,7 @@ mod bulk_pull {
             end: *DEV_GENESIS_HASH,
             count: 0,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         let block_out1 = pull_server.get_next().unwrap();
@@ -1522,6 +1632,7 @@ mod bulk_pull {
             end: 0.into(),
             count: 2,
             ascending: false,
+            compression: false,
         };
         let pull_server = create_bulk_pull_server(&node, bulk_pull);
         assert_eq!(pull_server.max_count(), 2);
@@ -1545,6 +1656,8 @@ mod bulk_pull {
             node.ledger.clone(),
             node.workers.clone(),
             node.runtime.clone(),
+            node.stats.clone(),
+            false,
         )
     }
 }
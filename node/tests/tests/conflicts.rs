@@ -1,4 +1,7 @@
-use rsban_core::{Amount, BlockSideband, PrivateKey, SavedBlock, UnsavedBlockLatticeBuilder};
+use rsban_core::{
+    Amount, BlockSideband, PrivateKey, SavedBlock, UnsavedBlockLatticeBuilder, DEV_GENESIS_KEY,
+};
+use rsban_ledger::{BlockStatus, DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
 use rsban_network::ChannelId;
 use rsban_node::block_processing::BlockSource;
 use std::time::Duration;
@@ -84,3 +87,91 @@ fn add_two() {
     assert!(node.active.election(&send_b.qualified_root()).is_some());
     assert_timely_eq(Duration::from_secs(5), || node.active.len(), 2);
 }
+
+#[test]
+fn track_fork_until_resolved() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let key1 = PrivateKey::new();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice.genesis().send(&key1, 100);
+    let mut fork_lattice = UnsavedBlockLatticeBuilder::new();
+    let send2 = fork_lattice.genesis().send(&key1, 200);
+
+    assert_eq!(
+        node1.process_local(send1.clone()).unwrap(),
+        BlockStatus::Progress
+    );
+    start_election(&node1, &send1.hash());
+    assert_eq!(
+        node1.process_local(send2.clone()).unwrap(),
+        BlockStatus::Fork
+    );
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node1.fork_tracker.active().len(),
+        1,
+    );
+    let active = node1.fork_tracker.active();
+    assert_eq!(active[0].root, send1.qualified_root());
+    assert_eq!(active[0].account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(
+        active[0]
+            .candidates
+            .iter()
+            .map(|c| c.hash)
+            .collect::<Vec<_>>(),
+        vec![send1.hash(), send2.hash()]
+    );
+    assert!(node1.fork_tracker.is_forked(&DEV_GENESIS_ACCOUNT));
+
+    node1.insert_into_wallet(&DEV_GENESIS_KEY);
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node1.fork_tracker.history().len(),
+        1,
+    );
+    assert!(node1.fork_tracker.active().is_empty());
+    let resolved = &node1.fork_tracker.history()[0];
+    assert_eq!(resolved.winner, Some(send1.hash()));
+    assert!(resolved.resolved.is_some());
+    assert_eq!(
+        resolved.candidates[0].tally,
+        node1.ledger.weight(&DEV_GENESIS_PUB_KEY)
+    );
+}
+
+#[test]
+fn resolve_fork_when_election_is_cancelled() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let key1 = PrivateKey::new();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice.genesis().send(&key1, 100);
+    let mut fork_lattice = UnsavedBlockLatticeBuilder::new();
+    let send2 = fork_lattice.genesis().send(&key1, 200);
+
+    node1.process_local(send1.clone()).unwrap();
+    start_election(&node1, &send1.hash());
+    node1.process_local(send2.clone()).unwrap();
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node1.fork_tracker.active().len(),
+        1,
+    );
+
+    assert!(node1.active.erase(&send1.qualified_root()));
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node1.fork_tracker.history().len(),
+        1,
+    );
+    assert!(!node1.fork_tracker.is_forked(&DEV_GENESIS_ACCOUNT));
+    let resolved = &node1.fork_tracker.history()[0];
+    assert_eq!(resolved.winner, None);
+    assert!(resolved.resolved.is_some());
+}
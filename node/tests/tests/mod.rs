@@ -16,6 +16,7 @@ mod optimistic_scheduler;
 mod peer_container;
 mod rep_crawler;
 mod request_aggregator;
+mod simulator;
 mod telemetry;
 mod unchecked_map;
 mod vote_processor;
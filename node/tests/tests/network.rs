@@ -6,18 +6,28 @@ use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
 use rsban_messages::{
     ConfirmAck, Keepalive, Message, MessageHeader, MessageSerializer, ProtocolInfo, Publish,
 };
-use rsban_network::{ChannelMode, DropPolicy, TrafficType};
+use rsban_network::{ChannelDirection, ChannelMode, DropPolicy, TrafficType, TransportEncryption};
 use rsban_node::{
     bootstrap::BootstrapInitiatorExt,
     config::NodeConfig,
     consensus::VoteProcessorConfig,
     stats::{DetailType, Direction, StatType},
+    transport::{CaptureReader, CaptureReplayer, CAPTURE_FILE_NAME},
+    unique_path,
     wallets::WalletsExt,
 };
-use std::{ops::Deref, sync::Arc, thread::sleep, time::Duration};
+use rsban_nullable_tcp::Socks5Proxy;
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener},
+    ops::Deref,
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
 use test_helpers::{
     assert_always_eq, assert_timely, assert_timely_eq, assert_timely_msg, establish_tcp,
-    make_fake_channel, start_election, System,
+    get_available_port, make_fake_channel, start_election, System,
 };
 
 #[test]
@@ -550,3 +560,244 @@ fn expire_duplicate_filter() {
         !node1.network_filter.check_message(&payload_bytes)
     });
 }
+
+#[test]
+fn encrypted_transport() {
+    let mut system = System::new();
+    let mut config = System::default_config_without_backlog_population();
+    config.transport_encryption = TransportEncryption::Preferred;
+    let node1 = system.build_node().config(config.clone()).finish();
+    config.peering_port = Some(get_available_port());
+    let node2 = system.build_node().config(config).finish();
+
+    let channel = node1
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node2.node_id())
+        .cloned()
+        .unwrap();
+    assert!(channel.is_encrypted());
+    assert!(node2
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node1.node_id())
+        .unwrap()
+        .is_encrypted());
+    assert!(
+        node1.stats.count(
+            StatType::TcpChannels,
+            DetailType::ChannelEncrypted,
+            Direction::In
+        ) > 0
+    );
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node1.process_active(send.clone());
+    assert_timely(Duration::from_secs(5), || node2.block_exists(&send.hash()));
+}
+
+#[test]
+fn encrypted_transport_falls_back_to_plaintext() {
+    let mut system = System::new();
+    let mut config = System::default_config_without_backlog_population();
+    config.transport_encryption = TransportEncryption::Preferred;
+    let node1 = system.build_node().config(config.clone()).finish();
+    config.peering_port = Some(get_available_port());
+    config.transport_encryption = TransportEncryption::Disabled;
+    let node2 = system.build_node().config(config).finish();
+
+    let network_info = node1.network_info.read().unwrap();
+    let channel = network_info.find_node_id(&node2.node_id()).unwrap();
+    assert!(!channel.is_encrypted());
+    assert_eq!(network_info.channels_info().encrypted, 0);
+    drop(network_info);
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node1.process_active(send.clone());
+    assert_timely(Duration::from_secs(5), || node2.block_exists(&send.hash()));
+}
+
+#[test]
+fn negotiate_bootstrap_compression() {
+    let mut system = System::new();
+    let mut config = System::default_config_without_backlog_population();
+    config.bootstrap_compression = true;
+    let node1 = system.build_node().config(config.clone()).finish();
+    config.peering_port = Some(get_available_port());
+    let node2 = system.build_node().config(config.clone()).finish();
+    config.peering_port = Some(get_available_port());
+    config.bootstrap_compression = false;
+    let node3 = system.build_node().config(config).finish();
+
+    let network_info = node1.network_info.read().unwrap();
+    assert!(network_info
+        .find_node_id(&node2.node_id())
+        .unwrap()
+        .supports_compression());
+    assert!(!network_info
+        .find_node_id(&node3.node_id())
+        .unwrap()
+        .supports_compression());
+}
+
+#[test]
+fn encrypted_transport_required() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.transport_encryption = TransportEncryption::Required;
+    let node1 = system.build_node().config(config).disconnected().finish();
+    let node2 = system.make_disconnected_node();
+
+    node2
+        .peer_connector
+        .connect_to(node1.tcp_listener.local_address());
+
+    assert_timely(Duration::from_secs(5), || {
+        node1.stats.count(
+            StatType::Handshake,
+            DetailType::EncryptionRequired,
+            Direction::In,
+        ) > 0
+    });
+    assert!(node1
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node2.node_id())
+        .is_none());
+}
+
+#[test]
+fn connect_through_socks5_proxy() {
+    let mut system = System::new();
+    let node1 = system.make_disconnected_node();
+    let (proxy_addr, targets) = start_socks5_proxy();
+    let mut config = System::default_config();
+    config.socks5_proxy = Some(Socks5Proxy::new(proxy_addr));
+    let node2 = system.build_node().config(config).disconnected().finish();
+
+    let node1_addr = node1.tcp_listener.local_address();
+    node2.peer_connector.connect_to(node1_addr);
+
+    assert_timely(Duration::from_secs(5), || {
+        node2
+            .network_info
+            .read()
+            .unwrap()
+            .find_node_id(&node1.node_id())
+            .is_some()
+    });
+    let network_info = node2.network_info.read().unwrap();
+    let channel = network_info.find_node_id(&node1.node_id()).unwrap();
+    assert_eq!(channel.peer_addr(), node1_addr);
+    let targets = targets.lock().unwrap();
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].port(), node1_addr.port());
+}
+
+/// Forwarding SOCKS5 proxy without authentication.
+/// Returns the proxy address and the requested target addresses
+fn start_socks5_proxy() -> (SocketAddr, Arc<Mutex<Vec<SocketAddr>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy_addr = listener.local_addr().unwrap();
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let targets_l = targets.clone();
+    std::thread::spawn(move || {
+        for mut client in listener.incoming().flatten() {
+            let mut greeting = [0; 3];
+            client.read_exact(&mut greeting).unwrap();
+            client.write_all(&[5, 0]).unwrap();
+
+            let mut request = [0; 4];
+            client.read_exact(&mut request).unwrap();
+            let ip: IpAddr = if request[3] == 1 {
+                let mut ip = [0; 4];
+                client.read_exact(&mut ip).unwrap();
+                Ipv4Addr::from(ip).into()
+            } else {
+                let mut ip = [0; 16];
+                client.read_exact(&mut ip).unwrap();
+                Ipv6Addr::from(ip).into()
+            };
+            let mut port = [0; 2];
+            client.read_exact(&mut port).unwrap();
+            let target = SocketAddr::new(ip, u16::from_be_bytes(port));
+            targets_l.lock().unwrap().push(target);
+
+            let server = std::net::TcpStream::connect(target).unwrap();
+            client.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+            let mut client_l = client.try_clone().unwrap();
+            let mut server_l = server.try_clone().unwrap();
+            std::thread::spawn(move || std::io::copy(&mut client_l, &mut server_l));
+            let (mut client, mut server) = (client, server);
+            std::thread::spawn(move || std::io::copy(&mut server, &mut client));
+        }
+    });
+    (proxy_addr, targets)
+}
+
+#[test]
+fn capture_and_replay_messages() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let mut config = System::default_config_without_backlog_population();
+    let capture_path = unique_path().unwrap();
+    config.message_capture.enabled = true;
+    config.message_capture.path = Some(capture_path.clone());
+    let node2 = system.build_node().config(config).finish();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node1.process_active(send.clone());
+    assert_timely(Duration::from_secs(5), || node2.block_exists(&send.hash()));
+
+    node2.message_capture.as_ref().unwrap().flush();
+    let records: Vec<_> = CaptureReader::open(capture_path.join(CAPTURE_FILE_NAME))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert!(records
+        .iter()
+        .any(|r| r.direction == ChannelDirection::Outbound));
+
+    let protocol_info = node2.network_params.network.protocol_info();
+    let work = node2.network_params.work.clone();
+    let publish_records: Vec<_> = node2.runtime.block_on(async {
+        let mut result = Vec::new();
+        for record in records {
+            if let Ok(Message::Publish(_)) = record.decode(protocol_info, work.clone()).await {
+                result.push(record);
+            }
+        }
+        result
+    });
+    assert!(publish_records
+        .iter()
+        .all(|r| r.direction == ChannelDirection::Inbound));
+    assert!(!publish_records.is_empty());
+
+    // Replay into a node without peers
+    let node3 = system.build_node().disconnected().finish();
+    let mut replayer = CaptureReplayer::new(
+        node3.inbound_message_queue.clone(),
+        protocol_info,
+        work,
+        node3.steady_clock.clone(),
+    );
+    node3.runtime.block_on(async {
+        for record in &publish_records {
+            assert_eq!(replayer.replay(record).await, Ok(true));
+        }
+    });
+    assert_timely(Duration::from_secs(5), || node3.block_exists(&send.hash()));
+}
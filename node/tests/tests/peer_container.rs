@@ -1,6 +1,7 @@
+use rsban_core::NodeId;
 use rsban_network::ChannelMode;
 use std::time::Duration;
-use test_helpers::{assert_never, System};
+use test_helpers::{assert_never, assert_timely, System};
 
 // Test a node cannot connect to its own endpoint.
 #[test]
@@ -17,3 +18,47 @@ fn no_self_incoming() {
             > 0
     })
 }
+
+#[test]
+fn private_network_rejects_unknown_node() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.private_network.allowed_node_ids = vec![NodeId::from(1)];
+    let node1 = system.build_node().config(config).disconnected().finish();
+    let node2 = system.make_disconnected_node();
+
+    node2
+        .peer_connector
+        .connect_to(node1.tcp_listener.local_address());
+
+    assert_never(Duration::from_secs(2), || {
+        node1
+            .network_info
+            .read()
+            .unwrap()
+            .count_by_mode(ChannelMode::Realtime)
+            > 0
+    })
+}
+
+#[test]
+fn private_network_accepts_allowed_node() {
+    let mut system = System::new();
+    let node2 = system.make_disconnected_node();
+    let mut config = System::default_config();
+    config.private_network.allowed_node_ids = vec![node2.node_id()];
+    let node1 = system.build_node().config(config).disconnected().finish();
+
+    node2
+        .peer_connector
+        .connect_to(node1.tcp_listener.local_address());
+
+    assert_timely(Duration::from_secs(5), || {
+        node1
+            .network_info
+            .read()
+            .unwrap()
+            .find_node_id(&node2.node_id())
+            .is_some()
+    })
+}
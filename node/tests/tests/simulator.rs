@@ -0,0 +1,104 @@
+use rsban_core::{Amount, UnsavedBlockLatticeBuilder, DEV_GENESIS_KEY};
+use rsban_node::stats::{DetailType, Direction, StatType};
+use std::time::Duration;
+use test_helpers::{LinkConfig, Simulator};
+
+#[test]
+fn confirm_block_in_simulated_network() {
+    let mut sim = Simulator::new(1);
+    let node0 = sim.add_node();
+    let node1 = sim.add_node();
+    let node2 = sim.add_node();
+    sim.connect_all();
+    node0.insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node0.process_active(send.clone());
+
+    let confirmed = sim.run_until(Duration::from_secs(30), |_| {
+        [&node0, &node1, &node2]
+            .iter()
+            .all(|n| n.block_confirmed(&send.hash()))
+    });
+    assert!(confirmed);
+    assert!(sim.stats().delivered > 0);
+    assert_eq!(sim.stats().lost, 0);
+}
+
+#[test]
+fn partition_and_heal() {
+    let mut sim = Simulator::new(2);
+    let node0 = sim.add_node();
+    let node1 = sim.add_node();
+    sim.connect_all();
+    sim.partition(&[&[0], &[1]]);
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node0.process_active(send.clone());
+
+    assert!(sim.run_until(Duration::from_secs(10), |sim| sim.stats().partitioned > 0));
+    sim.run_for(Duration::from_secs(1));
+    assert!(!node1.block_exists(&send.hash()));
+
+    sim.heal();
+    assert!(sim.run_until(Duration::from_secs(60), |_| node1
+        .block_exists(&send.hash())));
+}
+
+#[test]
+fn lossy_link() {
+    let mut sim = Simulator::new(3);
+    let node0 = sim.add_node();
+    let node1 = sim.add_node();
+    sim.connect_all();
+    sim.set_link(
+        0,
+        1,
+        LinkConfig {
+            loss: 1.0,
+            ..Default::default()
+        },
+    );
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node0.process_active(send.clone());
+
+    assert!(sim.run_until(Duration::from_secs(10), |sim| sim.stats().lost > 0));
+    sim.run_for(Duration::from_secs(1));
+    assert!(!node1.block_exists(&send.hash()));
+}
+
+#[test]
+fn filter_messages() {
+    let mut sim = Simulator::new(4);
+    let node0 = sim.add_node();
+    let node1 = sim.add_node();
+    sim.connect_all();
+    // node 0 can receive, but nothing it sends arrives
+    sim.set_filter(|from, _, _| from != 0);
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node0.process_active(send.clone());
+
+    assert!(sim.run_until(Duration::from_secs(10), |sim| sim.stats().filtered > 0));
+    sim.run_for(Duration::from_secs(1));
+    assert!(!node1.block_exists(&send.hash()));
+    assert_eq!(
+        node1
+            .stats
+            .count(StatType::Message, DetailType::Publish, Direction::In),
+        0
+    );
+}
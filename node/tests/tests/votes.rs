@@ -4,10 +4,12 @@ use rsban_core::{
 };
 use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
 use rsban_node::{
-    consensus::VoteSpacing,
+    consensus::{PersistentVoteHistory, RepVotingPolicy, VoteSpacing},
     stats::{DetailType, Direction, StatType},
+    unique_path,
     wallets::WalletsExt,
 };
+use rsban_store_lmdb::LmdbStore;
 use std::{
     sync::Arc,
     time::{Duration, SystemTime},
@@ -475,3 +477,169 @@ fn vote_spacing_rapid() {
         ) == 2
     });
 }
+
+#[test]
+fn persistent_vote_history_survives_restart() {
+    let mut system = System::new();
+    let config = System::default_config_without_backlog_population();
+    let node = system.build_node().config(config).finish();
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), true)
+        .unwrap();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+
+    let mut fork_lattice = UnsavedBlockLatticeBuilder::new();
+    let send2 = fork_lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1001));
+
+    node.ledger
+        .process(&mut node.ledger.rw_txn(), &send1)
+        .unwrap();
+    node.vote_generators
+        .generate_non_final_vote(&(*DEV_GENESIS_HASH).into(), &send1.hash());
+
+    assert_timely(Duration::from_secs(3), || {
+        node.ledger
+            .store
+            .local_vote
+            .iter(&node.ledger.read_txn())
+            .any(|(root, _)| root == (*DEV_GENESIS_HASH).into())
+    });
+
+    // Reload the history from the store, like a restarted node does
+    let history = PersistentVoteHistory::new(
+        node.config.persistent_vote_history.clone(),
+        node.ledger.clone(),
+    );
+    let root = (*DEV_GENESIS_HASH).into();
+    let now = SystemTime::now();
+    assert_eq!(history.previous_votes(), 1);
+    assert!(history.votable(&root, &send1.hash(), now));
+    assert!(!history.votable(&root, &send2.hash(), now));
+}
+
+#[test]
+fn generated_vote_is_durable_before_broadcast() {
+    let mut system = System::new();
+    let config = System::default_config_without_backlog_population();
+    let node = system.build_node().config(config).finish();
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), true)
+        .unwrap();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node.ledger
+        .process(&mut node.ledger.rw_txn(), &send)
+        .unwrap();
+
+    let root = (*DEV_GENESIS_HASH).into();
+    node.vote_generators
+        .generate_non_final_vote(&root, &send.hash());
+
+    // The vote is added to the local history right before it is broadcast
+    assert_timely(Duration::from_secs(3), || {
+        !node.history.votes(&root, &send.hash(), false).is_empty()
+    });
+
+    // Copy the database as it is on disk while the node keeps running, like after a crash
+    let crash_path = unique_path().unwrap();
+    std::fs::create_dir_all(&crash_path).unwrap();
+    let crash_file = crash_path.join("data.ldb");
+    std::fs::copy(node.data_path.join("data.ldb"), &crash_file).unwrap();
+
+    let store = LmdbStore::open(&crash_file).build().unwrap();
+    let votes: Vec<_> = store.local_vote.iter(&store.tx_begin_read()).collect();
+    assert!(votes
+        .iter()
+        .any(|(r, (hash, _))| *r == root && *hash == send.hash()));
+
+    drop(store);
+    std::fs::remove_dir_all(crash_path).unwrap();
+}
+
+#[test]
+fn vote_with_key_from_key_file() {
+    let key_path = unique_path().unwrap().join("rep.key");
+    std::fs::write(
+        &key_path,
+        format!("{}\n", DEV_GENESIS_KEY.raw_key().encode_hex()),
+    )
+    .unwrap();
+
+    let mut system = System::new();
+    let mut config = System::default_config_without_backlog_population();
+    config.voting_policies = vec![RepVotingPolicy {
+        key_file: Some(key_path),
+        ..RepVotingPolicy::new(*DEV_GENESIS_ACCOUNT)
+    }];
+    let node = system.build_node().config(config).finish();
+    assert_eq!(node.wallets.voting_reps_count(), 0);
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node.process(send.clone()).unwrap();
+    start_election(&node, &send.hash());
+
+    // The key file representative votes in the election and confirms the block
+    assert_timely(Duration::from_secs(5), || {
+        node.block_confirmed(&send.hash())
+    });
+    assert!(!node
+        .history
+        .votes(&(*DEV_GENESIS_HASH).into(), &send.hash(), true)
+        .is_empty());
+}
+
+#[test]
+fn disabled_representative_doesnt_vote() {
+    let mut system = System::new();
+    let mut config = System::default_config_without_backlog_population();
+    config.voting_policies = vec![RepVotingPolicy {
+        enabled: false,
+        ..RepVotingPolicy::new(*DEV_GENESIS_ACCOUNT)
+    }];
+    let node = system.build_node().config(config).finish();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), true)
+        .unwrap();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice
+        .genesis()
+        .send(&*DEV_GENESIS_KEY, Amount::nano(1000));
+    node.ledger
+        .process(&mut node.ledger.rw_txn(), &send)
+        .unwrap();
+    node.vote_generators
+        .generate_non_final_vote(&(*DEV_GENESIS_HASH).into(), &send.hash());
+
+    assert_timely(Duration::from_secs(3), || {
+        node.stats.count(
+            StatType::VoteGenerator,
+            DetailType::GeneratorPolicyDisabled,
+            Direction::In,
+        ) > 0
+    });
+    assert!(node
+        .history
+        .votes(&(*DEV_GENESIS_HASH).into(), &send.hash(), false)
+        .is_empty());
+}
@@ -1,6 +1,6 @@
 use rsban_core::{
-    deterministic_key, Account, Amount, Block, BlockHash, Epoch, EpochBlockArgs,
-    KeyDerivationFunction, PrivateKey, PublicKey, RawKey, UnsavedBlockLatticeBuilder,
+    deterministic_key, Account, Amount, Block, BlockHash, DerivationScheme, Epoch, EpochBlockArgs,
+    KeyDerivationFunction, PrivateKey, PublicKey, RawKey, Slip10Key, UnsavedBlockLatticeBuilder,
     DEV_GENESIS_KEY,
 };
 use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
@@ -8,10 +8,10 @@ use rsban_node::{
     config::{NodeConfig, NodeFlags},
     consensus::ActiveElectionsExt,
     unique_path,
-    wallets::{WalletsError, WalletsExt},
+    wallets::{WalletBackup, WalletsError, WalletsExt},
     Node, DEV_NETWORK_PARAMS,
 };
-use rsban_store_lmdb::{LmdbEnv, LmdbWalletStore};
+use rsban_store_lmdb::{LmdbEnv, LmdbWalletStore, Transaction, WalletValue};
 use std::{
     collections::HashSet,
     path::PathBuf,
@@ -884,6 +884,214 @@ fn deterministic_keys() {
     assert_eq!(wallet.exists(&tx, &key9.public_key()), true);
 }
 
+#[test]
+fn bip44_deterministic_keys() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let wallet = LmdbWalletStore::new(
+        0,
+        kdf.clone(),
+        &mut tx,
+        &DEV_GENESIS_PUB_KEY,
+        &PathBuf::from("0"),
+    )
+    .unwrap();
+    assert_eq!(wallet.derivation_scheme(&tx), DerivationScheme::Legacy);
+
+    let legacy_seed = wallet.seed(&tx);
+    let root = Slip10Key::banano_root(&[1; 64]);
+    wallet.set_bip44_seed(&mut tx, &root);
+    assert_eq!(wallet.derivation_scheme(&tx), DerivationScheme::Bip44);
+    assert_eq!(wallet.bip44_root(&tx), root);
+    assert_eq!(wallet.seed(&tx), legacy_seed);
+    assert_eq!(wallet.deterministic_key(&tx, 0), root.derive(0).key);
+    assert_eq!(wallet.deterministic_key(&tx, 5), root.derive(5).key);
+
+    let account = wallet.deterministic_insert(&mut tx);
+    assert_eq!(wallet.fetch(&tx, &account).unwrap(), root.derive(0).key);
+    assert_eq!(wallet.accounts(&tx), vec![account.into()]);
+
+    let seed = RawKey::from(42);
+    wallet.set_seed(&mut tx, &seed);
+    assert_eq!(wallet.derivation_scheme(&tx), DerivationScheme::Legacy);
+    assert_eq!(
+        wallet.deterministic_key(&tx, 0),
+        deterministic_key(&seed, 0)
+    );
+}
+
+#[test]
+fn bip44_chain_code_uses_random_iv() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let wallet =
+        LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("0")).unwrap();
+    let root = Slip10Key::banano_root(&[1; 64]);
+
+    wallet.set_bip44_seed(&mut tx, &root);
+    let ciphertext1 = wallet
+        .entry_get_raw(&tx, &LmdbWalletStore::chain_code_special())
+        .key;
+    wallet.set_bip44_seed(&mut tx, &root);
+    let ciphertext2 = wallet
+        .entry_get_raw(&tx, &LmdbWalletStore::chain_code_special())
+        .key;
+
+    assert_ne!(ciphertext1, ciphertext2);
+    assert_eq!(wallet.chain_code(&tx), root.chain_code);
+}
+
+#[test]
+fn upgrade_v4_wallet() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let seed = RawKey::from(42);
+    let adhoc = PrivateKey::from(43);
+    let watched = PrivateKey::from(44).public_key();
+    let representative = PrivateKey::from(45).public_key();
+    let (deterministic1, deterministic2) = {
+        let wallet = LmdbWalletStore::new(
+            0,
+            kdf.clone(),
+            &mut tx,
+            &DEV_GENESIS_PUB_KEY,
+            &PathBuf::from("0"),
+        )
+        .unwrap();
+        wallet.set_seed(&mut tx, &seed);
+        let deterministic1 = wallet.deterministic_insert(&mut tx);
+        let deterministic2 = wallet.deterministic_insert(&mut tx);
+        wallet.insert_adhoc(&mut tx, &adhoc.raw_key());
+        wallet.insert_watch(&mut tx, &watched).unwrap();
+        wallet.representative_set(&mut tx, &representative);
+        // Version 4 wallets have no derivation scheme entry
+        wallet.erase(&mut tx, &LmdbWalletStore::derivation_special());
+        wallet.version_put(&mut tx, 4);
+        (deterministic1, deterministic2)
+    };
+
+    let wallet =
+        LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("0")).unwrap();
+
+    assert_eq!(wallet.version(&tx), LmdbWalletStore::VERSION_CURRENT);
+    assert_eq!(wallet.derivation_scheme(&tx), DerivationScheme::Legacy);
+    assert_eq!(wallet.seed(&tx), seed);
+    assert_eq!(wallet.representative(&tx), representative);
+    assert_eq!(wallet.deterministic_index_get(&tx), 2);
+    assert_eq!(
+        wallet.fetch(&tx, &deterministic2).unwrap(),
+        deterministic_key(&seed, 1)
+    );
+    assert_eq!(
+        wallet.fetch(&tx, &adhoc.public_key()).unwrap(),
+        adhoc.raw_key()
+    );
+    let mut accounts = wallet.accounts(&tx);
+    accounts.sort();
+    let mut expected: Vec<Account> = vec![
+        deterministic1.into(),
+        deterministic2.into(),
+        adhoc.account(),
+        watched.into(),
+    ];
+    expected.sort();
+    assert_eq!(accounts, expected);
+}
+
+#[test]
+fn upgrade_v4_fails_if_reserved_keys_are_used() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let reserved = PublicKey::from(8);
+    let handle = {
+        let wallet = LmdbWalletStore::new(
+            0,
+            kdf.clone(),
+            &mut tx,
+            &DEV_GENESIS_PUB_KEY,
+            &PathBuf::from("0"),
+        )
+        .unwrap();
+        wallet.erase(&mut tx, &LmdbWalletStore::derivation_special());
+        // A version 4 wallet could hold a watch only account at a now reserved key
+        wallet.entry_put_raw(&mut tx, &reserved, &WalletValue::new(RawKey::zero(), 0));
+        wallet.version_put(&mut tx, 4);
+        wallet.db_handle()
+    };
+
+    let result = LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("0"));
+    assert!(result.is_err());
+
+    // The wallet is left untouched
+    let version = tx
+        .get(handle, LmdbWalletStore::version_special().as_bytes())
+        .unwrap();
+    assert_eq!(version[31], 4);
+    assert!(tx.get(handle, reserved.as_bytes()).is_ok());
+}
+
+#[test]
+fn backup_and_restore_bip44_wallet() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let wallet = LmdbWalletStore::new(
+        0,
+        kdf.clone(),
+        &mut tx,
+        &DEV_GENESIS_PUB_KEY,
+        &PathBuf::from("0"),
+    )
+    .unwrap();
+    wallet.set_bip44_seed(&mut tx, &Slip10Key::banano_root(&[1; 64]));
+    wallet.deterministic_insert(&mut tx);
+    let removed = wallet.deterministic_insert(&mut tx);
+    wallet.deterministic_insert(&mut tx);
+    wallet.erase(&mut tx, &removed);
+    wallet.insert_adhoc(&mut tx, &RawKey::from(7));
+    wallet
+        .insert_watch(&mut tx, &PrivateKey::from(8).public_key())
+        .unwrap();
+
+    let backup = WalletBackup::from_store(&wallet, &tx).unwrap();
+    let encrypted = backup.encrypt("secret");
+    let decrypted = WalletBackup::decrypt(&encrypted, "secret").unwrap();
+    assert_eq!(decrypted, backup);
+
+    let restored =
+        LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("1")).unwrap();
+    decrypted.restore(&restored, &mut tx).unwrap();
+
+    assert_eq!(restored.derivation_scheme(&tx), DerivationScheme::Bip44);
+    assert_eq!(restored.deterministic_index_get(&tx), 3);
+    let mut expected = wallet.accounts(&tx);
+    let mut accounts = restored.accounts(&tx);
+    expected.sort();
+    accounts.sort();
+    assert_eq!(accounts, expected);
+    assert_eq!(accounts.len(), 4);
+    for account in &accounts {
+        assert_eq!(
+            restored.fetch(&tx, &account.into()).ok(),
+            wallet.fetch(&tx, &account.into()).ok()
+        );
+    }
+}
+
 #[test]
 fn reseed() {
     let mut test_file = unique_path().unwrap();
@@ -1094,6 +1302,27 @@ fn change_seed() {
     assert!(node1.wallets.exists(&pub_key));
 }
 
+#[test]
+fn legacy_seed_operations_fail_on_bip44_wallet() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    let root = Slip10Key::banano_root(&[1; 64]);
+    node.wallets.change_bip44_seed(wallet_id, &root, 1).unwrap();
+
+    assert_eq!(
+        node.wallets.get_seed(wallet_id),
+        Err(WalletsError::Bip44Wallet)
+    );
+    assert_eq!(
+        node.wallets.change_seed(wallet_id, &RawKey::from(1), 0),
+        Err(WalletsError::Bip44Wallet)
+    );
+    assert!(node
+        .wallets
+        .exists(&PublicKey::try_from(&root.derive(0).key).unwrap()));
+}
+
 #[test]
 fn epoch_2_validation() {
     let mut system = System::new();
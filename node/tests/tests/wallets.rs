@@ -1,12 +1,23 @@
-use rsban_core::{Amount, PrivateKey, UnsavedBlockLatticeBuilder, WalletId, DEV_GENESIS_KEY};
+use rsban_core::{
+    utils::seconds_since_epoch, Amount, BlockHash, PrivateKey, UnsavedBlockLatticeBuilder,
+    WalletId, DEV_GENESIS_KEY,
+};
 use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
 use rsban_node::{
     config::{NodeConfig, NodeFlags},
     consensus::ActiveElectionsExt,
-    wallets::WalletsExt,
+    unique_path,
+    wallets::{
+        PendingSend, ReceivePolicy, SendIdRecord, SendIdStatus, SendRequest, SpendingLimit,
+        Wallets, WalletsExt, WatchReceive,
+    },
+};
+use rsban_store_lmdb::LmdbEnv;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
 };
-use std::time::Duration;
-use test_helpers::{assert_timely, assert_timely_eq, System};
+use test_helpers::{assert_always_eq, assert_timely, assert_timely_eq, System};
 
 #[test]
 fn open_create() {
@@ -189,3 +200,566 @@ fn search_receivable() {
         assert_eq!(send.hash(), receive.source().unwrap());
     }
 }
+
+#[test]
+fn send_id_is_recorded() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let hash = node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_ACCOUNT,
+        Amount::raw(1),
+        0,
+        true,
+        Some("payout-1".to_string()),
+    );
+    assert!(!hash.is_zero());
+
+    let info = node.wallets.send_id_info("payout-1").unwrap().unwrap();
+    assert_eq!(info.record.hash, hash);
+    assert_eq!(info.record.wallet_id, wallet_id);
+    assert_ne!(info.status, SendIdStatus::Failed);
+
+    // Sending again with the same id must not create a second block
+    let hash2 = node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_ACCOUNT,
+        Amount::raw(1),
+        0,
+        true,
+        Some("payout-1".to_string()),
+    );
+    assert_eq!(hash2, hash);
+    assert_eq!(
+        node.wallets.send_ids_of_wallet(&wallet_id).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn send_id_of_queued_block_is_not_reused() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let path = unique_path().unwrap();
+    std::fs::create_dir_all(&path).unwrap();
+    let env = Arc::new(LmdbEnv::new(&path.join("wallets.ldb")).unwrap());
+    let mut wallets = Wallets::new_null_with_env(env.clone(), node.runtime.clone());
+    wallets.initialize().unwrap();
+    let wallets = Arc::new(wallets);
+    let wallet_id = WalletId::random();
+    wallets.create(wallet_id);
+    wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    let wallet = Arc::clone(wallets.mutex.lock().unwrap().get(&wallet_id).unwrap());
+    let send = || {
+        wallets.send_action(
+            &wallet,
+            *DEV_GENESIS_ACCOUNT,
+            *DEV_GENESIS_ACCOUNT,
+            Amount::raw(1),
+            0,
+            true,
+            Some("payout-1".to_string()),
+        )
+    };
+
+    // The first block was handed to the block processor but isn't in the ledger yet
+    let mut record = SendIdRecord {
+        wallet_id,
+        hash: BlockHash::from(42),
+        created: seconds_since_epoch(),
+        rejected: false,
+    };
+    wallets
+        .set_send_id(&mut env.tx_begin_write(), "payout-1", &record)
+        .unwrap();
+    let info = wallets.send_id_info("payout-1").unwrap().unwrap();
+    assert_eq!(info.status, SendIdStatus::Pending);
+    let error = send().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "send id payout-1 is still being processed"
+    );
+
+    // After the grace period the block is considered lost and the id can be retried
+    record.created -= SendIdRecord::PENDING_GRACE_PERIOD_SECS;
+    wallets
+        .set_send_id(&mut env.tx_begin_write(), "payout-1", &record)
+        .unwrap();
+    let info = wallets.send_id_info("payout-1").unwrap().unwrap();
+    assert_eq!(info.status, SendIdStatus::Failed);
+    let error = send().unwrap_err();
+    assert_eq!(error.to_string(), "insufficient balance");
+
+    drop(wallet);
+    drop(wallets);
+    drop(env);
+    std::fs::remove_dir_all(path).unwrap();
+}
+
+#[test]
+fn purge_send_ids() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_ACCOUNT,
+        Amount::raw(1),
+        0,
+        true,
+        Some("payout-1".to_string()),
+    );
+
+    assert_eq!(node.wallets.purge_send_ids(0), 0);
+    assert_eq!(node.wallets.purge_send_ids(u64::MAX), 1);
+    assert!(node.wallets.send_id_info("payout-1").unwrap().is_none());
+}
+
+#[test]
+fn receive_policy_disables_auto_receive() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let merchant = WalletId::random();
+    node.wallets.create(merchant);
+    let merchant_key = PrivateKey::from(1);
+    node.wallets
+        .insert_adhoc2(&merchant, &merchant_key.raw_key(), false)
+        .unwrap();
+    node.wallets
+        .set_receive_policy(
+            &merchant,
+            ReceivePolicy {
+                auto_receive: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let other = WalletId::random();
+    node.wallets.create(other);
+    let other_key = PrivateKey::from(2);
+    node.wallets
+        .insert_adhoc2(&other, &other_key.raw_key(), false)
+        .unwrap();
+
+    let send = node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        merchant_key.account(),
+        node.config.receive_minimum,
+        0,
+        true,
+        None,
+    );
+    assert_timely(Duration::from_secs(5), || node.block_confirmed(&send));
+
+    // A wallet without policy still receives automatically
+    node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        other_key.account(),
+        node.config.receive_minimum,
+        0,
+        true,
+        None,
+    );
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.balance(&other_key.account()),
+        node.config.receive_minimum,
+    );
+
+    assert_eq!(node.balance(&merchant_key.account()), Amount::zero());
+    assert!(!node.wallets.receive_policy(&merchant).unwrap().auto_receive);
+
+    // An explicit search ignores the policy
+    node.wallets.search_receivable_wallet(merchant).unwrap();
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.balance(&merchant_key.account()),
+        node.config.receive_minimum,
+    );
+}
+
+#[test]
+fn hourly_receive_limit_holds_for_receivables_found_in_one_search() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    // The sends are confirmed before the destination is in a wallet, so that
+    // a single search finds all of them
+    let merchant_key = PrivateKey::from(1);
+    let amount = node.config.receive_minimum;
+    for _ in 0..3 {
+        let send = node.wallets.send_sync(
+            wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            merchant_key.account(),
+            amount,
+            0,
+            true,
+            None,
+        );
+        assert_timely(Duration::from_secs(5), || node.block_confirmed(&send));
+    }
+
+    let merchant = WalletId::random();
+    node.wallets.create(merchant);
+    node.wallets
+        .set_receive_policy(
+            &merchant,
+            ReceivePolicy {
+                max_receive_per_hour: Some(amount * 2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    node.wallets
+        .insert_adhoc2(&merchant, &merchant_key.raw_key(), false)
+        .unwrap();
+
+    node.wallets.search_receivable_all();
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.balance(&merchant_key.account()),
+        amount * 2,
+    );
+    assert_always_eq(
+        Duration::from_millis(500),
+        || node.balance(&merchant_key.account()),
+        amount * 2,
+    );
+    assert_eq!(node.wallets.received_last_hour(&merchant), amount * 2);
+}
+
+#[test]
+fn watch_only_account_notifies_receive() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let watched = WalletId::random();
+    node.wallets.create(watched);
+    let watched_account = PrivateKey::from(1).account();
+    node.wallets
+        .insert_watch(&watched, &[watched_account])
+        .unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events2 = events.clone();
+    node.wallets.on_watch_receive(Box::new(move |event| {
+        events2.lock().unwrap().push(event.clone());
+    }));
+
+    let send = node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        watched_account,
+        Amount::raw(42),
+        0,
+        true,
+        None,
+    );
+
+    assert_timely_eq(Duration::from_secs(5), || events.lock().unwrap().len(), 1);
+    assert_eq!(
+        events.lock().unwrap()[0],
+        WatchReceive {
+            wallet_id: watched,
+            account: watched_account,
+            hash: send,
+            source: *DEV_GENESIS_ACCOUNT,
+            amount: Amount::raw(42),
+        }
+    );
+}
+
+#[test]
+fn spending_limits() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    node.wallets
+        .set_spending_limit(
+            &wallet_id,
+            Some(*DEV_GENESIS_ACCOUNT),
+            SpendingLimit {
+                per_transaction: Some(Amount::raw(100)),
+                daily: Some(Amount::raw(150)),
+            },
+            None,
+        )
+        .unwrap();
+    let destination = PrivateKey::from(1).account();
+
+    let send = |amount| {
+        node.wallets.send_sync(
+            wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            destination,
+            amount,
+            0,
+            true,
+            None,
+        )
+    };
+
+    assert!(send(Amount::raw(101)).is_zero());
+    assert!(!send(Amount::raw(100)).is_zero());
+    assert!(send(Amount::raw(51)).is_zero());
+    assert!(!send(Amount::raw(50)).is_zero());
+    assert_eq!(
+        node.wallets.spent_last_day(&wallet_id, None).unwrap(),
+        Amount::raw(150)
+    );
+
+    // Removing the limit allows sends again
+    node.wallets
+        .set_spending_limit(
+            &wallet_id,
+            Some(*DEV_GENESIS_ACCOUNT),
+            SpendingLimit::default(),
+            None,
+        )
+        .unwrap();
+    assert!(!send(Amount::raw(51)).is_zero());
+}
+
+#[test]
+fn send_approval() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    let destination = PrivateKey::from(1).account();
+
+    // The wallet password can't be used for approval
+    assert!(node.wallets.enable_send_approval(&wallet_id, "").is_err());
+    node.wallets
+        .enable_send_approval(&wallet_id, "second factor")
+        .unwrap();
+
+    assert!(node
+        .wallets
+        .send_action2(
+            &wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            destination,
+            Amount::raw(100),
+            0,
+            true,
+            None
+        )
+        .is_err());
+
+    let pending = pending_send(node.wallets.request_send(
+        &wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        destination,
+        Amount::raw(100),
+        0,
+        None,
+    ));
+    assert_eq!(
+        node.wallets.pending_sends(&wallet_id).unwrap(),
+        vec![pending.clone()]
+    );
+
+    assert!(node
+        .wallets
+        .approve_send(&wallet_id, &pending.id, "wrong")
+        .is_err());
+    let block = node
+        .wallets
+        .approve_send(&wallet_id, &pending.id, "second factor")
+        .unwrap();
+    assert_eq!(block.destination_or_link(), destination);
+    assert!(node.block_exists(&block.hash()));
+    assert!(node.wallets.pending_sends(&wallet_id).unwrap().is_empty());
+
+    // A pending send can only be approved once
+    assert!(node
+        .wallets
+        .approve_send(&wallet_id, &pending.id, "second factor")
+        .is_err());
+
+    let rejected = pending_send(node.wallets.request_send(
+        &wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        destination,
+        Amount::raw(100),
+        0,
+        None,
+    ));
+    // Rejecting requires the approval password as well
+    assert!(node
+        .wallets
+        .reject_send(&wallet_id, &rejected.id, "wrong")
+        .is_err());
+    node.wallets
+        .reject_send(&wallet_id, &rejected.id, "second factor")
+        .unwrap();
+    assert!(node.wallets.pending_sends(&wallet_id).unwrap().is_empty());
+
+    node.wallets
+        .disable_send_approval(&wallet_id, "second factor")
+        .unwrap();
+    assert!(node
+        .wallets
+        .send_action2(
+            &wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            destination,
+            Amount::raw(100),
+            0,
+            true,
+            None
+        )
+        .is_ok());
+}
+
+#[test]
+fn failed_approval_keeps_pending_send() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    let key = PrivateKey::from(1);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.raw_key(), false)
+        .unwrap();
+    node.wallets
+        .enable_send_approval(&wallet_id, "second factor")
+        .unwrap();
+
+    // The account has no balance yet, so the send block can't be built
+    let pending = pending_send(node.wallets.request_send(
+        &wallet_id,
+        key.account(),
+        *DEV_GENESIS_ACCOUNT,
+        Amount::raw(100),
+        0,
+        None,
+    ));
+    assert!(node
+        .wallets
+        .approve_send(&wallet_id, &pending.id, "second factor")
+        .is_err());
+    assert_eq!(
+        node.wallets.pending_sends(&wallet_id).unwrap(),
+        vec![pending]
+    );
+}
+
+#[test]
+fn repeated_send_request_with_send_id() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    node.wallets
+        .enable_send_approval(&wallet_id, "second factor")
+        .unwrap();
+    let destination = PrivateKey::from(1).account();
+    let request = || {
+        node.wallets.request_send(
+            &wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            destination,
+            Amount::raw(100),
+            0,
+            Some("payout-1".to_string()),
+        )
+    };
+
+    let pending = pending_send(request());
+    assert_eq!(pending_send(request()), pending);
+    assert_eq!(node.wallets.pending_sends(&wallet_id).unwrap().len(), 1);
+
+    let block = node
+        .wallets
+        .approve_send(&wallet_id, &pending.id, "second factor")
+        .unwrap();
+    assert_eq!(request().unwrap(), SendRequest::Sent(block.hash()));
+    assert!(node.wallets.pending_sends(&wallet_id).unwrap().is_empty());
+}
+
+#[test]
+fn pending_sends_survive_restart() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    node.wallets
+        .enable_send_approval(&wallet_id, "second factor")
+        .unwrap();
+    let pending = pending_send(node.wallets.request_send(
+        &wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        PrivateKey::from(1).account(),
+        Amount::raw(100),
+        0,
+        Some("payout-1".to_string()),
+    ));
+
+    // Load a copy of the wallet store, like a restarted node does
+    let copy_path = unique_path().unwrap();
+    std::fs::create_dir_all(&copy_path).unwrap();
+    let copy_file = copy_path.join("wallets.ldb");
+    std::fs::copy(node.data_path.join("wallets.ldb"), &copy_file).unwrap();
+    let mut wallets = Wallets::new_null_with_env(
+        Arc::new(LmdbEnv::new(&copy_file).unwrap()),
+        node.runtime.clone(),
+    );
+    wallets.initialize().unwrap();
+
+    assert_eq!(wallets.pending_sends(&wallet_id).unwrap(), vec![pending]);
+
+    drop(wallets);
+    std::fs::remove_dir_all(copy_path).unwrap();
+}
+
+fn pending_send(request: anyhow::Result<SendRequest>) -> PendingSend {
+    match request.unwrap() {
+        SendRequest::Pending(pending) => pending,
+        SendRequest::Sent(hash) => panic!("send was already sent in block {}", hash),
+    }
+}
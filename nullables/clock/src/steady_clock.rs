@@ -1,5 +1,6 @@
 use std::{
     ops::{Add, Sub},
+    sync::atomic::{AtomicI64, Ordering},
     time::{Duration, Instant},
 };
 
@@ -10,13 +11,28 @@ pub struct SteadyClock {
 impl SteadyClock {
     pub fn new_null() -> Self {
         Self {
-            time_source: TimeSource::Stub(DEFAULT_STUB_DURATION),
+            time_source: TimeSource::Stub(AtomicI64::new(DEFAULT_STUB_DURATION)),
         }
     }
 
     pub fn now(&self) -> Timestamp {
         Timestamp(self.time_source.now())
     }
+
+    pub fn is_nulled(&self) -> bool {
+        matches!(self.time_source, TimeSource::Stub(_))
+    }
+
+    /// Moves a nulled clock forward. This makes it possible to run components
+    /// on a virtual time
+    pub fn advance(&self, duration: Duration) {
+        match &self.time_source {
+            TimeSource::Stub(value) => {
+                value.fetch_add(duration.as_millis() as i64, Ordering::SeqCst);
+            }
+            TimeSource::System(_) => panic!("only a nulled clock can be advanced"),
+        }
+    }
 }
 
 impl Default for SteadyClock {
@@ -29,14 +45,14 @@ impl Default for SteadyClock {
 
 enum TimeSource {
     System(Instant),
-    Stub(i64),
+    Stub(AtomicI64),
 }
 
 impl TimeSource {
     fn now(&self) -> i64 {
         match self {
             TimeSource::System(instant) => instant.elapsed().as_millis() as i64,
-            TimeSource::Stub(value) => *value,
+            TimeSource::Stub(value) => value.load(Ordering::SeqCst),
         }
     }
 }
@@ -141,5 +157,13 @@ mod tests {
             let now2 = clock.now();
             assert_eq!(now1, now2);
         }
+
+        #[test]
+        fn advance() {
+            let clock = SteadyClock::new_null();
+            let now1 = clock.now();
+            clock.advance(Duration::from_millis(300));
+            assert_eq!(clock.now(), now1 + Duration::from_millis(300));
+        }
     }
 }
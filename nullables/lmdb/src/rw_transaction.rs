@@ -1,4 +1,5 @@
 use super::{ConfiguredDatabase, LmdbDatabase, RoCursor};
+use crate::EMPTY_DATABASE;
 use lmdb::DatabaseFlags;
 
 pub struct RwTransaction {
@@ -201,9 +202,10 @@ impl RwTransactionStub {
     }
 
     fn open_ro_cursor(&self, database: LmdbDatabase) -> lmdb::Result<RoCursor> {
-        Ok(RoCursor::new_null(
-            self.databases.iter().find(|db| db.dbi == database).unwrap(),
-        ))
+        match self.databases.iter().find(|db| db.dbi == database) {
+            Some(db) => Ok(RoCursor::new_null(db)),
+            None => Ok(RoCursor::new_null(&EMPTY_DATABASE)),
+        }
     }
 
     fn create_db(&self, _name: Option<&str>, _flags: DatabaseFlags) -> lmdb::Result<LmdbDatabase> {
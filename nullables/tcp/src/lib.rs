@@ -1,5 +1,7 @@
+mod socks5;
 mod tcp_stream;
 mod tcp_stream_factory;
 
+pub use socks5::*;
 pub use tcp_stream::TcpStream;
 pub use tcp_stream_factory::TcpStreamFactory;
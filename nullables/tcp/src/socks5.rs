@@ -0,0 +1,318 @@
+use std::{
+    io::{Error, ErrorKind},
+    net::{IpAddr, SocketAddr},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+const VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const USERNAME_PASSWORD: u8 = 2;
+const NO_ACCEPTABLE_METHODS: u8 = 0xff;
+const CONNECT: u8 = 1;
+const ADDRESS_IPV4: u8 = 1;
+const ADDRESS_DOMAIN: u8 = 3;
+const ADDRESS_IPV6: u8 = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Socks5Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// SOCKS5 proxy (RFC 1928) which is used for outgoing connections
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Socks5Proxy {
+    pub address: SocketAddr,
+    pub credentials: Option<Socks5Credentials>,
+}
+
+impl Socks5Proxy {
+    pub fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            credentials: None,
+        }
+    }
+
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some(Socks5Credentials {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+
+    /// Connects to the proxy and asks it to open a connection to `target`
+    pub async fn connect(&self, target: SocketAddr) -> tokio::io::Result<TcpStream> {
+        let mut stream = TcpStream::connect(self.address).await?;
+        self.negotiate_method(&mut stream).await?;
+        send_connect(&mut stream, target).await?;
+        Ok(stream)
+    }
+
+    async fn negotiate_method(&self, stream: &mut TcpStream) -> tokio::io::Result<()> {
+        let greeting: &[u8] = match &self.credentials {
+            Some(_) => &[VERSION, 2, NO_AUTHENTICATION, USERNAME_PASSWORD],
+            None => &[VERSION, 1, NO_AUTHENTICATION],
+        };
+        stream.write_all(greeting).await?;
+
+        let mut reply = [0; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != VERSION {
+            return Err(proxy_error("invalid SOCKS version"));
+        }
+        match (reply[1], &self.credentials) {
+            (NO_AUTHENTICATION, _) => Ok(()),
+            (USERNAME_PASSWORD, Some(credentials)) => authenticate(stream, credentials).await,
+            (NO_ACCEPTABLE_METHODS, _) => Err(proxy_error(
+                "proxy doesn't accept the authentication methods",
+            )),
+            _ => Err(proxy_error("proxy selected an unsupported method")),
+        }
+    }
+}
+
+/// Username/password authentication (RFC 1929)
+async fn authenticate(
+    stream: &mut TcpStream,
+    credentials: &Socks5Credentials,
+) -> tokio::io::Result<()> {
+    let username = credentials.username.as_bytes();
+    let password = credentials.password.as_bytes();
+    if username.len() > u8::MAX as usize || password.len() > u8::MAX as usize {
+        return Err(proxy_error("proxy username or password too long"));
+    }
+
+    let mut request = Vec::with_capacity(3 + username.len() + password.len());
+    request.push(1);
+    request.push(username.len() as u8);
+    request.extend_from_slice(username);
+    request.push(password.len() as u8);
+    request.extend_from_slice(password);
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0 {
+        return Err(proxy_error("proxy authentication failed"));
+    }
+    Ok(())
+}
+
+async fn send_connect(stream: &mut TcpStream, target: SocketAddr) -> tokio::io::Result<()> {
+    let mut request = vec![VERSION, CONNECT, 0];
+    match target.ip() {
+        IpAddr::V4(ip) => {
+            request.push(ADDRESS_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ipv4) => {
+                request.push(ADDRESS_IPV4);
+                request.extend_from_slice(&ipv4.octets());
+            }
+            None => {
+                request.push(ADDRESS_IPV6);
+                request.extend_from_slice(&ip.octets());
+            }
+        },
+    }
+    request.extend_from_slice(&target.port().to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err(proxy_error("invalid SOCKS version"));
+    }
+    if reply[1] != 0 {
+        return Err(Error::new(
+            ErrorKind::ConnectionRefused,
+            format!(
+                "proxy could not connect to {}: {}",
+                target,
+                reply_message(reply[1])
+            ),
+        ));
+    }
+
+    // Skip the bound address
+    let address_len = match reply[3] {
+        ADDRESS_IPV4 => 4,
+        ADDRESS_IPV6 => 16,
+        ADDRESS_DOMAIN => stream.read_u8().await? as usize,
+        _ => return Err(proxy_error("invalid address type in proxy reply")),
+    };
+    let mut bound_address = vec![0; address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+    Ok(())
+}
+
+fn reply_message(code: u8) -> &'static str {
+    match code {
+        1 => "general failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+fn proxy_error(message: &str) -> Error {
+    Error::other(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV6};
+    use tokio::{net::TcpListener, spawn};
+
+    /// Minimal SOCKS5 server which accepts a single connection and answers
+    /// with "hello" instead of connecting to the target.
+    /// Returns the requested target address
+    async fn run_proxy(
+        listener: TcpListener,
+        credentials: Option<(&'static str, &'static str)>,
+    ) -> Option<SocketAddr> {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut header = [0; 2];
+        stream.read_exact(&mut header).await.unwrap();
+        let mut methods = vec![0; header[1] as usize];
+        stream.read_exact(&mut methods).await.unwrap();
+
+        if let Some((username, password)) = credentials {
+            if !methods.contains(&USERNAME_PASSWORD) {
+                stream
+                    .write_all(&[VERSION, NO_ACCEPTABLE_METHODS])
+                    .await
+                    .unwrap();
+                return None;
+            }
+            stream
+                .write_all(&[VERSION, USERNAME_PASSWORD])
+                .await
+                .unwrap();
+            let mut version_and_len = [0; 2];
+            stream.read_exact(&mut version_and_len).await.unwrap();
+            let mut user = vec![0; version_and_len[1] as usize];
+            stream.read_exact(&mut user).await.unwrap();
+            let pass_len = stream.read_u8().await.unwrap();
+            let mut pass = vec![0; pass_len as usize];
+            stream.read_exact(&mut pass).await.unwrap();
+            let ok = user == username.as_bytes() && pass == password.as_bytes();
+            stream
+                .write_all(&[1, if ok { 0 } else { 1 }])
+                .await
+                .unwrap();
+            if !ok {
+                return None;
+            }
+        } else {
+            stream
+                .write_all(&[VERSION, NO_AUTHENTICATION])
+                .await
+                .unwrap();
+        }
+
+        let mut request = [0; 4];
+        stream.read_exact(&mut request).await.unwrap();
+        let ip: IpAddr = match request[3] {
+            ADDRESS_IPV4 => {
+                let mut ip = [0; 4];
+                stream.read_exact(&mut ip).await.unwrap();
+                Ipv4Addr::from(ip).into()
+            }
+            _ => {
+                let mut ip = [0; 16];
+                stream.read_exact(&mut ip).await.unwrap();
+                Ipv6Addr::from(ip).into()
+            }
+        };
+        let port = stream.read_u16().await.unwrap();
+        stream
+            .write_all(&[VERSION, 0, 0, ADDRESS_IPV4, 0, 0, 0, 0, 0, 0])
+            .await
+            .unwrap();
+        stream.write_all(b"hello").await.unwrap();
+        Some(SocketAddr::new(ip, port))
+    }
+
+    async fn start_proxy(
+        credentials: Option<(&'static str, &'static str)>,
+    ) -> (SocketAddr, tokio::task::JoinHandle<Option<SocketAddr>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        (address, spawn(run_proxy(listener, credentials)))
+    }
+
+    const TARGET: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 7071);
+
+    #[tokio::test]
+    async fn connect_without_authentication() {
+        let (address, proxy) = start_proxy(None).await;
+        let mut stream = Socks5Proxy::new(address).connect(TARGET).await.unwrap();
+
+        let mut buffer = [0; 5];
+        stream.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"hello");
+        assert_eq!(proxy.await.unwrap(), Some(TARGET));
+    }
+
+    #[tokio::test]
+    async fn connect_with_credentials() {
+        let (address, proxy) = start_proxy(Some(("user", "secret"))).await;
+        Socks5Proxy::new(address)
+            .with_credentials("user", "secret")
+            .connect(TARGET)
+            .await
+            .unwrap();
+        assert_eq!(proxy.await.unwrap(), Some(TARGET));
+    }
+
+    #[tokio::test]
+    async fn ipv4_mapped_target_is_sent_as_ipv4() {
+        let (address, proxy) = start_proxy(None).await;
+        let mapped = SocketAddrV6::new(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped(), 7071, 0, 0);
+        Socks5Proxy::new(address)
+            .connect(mapped.into())
+            .await
+            .unwrap();
+        assert_eq!(proxy.await.unwrap(), Some(TARGET));
+    }
+
+    #[tokio::test]
+    async fn wrong_credentials() {
+        let (address, _proxy) = start_proxy(Some(("user", "secret"))).await;
+        let result = Socks5Proxy::new(address)
+            .with_credentials("user", "wrong")
+            .connect(TARGET)
+            .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "proxy authentication failed"
+        );
+    }
+
+    #[tokio::test]
+    async fn credentials_required() {
+        let (address, _proxy) = start_proxy(Some(("user", "secret"))).await;
+        let result = Socks5Proxy::new(address).connect(TARGET).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "proxy doesn't accept the authentication methods"
+        );
+    }
+}
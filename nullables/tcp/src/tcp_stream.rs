@@ -21,6 +21,14 @@ impl TcpStream {
         }
     }
 
+    /// A stream that was opened through a proxy. `peer_addr` returns the
+    /// address of the target instead of the proxy address
+    pub fn new_proxied(stream: tokio::net::TcpStream, target: SocketAddr) -> Self {
+        Self {
+            stream: Box::new(ProxiedTcpStream { stream, target }),
+        }
+    }
+
     pub fn new_null() -> Self {
         Self {
             stream: Box::new(TcpStreamStub::new(TEST_ENDPOINT_1, Vec::new())),
@@ -112,6 +120,42 @@ impl InternalTcpStream for TokioTcpStreamWrapper {
     }
 }
 
+struct ProxiedTcpStream {
+    stream: tokio::net::TcpStream,
+    target: SocketAddr,
+}
+
+#[async_trait]
+impl InternalTcpStream for ProxiedTcpStream {
+    async fn readable(&self) -> tokio::io::Result<()> {
+        self.stream.readable().await
+    }
+
+    fn try_read(&self, buf: &mut [u8]) -> tokio::io::Result<usize> {
+        self.stream.try_read(buf)
+    }
+
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.stream.local_addr()
+    }
+
+    fn peer_addr(&self) -> std::io::Result<SocketAddr> {
+        Ok(self.target)
+    }
+
+    async fn writable(&self) -> tokio::io::Result<()> {
+        self.stream.writable().await
+    }
+
+    fn try_write(&self, buf: &[u8]) -> tokio::io::Result<usize> {
+        self.stream.try_write(buf)
+    }
+
+    async fn shutdown(&mut self) -> tokio::io::Result<()> {
+        self.stream.shutdown().await
+    }
+}
+
 struct TcpStreamStub {
     incoming: Vec<u8>,
     position: AtomicUsize,
@@ -1,4 +1,5 @@
 use super::tcp_stream::TcpStream;
+use crate::Socks5Proxy;
 use async_trait::async_trait;
 use std::net::{SocketAddr, ToSocketAddrs};
 
@@ -13,6 +14,13 @@ impl TcpStreamFactory {
         }
     }
 
+    /// Opens all connections through the given SOCKS5 proxy
+    pub fn with_proxy(proxy: Socks5Proxy) -> Self {
+        Self {
+            inner: Box::new(ProxyTcpStreamFactory { proxy }),
+        }
+    }
+
     pub fn new_null() -> Self {
         Self {
             inner: Box::new(NullTcpStreamFactory {}),
@@ -59,6 +67,18 @@ impl InternalTcpStreamFactory for TcpStreamFactoryWrapper {
     }
 }
 
+struct ProxyTcpStreamFactory {
+    proxy: Socks5Proxy,
+}
+
+#[async_trait]
+impl InternalTcpStreamFactory for ProxyTcpStreamFactory {
+    async fn connect(&self, addr: SocketAddr) -> tokio::io::Result<TcpStream> {
+        let tokio_stream = self.proxy.connect(addr).await?;
+        Ok(TcpStream::new_proxied(tokio_stream, addr))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
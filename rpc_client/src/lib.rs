@@ -3,12 +3,13 @@ use anyhow::{anyhow, Ok, Result};
 use reqwest::Client;
 pub use reqwest::Url;
 use rsban_core::{
-    Account, Amount, BlockHash, HashOrAccount, JsonBlock, PublicKey, RawKey, WalletId, WorkNonce,
+    Account, Amount, BlockHash, HashOrAccount, JsonBlock, PublicKey, RawKey, Root, WalletId,
+    WorkNonce,
 };
 use rsban_rpc_messages::*;
 use serde::Serialize;
 use serde_json::Value;
-use std::time::Duration;
+use std::{net::Ipv6Addr, time::Duration};
 
 pub struct NanoRpcClient {
     url: Url,
@@ -96,7 +97,8 @@ impl NanoRpcClient {
         &self,
         args: RepresentativesOnlineArgs,
     ) -> Result<RepresentativesOnlineResponse> {
-        let detailed = args.weight.unwrap_or(false.into()).inner();
+        let detailed = args.weight.unwrap_or(false.into()).inner()
+            || args.details.unwrap_or(false.into()).inner();
         let cmd = RpcCommand::representatives_online(args);
         let result = self.request_raw(&cmd).await?;
         if detailed {
@@ -297,6 +299,19 @@ impl NanoRpcClient {
         self.request(&cmd).await
     }
 
+    /// Moves accounts out of a wallet which requires send approval
+    pub async fn account_move_with_approval(
+        &self,
+        wallet: WalletId,
+        source: WalletId,
+        accounts: Vec<Account>,
+        approval_password: impl Into<String>,
+    ) -> Result<MovedResponse> {
+        let args = AccountMoveArgs::new(wallet, source, accounts)
+            .with_approval_password(approval_password);
+        self.request(&RpcCommand::AccountMove(args)).await
+    }
+
     pub async fn account_list(&self, wallet: WalletId) -> Result<AccountsRpcMessage> {
         self.request(&RpcCommand::account_list(wallet)).await
     }
@@ -314,8 +329,11 @@ impl NanoRpcClient {
         self.request(&cmd).await
     }
 
-    pub async fn wallet_destroy(&self, wallet: WalletId) -> Result<DestroyedResponse> {
-        self.request(&RpcCommand::wallet_destroy(wallet)).await
+    pub async fn wallet_destroy(
+        &self,
+        args: impl Into<WalletWithApprovalArgs>,
+    ) -> Result<DestroyedResponse> {
+        self.request(&RpcCommand::wallet_destroy(args)).await
     }
 
     pub async fn wallet_lock(&self, wallet: WalletId) -> Result<LockedResponse> {
@@ -371,6 +389,91 @@ impl NanoRpcClient {
         self.request(&RpcCommand::uptime()).await
     }
 
+    pub async fn peer_exclusions(&self) -> Result<PeerExclusionsResponse> {
+        self.request(&RpcCommand::peer_exclusions()).await
+    }
+
+    pub async fn peer_ban(&self, args: PeerBanArgs) -> Result<PeerBanResponse> {
+        self.request(&RpcCommand::peer_ban(args)).await
+    }
+
+    pub async fn peer_unban(&self, address: impl Into<String>) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::peer_unban(PeerUnbanArgs::new(address)))
+            .await
+    }
+
+    pub async fn peer_drop(&self, args: PeerDropArgs) -> Result<PeerDropResponse> {
+        self.request(&RpcCommand::peer_drop(args)).await
+    }
+
+    pub async fn peer_pin(&self, address: Ipv6Addr, port: u16) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::peer_pin(AddressWithPortArgs::new(
+            address, port,
+        )))
+        .await
+    }
+
+    pub async fn peer_unpin(&self, address: Ipv6Addr, port: u16) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::peer_unpin(AddressWithPortArgs::new(
+            address, port,
+        )))
+        .await
+    }
+
+    pub async fn peers_pinned(&self) -> Result<PeersPinnedResponse> {
+        self.request(&RpcCommand::peers_pinned()).await
+    }
+
+    pub async fn representative_stats(
+        &self,
+        args: RepresentativeStatsArgs,
+    ) -> Result<RepresentativeStatsResponse> {
+        self.request(&RpcCommand::representative_stats(args)).await
+    }
+
+    pub async fn forks_active(&self, args: ForksActiveArgs) -> Result<ForksDto> {
+        self.request(&RpcCommand::forks_active(args)).await
+    }
+
+    pub async fn forks_history(&self, args: ForksHistoryArgs) -> Result<ForksDto> {
+        self.request(&RpcCommand::forks_history(args)).await
+    }
+
+    pub async fn election_trace(&self, args: ElectionTraceArgs) -> Result<ElectionTracesDto> {
+        self.request(&RpcCommand::election_trace(args)).await
+    }
+
+    pub async fn election_start(&self, args: ElectionStartArgs) -> Result<StartedResponse> {
+        self.request(&RpcCommand::election_start(args)).await
+    }
+
+    pub async fn election_info(&self, args: ElectionArgs) -> Result<ElectionInfoDto> {
+        self.request(&RpcCommand::election_info(args)).await
+    }
+
+    pub async fn election_cancel(&self, args: ElectionArgs) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::election_cancel(args)).await
+    }
+
+    pub async fn final_vote_count(&self) -> Result<CountResponse> {
+        self.request(&RpcCommand::FinalVoteCount).await
+    }
+
+    pub async fn final_vote_info(&self, root: Root) -> Result<FinalVoteInfoDto> {
+        self.request(&RpcCommand::final_vote_info(root)).await
+    }
+
+    pub async fn final_vote_prune(&self, depth: u64) -> Result<FinalVotePruneResponse> {
+        self.request(&RpcCommand::final_vote_prune(depth)).await
+    }
+
+    pub async fn quorum_simulate(
+        &self,
+        changes: Vec<WeightChangeDto>,
+    ) -> Result<QuorumSimulateResponse> {
+        self.request(&RpcCommand::quorum_simulate(changes)).await
+    }
+
     pub async fn frontier_count(&self) -> Result<CountResponse> {
         self.request(&RpcCommand::FrontierCount).await
     }
@@ -443,8 +546,11 @@ impl NanoRpcClient {
         self.request(&RpcCommand::wallet_info(wallet)).await
     }
 
-    pub async fn wallet_export(&self, wallet: WalletId) -> Result<JsonResponse> {
-        self.request(&RpcCommand::wallet_export(wallet)).await
+    pub async fn wallet_export(
+        &self,
+        args: impl Into<WalletWithApprovalArgs>,
+    ) -> Result<JsonResponse> {
+        self.request(&RpcCommand::wallet_export(args)).await
     }
 
     pub async fn password_change(
@@ -626,10 +732,144 @@ impl NanoRpcClient {
         self.request(&RpcCommand::Receive(args)).await
     }
 
+    /// Fails if the wallet requires send approval. Use `request_send` for such wallets
     pub async fn send(&self, args: SendArgs) -> Result<BlockDto> {
+        match self.request_send(args).await? {
+            SendResponse::Block(block) => Ok(block),
+            SendResponse::PendingApproval(pending) => {
+                Err(anyhow!("Send {} waits for approval", pending.id))
+            }
+        }
+    }
+
+    /// Returns the pending send instead of a block if the wallet requires send approval
+    pub async fn request_send(&self, args: SendArgs) -> Result<SendResponse> {
         self.request(&RpcCommand::send(args)).await
     }
 
+    pub async fn send_id_info(&self, id: impl Into<String>) -> Result<SendIdInfoDto> {
+        self.request(&RpcCommand::send_id_info(id)).await
+    }
+
+    pub async fn wallet_send_ids(&self, wallet: WalletId) -> Result<WalletSendIdsResponse> {
+        self.request(&RpcCommand::wallet_send_ids(wallet)).await
+    }
+
+    pub async fn mnemonic_to_seed(
+        &self,
+        mnemonic: impl Into<String>,
+        passphrase: Option<String>,
+    ) -> Result<MnemonicToSeedResponse> {
+        self.request(&RpcCommand::mnemonic_to_seed(mnemonic, passphrase))
+            .await
+    }
+
+    pub async fn wallet_create_mnemonic(
+        &self,
+        passphrase: Option<String>,
+        derivation: Option<DerivationSchemeDto>,
+    ) -> Result<WalletCreateMnemonicResponse> {
+        self.request(&RpcCommand::wallet_create_mnemonic(passphrase, derivation))
+            .await
+    }
+
+    pub async fn wallet_restore_mnemonic(
+        &self,
+        args: WalletRestoreMnemonicArgs,
+    ) -> Result<WalletCreateResponse> {
+        self.request(&RpcCommand::wallet_restore_mnemonic(args))
+            .await
+    }
+
+    pub async fn wallet_backup(&self, args: WalletBackupArgs) -> Result<WalletBackupResponse> {
+        self.request(&RpcCommand::wallet_backup(args)).await
+    }
+
+    pub async fn wallet_restore(
+        &self,
+        backup: impl Into<String>,
+        password: impl Into<String>,
+        wallet_password: impl Into<String>,
+    ) -> Result<WalletRpcMessage> {
+        self.request(&RpcCommand::wallet_restore(
+            backup,
+            password,
+            wallet_password,
+        ))
+        .await
+    }
+
+    pub async fn wallet_receive_policy(
+        &self,
+        wallet: WalletId,
+    ) -> Result<WalletReceivePolicyResponse> {
+        self.request(&RpcCommand::wallet_receive_policy(wallet))
+            .await
+    }
+
+    pub async fn wallet_receive_policy_set(
+        &self,
+        args: WalletReceivePolicySetArgs,
+    ) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::wallet_receive_policy_set(args))
+            .await
+    }
+
+    pub async fn wallet_spending_limits(
+        &self,
+        wallet: WalletId,
+    ) -> Result<WalletSpendingLimitsResponse> {
+        self.request(&RpcCommand::wallet_spending_limits(wallet))
+            .await
+    }
+
+    pub async fn wallet_spending_limit_set(
+        &self,
+        args: WalletSpendingLimitSetArgs,
+    ) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::wallet_spending_limit_set(args))
+            .await
+    }
+
+    pub async fn wallet_send_approval_set(
+        &self,
+        wallet: WalletId,
+        enabled: bool,
+        approval_password: impl Into<String>,
+    ) -> Result<SuccessResponse> {
+        let args = WalletSendApprovalSetArgs::new(wallet, enabled, approval_password);
+        self.request(&RpcCommand::wallet_send_approval_set(args))
+            .await
+    }
+
+    pub async fn wallet_pending_sends(
+        &self,
+        wallet: WalletId,
+    ) -> Result<WalletPendingSendsResponse> {
+        self.request(&RpcCommand::wallet_pending_sends(wallet))
+            .await
+    }
+
+    pub async fn send_approve(
+        &self,
+        wallet: WalletId,
+        id: impl Into<String>,
+        approval_password: impl Into<String>,
+    ) -> Result<BlockDto> {
+        let args = SendApproveArgs::new(wallet, id, approval_password);
+        self.request(&RpcCommand::send_approve(args)).await
+    }
+
+    pub async fn send_reject(
+        &self,
+        wallet: WalletId,
+        id: impl Into<String>,
+        approval_password: impl Into<String>,
+    ) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::send_reject(wallet, id, approval_password))
+            .await
+    }
+
     pub async fn send_receive(
         &self,
         wallet: WalletId,
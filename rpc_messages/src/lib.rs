@@ -35,7 +35,7 @@ pub enum RpcCommand {
     ActiveDifficulty,
     WalletCreate(WalletCreateArgs),
     WalletContains(WalletWithAccountArgs),
-    WalletDestroy(WalletRpcMessage),
+    WalletDestroy(WalletWithApprovalArgs),
     WalletLock(WalletRpcMessage),
     WalletLocked(WalletRpcMessage),
     AccountBlockCount(AccountArg),
@@ -63,7 +63,7 @@ pub enum RpcCommand {
     WalletFrontiers(WalletRpcMessage),
     Frontiers(FrontiersArgs),
     WalletInfo(WalletRpcMessage),
-    WalletExport(WalletRpcMessage),
+    WalletExport(WalletWithApprovalArgs),
     PasswordChange(WalletWithPasswordArgs),
     PasswordEnter(WalletWithPasswordArgs),
     PasswordValid(WalletRpcMessage),
@@ -122,6 +122,39 @@ pub enum RpcCommand {
     BlockCreate(BlockCreateArgs),
     WorkPeersClear,
     Version,
+    SendIdInfo(SendIdArgs),
+    WalletSendIds(WalletRpcMessage),
+    MnemonicToSeed(MnemonicToSeedArgs),
+    WalletCreateMnemonic(WalletCreateMnemonicArgs),
+    WalletRestoreMnemonic(WalletRestoreMnemonicArgs),
+    WalletBackup(WalletBackupArgs),
+    WalletRestore(WalletRestoreArgs),
+    WalletReceivePolicy(WalletRpcMessage),
+    WalletReceivePolicySet(WalletReceivePolicySetArgs),
+    WalletSpendingLimits(WalletRpcMessage),
+    WalletSpendingLimitSet(WalletSpendingLimitSetArgs),
+    WalletSendApprovalSet(WalletSendApprovalSetArgs),
+    WalletPendingSends(WalletRpcMessage),
+    SendApprove(SendApproveArgs),
+    SendReject(SendRejectArgs),
+    PeerExclusions,
+    PeerBan(PeerBanArgs),
+    PeerUnban(PeerUnbanArgs),
+    PeerDrop(PeerDropArgs),
+    PeerPin(AddressWithPortArgs),
+    PeerUnpin(AddressWithPortArgs),
+    PeersPinned,
+    RepresentativeStats(RepresentativeStatsArgs),
+    ForksActive(ForksActiveArgs),
+    ForksHistory(ForksHistoryArgs),
+    ElectionTrace(ElectionTraceArgs),
+    ElectionStart(ElectionStartArgs),
+    ElectionInfo(ElectionArgs),
+    ElectionCancel(ElectionArgs),
+    FinalVoteCount,
+    FinalVoteInfo(FinalVoteInfoArgs),
+    FinalVotePrune(FinalVotePruneArgs),
+    QuorumSimulate(QuorumSimulateArgs),
 }
 
 pub fn check_error(value: &serde_json::Value) -> Result<(), String> {
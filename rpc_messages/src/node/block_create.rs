@@ -39,6 +39,9 @@ pub struct BlockCreateArgs {
     pub version: Option<WorkVersionDto>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub difficulty: Option<RpcU64>,
+    /// Required if the wallet key is used and the wallet requires send approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_password: Option<String>,
 }
 
 impl BlockCreateArgs {
@@ -71,6 +74,7 @@ impl BlockCreateArgs {
             work,
             version,
             difficulty: difficulty.map(|i| i.into()),
+            approval_password: None,
         }
     }
 }
@@ -125,6 +129,7 @@ mod tests {
             work: Some(WorkNonce::from(work)),
             version: Some(WorkVersionDto::Work1),
             difficulty: None,
+            approval_password: None,
         };
 
         // Create the RpcCommand
@@ -209,6 +214,7 @@ mod tests {
             work: Some(WorkNonce::from(0x123)),
             version: Some(WorkVersionDto::Work1),
             difficulty: None,
+            approval_password: None,
         });
 
         assert_eq!(command, expected_command);
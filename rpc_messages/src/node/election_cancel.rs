@@ -0,0 +1,36 @@
+use crate::{ElectionArgs, RpcCommand};
+
+impl RpcCommand {
+    pub fn election_cancel(args: ElectionArgs) -> Self {
+        Self::ElectionCancel(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::BlockHash;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_election_cancel_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::election_cancel(ElectionArgs::hash(
+                BlockHash::from(1)
+            )))
+            .unwrap(),
+            r#"{
+  "action": "election_cancel",
+  "hash": "0000000000000000000000000000000000000000000000000000000000000001"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_election_cancel_command() {
+        let cmd = RpcCommand::election_cancel(ElectionArgs::hash(BlockHash::from(1)));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{RpcBool, RpcCommand, RpcU32, RpcU64};
+use rsban_core::{Account, Amount, BlockHash, QualifiedRoot};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn election_info(args: ElectionArgs) -> Self {
+        Self::ElectionInfo(args)
+    }
+}
+
+/// Selects an active election either by its qualified root or by one of its blocks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<QualifiedRoot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<BlockHash>,
+}
+
+impl ElectionArgs {
+    pub fn root(root: QualifiedRoot) -> Self {
+        Self {
+            root: Some(root),
+            hash: None,
+        }
+    }
+
+    pub fn hash(hash: BlockHash) -> Self {
+        Self {
+            root: None,
+            hash: Some(hash),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionInfoDto {
+    pub root: QualifiedRoot,
+    pub behavior: String,
+    pub state: String,
+    /// Milliseconds since the election was started
+    pub duration: RpcU64,
+    pub confirmation_requests: RpcU32,
+    pub winner: BlockHash,
+    pub blocks: Vec<ElectionBlockDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionBlockDto {
+    pub hash: BlockHash,
+    pub tally: Amount,
+    pub final_tally: Amount,
+    pub voters: Vec<ElectionVoterDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionVoterDto {
+    pub representative: Account,
+    pub weight: Amount,
+    #[serde(rename = "final")]
+    pub is_final: RpcBool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_election_info_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::election_info(ElectionArgs::hash(
+                BlockHash::from(1)
+            )))
+            .unwrap(),
+            r#"{
+  "action": "election_info",
+  "hash": "0000000000000000000000000000000000000000000000000000000000000001"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_election_info_command() {
+        let cmd = RpcCommand::election_info(ElectionArgs::root(QualifiedRoot::new_test_instance()));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+
+    #[test]
+    fn serialize_election_info_dto() {
+        let dto = ElectionInfoDto {
+            root: QualifiedRoot::new(BlockHash::from(1).into(), BlockHash::from(2)),
+            behavior: "manual".to_owned(),
+            state: "active".to_owned(),
+            duration: 1500.into(),
+            confirmation_requests: 3.into(),
+            winner: BlockHash::from(3),
+            blocks: vec![ElectionBlockDto {
+                hash: BlockHash::from(3),
+                tally: Amount::raw(10),
+                final_tally: Amount::raw(5),
+                voters: vec![ElectionVoterDto {
+                    representative: Account::from(4),
+                    weight: Amount::raw(5),
+                    is_final: true.into(),
+                }],
+            }],
+        };
+        let serialized = to_string_pretty(&dto).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{
+  "root": {
+    "root": "0000000000000000000000000000000000000000000000000000000000000001",
+    "previous": "0000000000000000000000000000000000000000000000000000000000000002"
+  },
+  "behavior": "manual",
+  "state": "active",
+  "duration": "1500",
+  "confirmation_requests": "3",
+  "winner": "0000000000000000000000000000000000000000000000000000000000000003",
+  "blocks": [
+    {
+      "hash": "0000000000000000000000000000000000000000000000000000000000000003",
+      "tally": "10",
+      "final_tally": "5",
+      "voters": [
+        {
+          "representative": "ban_1111111111111111111111111111111111111111111111111116i3bqjdmq",
+          "weight": "5",
+          "final": "true"
+        }
+      ]
+    }
+  ]
+}"#
+        );
+        let deserialized: ElectionInfoDto = from_str(&serialized).unwrap();
+        assert_eq!(deserialized, dto);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::RpcCommand;
+use rsban_core::BlockHash;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn election_start(args: ElectionStartArgs) -> Self {
+        Self::ElectionStart(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionStartArgs {
+    pub hash: BlockHash,
+    /// "manual" (default), "priority", "hinted" or "optimistic"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub behavior: Option<String>,
+}
+
+impl ElectionStartArgs {
+    pub fn new(hash: BlockHash) -> Self {
+        Self {
+            hash,
+            behavior: None,
+        }
+    }
+
+    pub fn with_behavior(hash: BlockHash, behavior: impl Into<String>) -> Self {
+        Self {
+            hash,
+            behavior: Some(behavior.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_election_start_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::election_start(
+                ElectionStartArgs::with_behavior(BlockHash::from(1), "hinted")
+            ))
+            .unwrap(),
+            r#"{
+  "action": "election_start",
+  "hash": "0000000000000000000000000000000000000000000000000000000000000001",
+  "behavior": "hinted"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_election_start_command() {
+        let cmd = RpcCommand::election_start(ElectionStartArgs::new(BlockHash::from(1)));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
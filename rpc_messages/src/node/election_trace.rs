@@ -0,0 +1,159 @@
+use crate::{RpcBool, RpcCommand, RpcU64};
+use rsban_core::{Account, Amount, BlockHash, QualifiedRoot};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn election_trace(args: ElectionTraceArgs) -> Self {
+        Self::ElectionTrace(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ElectionTraceArgs {
+    /// Only return the trace of the election for this block. All traces are returned if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<BlockHash>,
+}
+
+impl ElectionTraceArgs {
+    pub fn for_hash(hash: BlockHash) -> Self {
+        Self { hash: Some(hash) }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionTracesDto {
+    pub traces: Vec<ElectionTraceDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionTraceDto {
+    pub root: QualifiedRoot,
+    pub account: Account,
+    pub hash: BlockHash,
+    pub behavior: String,
+    /// Milliseconds since epoch
+    pub started: RpcU64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<BlockHash>,
+    /// Not set while the election is still active
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    pub dropped_events: RpcU64,
+    pub events: Vec<ElectionTraceEventDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionTraceEventDto {
+    /// Milliseconds since epoch
+    pub time: RpcU64,
+    #[serde(flatten)]
+    pub event: ElectionTraceEventKindDto,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ElectionTraceEventKindDto {
+    Started {
+        behavior: String,
+    },
+    BlockAdded {
+        hash: BlockHash,
+    },
+    Vote {
+        representative: Account,
+        hash: BlockHash,
+        weight: Amount,
+        #[serde(rename = "final")]
+        is_final: RpcBool,
+        channel_id: RpcU64,
+        source: String,
+        code: String,
+    },
+    ConfirmReq {
+        hash: BlockHash,
+        channels: Vec<RpcU64>,
+    },
+    StateChanged {
+        state: String,
+    },
+    Cemented {
+        hash: BlockHash,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_election_trace_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::election_trace(ElectionTraceArgs::for_hash(
+                BlockHash::from(1)
+            )))
+            .unwrap(),
+            r#"{
+  "action": "election_trace",
+  "hash": "0000000000000000000000000000000000000000000000000000000000000001"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_election_trace_command() {
+        let cmd = RpcCommand::election_trace(ElectionTraceArgs::default());
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+
+    #[test]
+    fn serialize_events() {
+        let events = vec![
+            ElectionTraceEventDto {
+                time: 1000.into(),
+                event: ElectionTraceEventKindDto::Vote {
+                    representative: Account::from(1),
+                    hash: BlockHash::from(2),
+                    weight: Amount::raw(3),
+                    is_final: true.into(),
+                    channel_id: 4.into(),
+                    source: "live".to_owned(),
+                    code: "vote".to_owned(),
+                },
+            },
+            ElectionTraceEventDto {
+                time: 1001.into(),
+                event: ElectionTraceEventKindDto::StateChanged {
+                    state: "confirmed".to_owned(),
+                },
+            },
+        ];
+        let serialized = to_string_pretty(&events).unwrap();
+        assert_eq!(
+            serialized,
+            r#"[
+  {
+    "time": "1000",
+    "type": "vote",
+    "representative": "ban_1111111111111111111111111111111111111111111111111113b8661hfk",
+    "hash": "0000000000000000000000000000000000000000000000000000000000000002",
+    "weight": "3",
+    "final": "true",
+    "channel_id": "4",
+    "source": "live",
+    "code": "vote"
+  },
+  {
+    "time": "1001",
+    "type": "state_changed",
+    "state": "confirmed"
+  }
+]"#
+        );
+        let deserialized: Vec<ElectionTraceEventDto> = from_str(&serialized).unwrap();
+        assert_eq!(deserialized, events);
+    }
+}
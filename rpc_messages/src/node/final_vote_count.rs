@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use crate::RpcCommand;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_final_vote_count_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::FinalVoteCount).unwrap(),
+            r#"{
+  "action": "final_vote_count"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_final_vote_count_command() {
+        let deserialized: RpcCommand = from_str(r#"{"action": "final_vote_count"}"#).unwrap();
+        assert_eq!(deserialized, RpcCommand::FinalVoteCount);
+    }
+}
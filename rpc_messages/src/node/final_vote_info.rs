@@ -0,0 +1,79 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::{BlockHash, QualifiedRoot, Root};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn final_vote_info(root: Root) -> Self {
+        Self::FinalVoteInfo(FinalVoteInfoArgs { root })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVoteInfoArgs {
+    pub root: Root,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVoteInfoDto {
+    pub votes: Vec<FinalVoteDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVoteDto {
+    pub root: QualifiedRoot,
+    pub hash: BlockHash,
+    /// How many blocks the account is cemented past the voted block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cemented_depth: Option<RpcU64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_final_vote_info_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::final_vote_info(Root::from(1))).unwrap(),
+            r#"{
+  "action": "final_vote_info",
+  "root": "0000000000000000000000000000000000000000000000000000000000000001"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_final_vote_info_command() {
+        let cmd = RpcCommand::final_vote_info(Root::from(1));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+
+    #[test]
+    fn serialize_final_vote_info_dto() {
+        let dto = FinalVoteInfoDto {
+            votes: vec![FinalVoteDto {
+                root: QualifiedRoot::new(Root::from(1), BlockHash::from(2)),
+                hash: BlockHash::from(3),
+                cemented_depth: Some(4.into()),
+            }],
+        };
+        assert_eq!(
+            to_string_pretty(&dto).unwrap(),
+            r#"{
+  "votes": [
+    {
+      "root": {
+        "root": "0000000000000000000000000000000000000000000000000000000000000001",
+        "previous": "0000000000000000000000000000000000000000000000000000000000000002"
+      },
+      "hash": "0000000000000000000000000000000000000000000000000000000000000003",
+      "cemented_depth": "4"
+    }
+  ]
+}"#
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{RpcCommand, RpcU64};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn final_vote_prune(depth: u64) -> Self {
+        Self::FinalVotePrune(FinalVotePruneArgs {
+            depth: depth.into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVotePruneArgs {
+    /// Deletes final votes whose account is cemented at least this many blocks past the voted block
+    pub depth: RpcU64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVotePruneResponse {
+    pub deleted: RpcU64,
+}
+
+impl FinalVotePruneResponse {
+    pub fn new(deleted: u64) -> Self {
+        Self {
+            deleted: deleted.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_final_vote_prune_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::final_vote_prune(10)).unwrap(),
+            r#"{
+  "action": "final_vote_prune",
+  "depth": "10"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_final_vote_prune_command() {
+        let cmd = RpcCommand::final_vote_prune(10);
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::{Account, Amount, BlockHash, QualifiedRoot};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn forks_active(args: ForksActiveArgs) -> Self {
+        Self::ForksActive(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ForksActiveArgs {
+    /// Only return forks of this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+}
+
+impl ForksActiveArgs {
+    pub fn for_account(account: Account) -> Self {
+        Self {
+            account: Some(account),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForksDto {
+    pub forks: Vec<ForkDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForkDto {
+    pub root: QualifiedRoot,
+    pub account: Account,
+    /// Seconds since epoch
+    pub first_seen: RpcU64,
+    pub candidates: Vec<ForkCandidateDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<BlockHash>,
+    /// Seconds since epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<RpcU64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForkCandidateDto {
+    pub hash: BlockHash,
+    /// Seconds since epoch
+    pub first_seen: RpcU64,
+    pub tally: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_forks_active_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::forks_active(ForksActiveArgs::for_account(
+                Account::from(1)
+            )))
+            .unwrap(),
+            r#"{
+  "action": "forks_active",
+  "account": "ban_1111111111111111111111111111111111111111111111111113b8661hfk"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_forks_active_command() {
+        let cmd = RpcCommand::forks_active(ForksActiveArgs::default());
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+
+    #[test]
+    fn serialize_forks_dto() {
+        let forks = ForksDto {
+            forks: vec![ForkDto {
+                root: QualifiedRoot::new(BlockHash::from(1).into(), BlockHash::from(2)),
+                account: Account::from(3),
+                first_seen: 1000.into(),
+                candidates: vec![ForkCandidateDto {
+                    hash: BlockHash::from(4),
+                    first_seen: 1000.into(),
+                    tally: Amount::raw(5),
+                }],
+                winner: Some(BlockHash::from(4)),
+                resolved: Some(1001.into()),
+            }],
+        };
+        let serialized = to_string_pretty(&forks).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{
+  "forks": [
+    {
+      "root": {
+        "root": "0000000000000000000000000000000000000000000000000000000000000001",
+        "previous": "0000000000000000000000000000000000000000000000000000000000000002"
+      },
+      "account": "ban_11111111111111111111111111111111111111111111111111159bmrz31g",
+      "first_seen": "1000",
+      "candidates": [
+        {
+          "hash": "0000000000000000000000000000000000000000000000000000000000000004",
+          "first_seen": "1000",
+          "tally": "5"
+        }
+      ],
+      "winner": "0000000000000000000000000000000000000000000000000000000000000004",
+      "resolved": "1001"
+    }
+  ]
+}"#
+        );
+        let deserialized: ForksDto = from_str(&serialized).unwrap();
+        assert_eq!(deserialized, forks);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::Account;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn forks_history(args: ForksHistoryArgs) -> Self {
+        Self::ForksHistory(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ForksHistoryArgs {
+    /// Only return forks of this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+    /// Maximum number of forks to return, newest first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<RpcU64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_forks_history_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::forks_history(ForksHistoryArgs {
+                account: None,
+                count: Some(10.into())
+            }))
+            .unwrap(),
+            r#"{
+  "action": "forks_history",
+  "count": "10"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_forks_history_command() {
+        let cmd = RpcCommand::forks_history(ForksHistoryArgs {
+            account: Some(Account::from(1)),
+            count: None,
+        });
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
@@ -7,13 +7,30 @@ mod confirmation_active;
 mod confirmation_history;
 mod confirmation_info;
 mod confirmation_quorum;
+mod election_cancel;
+mod election_info;
+mod election_start;
+mod election_trace;
+mod final_vote_count;
+mod final_vote_info;
+mod final_vote_prune;
+mod forks_active;
+mod forks_history;
 mod keepalive;
 mod node_id;
+mod peer_ban;
+mod peer_drop;
+mod peer_exclusions;
+mod peer_pin;
+mod peer_unban;
 mod peers;
+mod peers_pinned;
 mod populate_backlog;
 mod process;
+mod quorum_simulate;
 mod receivable;
 mod receivable_exists;
+mod representative_stats;
 mod representatives_online;
 mod republish;
 mod sign;
@@ -42,11 +59,25 @@ pub use confirmation_active::*;
 pub use confirmation_history::*;
 pub use confirmation_info::*;
 pub use confirmation_quorum::*;
+pub use election_info::*;
+pub use election_start::*;
+pub use election_trace::*;
+pub use final_vote_info::*;
+pub use final_vote_prune::*;
+pub use forks_active::*;
+pub use forks_history::*;
 pub use node_id::*;
+pub use peer_ban::*;
+pub use peer_drop::*;
+pub use peer_exclusions::*;
+pub use peer_unban::*;
 pub use peers::*;
+pub use peers_pinned::*;
 pub use process::*;
+pub use quorum_simulate::*;
 pub use receivable::*;
 pub use receivable_exists::*;
+pub use representative_stats::*;
 pub use representatives_online::*;
 pub use republish::*;
 pub use sign::*;
@@ -0,0 +1,112 @@
+use crate::{RpcBool, RpcCommand, RpcU64};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn peer_ban(args: PeerBanArgs) -> Self {
+        Self::PeerBan(args)
+    }
+}
+
+/// Bans an IP address or a subnet in CIDR notation (for example "10.0.0.0/8")
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerBanArgs {
+    pub address: String,
+    /// Duration of the ban in seconds. The ban is permanent if omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<RpcU64>,
+    /// Store the ban in the ledger, so that it survives a restart
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persist: Option<RpcBool>,
+}
+
+impl PeerBanArgs {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            duration: None,
+            persist: None,
+        }
+    }
+
+    pub fn builder(address: impl Into<String>) -> PeerBanArgsBuilder {
+        PeerBanArgsBuilder {
+            args: PeerBanArgs::new(address),
+        }
+    }
+}
+
+pub struct PeerBanArgsBuilder {
+    args: PeerBanArgs,
+}
+
+impl PeerBanArgsBuilder {
+    pub fn duration(mut self, seconds: u64) -> Self {
+        self.args.duration = Some(seconds.into());
+        self
+    }
+
+    pub fn persist(mut self) -> Self {
+        self.args.persist = Some(true.into());
+        self
+    }
+
+    pub fn build(self) -> PeerBanArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerBanResponse {
+    /// Number of channels that were closed because of the ban
+    pub closed: RpcU64,
+}
+
+impl PeerBanResponse {
+    pub fn new(closed: u64) -> Self {
+        Self {
+            closed: closed.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_ban_command() {
+        let args = PeerBanArgs::builder("10.0.0.0/8")
+            .duration(3600)
+            .persist()
+            .build();
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_ban(args)).unwrap(),
+            r#"{
+  "action": "peer_ban",
+  "address": "10.0.0.0/8",
+  "duration": "3600",
+  "persist": "true"
+}"#
+        );
+    }
+
+    #[test]
+    fn serialize_peer_ban_command_options_none() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_ban(PeerBanArgs::new("::1"))).unwrap(),
+            r#"{
+  "action": "peer_ban",
+  "address": "::1"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_peer_ban_command() {
+        let cmd = RpcCommand::peer_ban(PeerBanArgs::builder("10.0.0.1").duration(60).build());
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::NodeId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn peer_drop(args: PeerDropArgs) -> Self {
+        Self::PeerDrop(args)
+    }
+}
+
+/// Closes a single channel or all channels to a node
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerDropArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<RpcU64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<NodeId>,
+}
+
+impl PeerDropArgs {
+    pub fn channel(channel_id: u64) -> Self {
+        Self {
+            channel_id: Some(channel_id.into()),
+            node_id: None,
+        }
+    }
+
+    pub fn node(node_id: NodeId) -> Self {
+        Self {
+            channel_id: None,
+            node_id: Some(node_id),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerDropResponse {
+    pub dropped: RpcU64,
+}
+
+impl PeerDropResponse {
+    pub fn new(dropped: u64) -> Self {
+        Self {
+            dropped: dropped.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_drop_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_drop(PeerDropArgs::channel(7))).unwrap(),
+            r#"{
+  "action": "peer_drop",
+  "channel_id": "7"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_peer_drop_command() {
+        let cmd = RpcCommand::peer_drop(PeerDropArgs::node(NodeId::from(42)));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
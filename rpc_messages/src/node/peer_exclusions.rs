@@ -0,0 +1,87 @@
+use crate::{RpcCommand, RpcU64};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn peer_exclusions() -> Self {
+        Self::PeerExclusions
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerExclusionsResponse {
+    pub excluded: Vec<ExcludedPeerDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ExcludedPeerDto {
+    /// IP address or subnet in CIDR notation
+    pub address: String,
+    /// "misbehavior", "banned" or "perma_ban"
+    pub reason: String,
+    pub score: RpcU64,
+    /// End of the exclusion in seconds since epoch. Omitted if the peer is excluded forever
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_until: Option<RpcU64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_exclusions_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_exclusions()).unwrap(),
+            r#"{
+  "action": "peer_exclusions"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_peer_exclusions_command() {
+        let cmd = RpcCommand::peer_exclusions();
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+
+    #[test]
+    fn serialize_peer_exclusions_response() {
+        let response = PeerExclusionsResponse {
+            excluded: vec![
+                ExcludedPeerDto {
+                    address: "10.0.0.0/8".to_owned(),
+                    reason: "banned".to_owned(),
+                    score: 0.into(),
+                    excluded_until: None,
+                },
+                ExcludedPeerDto {
+                    address: "::ffff:10.0.0.1".to_owned(),
+                    reason: "misbehavior".to_owned(),
+                    score: 2.into(),
+                    excluded_until: Some(1000.into()),
+                },
+            ],
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "excluded": [
+    {
+      "address": "10.0.0.0/8",
+      "reason": "banned",
+      "score": "0"
+    },
+    {
+      "address": "::ffff:10.0.0.1",
+      "reason": "misbehavior",
+      "score": "2",
+      "excluded_until": "1000"
+    }
+  ]
+}"#
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{AddressWithPortArgs, RpcCommand};
+
+impl RpcCommand {
+    pub fn peer_pin(args: AddressWithPortArgs) -> Self {
+        Self::PeerPin(args)
+    }
+
+    pub fn peer_unpin(args: AddressWithPortArgs) -> Self {
+        Self::PeerUnpin(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn serialize_peer_pin_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_pin(AddressWithPortArgs::new(
+                Ipv6Addr::LOCALHOST,
+                7075
+            )))
+            .unwrap(),
+            r#"{
+  "action": "peer_pin",
+  "address": "::1",
+  "port": "7075"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_peer_unpin_command() {
+        let cmd = RpcCommand::peer_unpin(AddressWithPortArgs::new(Ipv6Addr::LOCALHOST, 7075));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn peer_unban(args: PeerUnbanArgs) -> Self {
+        Self::PeerUnban(args)
+    }
+}
+
+/// Lifts a ban and forgets the misbehavior of all peers within the subnet
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerUnbanArgs {
+    pub address: String,
+}
+
+impl PeerUnbanArgs {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_unban_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_unban(PeerUnbanArgs::new("10.0.0.0/8"))).unwrap(),
+            r#"{
+  "action": "peer_unban",
+  "address": "10.0.0.0/8"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_peer_unban_command() {
+        let cmd = RpcCommand::peer_unban(PeerUnbanArgs::new("::1"));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
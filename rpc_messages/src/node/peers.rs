@@ -1,6 +1,9 @@
-use crate::{RpcBool, RpcCommand, RpcU8};
+use crate::{RpcBool, RpcCommand, RpcU64, RpcU8};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, net::SocketAddrV6};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddrV6,
+};
 
 impl RpcCommand {
     pub fn peers(peer_details: Option<bool>) -> Self {
@@ -28,6 +31,33 @@ pub struct PeerInfo {
     #[serde(rename = "type")]
     pub connection_type: String,
     pub peering: SocketAddrV6,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    /// Milliseconds since the last successful read or write
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_time_ms: Option<RpcU64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<PeerTrafficDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerTrafficDto {
+    pub bytes_in: RpcU64,
+    pub bytes_out: RpcU64,
+    pub messages_in: RpcU64,
+    pub messages_out: RpcU64,
+    pub queue_drops: RpcU64,
+    pub messages: BTreeMap<String, MessageTrafficDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MessageTrafficDto {
+    pub messages_in: RpcU64,
+    pub bytes_in: RpcU64,
+    pub messages_out: RpcU64,
+    pub bytes_out: RpcU64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -82,6 +112,10 @@ mod tests {
                     .to_string(),
                 connection_type: "tcp".to_string(),
                 peering: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 111, 0, 0),
+                mode: None,
+                direction: None,
+                idle_time_ms: None,
+                traffic: None,
             },
         );
 
@@ -94,6 +128,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_peer_traffic() {
+        let peer = PeerInfo {
+            protocol_version: 18.into(),
+            node_id: "node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3"
+                .to_string(),
+            connection_type: "tcp".to_string(),
+            peering: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 111, 0, 0),
+            mode: Some("realtime".to_string()),
+            direction: Some("inbound".to_string()),
+            idle_time_ms: Some(1500.into()),
+            traffic: Some(PeerTrafficDto {
+                bytes_in: 300.into(),
+                bytes_out: 200.into(),
+                messages_in: 2.into(),
+                messages_out: 1.into(),
+                queue_drops: 3.into(),
+                messages: [(
+                    "publish".to_string(),
+                    MessageTrafficDto {
+                        messages_in: 2.into(),
+                        bytes_in: 300.into(),
+                        messages_out: 1.into(),
+                        bytes_out: 200.into(),
+                    },
+                )]
+                .into(),
+            }),
+        };
+
+        let json = serde_json::to_string(&peer).unwrap();
+        assert_eq!(
+            json,
+            r#"{"protocol_version":"18","node_id":"node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3","type":"tcp","peering":"[::1]:111","mode":"realtime","direction":"inbound","idle_time_ms":"1500","traffic":{"bytes_in":"300","bytes_out":"200","messages_in":"2","messages_out":"1","queue_drops":"3","messages":{"publish":{"messages_in":"2","bytes_in":"300","messages_out":"1","bytes_out":"200"}}}}"#
+        );
+        let deserialized: PeerInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, peer);
+    }
+
     #[test]
     fn deserialize_detailed_peers() {
         let json = r#"{"peers":{"[::ffff:172.17.0.1]:7075":{"protocol_version":"18","node_id":"node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3","type":"tcp","peering":"[::1]:111"}}}"#;
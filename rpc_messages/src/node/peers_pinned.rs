@@ -0,0 +1,40 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddrV6;
+
+impl RpcCommand {
+    pub fn peers_pinned() -> Self {
+        Self::PeersPinned
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeersPinnedResponse {
+    pub peers: Vec<SocketAddrV6>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peers_pinned_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peers_pinned()).unwrap(),
+            r#"{
+  "action": "peers_pinned"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_peers_pinned_response() {
+        let json = r#"{"peers":["[::ffff:10.0.0.1]:7075"]}"#;
+        let response: PeersPinnedResponse = from_str(json).unwrap();
+        assert_eq!(
+            response.peers,
+            vec!["[::ffff:10.0.0.1]:7075".parse::<SocketAddrV6>().unwrap()]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{RpcBool, RpcCommand};
+use rsban_core::{Account, Amount};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn quorum_simulate(changes: Vec<WeightChangeDto>) -> Self {
+        Self::QuorumSimulate(QuorumSimulateArgs { changes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumSimulateArgs {
+    pub changes: Vec<WeightChangeDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WeightChangeDto {
+    /// Delegators of `from` move `amount` of weight to `to`
+    Move {
+        from: Account,
+        to: Account,
+        amount: Amount,
+    },
+    Offline {
+        representative: Account,
+    },
+    Online {
+        representative: Account,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumSimulateResponse {
+    pub before: QuorumSnapshotDto,
+    pub after: QuorumSnapshotDto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumSnapshotDto {
+    pub online_stake_total: Amount,
+    pub trended_stake_total: Amount,
+    pub quorum_delta: Amount,
+    pub principal_representatives: Vec<SimulatedRepDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedRepDto {
+    pub account: Account,
+    pub weight: Amount,
+    pub online: RpcBool,
+    pub tier: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_quorum_simulate_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::quorum_simulate(vec![
+                WeightChangeDto::Move {
+                    from: Account::from(3),
+                    to: Account::from(4),
+                    amount: Amount::raw(1000),
+                },
+                WeightChangeDto::Offline {
+                    representative: Account::from(4)
+                }
+            ]))
+            .unwrap(),
+            r#"{
+  "action": "quorum_simulate",
+  "changes": [
+    {
+      "type": "move",
+      "from": "ban_11111111111111111111111111111111111111111111111111159bmrz31g",
+      "to": "ban_1111111111111111111111111111111111111111111111111116i3bqjdmq",
+      "amount": "1000"
+    },
+    {
+      "type": "offline",
+      "representative": "ban_1111111111111111111111111111111111111111111111111116i3bqjdmq"
+    }
+  ]
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_quorum_simulate_command() {
+        let cmd = RpcCommand::quorum_simulate(vec![WeightChangeDto::Online {
+            representative: Account::from(3),
+        }]);
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{RpcBool, RpcCommand, RpcF64, RpcU64};
+use rsban_core::Account;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn representative_stats(args: RepresentativeStatsArgs) -> Self {
+        Self::RepresentativeStats(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepresentativeStatsArgs {
+    pub account: Account,
+    /// Include the individual history buckets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<RpcBool>,
+}
+
+impl RepresentativeStatsArgs {
+    pub fn new(account: Account) -> Self {
+        Self {
+            account,
+            history: None,
+        }
+    }
+
+    pub fn with_history(account: Account) -> Self {
+        Self {
+            account,
+            history: Some(true.into()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RepresentativeStatsResponse {
+    /// Whether the representative is online right now
+    pub online: RpcBool,
+    /// Percentage of samples in which the representative was online
+    pub uptime: RpcF64,
+    pub samples: RpcU64,
+    pub online_samples: RpcU64,
+    /// Number of confirmed elections while the representative was tracked
+    pub elections: RpcU64,
+    pub votes: RpcU64,
+    pub final_votes: RpcU64,
+    /// Percentage of confirmed elections the representative voted in
+    pub participation: RpcF64,
+    /// Percentage of confirmed elections the representative sent a final vote for
+    pub final_participation: RpcF64,
+    /// Average time between election start and vote arrival in milliseconds
+    pub average_vote_latency: RpcU64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<RepStatsBucketDto>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepStatsBucketDto {
+    /// Start of the bucket in seconds since epoch
+    pub start: RpcU64,
+    pub samples: RpcU64,
+    pub online_samples: RpcU64,
+    pub elections: RpcU64,
+    pub votes: RpcU64,
+    pub final_votes: RpcU64,
+    pub average_vote_latency: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_representative_stats_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::representative_stats(
+                RepresentativeStatsArgs::with_history(Account::from(1))
+            ))
+            .unwrap(),
+            r#"{
+  "action": "representative_stats",
+  "account": "ban_1111111111111111111111111111111111111111111111111113b8661hfk",
+  "history": "true"
+}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_representative_stats_command() {
+        let cmd = RpcCommand::representative_stats(RepresentativeStatsArgs::new(Account::from(1)));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized);
+    }
+
+    #[test]
+    fn serialize_representative_stats_response() {
+        let response = RepresentativeStatsResponse {
+            online: true.into(),
+            uptime: 99.5.into(),
+            samples: 200.into(),
+            online_samples: 199.into(),
+            elections: 10.into(),
+            votes: 9.into(),
+            final_votes: 8.into(),
+            participation: 90.0.into(),
+            final_participation: 80.0.into(),
+            average_vote_latency: 250.into(),
+            history: Some(vec![RepStatsBucketDto {
+                start: 3600.into(),
+                samples: 200.into(),
+                online_samples: 199.into(),
+                elections: 10.into(),
+                votes: 9.into(),
+                final_votes: 8.into(),
+                average_vote_latency: 250.into(),
+            }]),
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "online": "true",
+  "uptime": "99.5",
+  "samples": "200",
+  "online_samples": "199",
+  "elections": "10",
+  "votes": "9",
+  "final_votes": "8",
+  "participation": "90",
+  "final_participation": "80",
+  "average_vote_latency": "250",
+  "history": [
+    {
+      "start": "3600",
+      "samples": "200",
+      "online_samples": "199",
+      "elections": "10",
+      "votes": "9",
+      "final_votes": "8",
+      "average_vote_latency": "250"
+    }
+  ]
+}"#
+        );
+    }
+}
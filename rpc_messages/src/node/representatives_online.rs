@@ -1,7 +1,7 @@
-use crate::{RpcBool, RpcCommand};
+use crate::{RpcBool, RpcCommand, RpcU64, RpcU8};
 use rsban_core::{Account, Amount};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, net::SocketAddrV6};
 
 impl RpcCommand {
     pub fn representatives_online(args: RepresentativesOnlineArgs) -> Self {
@@ -15,6 +15,10 @@ pub struct RepresentativesOnlineArgs {
     pub weight: Option<RpcBool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub accounts: Option<Vec<Account>>,
+    /// Include the weight and the connection details of directly connected representatives,
+    /// including peered representatives that are unresponsive or haven't voted recently
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<RpcBool>,
 }
 
 impl RepresentativesOnlineArgs {
@@ -35,6 +39,11 @@ impl RepresentativesOnlineArgsBuilder {
         self
     }
 
+    pub fn details(mut self) -> Self {
+        self.args.details = Some(true.into());
+        self
+    }
+
     pub fn accounts(mut self, accounts: Vec<Account>) -> Self {
         self.args.accounts = Some(accounts);
         self
@@ -62,9 +71,31 @@ pub struct DetailedRepresentativesOnline {
     pub representatives: HashMap<Account, RepWeightDto>,
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct RepWeightDto {
     pub weight: Amount,
+    /// Peering address of the channel on which the representative answered the rep crawler
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<SocketAddrV6>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<RpcU8>,
+    /// Milliseconds since the last vote of the representative was observed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_vote_elapsed_ms: Option<RpcU64>,
+    /// Milliseconds since the representative last answered a rep crawler query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_response_elapsed_ms: Option<RpcU64>,
+    /// Response time of the last answered rep crawler query in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<RpcU64>,
+    /// Whether a vote of the representative was observed in the current weight period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub online: Option<RpcBool>,
+    /// Whether the representative didn't answer the last rep crawler query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unresponsive: Option<RpcBool>,
 }
 
 #[cfg(test)]
@@ -140,6 +171,7 @@ mod tests {
             .unwrap(),
             RepWeightDto {
                 weight: Amount::raw(150462654614686936429917024683496890),
+                ..Default::default()
             },
         );
         let dto = RepresentativesOnlineResponse::Detailed(detailed);
@@ -192,4 +224,41 @@ mod tests {
         .unwrap();
         assert!(deserialized.representatives.contains(&account));
     }
+
+    #[test]
+    fn serialize_representatives_online_dto_with_details() {
+        let mut detailed = DetailedRepresentativesOnline::default();
+        detailed.representatives.insert(
+            Account::from(3),
+            RepWeightDto {
+                weight: Amount::raw(1000),
+                address: Some("[::ffff:10.0.0.1]:7071".parse().unwrap()),
+                node_id: Some("node_1".to_string()),
+                protocol_version: Some(20.into()),
+                last_vote_elapsed_ms: Some(100.into()),
+                last_response_elapsed_ms: Some(200.into()),
+                latency_ms: Some(30.into()),
+                online: Some(true.into()),
+                unresponsive: Some(false.into()),
+            },
+        );
+        let dto = RepresentativesOnlineResponse::Detailed(detailed);
+        let serialized = serde_json::to_value(&dto).unwrap();
+        let expected = json!({
+            "representatives": {
+                "ban_11111111111111111111111111111111111111111111111111159bmrz31g": {
+                    "weight": "1000",
+                    "address": "[::ffff:10.0.0.1]:7071",
+                    "node_id": "node_1",
+                    "protocol_version": "20",
+                    "last_vote_elapsed_ms": "100",
+                    "last_response_elapsed_ms": "200",
+                    "latency_ms": "30",
+                    "online": "true",
+                    "unresponsive": "false"
+                }
+            }
+        });
+        assert_eq!(serialized, expected);
+    }
 }
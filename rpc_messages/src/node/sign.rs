@@ -16,6 +16,7 @@ impl From<JsonBlock> for SignArgs {
             wallet: None,
             account: None,
             hash: None,
+            approval_password: None,
         }
     }
 }
@@ -32,6 +33,9 @@ pub struct SignArgs {
     pub account: Option<Account>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hash: Option<BlockHash>,
+    /// Required if the wallet key is used and the wallet requires send approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_password: Option<String>,
 }
 
 pub struct SignArgsBuilder {
@@ -54,6 +58,11 @@ impl SignArgsBuilder {
         self
     }
 
+    pub fn approval_password(mut self, password: impl Into<String>) -> Self {
+        self.args.approval_password = Some(password.into());
+        self
+    }
+
     pub fn build(self) -> SignArgs {
         self.args
     }
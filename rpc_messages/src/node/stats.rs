@@ -1,4 +1,6 @@
+use crate::RpcU64;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct StatsArgs {
@@ -13,4 +15,87 @@ pub enum StatsType {
     Objects,
     Samples,
     Database,
+    ConfirmationLatency,
+}
+
+/// Latency histograms keyed by block source and by election behavior
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ConfirmationLatencyDto {
+    pub by_source: BTreeMap<String, LatencyHistogramsDto>,
+    pub by_behavior: BTreeMap<String, LatencyHistogramsDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LatencyHistogramsDto {
+    pub arrival_to_election: LatencyHistogramDto,
+    pub election_to_quorum: LatencyHistogramDto,
+    pub quorum_to_cemented: LatencyHistogramDto,
+    pub arrival_to_cemented: LatencyHistogramDto,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LatencyHistogramDto {
+    pub count: RpcU64,
+    pub sum_ms: RpcU64,
+    pub buckets: Vec<LatencyBucketDto>,
+}
+
+/// Cumulative number of samples that took at most `le` milliseconds
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LatencyBucketDto {
+    pub le: String,
+    pub count: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RpcCommand;
+    use serde_json::{from_str, json, to_string_pretty, to_value};
+
+    #[test]
+    fn serialize_confirmation_latency_stats_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::Stats(StatsArgs {
+                stats_type: StatsType::ConfirmationLatency
+            }))
+            .unwrap(),
+            r#"{
+  "action": "stats",
+  "type": "confirmation_latency"
+}"#
+        );
+    }
+
+    #[test]
+    fn serialize_latency_histogram_dto() {
+        let histogram = LatencyHistogramDto {
+            count: 2.into(),
+            sum_ms: 150.into(),
+            buckets: vec![
+                LatencyBucketDto {
+                    le: "50".to_owned(),
+                    count: 1.into(),
+                },
+                LatencyBucketDto {
+                    le: "+Inf".to_owned(),
+                    count: 2.into(),
+                },
+            ],
+        };
+        let value = to_value(&histogram).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "count": "2",
+                "sum_ms": "150",
+                "buckets": [
+                    { "le": "50", "count": "1" },
+                    { "le": "+Inf", "count": "2" }
+                ]
+            })
+        );
+        let deserialized: LatencyHistogramDto = from_str(&value.to_string()).unwrap();
+        assert_eq!(deserialized, histogram);
+    }
 }
@@ -0,0 +1,79 @@
+use crate::RpcCommand;
+use rsban_core::RawKey;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn mnemonic_to_seed(mnemonic: impl Into<String>, passphrase: Option<String>) -> Self {
+        Self::MnemonicToSeed(MnemonicToSeedArgs::new(mnemonic, passphrase))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MnemonicToSeedArgs {
+    pub mnemonic: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+}
+
+impl MnemonicToSeedArgs {
+    pub fn new(mnemonic: impl Into<String>, passphrase: Option<String>) -> Self {
+        Self {
+            mnemonic: mnemonic.into(),
+            passphrase,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MnemonicToSeedResponse {
+    /// The legacy seed encoded by a 24 word mnemonic (Natrium)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<RawKey>,
+    /// The 64 byte BIP39 seed as hex
+    pub bip39_seed: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_mnemonic_to_seed_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::mnemonic_to_seed(
+                "abandon about",
+                Some("secret".to_owned())
+            ))
+            .unwrap(),
+            r#"{
+  "action": "mnemonic_to_seed",
+  "mnemonic": "abandon about",
+  "passphrase": "secret"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_mnemonic_to_seed_command() {
+        let cmd = RpcCommand::mnemonic_to_seed("abandon about", None);
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_mnemonic_to_seed_response() {
+        let response = MnemonicToSeedResponse {
+            seed: Some(RawKey::zero()),
+            bip39_seed: "00ff".to_owned(),
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "seed": "0000000000000000000000000000000000000000000000000000000000000000",
+  "bip39_seed": "00ff"
+}"#
+        )
+    }
+}
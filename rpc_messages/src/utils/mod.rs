@@ -4,6 +4,7 @@ mod block_hash;
 mod deterministic_key;
 mod key_create;
 mod key_expand;
+mod mnemonic_to_seed;
 mod nano_to_raw;
 mod raw_to_nano;
 mod validate_account_number;
@@ -11,3 +12,4 @@ mod validate_account_number;
 pub use block_hash::*;
 pub use deterministic_key::*;
 pub use key_expand::*;
+pub use mnemonic_to_seed::*;
@@ -4,11 +4,7 @@ use serde::{Deserialize, Serialize};
 
 impl RpcCommand {
     pub fn account_move(wallet: WalletId, source: WalletId, accounts: Vec<Account>) -> Self {
-        Self::AccountMove(AccountMoveArgs {
-            wallet,
-            source,
-            accounts,
-        })
+        Self::AccountMove(AccountMoveArgs::new(wallet, source, accounts))
     }
 }
 
@@ -17,11 +13,30 @@ pub struct AccountMoveArgs {
     pub wallet: WalletId,
     pub source: WalletId,
     pub accounts: Vec<Account>,
+    /// Required if the source wallet requires send approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_password: Option<String>,
+}
+
+impl AccountMoveArgs {
+    pub fn new(wallet: WalletId, source: WalletId, accounts: Vec<Account>) -> Self {
+        Self {
+            wallet,
+            source,
+            accounts,
+            approval_password: None,
+        }
+    }
+
+    pub fn with_approval_password(mut self, password: impl Into<String>) -> Self {
+        self.approval_password = Some(password.into());
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::RpcCommand;
+    use crate::{AccountMoveArgs, RpcCommand};
     use rsban_core::Account;
     use serde_json::{from_str, to_string_pretty, Value};
 
@@ -50,7 +65,10 @@ mod tests {
     #[test]
     fn deserialize_account_remove_command() {
         let account = Account::from(123);
-        let cmd = RpcCommand::account_move(1.into(), 2.into(), vec![account]);
+        let cmd = RpcCommand::AccountMove(
+            AccountMoveArgs::new(1.into(), 2.into(), vec![account])
+                .with_approval_password("second factor"),
+        );
         let serialized = serde_json::to_string_pretty(&cmd).unwrap();
         let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
         assert_eq!(cmd, deserialized)
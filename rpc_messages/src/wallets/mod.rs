@@ -11,12 +11,17 @@ mod receive_minimum;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
+mod send_approve;
+mod send_id_info;
+mod send_reject;
 mod wallet_add;
 mod wallet_add_watch;
+mod wallet_backup;
 mod wallet_balances;
 mod wallet_change_seed;
 mod wallet_contains;
 mod wallet_create;
+mod wallet_create_mnemonic;
 mod wallet_destroy;
 mod wallet_export;
 mod wallet_frontiers;
@@ -25,11 +30,21 @@ mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_pending_sends;
 mod wallet_receivable;
+mod wallet_receive_policy;
+mod wallet_receive_policy_set;
 mod wallet_representative;
 mod wallet_representative_set;
 mod wallet_republish;
+mod wallet_restore;
+mod wallet_restore_mnemonic;
+mod wallet_send_approval_set;
+mod wallet_send_ids;
+mod wallet_spending_limit_set;
+mod wallet_spending_limits;
 mod wallet_with_account;
+mod wallet_with_approval;
 mod wallet_with_count;
 mod wallet_with_password;
 mod wallet_work_get;
@@ -41,19 +56,34 @@ pub use account_move::*;
 pub use accounts_create::*;
 pub use receive::*;
 pub use send::*;
+pub use send_approve::*;
+pub use send_id_info::*;
+pub use send_reject::*;
 pub use wallet_add::*;
 pub use wallet_add_watch::*;
+pub use wallet_backup::*;
 pub use wallet_balances::*;
 pub use wallet_change_seed::*;
 pub use wallet_create::*;
+pub use wallet_create_mnemonic::*;
 pub use wallet_export::*;
 pub use wallet_history::*;
 pub use wallet_info::*;
 pub use wallet_ledger::*;
+pub use wallet_pending_sends::*;
 pub use wallet_receivable::*;
+pub use wallet_receive_policy::*;
+pub use wallet_receive_policy_set::*;
 pub use wallet_representative::*;
 pub use wallet_representative_set::*;
+pub use wallet_restore::*;
+pub use wallet_restore_mnemonic::*;
+pub use wallet_send_approval_set::*;
+pub use wallet_send_ids::*;
+pub use wallet_spending_limit_set::*;
+pub use wallet_spending_limits::*;
 pub use wallet_with_account::*;
+pub use wallet_with_approval::*;
 pub use wallet_with_count::*;
 pub use wallet_with_password::*;
 pub use wallet_work_get::*;
@@ -1,4 +1,4 @@
-use crate::RpcCommand;
+use crate::{BlockDto, PendingSendDto, RpcCommand};
 use rsban_core::{Account, Amount, WalletId, WorkNonce};
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +20,14 @@ pub struct SendArgs {
     pub id: Option<String>,
 }
 
+/// Wallets which require send approval return the pending send instead of a block
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SendResponse {
+    Block(BlockDto),
+    PendingApproval(PendingSendDto),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,6 +69,28 @@ mod tests {
         assert_eq!(serialized, expected);
     }
 
+    #[test]
+    fn deserialize_send_response() {
+        let block: SendResponse = serde_json::from_str(
+            r#"{"block":"000D1BAEC8EC208142C99059B393051BAC8380F9B5A2E6B2489A277D81789F3F"}"#,
+        )
+        .unwrap();
+        assert!(matches!(block, SendResponse::Block(_)));
+
+        let pending: SendResponse = serde_json::from_value(json!({
+            "id": "00000000000000AB",
+            "source": "ban_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3",
+            "destination": "ban_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3",
+            "amount": "1000000",
+            "created": "42"
+        }))
+        .unwrap();
+        let SendResponse::PendingApproval(pending) = pending else {
+            panic!("not a pending send")
+        };
+        assert_eq!(pending.id, "00000000000000AB");
+    }
+
     #[test]
     fn deserialize_send_command() {
         let json_str = r#"{
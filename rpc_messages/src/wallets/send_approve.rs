@@ -0,0 +1,59 @@
+use crate::RpcCommand;
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn send_approve(args: SendApproveArgs) -> Self {
+        Self::SendApprove(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SendApproveArgs {
+    pub wallet: WalletId,
+    /// Id of the pending send
+    pub id: String,
+    pub approval_password: String,
+}
+
+impl SendApproveArgs {
+    pub fn new(
+        wallet: WalletId,
+        id: impl Into<String>,
+        approval_password: impl Into<String>,
+    ) -> Self {
+        Self {
+            wallet,
+            id: id.into(),
+            approval_password: approval_password.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_send_approve_command() {
+        let args = SendApproveArgs::new(WalletId::zero(), "00000000000000AB", "secret");
+        assert_eq!(
+            to_string_pretty(&RpcCommand::send_approve(args)).unwrap(),
+            r#"{
+  "action": "send_approve",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "id": "00000000000000AB",
+  "approval_password": "secret"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_send_approve_command() {
+        let cmd = RpcCommand::send_approve(SendApproveArgs::new(WalletId::from(1), "1", "2"));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
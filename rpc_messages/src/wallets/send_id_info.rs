@@ -0,0 +1,103 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::{BlockHash, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn send_id_info(id: impl Into<String>) -> Self {
+        Self::SendIdInfo(SendIdArgs::new(id))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SendIdArgs {
+    pub id: String,
+}
+
+impl SendIdArgs {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SendIdStatusDto {
+    Pending,
+    Published,
+    Confirmed,
+    Failed,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct SendIdInfoDto {
+    pub id: String,
+    pub wallet: WalletId,
+    pub block: BlockHash,
+    pub status: SendIdStatusDto,
+    pub created: RpcU64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_timestamp: Option<RpcU64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_send_id_info_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::send_id_info("payout-1")).unwrap(),
+            r#"{
+  "action": "send_id_info",
+  "id": "payout-1"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_send_id_info_command() {
+        let cmd = RpcCommand::send_id_info("payout-1");
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_send_id_info_dto() {
+        let dto = SendIdInfoDto {
+            id: "payout-1".to_string(),
+            wallet: WalletId::from(1),
+            block: BlockHash::from(2),
+            status: SendIdStatusDto::Confirmed,
+            created: 3.into(),
+            local_timestamp: Some(4.into()),
+        };
+
+        assert_eq!(
+            to_string_pretty(&dto).unwrap(),
+            r#"{
+  "id": "payout-1",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000001",
+  "block": "0000000000000000000000000000000000000000000000000000000000000002",
+  "status": "confirmed",
+  "created": "3",
+  "local_timestamp": "4"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_send_id_info_dto_without_timestamp() {
+        let json = r#"{
+  "id": "payout-1",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000001",
+  "block": "0000000000000000000000000000000000000000000000000000000000000002",
+  "status": "failed",
+  "created": "3"
+}"#;
+        let dto: SendIdInfoDto = serde_json::from_str(json).unwrap();
+        assert_eq!(dto.status, SendIdStatusDto::Failed);
+        assert_eq!(dto.local_timestamp, None);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::RpcCommand;
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn send_reject(
+        wallet: WalletId,
+        id: impl Into<String>,
+        approval_password: impl Into<String>,
+    ) -> Self {
+        Self::SendReject(SendRejectArgs::new(wallet, id, approval_password))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SendRejectArgs {
+    pub wallet: WalletId,
+    /// Id of the pending send
+    pub id: String,
+    pub approval_password: String,
+}
+
+impl SendRejectArgs {
+    pub fn new(
+        wallet: WalletId,
+        id: impl Into<String>,
+        approval_password: impl Into<String>,
+    ) -> Self {
+        Self {
+            wallet,
+            id: id.into(),
+            approval_password: approval_password.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_send_reject_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::send_reject(
+                WalletId::zero(),
+                "00000000000000AB",
+                "secret"
+            ))
+            .unwrap(),
+            r#"{
+  "action": "send_reject",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "id": "00000000000000AB",
+  "approval_password": "secret"
+}"#
+        )
+    }
+}
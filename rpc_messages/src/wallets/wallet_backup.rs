@@ -0,0 +1,73 @@
+use crate::RpcCommand;
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_backup(args: WalletBackupArgs) -> Self {
+        Self::WalletBackup(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletBackupArgs {
+    pub wallet: WalletId,
+    /// Encrypts the backup. It is independent of the wallet password
+    pub password: String,
+    /// Required if the wallet requires send approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_password: Option<String>,
+}
+
+impl WalletBackupArgs {
+    pub fn new(wallet: WalletId, password: impl Into<String>) -> Self {
+        Self {
+            wallet,
+            password: password.into(),
+            approval_password: None,
+        }
+    }
+
+    pub fn with_approval_password(mut self, password: impl Into<String>) -> Self {
+        self.approval_password = Some(password.into());
+        self
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletBackupResponse {
+    /// The encrypted backup as hex string
+    pub backup: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_backup_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_backup(WalletBackupArgs::new(
+                WalletId::zero(),
+                "secret"
+            )))
+            .unwrap(),
+            r#"{
+  "action": "wallet_backup",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "password": "secret"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_backup_command() {
+        let cmd = RpcCommand::wallet_backup(
+            WalletBackupArgs::new(WalletId::from(1), "secret")
+                .with_approval_password("second factor"),
+        );
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
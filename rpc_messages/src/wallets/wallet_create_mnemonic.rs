@@ -0,0 +1,96 @@
+use crate::RpcCommand;
+use rsban_core::{Account, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_create_mnemonic(
+        passphrase: Option<String>,
+        derivation: Option<DerivationSchemeDto>,
+    ) -> Self {
+        Self::WalletCreateMnemonic(WalletCreateMnemonicArgs::new(passphrase, derivation))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DerivationSchemeDto {
+    Legacy,
+    Bip44,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletCreateMnemonicArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    /// Defaults to bip44
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation: Option<DerivationSchemeDto>,
+}
+
+impl WalletCreateMnemonicArgs {
+    pub fn new(passphrase: Option<String>, derivation: Option<DerivationSchemeDto>) -> Self {
+        Self {
+            passphrase,
+            derivation,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletCreateMnemonicResponse {
+    pub wallet: WalletId,
+    pub mnemonic: String,
+    pub account: Account,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_create_mnemonic_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_create_mnemonic(
+                Some("secret".to_owned()),
+                Some(DerivationSchemeDto::Bip44)
+            ))
+            .unwrap(),
+            r#"{
+  "action": "wallet_create_mnemonic",
+  "passphrase": "secret",
+  "derivation": "bip44"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_wallet_create_mnemonic_command_without_options() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_create_mnemonic(None, None)).unwrap(),
+            r#"{
+  "action": "wallet_create_mnemonic"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_create_mnemonic_command() {
+        let cmd = RpcCommand::wallet_create_mnemonic(None, Some(DerivationSchemeDto::Legacy));
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn deserialize_wallet_create_mnemonic_response() {
+        let response = WalletCreateMnemonicResponse {
+            wallet: WalletId::from(1),
+            mnemonic: "abandon about".to_owned(),
+            account: Account::from(2),
+        };
+        let serialized = serde_json::to_string_pretty(&response).unwrap();
+        let deserialized: WalletCreateMnemonicResponse = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(response, deserialized)
+    }
+}
@@ -1,21 +1,21 @@
-use crate::{common::WalletRpcMessage, RpcCommand};
-use rsban_core::WalletId;
+use crate::{RpcCommand, WalletWithApprovalArgs};
 
 impl RpcCommand {
-    pub fn wallet_destroy(wallet: WalletId) -> Self {
-        Self::WalletDestroy(WalletRpcMessage::new(wallet))
+    pub fn wallet_destroy(args: impl Into<WalletWithApprovalArgs>) -> Self {
+        Self::WalletDestroy(args.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::RpcCommand;
+    use rsban_core::WalletId;
     use serde_json::to_string_pretty;
 
     #[test]
     fn serialize_wallet_destroy_command() {
         assert_eq!(
-            to_string_pretty(&RpcCommand::wallet_destroy(1.into())).unwrap(),
+            to_string_pretty(&RpcCommand::wallet_destroy(WalletId::from(1))).unwrap(),
             r#"{
   "action": "wallet_destroy",
   "wallet": "0000000000000000000000000000000000000000000000000000000000000001"
@@ -25,7 +25,7 @@ mod tests {
 
     #[test]
     fn deserialize_wallet_destroy_command() {
-        let cmd = RpcCommand::wallet_destroy(1.into());
+        let cmd = RpcCommand::wallet_destroy(WalletId::from(1));
         let serialized = serde_json::to_string_pretty(&cmd).unwrap();
         let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
         assert_eq!(cmd, deserialized)
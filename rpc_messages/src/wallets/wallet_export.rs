@@ -1,10 +1,9 @@
-use crate::{common::WalletRpcMessage, RpcCommand};
-use rsban_core::WalletId;
+use crate::{RpcCommand, WalletWithApprovalArgs};
 use serde::{Deserialize, Serialize};
 
 impl RpcCommand {
-    pub fn wallet_export(wallet: WalletId) -> Self {
-        Self::WalletExport(WalletRpcMessage::new(wallet))
+    pub fn wallet_export(args: impl Into<WalletWithApprovalArgs>) -> Self {
+        Self::WalletExport(args.into())
     }
 }
 
@@ -0,0 +1,72 @@
+use crate::{RpcCommand, RpcU64, WalletRpcMessage};
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_pending_sends(wallet: WalletId) -> Self {
+        Self::WalletPendingSends(WalletRpcMessage::new(wallet))
+    }
+}
+
+/// A send which waits for approval
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSendDto {
+    pub id: String,
+    pub source: Account,
+    pub destination: Account,
+    pub amount: Amount,
+    /// The send id which is used once the send is approved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_id: Option<String>,
+    pub created: RpcU64,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletPendingSendsResponse {
+    pub pending: Vec<PendingSendDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_pending_sends_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_pending_sends(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "wallet_pending_sends",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_wallet_pending_sends_response() {
+        let response = WalletPendingSendsResponse {
+            pending: vec![PendingSendDto {
+                id: "00000000000000AB".to_string(),
+                source: Account::zero(),
+                destination: Account::zero(),
+                amount: Amount::raw(100),
+                send_id: None,
+                created: 123.into(),
+            }],
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "pending": [
+    {
+      "id": "00000000000000AB",
+      "source": "ban_1111111111111111111111111111111111111111111111111111hifc8npp",
+      "destination": "ban_1111111111111111111111111111111111111111111111111111hifc8npp",
+      "amount": "100",
+      "created": "123"
+    }
+  ]
+}"#
+        )
+    }
+}
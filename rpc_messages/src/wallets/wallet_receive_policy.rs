@@ -0,0 +1,80 @@
+use crate::{RpcBool, RpcCommand, WalletRpcMessage};
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_receive_policy(wallet: WalletId) -> Self {
+        Self::WalletReceivePolicy(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletReceivePolicyResponse {
+    /// Falls back to the node wide receive_minimum if the wallet has none
+    pub receive_minimum: Amount,
+    pub auto_receive: RpcBool,
+    pub allowed_senders: Vec<Account>,
+    pub denied_senders: Vec<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_receive_per_hour: Option<Amount>,
+    /// Amount received automatically within the last hour. Only tracked while
+    /// `max_receive_per_hour` is set
+    pub received_last_hour: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_receive_policy_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_receive_policy(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "wallet_receive_policy",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_wallet_receive_policy_response() {
+        let response = WalletReceivePolicyResponse {
+            receive_minimum: Amount::raw(100),
+            auto_receive: true.into(),
+            allowed_senders: vec![Account::zero()],
+            denied_senders: Vec::new(),
+            max_receive_per_hour: Some(Amount::raw(1000)),
+            received_last_hour: Amount::raw(200),
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "receive_minimum": "100",
+  "auto_receive": "true",
+  "allowed_senders": [
+    "ban_1111111111111111111111111111111111111111111111111111hifc8npp"
+  ],
+  "denied_senders": [],
+  "max_receive_per_hour": "1000",
+  "received_last_hour": "200"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_receive_policy_response() {
+        let response = WalletReceivePolicyResponse {
+            receive_minimum: Amount::raw(100),
+            auto_receive: false.into(),
+            allowed_senders: Vec::new(),
+            denied_senders: vec![Account::from(1)],
+            max_receive_per_hour: None,
+            received_last_hour: Amount::zero(),
+        };
+        let serialized = to_string_pretty(&response).unwrap();
+        let deserialized: WalletReceivePolicyResponse = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(response, deserialized)
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{RpcBool, RpcCommand};
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_receive_policy_set(args: WalletReceivePolicySetArgs) -> Self {
+        Self::WalletReceivePolicySet(args)
+    }
+}
+
+/// Replaces the receive policy of a wallet. Omitted fields are reset to their defaults.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletReceivePolicySetArgs {
+    pub wallet: WalletId,
+    /// Defaults to the node wide receive_minimum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receive_minimum: Option<Amount>,
+    /// Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_receive: Option<RpcBool>,
+    /// If not empty, only sends from these accounts are received automatically
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_senders: Vec<Account>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_senders: Vec<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_receive_per_hour: Option<Amount>,
+}
+
+impl WalletReceivePolicySetArgs {
+    pub fn new(wallet: WalletId) -> Self {
+        Self {
+            wallet,
+            receive_minimum: None,
+            auto_receive: None,
+            allowed_senders: Vec::new(),
+            denied_senders: Vec::new(),
+            max_receive_per_hour: None,
+        }
+    }
+
+    pub fn builder(wallet: WalletId) -> WalletReceivePolicySetArgsBuilder {
+        WalletReceivePolicySetArgsBuilder {
+            args: WalletReceivePolicySetArgs::new(wallet),
+        }
+    }
+}
+
+pub struct WalletReceivePolicySetArgsBuilder {
+    args: WalletReceivePolicySetArgs,
+}
+
+impl WalletReceivePolicySetArgsBuilder {
+    pub fn receive_minimum(mut self, minimum: Amount) -> Self {
+        self.args.receive_minimum = Some(minimum);
+        self
+    }
+
+    pub fn disable_auto_receive(mut self) -> Self {
+        self.args.auto_receive = Some(false.into());
+        self
+    }
+
+    pub fn allowed_senders(mut self, senders: Vec<Account>) -> Self {
+        self.args.allowed_senders = senders;
+        self
+    }
+
+    pub fn denied_senders(mut self, senders: Vec<Account>) -> Self {
+        self.args.denied_senders = senders;
+        self
+    }
+
+    pub fn max_receive_per_hour(mut self, max: Amount) -> Self {
+        self.args.max_receive_per_hour = Some(max);
+        self
+    }
+
+    pub fn build(self) -> WalletReceivePolicySetArgs {
+        self.args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_receive_policy_set_command() {
+        let args = WalletReceivePolicySetArgs::builder(WalletId::zero())
+            .receive_minimum(Amount::raw(100))
+            .disable_auto_receive()
+            .denied_senders(vec![Account::zero()])
+            .max_receive_per_hour(Amount::raw(1000))
+            .build();
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_receive_policy_set(args)).unwrap(),
+            r#"{
+  "action": "wallet_receive_policy_set",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "receive_minimum": "100",
+  "auto_receive": "false",
+  "denied_senders": [
+    "ban_1111111111111111111111111111111111111111111111111111hifc8npp"
+  ],
+  "max_receive_per_hour": "1000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_receive_policy_set_command() {
+        let args = WalletReceivePolicySetArgs::builder(WalletId::from(1))
+            .allowed_senders(vec![Account::from(2)])
+            .build();
+        let cmd = RpcCommand::wallet_receive_policy_set(args);
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_restore(
+        backup: impl Into<String>,
+        password: impl Into<String>,
+        wallet_password: impl Into<String>,
+    ) -> Self {
+        Self::WalletRestore(WalletRestoreArgs::new(backup, password, wallet_password))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletRestoreArgs {
+    /// Hex encoded backup as returned by `wallet_backup`
+    pub backup: String,
+    /// Password of the backup
+    pub password: String,
+    /// Password that protects the restored wallet
+    pub wallet_password: String,
+}
+
+impl WalletRestoreArgs {
+    pub fn new(
+        backup: impl Into<String>,
+        password: impl Into<String>,
+        wallet_password: impl Into<String>,
+    ) -> Self {
+        Self {
+            backup: backup.into(),
+            password: password.into(),
+            wallet_password: wallet_password.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_restore_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_restore("42414e", "secret", "wallet")).unwrap(),
+            r#"{
+  "action": "wallet_restore",
+  "backup": "42414e",
+  "password": "secret",
+  "wallet_password": "wallet"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_restore_command() {
+        let cmd = RpcCommand::wallet_restore("42414e", "secret", "wallet");
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
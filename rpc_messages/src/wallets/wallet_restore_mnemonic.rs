@@ -0,0 +1,69 @@
+use crate::{DerivationSchemeDto, RpcCommand};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_restore_mnemonic(args: WalletRestoreMnemonicArgs) -> Self {
+        Self::WalletRestoreMnemonic(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletRestoreMnemonicArgs {
+    pub mnemonic: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    /// Defaults to bip44. Use legacy for Natrium mnemonics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation: Option<DerivationSchemeDto>,
+}
+
+impl WalletRestoreMnemonicArgs {
+    pub fn new(mnemonic: impl Into<String>) -> Self {
+        Self {
+            mnemonic: mnemonic.into(),
+            passphrase: None,
+            derivation: None,
+        }
+    }
+
+    pub fn with_passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    pub fn with_derivation(mut self, derivation: DerivationSchemeDto) -> Self {
+        self.derivation = Some(derivation);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_restore_mnemonic_command() {
+        let args = WalletRestoreMnemonicArgs::new("abandon about")
+            .with_passphrase("secret")
+            .with_derivation(DerivationSchemeDto::Legacy);
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_restore_mnemonic(args)).unwrap(),
+            r#"{
+  "action": "wallet_restore_mnemonic",
+  "mnemonic": "abandon about",
+  "passphrase": "secret",
+  "derivation": "legacy"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_restore_mnemonic_command() {
+        let cmd =
+            RpcCommand::wallet_restore_mnemonic(WalletRestoreMnemonicArgs::new("abandon about"));
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
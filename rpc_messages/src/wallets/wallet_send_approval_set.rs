@@ -0,0 +1,59 @@
+use crate::{RpcBool, RpcCommand};
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_send_approval_set(args: WalletSendApprovalSetArgs) -> Self {
+        Self::WalletSendApprovalSet(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletSendApprovalSetArgs {
+    pub wallet: WalletId,
+    pub enabled: RpcBool,
+    /// Must differ from the wallet password
+    pub approval_password: String,
+}
+
+impl WalletSendApprovalSetArgs {
+    pub fn new(wallet: WalletId, enabled: bool, approval_password: impl Into<String>) -> Self {
+        Self {
+            wallet,
+            enabled: enabled.into(),
+            approval_password: approval_password.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_send_approval_set_command() {
+        let args = WalletSendApprovalSetArgs::new(WalletId::zero(), true, "secret");
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_send_approval_set(args)).unwrap(),
+            r#"{
+  "action": "wallet_send_approval_set",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "enabled": "true",
+  "approval_password": "secret"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_send_approval_set_command() {
+        let cmd = RpcCommand::wallet_send_approval_set(WalletSendApprovalSetArgs::new(
+            WalletId::from(1),
+            false,
+            "secret",
+        ));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{RpcCommand, SendIdInfoDto, WalletRpcMessage};
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_send_ids(wallet: WalletId) -> Self {
+        Self::WalletSendIds(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletSendIdsResponse {
+    pub send_ids: Vec<SendIdInfoDto>,
+}
+
+impl WalletSendIdsResponse {
+    pub fn new(send_ids: Vec<SendIdInfoDto>) -> Self {
+        Self { send_ids }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_send_ids_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_send_ids(1.into())).unwrap(),
+            r#"{
+  "action": "wallet_send_ids",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000001"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_send_ids_command() {
+        let cmd = RpcCommand::wallet_send_ids(1.into());
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::RpcCommand;
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_spending_limit_set(args: WalletSpendingLimitSetArgs) -> Self {
+        Self::WalletSpendingLimitSet(args)
+    }
+}
+
+/// Replaces the spending limit of a wallet or, if `account` is given, of a single account.
+/// Omitted limits are removed.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletSpendingLimitSetArgs {
+    pub wallet: WalletId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_transaction: Option<Amount>,
+    /// Maximum total amount sent within any 24 hours
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily: Option<Amount>,
+    /// Required if send approval is enabled for the wallet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_password: Option<String>,
+}
+
+impl WalletSpendingLimitSetArgs {
+    pub fn new(wallet: WalletId) -> Self {
+        Self {
+            wallet,
+            account: None,
+            per_transaction: None,
+            daily: None,
+            approval_password: None,
+        }
+    }
+
+    pub fn builder(wallet: WalletId) -> WalletSpendingLimitSetArgsBuilder {
+        WalletSpendingLimitSetArgsBuilder {
+            args: WalletSpendingLimitSetArgs::new(wallet),
+        }
+    }
+}
+
+pub struct WalletSpendingLimitSetArgsBuilder {
+    args: WalletSpendingLimitSetArgs,
+}
+
+impl WalletSpendingLimitSetArgsBuilder {
+    pub fn account(mut self, account: Account) -> Self {
+        self.args.account = Some(account);
+        self
+    }
+
+    pub fn per_transaction(mut self, max: Amount) -> Self {
+        self.args.per_transaction = Some(max);
+        self
+    }
+
+    pub fn daily(mut self, max: Amount) -> Self {
+        self.args.daily = Some(max);
+        self
+    }
+
+    pub fn approval_password(mut self, password: impl Into<String>) -> Self {
+        self.args.approval_password = Some(password.into());
+        self
+    }
+
+    pub fn build(self) -> WalletSpendingLimitSetArgs {
+        self.args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_spending_limit_set_command() {
+        let args = WalletSpendingLimitSetArgs::builder(WalletId::zero())
+            .account(Account::zero())
+            .per_transaction(Amount::raw(100))
+            .daily(Amount::raw(1000))
+            .build();
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_spending_limit_set(args)).unwrap(),
+            r#"{
+  "action": "wallet_spending_limit_set",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "account": "ban_1111111111111111111111111111111111111111111111111111hifc8npp",
+  "per_transaction": "100",
+  "daily": "1000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_spending_limit_set_command() {
+        let args = WalletSpendingLimitSetArgs::builder(WalletId::from(1))
+            .daily(Amount::raw(5))
+            .approval_password("secret")
+            .build();
+        let cmd = RpcCommand::wallet_spending_limit_set(args);
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{RpcBool, RpcCommand, WalletRpcMessage};
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_spending_limits(wallet: WalletId) -> Self {
+        Self::WalletSpendingLimits(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletSpendingLimitsResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_transaction: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily: Option<Amount>,
+    /// Amount sent by all accounts of the wallet within the last 24 hours
+    pub spent_last_day: Amount,
+    pub approval_required: RpcBool,
+    pub accounts: Vec<AccountSpendingLimitDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountSpendingLimitDto {
+    pub account: Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_transaction: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily: Option<Amount>,
+    pub spent_last_day: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_spending_limits_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_spending_limits(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "wallet_spending_limits",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_wallet_spending_limits_response() {
+        let response = WalletSpendingLimitsResponse {
+            per_transaction: None,
+            daily: Some(Amount::raw(1000)),
+            spent_last_day: Amount::raw(300),
+            approval_required: true.into(),
+            accounts: vec![AccountSpendingLimitDto {
+                account: Account::zero(),
+                per_transaction: Some(Amount::raw(100)),
+                daily: None,
+                spent_last_day: Amount::raw(200),
+            }],
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "daily": "1000",
+  "spent_last_day": "300",
+  "approval_required": "true",
+  "accounts": [
+    {
+      "account": "ban_1111111111111111111111111111111111111111111111111111hifc8npp",
+      "per_transaction": "100",
+      "spent_last_day": "200"
+    }
+  ]
+}"#
+        )
+    }
+}
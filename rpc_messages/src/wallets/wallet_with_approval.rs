@@ -0,0 +1,63 @@
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+/// Arguments of wallet commands which bypass the spending policy of the wallet
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletWithApprovalArgs {
+    pub wallet: WalletId,
+    /// Required if the wallet requires send approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_password: Option<String>,
+}
+
+impl WalletWithApprovalArgs {
+    pub fn new(wallet: WalletId) -> Self {
+        Self {
+            wallet,
+            approval_password: None,
+        }
+    }
+
+    pub fn with_approval_password(mut self, password: impl Into<String>) -> Self {
+        self.approval_password = Some(password.into());
+        self
+    }
+}
+
+impl From<WalletId> for WalletWithApprovalArgs {
+    fn from(value: WalletId) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string};
+
+    #[test]
+    fn serialize_wallet_with_approval_args() {
+        let args = WalletWithApprovalArgs::new(WalletId::zero()).with_approval_password("pw");
+
+        let serialized = to_string(&args).unwrap();
+
+        let expected_json = serde_json::json!({
+            "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+            "approval_password": "pw"
+        });
+
+        let actual_json: serde_json::Value = from_str(&serialized).unwrap();
+        assert_eq!(actual_json, expected_json);
+    }
+
+    #[test]
+    fn deserialize_wallet_with_approval_args_without_password() {
+        let json_str = r#"{
+            "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+        }"#;
+
+        let deserialized: WalletWithApprovalArgs = from_str(json_str).unwrap();
+
+        assert_eq!(deserialized, WalletWithApprovalArgs::new(WalletId::zero()));
+    }
+}
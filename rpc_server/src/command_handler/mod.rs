@@ -147,6 +147,47 @@ impl RpcCommandHandler {
             RpcCommand::ConfirmationHistory(args) => to_value(self.confirmation_history(args)),
             RpcCommand::Version => to_value(self.version()),
             RpcCommand::ActiveDifficulty => to_value(self.active_difficulty()),
+            RpcCommand::SendIdInfo(args) => to_value(self.send_id_info(args)?),
+            RpcCommand::WalletSendIds(args) => to_value(self.wallet_send_ids(args)?),
+            RpcCommand::MnemonicToSeed(args) => to_value(mnemonic_to_seed(args)?),
+            RpcCommand::WalletCreateMnemonic(args) => to_value(self.wallet_create_mnemonic(args)?),
+            RpcCommand::WalletRestoreMnemonic(args) => {
+                to_value(self.wallet_restore_mnemonic(args)?)
+            }
+            RpcCommand::WalletBackup(args) => to_value(self.wallet_backup(args)?),
+            RpcCommand::WalletRestore(args) => to_value(self.wallet_restore(args)?),
+            RpcCommand::WalletReceivePolicy(args) => to_value(self.wallet_receive_policy(args)?),
+            RpcCommand::WalletReceivePolicySet(args) => {
+                to_value(self.wallet_receive_policy_set(args)?)
+            }
+            RpcCommand::WalletSpendingLimits(args) => to_value(self.wallet_spending_limits(args)?),
+            RpcCommand::WalletSpendingLimitSet(args) => {
+                to_value(self.wallet_spending_limit_set(args)?)
+            }
+            RpcCommand::WalletSendApprovalSet(args) => {
+                to_value(self.wallet_send_approval_set(args)?)
+            }
+            RpcCommand::WalletPendingSends(args) => to_value(self.wallet_pending_sends(args)?),
+            RpcCommand::SendApprove(args) => to_value(self.send_approve(args)?),
+            RpcCommand::SendReject(args) => to_value(self.send_reject(args)?),
+            RpcCommand::PeerExclusions => to_value(self.peer_exclusions()),
+            RpcCommand::PeerBan(args) => to_value(self.peer_ban(args)?),
+            RpcCommand::PeerUnban(args) => to_value(self.peer_unban(args)?),
+            RpcCommand::PeerDrop(args) => to_value(self.peer_drop(args)?),
+            RpcCommand::PeerPin(args) => to_value(self.peer_pin(args)),
+            RpcCommand::PeerUnpin(args) => to_value(self.peer_unpin(args)?),
+            RpcCommand::PeersPinned => to_value(self.peers_pinned()),
+            RpcCommand::RepresentativeStats(args) => to_value(self.representative_stats(args)),
+            RpcCommand::ForksActive(args) => to_value(self.forks_active(args)),
+            RpcCommand::ForksHistory(args) => to_value(self.forks_history(args)),
+            RpcCommand::ElectionTrace(args) => to_value(self.election_trace(args)),
+            RpcCommand::ElectionStart(args) => to_value(self.election_start(args)?),
+            RpcCommand::ElectionInfo(args) => to_value(self.election_info(args)?),
+            RpcCommand::ElectionCancel(args) => to_value(self.election_cancel(args)?),
+            RpcCommand::FinalVoteCount => to_value(self.final_vote_count()),
+            RpcCommand::FinalVoteInfo(args) => to_value(self.final_vote_info(args)),
+            RpcCommand::FinalVotePrune(args) => to_value(self.final_vote_prune(args)),
+            RpcCommand::QuorumSimulate(args) => to_value(self.quorum_simulate(args)?),
 
             // Not implemented:
             RpcCommand::AccountRepresentativeSet(_) => self.not_implemented(),
@@ -211,10 +252,18 @@ fn requires_control(command: &RpcCommand) -> bool {
         | RpcCommand::BlockCreate(_)
         | RpcCommand::BootstrapLazy(_)
         | RpcCommand::DatabaseTxnTracker(_)
+        | RpcCommand::ElectionCancel(_)
+        | RpcCommand::ElectionStart(_)
+        | RpcCommand::FinalVotePrune(_)
         | RpcCommand::Keepalive(_)
         | RpcCommand::Ledger(_)
         | RpcCommand::NodeId
         | RpcCommand::PasswordChange(_)
+        | RpcCommand::PeerBan(_)
+        | RpcCommand::PeerDrop(_)
+        | RpcCommand::PeerPin(_)
+        | RpcCommand::PeerUnban(_)
+        | RpcCommand::PeerUnpin(_)
         | RpcCommand::PopulateBacklog
         | RpcCommand::Receive(_)
         | RpcCommand::ReceiveMinimum
@@ -222,19 +271,31 @@ fn requires_control(command: &RpcCommand) -> bool {
         | RpcCommand::SearchReceivable(_)
         | RpcCommand::SearchReceivableAll
         | RpcCommand::Send(_)
+        | RpcCommand::SendApprove(_)
+        | RpcCommand::SendIdInfo(_)
+        | RpcCommand::SendReject(_)
         | RpcCommand::Stop
         | RpcCommand::UncheckedClear
         | RpcCommand::Unopened(_)
         | RpcCommand::WalletAdd(_)
         | RpcCommand::WalletAddWatch(_)
+        | RpcCommand::WalletBackup(_)
         | RpcCommand::WalletChangeSeed(_)
         | RpcCommand::WalletCreate(_)
+        | RpcCommand::WalletCreateMnemonic(_)
         | RpcCommand::WalletDestroy(_)
         | RpcCommand::WalletLock(_)
         | RpcCommand::WalletLedger(_)
+        | RpcCommand::WalletPendingSends(_)
         | RpcCommand::WalletRepresentativeSet(_)
         | RpcCommand::WalletReceivable(_)
+        | RpcCommand::WalletReceivePolicySet(_)
         | RpcCommand::WalletRepublish(_)
+        | RpcCommand::WalletRestore(_)
+        | RpcCommand::WalletRestoreMnemonic(_)
+        | RpcCommand::WalletSendApprovalSet(_)
+        | RpcCommand::WalletSendIds(_)
+        | RpcCommand::WalletSpendingLimitSet(_)
         | RpcCommand::WalletWorkGet(_)
         | RpcCommand::WorkGenerate(_)
         | RpcCommand::WorkCancel(_)
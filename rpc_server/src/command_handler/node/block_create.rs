@@ -36,7 +36,12 @@ impl RpcCommandHandler {
         }
 
         if !wallet_id.is_zero() && !account.is_zero() {
-            self.node.wallets.fetch(&wallet_id, &account.into())?;
+            if args.key.is_none() {
+                self.node
+                    .wallets
+                    .verify_key_access(&wallet_id, args.approval_password.as_deref())?;
+            }
+            prv_key = self.node.wallets.fetch(&wallet_id, &account.into())?.into();
             let tx = self.node.ledger.read_txn();
             previous = self
                 .node
@@ -0,0 +1,17 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_node::consensus::ElectionState;
+use rsban_rpc_messages::{ElectionArgs, SuccessResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn election_cancel(&self, args: ElectionArgs) -> anyhow::Result<SuccessResponse> {
+        let election = self.find_election(&args)?;
+        election.cancel();
+        // Confirmed elections can't be cancelled anymore
+        if election.state() != ElectionState::Cancelled {
+            bail!("Election already confirmed");
+        }
+        self.node.active.erase(&election.qualified_root);
+        Ok(SuccessResponse::new())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::{anyhow, bail};
+use rsban_core::{Amount, QualifiedRoot, Vote};
+use rsban_node::{consensus::Election, stats::DetailType};
+use rsban_rpc_messages::{ElectionArgs, ElectionBlockDto, ElectionInfoDto, ElectionVoterDto};
+use std::{cmp::Reverse, sync::Arc};
+
+impl RpcCommandHandler {
+    pub(crate) fn election_info(&self, args: ElectionArgs) -> anyhow::Result<ElectionInfoDto> {
+        let election = self.find_election(&args)?;
+        let guard = election.mutex.lock().unwrap();
+
+        let mut blocks: Vec<_> = guard
+            .last_blocks
+            .keys()
+            .map(|hash| {
+                let mut final_tally = Amount::zero();
+                let mut voters = Vec::new();
+                for (representative, vote) in &guard.last_votes {
+                    if vote.hash != *hash {
+                        continue;
+                    }
+                    let weight = self.node.ledger.rep_weights.weight(representative);
+                    let is_final = vote.timestamp == Vote::FINAL_TIMESTAMP;
+                    if is_final {
+                        final_tally += weight;
+                    }
+                    voters.push(ElectionVoterDto {
+                        representative: representative.into(),
+                        weight,
+                        is_final: is_final.into(),
+                    });
+                }
+                voters.sort_by_key(|voter| Reverse(voter.weight));
+
+                ElectionBlockDto {
+                    hash: *hash,
+                    tally: guard.last_tally.get(hash).cloned().unwrap_or_default(),
+                    final_tally,
+                    voters,
+                }
+            })
+            .collect();
+        blocks.sort_by_key(|block| Reverse(block.tally));
+
+        Ok(ElectionInfoDto {
+            root: election.qualified_root.clone(),
+            behavior: DetailType::from(election.behavior).as_str().to_owned(),
+            state: DetailType::from(guard.state).as_str().to_owned(),
+            duration: (election.duration().as_millis() as u64).into(),
+            confirmation_requests: guard.status.confirmation_request_count.into(),
+            winner: guard
+                .status
+                .winner
+                .as_ref()
+                .map(|b| b.hash())
+                .unwrap_or_default(),
+            blocks,
+        })
+    }
+
+    pub(super) fn find_election(&self, args: &ElectionArgs) -> anyhow::Result<Arc<Election>> {
+        let root = match (&args.root, &args.hash) {
+            (Some(root), _) => root.clone(),
+            (None, Some(hash)) => {
+                let tx = self.node.ledger.read_txn();
+                self.load_block_any(&tx, hash)?.qualified_root()
+            }
+            (None, None) => bail!("Either root or hash is required"),
+        };
+        self.election_for_root(&root)
+    }
+
+    fn election_for_root(&self, root: &QualifiedRoot) -> anyhow::Result<Arc<Election>> {
+        self.node
+            .active
+            .election(root)
+            .ok_or_else(|| anyhow!("Election not found"))
+    }
+}
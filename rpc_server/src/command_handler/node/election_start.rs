@@ -0,0 +1,31 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_node::consensus::ElectionBehavior;
+use rsban_rpc_messages::{ElectionStartArgs, StartedResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn election_start(
+        &self,
+        args: ElectionStartArgs,
+    ) -> anyhow::Result<StartedResponse> {
+        let behavior = match &args.behavior {
+            Some(behavior) => behavior.parse()?,
+            None => ElectionBehavior::Manual,
+        };
+
+        let block = {
+            let tx = self.node.ledger.read_txn();
+            let block = self.load_block_any(&tx, &args.hash)?;
+            if self.node.ledger.confirmed().block_exists(&tx, &args.hash) {
+                bail!("Block already confirmed");
+            }
+            block
+        };
+
+        self.node
+            .election_schedulers
+            .manual
+            .push_with_behavior(block, None, behavior);
+        Ok(StartedResponse::new(true))
+    }
+}
@@ -0,0 +1,95 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::{
+    consensus::{ElectionTrace, ElectionTraceEvent},
+    stats::DetailType,
+};
+use rsban_rpc_messages::{
+    ElectionTraceArgs, ElectionTraceDto, ElectionTraceEventDto, ElectionTraceEventKindDto,
+    ElectionTracesDto,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl RpcCommandHandler {
+    pub(crate) fn election_trace(&self, args: ElectionTraceArgs) -> ElectionTracesDto {
+        let traces = match args.hash {
+            Some(hash) => self.node.election_tracer.trace(&hash).into_iter().collect(),
+            None => self.node.election_tracer.traces(),
+        };
+
+        ElectionTracesDto {
+            traces: traces.iter().map(trace_dto).collect(),
+        }
+    }
+}
+
+fn trace_dto(trace: &ElectionTrace) -> ElectionTraceDto {
+    ElectionTraceDto {
+        root: trace.root.clone(),
+        account: trace.account,
+        hash: trace.hash,
+        behavior: DetailType::from(trace.behavior).as_str().to_owned(),
+        started: as_millis(trace.started).into(),
+        winner: trace.winner,
+        state: trace
+            .final_state
+            .map(|state| DetailType::from(state).as_str().to_owned()),
+        dropped_events: (trace.dropped_events as u64).into(),
+        events: trace
+            .events
+            .iter()
+            .map(|entry| ElectionTraceEventDto {
+                time: as_millis(entry.time).into(),
+                event: event_dto(&entry.event),
+            })
+            .collect(),
+    }
+}
+
+fn event_dto(event: &ElectionTraceEvent) -> ElectionTraceEventKindDto {
+    match event {
+        ElectionTraceEvent::Started { behavior } => ElectionTraceEventKindDto::Started {
+            behavior: DetailType::from(*behavior).as_str().to_owned(),
+        },
+        ElectionTraceEvent::BlockAdded { hash } => {
+            ElectionTraceEventKindDto::BlockAdded { hash: *hash }
+        }
+        ElectionTraceEvent::Vote {
+            representative,
+            hash,
+            weight,
+            is_final,
+            channel_id,
+            source,
+            code,
+        } => ElectionTraceEventKindDto::Vote {
+            representative: representative.into(),
+            hash: *hash,
+            weight: *weight,
+            is_final: (*is_final).into(),
+            channel_id: (channel_id.as_usize() as u64).into(),
+            source: DetailType::from(*source).as_str().to_owned(),
+            code: code.as_str().to_owned(),
+        },
+        ElectionTraceEvent::ConfirmReq { hash, channels } => {
+            ElectionTraceEventKindDto::ConfirmReq {
+                hash: *hash,
+                channels: channels
+                    .iter()
+                    .map(|c| (c.as_usize() as u64).into())
+                    .collect(),
+            }
+        }
+        ElectionTraceEvent::StateChanged { state } => ElectionTraceEventKindDto::StateChanged {
+            state: DetailType::from(*state).as_str().to_owned(),
+        },
+        ElectionTraceEvent::Cemented { hash } => {
+            ElectionTraceEventKindDto::Cemented { hash: *hash }
+        }
+    }
+}
+
+fn as_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,8 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::CountResponse;
+
+impl RpcCommandHandler {
+    pub(crate) fn final_vote_count(&self) -> CountResponse {
+        CountResponse::new(self.node.final_vote_cleanup.count())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{FinalVoteDto, FinalVoteInfoArgs, FinalVoteInfoDto};
+
+impl RpcCommandHandler {
+    pub(crate) fn final_vote_info(&self, args: FinalVoteInfoArgs) -> FinalVoteInfoDto {
+        let votes = self
+            .node
+            .final_vote_cleanup
+            .entries(&args.root)
+            .into_iter()
+            .map(|entry| FinalVoteDto {
+                root: entry.qualified_root,
+                hash: entry.hash,
+                cemented_depth: entry.cemented_depth.map(|depth| depth.into()),
+            })
+            .collect();
+        FinalVoteInfoDto { votes }
+    }
+}
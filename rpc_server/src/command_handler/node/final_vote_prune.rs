@@ -0,0 +1,12 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{FinalVotePruneArgs, FinalVotePruneResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn final_vote_prune(&self, args: FinalVotePruneArgs) -> FinalVotePruneResponse {
+        let deleted = self.node.final_vote_cleanup.prune(
+            args.depth.inner(),
+            self.node.config.final_vote_cleanup.batch_size,
+        );
+        FinalVotePruneResponse::new(deleted)
+    }
+}
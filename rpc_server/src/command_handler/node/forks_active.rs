@@ -0,0 +1,56 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::consensus::Fork;
+use rsban_rpc_messages::{ForkCandidateDto, ForkDto, ForksActiveArgs, ForksDto};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl RpcCommandHandler {
+    pub(crate) fn forks_active(&self, args: ForksActiveArgs) -> ForksDto {
+        let forks = self
+            .node
+            .fork_tracker
+            .active()
+            .into_iter()
+            .filter(|fork| args.account.is_none_or(|account| fork.account == account))
+            .map(|mut fork| {
+                // Tallies of unresolved forks come from the running election
+                if let Some(election) = self.node.active.election(&fork.root) {
+                    let guard = election.mutex.lock().unwrap();
+                    for candidate in &mut fork.candidates {
+                        candidate.tally = guard
+                            .last_tally
+                            .get(&candidate.hash)
+                            .cloned()
+                            .unwrap_or_default();
+                    }
+                }
+                fork_dto(&fork)
+            })
+            .collect();
+        ForksDto { forks }
+    }
+}
+
+pub(super) fn fork_dto(fork: &Fork) -> ForkDto {
+    ForkDto {
+        root: fork.root.clone(),
+        account: fork.account,
+        first_seen: as_secs(fork.first_seen).into(),
+        candidates: fork
+            .candidates
+            .iter()
+            .map(|candidate| ForkCandidateDto {
+                hash: candidate.hash,
+                first_seen: as_secs(candidate.first_seen).into(),
+                tally: candidate.tally,
+            })
+            .collect(),
+        winner: fork.winner,
+        resolved: fork.resolved.map(|resolved| as_secs(resolved).into()),
+    }
+}
+
+fn as_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
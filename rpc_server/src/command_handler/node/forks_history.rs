@@ -0,0 +1,19 @@
+use super::forks_active::fork_dto;
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{ForksDto, ForksHistoryArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn forks_history(&self, args: ForksHistoryArgs) -> ForksDto {
+        let count = args.count.map(|c| c.inner() as usize).unwrap_or(usize::MAX);
+        let forks = self
+            .node
+            .fork_tracker
+            .history()
+            .iter()
+            .filter(|fork| args.account.is_none_or(|account| fork.account == account))
+            .take(count)
+            .map(fork_dto)
+            .collect();
+        ForksDto { forks }
+    }
+}
@@ -7,13 +7,29 @@ mod confirmation_active;
 mod confirmation_history;
 mod confirmation_info;
 mod confirmation_quorum;
+mod election_cancel;
+mod election_info;
+mod election_start;
+mod election_trace;
+mod final_vote_count;
+mod final_vote_info;
+mod final_vote_prune;
+mod forks_active;
+mod forks_history;
 mod keepalive;
 mod node_id;
+mod peer_ban;
+mod peer_drop;
+mod peer_exclusions;
+mod peer_pin;
+mod peer_unban;
 mod peers;
 mod populate_backlog;
 mod process;
+mod quorum_simulate;
 mod receivable;
 mod receivable_exists;
+mod representative_stats;
 mod representatives_online;
 mod republish;
 mod sign;
@@ -0,0 +1,18 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::anyhow;
+use rsban_network::IpSubnet;
+use rsban_rpc_messages::{PeerBanArgs, PeerBanResponse};
+use std::time::Duration;
+
+impl RpcCommandHandler {
+    pub(crate) fn peer_ban(&self, args: PeerBanArgs) -> anyhow::Result<PeerBanResponse> {
+        let subnet: IpSubnet = args
+            .address
+            .parse()
+            .map_err(|_| anyhow!("Invalid address"))?;
+        let duration = args.duration.map(|secs| Duration::from_secs(secs.inner()));
+        let persist = args.persist.unwrap_or_default().inner();
+        let closed = self.node.ban_peer(subnet, duration, persist);
+        Ok(PeerBanResponse::new(closed as u64))
+    }
+}
@@ -0,0 +1,22 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_network::ChannelId;
+use rsban_rpc_messages::{PeerDropArgs, PeerDropResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn peer_drop(&self, args: PeerDropArgs) -> anyhow::Result<PeerDropResponse> {
+        let network = self.node.network_info.read().unwrap();
+        let dropped = match (args.channel_id, args.node_id) {
+            (Some(channel_id), None) => {
+                let channel_id = ChannelId::from(channel_id.inner() as usize);
+                network.drop_channel(channel_id) as usize
+            }
+            (None, Some(node_id)) => network.drop_node(&node_id),
+            _ => bail!("Either channel_id or node_id is required"),
+        };
+        if dropped == 0 {
+            bail!("Peer not found");
+        }
+        Ok(PeerDropResponse::new(dropped as u64))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_nullable_clock::Timestamp;
+use rsban_rpc_messages::{ExcludedPeerDto, PeerExclusionsResponse};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl RpcCommandHandler {
+    pub(crate) fn peer_exclusions(&self) -> PeerExclusionsResponse {
+        let now = self.node.steady_clock.now();
+        let system_now = SystemTime::now();
+        let excluded = self
+            .node
+            .network_info
+            .read()
+            .unwrap()
+            .excluded_peers(now)
+            .into_iter()
+            .map(|peer| ExcludedPeerDto {
+                address: peer.subnet.to_string(),
+                reason: peer.reason.as_str().to_owned(),
+                score: peer.score.into(),
+                excluded_until: if peer.excluded_until == Timestamp::MAX {
+                    None
+                } else {
+                    let until = system_now + (peer.excluded_until - now);
+                    Some(
+                        until
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs()
+                            .into(),
+                    )
+                },
+            })
+            .collect();
+        PeerExclusionsResponse { excluded }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_rpc_messages::{AddressWithPortArgs, PeersPinnedResponse, SuccessResponse};
+use std::net::SocketAddrV6;
+
+impl RpcCommandHandler {
+    pub(crate) fn peer_pin(&self, args: AddressWithPortArgs) -> SuccessResponse {
+        let peer = SocketAddrV6::new(args.address, args.port.inner(), 0, 0);
+        self.node.network_info.write().unwrap().pin(peer);
+        SuccessResponse::new()
+    }
+
+    pub(crate) fn peer_unpin(&self, args: AddressWithPortArgs) -> anyhow::Result<SuccessResponse> {
+        let peer = SocketAddrV6::new(args.address, args.port.inner(), 0, 0);
+        if !self.node.network_info.write().unwrap().unpin(&peer) {
+            bail!("Peer not pinned");
+        }
+        Ok(SuccessResponse::new())
+    }
+
+    pub(crate) fn peers_pinned(&self) -> PeersPinnedResponse {
+        PeersPinnedResponse {
+            peers: self.node.network_info.read().unwrap().pinned_peers(),
+        }
+    }
+}
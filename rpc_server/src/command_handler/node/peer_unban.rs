@@ -0,0 +1,17 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::{anyhow, bail};
+use rsban_network::IpSubnet;
+use rsban_rpc_messages::{PeerUnbanArgs, SuccessResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn peer_unban(&self, args: PeerUnbanArgs) -> anyhow::Result<SuccessResponse> {
+        let subnet: IpSubnet = args
+            .address
+            .parse()
+            .map_err(|_| anyhow!("Invalid address"))?;
+        if !self.node.unban_peer(&subnet) {
+            bail!("Peer not excluded");
+        }
+        Ok(SuccessResponse::new())
+    }
+}
@@ -1,10 +1,14 @@
 use crate::command_handler::RpcCommandHandler;
-use rsban_rpc_messages::{DetailedPeers, PeerInfo, PeersArgs, PeersDto, SimplePeers};
+use rsban_network::ChannelInfo;
+use rsban_rpc_messages::{
+    DetailedPeers, MessageTrafficDto, PeerInfo, PeerTrafficDto, PeersArgs, PeersDto, SimplePeers,
+};
 use std::{collections::HashMap, net::SocketAddrV6};
 
 impl RpcCommandHandler {
     pub(crate) fn peers(&self, args: PeersArgs) -> PeersDto {
         let peer_details = args.peer_details.unwrap_or_default().inner();
+        let now = self.node.steady_clock.now();
         let mut peers: HashMap<SocketAddrV6, PeerInfo> = HashMap::new();
 
         self.node
@@ -21,6 +25,12 @@ impl RpcCommandHandler {
                         node_id: channel.node_id().map(|i| i.to_string()).unwrap_or_default(),
                         connection_type: "tcp".to_string(),
                         peering: channel.peering_addr_or_peer_addr(),
+                        mode: peer_details.then(|| channel.mode().as_str().to_string()),
+                        direction: peer_details.then(|| channel.direction().as_str().to_string()),
+                        idle_time_ms: peer_details.then(|| {
+                            (channel.last_activity().elapsed(now).as_millis() as u64).into()
+                        }),
+                        traffic: peer_details.then(|| peer_traffic(channel)),
                     },
                 );
             });
@@ -37,3 +47,29 @@ impl RpcCommandHandler {
         }
     }
 }
+
+fn peer_traffic(channel: &ChannelInfo) -> PeerTrafficDto {
+    let traffic = channel.traffic();
+    PeerTrafficDto {
+        bytes_in: traffic.bytes_in.into(),
+        bytes_out: traffic.bytes_out.into(),
+        messages_in: traffic.messages_in().into(),
+        messages_out: traffic.messages_out().into(),
+        queue_drops: traffic.queue_drops.into(),
+        messages: traffic
+            .by_message_type
+            .iter()
+            .map(|(message_type, i)| {
+                (
+                    message_type.to_string(),
+                    MessageTrafficDto {
+                        messages_in: i.messages_in.into(),
+                        bytes_in: i.bytes_in.into(),
+                        messages_out: i.messages_out.into(),
+                        bytes_out: i.bytes_out.into(),
+                    },
+                )
+            })
+            .collect(),
+    }
+}
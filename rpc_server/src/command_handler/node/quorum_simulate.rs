@@ -0,0 +1,53 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::representatives::{QuorumSimulator, QuorumSnapshot, WeightChange};
+use rsban_rpc_messages::{
+    QuorumSimulateArgs, QuorumSimulateResponse, QuorumSnapshotDto, SimulatedRepDto, WeightChangeDto,
+};
+
+impl RpcCommandHandler {
+    pub(crate) fn quorum_simulate(
+        &self,
+        args: QuorumSimulateArgs,
+    ) -> anyhow::Result<QuorumSimulateResponse> {
+        let simulator = QuorumSimulator::from_online_reps(
+            &self.node.online_reps.lock().unwrap(),
+            &self.node.ledger.rep_weights,
+        );
+        let changes: Vec<_> = args.changes.into_iter().map(weight_change).collect();
+        let (before, after) = simulator.simulate(&changes)?;
+        Ok(QuorumSimulateResponse {
+            before: snapshot_dto(before),
+            after: snapshot_dto(after),
+        })
+    }
+}
+
+fn weight_change(dto: WeightChangeDto) -> WeightChange {
+    match dto {
+        WeightChangeDto::Move { from, to, amount } => WeightChange::Move {
+            from: from.into(),
+            to: to.into(),
+            amount,
+        },
+        WeightChangeDto::Offline { representative } => WeightChange::Offline(representative.into()),
+        WeightChangeDto::Online { representative } => WeightChange::Online(representative.into()),
+    }
+}
+
+fn snapshot_dto(snapshot: QuorumSnapshot) -> QuorumSnapshotDto {
+    QuorumSnapshotDto {
+        online_stake_total: snapshot.online_weight,
+        trended_stake_total: snapshot.trended_weight,
+        quorum_delta: snapshot.quorum_delta,
+        principal_representatives: snapshot
+            .principal_reps
+            .into_iter()
+            .map(|rep| SimulatedRepDto {
+                account: rep.representative.as_account(),
+                weight: rep.weight,
+                online: rep.online.into(),
+                tier: rep.tier.as_str().to_owned(),
+            })
+            .collect(),
+    }
+}
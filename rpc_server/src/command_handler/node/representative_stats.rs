@@ -0,0 +1,58 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{RepStatsBucketDto, RepresentativeStatsArgs, RepresentativeStatsResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn representative_stats(
+        &self,
+        args: RepresentativeStatsArgs,
+    ) -> RepresentativeStatsResponse {
+        let representative = args.account.into();
+        let summary = self.node.rep_stats.summary(&representative);
+        let online = self
+            .node
+            .online_reps
+            .lock()
+            .unwrap()
+            .online_reps()
+            .any(|rep| *rep == representative);
+
+        let history = if args.history.unwrap_or_default().inner() {
+            Some(
+                summary
+                    .history
+                    .iter()
+                    .map(|(start, bucket)| RepStatsBucketDto {
+                        start: (*start).into(),
+                        samples: (bucket.samples as u64).into(),
+                        online_samples: (bucket.online_samples as u64).into(),
+                        elections: (bucket.elections as u64).into(),
+                        votes: (bucket.votes as u64).into(),
+                        final_votes: (bucket.final_votes as u64).into(),
+                        average_vote_latency: bucket
+                            .vote_latency_ms
+                            .checked_div(bucket.votes as u64)
+                            .unwrap_or_default()
+                            .into(),
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        let totals = &summary.totals;
+        RepresentativeStatsResponse {
+            online: online.into(),
+            uptime: (summary.uptime() * 100.0).into(),
+            samples: (totals.samples as u64).into(),
+            online_samples: (totals.online_samples as u64).into(),
+            elections: (totals.elections as u64).into(),
+            votes: (totals.votes as u64).into(),
+            final_votes: (totals.final_votes as u64).into(),
+            participation: (summary.participation() * 100.0).into(),
+            final_participation: (summary.final_participation() * 100.0).into(),
+            average_vote_latency: (summary.average_vote_latency().as_millis() as u64).into(),
+            history,
+        }
+    }
+}
@@ -1,19 +1,20 @@
 use crate::command_handler::RpcCommandHandler;
-use rsban_core::Account;
+use rsban_core::{Account, PublicKey};
+use rsban_node::representatives::PeeredRep;
+use rsban_nullable_clock::Timestamp;
 use rsban_rpc_messages::{
     DetailedRepresentativesOnline, RepWeightDto, RepresentativesOnlineArgs,
     RepresentativesOnlineResponse, SimpleRepresentativesOnline,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 impl RpcCommandHandler {
     pub(crate) fn representatives_online(
         &self,
         args: RepresentativesOnlineArgs,
     ) -> RepresentativesOnlineResponse {
-        let lock = self.node.online_reps.lock().unwrap();
-        let online_reps = lock.online_reps();
-        let weight = args.weight.unwrap_or_default().inner();
+        let details = args.details.unwrap_or_default().inner();
+        let weight = args.weight.unwrap_or_default().inner() || details;
 
         let mut representatives_simple = Vec::new();
         let mut representatives_detailed = HashMap::new();
@@ -21,26 +22,84 @@ impl RpcCommandHandler {
         let filtering = args.accounts.is_some();
         let mut accounts_to_filter = args.accounts.unwrap_or_default();
 
-        for rep in online_reps {
-            let account = Account::from(rep);
+        let mut peered: Vec<(PublicKey, bool, Option<Timestamp>, Option<PeeredRep>)> = Vec::new();
+        {
+            let lock = self.node.online_reps.lock().unwrap();
+            let mut reps: Vec<(PublicKey, bool)> =
+                lock.online_reps().map(|rep| (*rep, true)).collect();
+            if details {
+                // Peered reps that are unresponsive or stale are listed too
+                let online: HashSet<PublicKey> = reps.iter().map(|(rep, _)| *rep).collect();
+                reps.extend(
+                    lock.peered_reps()
+                        .into_iter()
+                        .filter(|rep| !online.contains(&rep.account))
+                        .map(|rep| (rep.account, false)),
+                );
+            }
 
-            if filtering {
-                if accounts_to_filter.is_empty() {
-                    break;
-                }
+            for (rep, online) in reps {
+                let account = Account::from(rep);
+
+                if filtering {
+                    if accounts_to_filter.is_empty() {
+                        break;
+                    }
 
-                if !accounts_to_filter.contains(&account) {
-                    continue;
+                    if !accounts_to_filter.contains(&account) {
+                        continue;
+                    }
+                    accounts_to_filter.retain(|a| *a != account);
                 }
-                accounts_to_filter.retain(|a| *a != account);
+
+                if details {
+                    peered.push((
+                        rep,
+                        online,
+                        lock.last_vote(&rep),
+                        lock.peered_rep(&rep).cloned(),
+                    ));
+                } else if weight {
+                    let weight = self.node.ledger.weight(&rep);
+                    representatives_detailed.insert(
+                        account,
+                        RepWeightDto {
+                            weight,
+                            ..Default::default()
+                        },
+                    );
+                } else {
+                    representatives_simple.push(account);
+                };
             }
+        }
 
-            if weight {
-                let weight = self.node.ledger.weight(rep);
-                representatives_detailed.insert(account, RepWeightDto { weight });
-            } else {
-                representatives_simple.push(account);
-            };
+        if details {
+            // The online reps lock must not be held while the network info is locked
+            let now = self.node.steady_clock.now();
+            let network_info = self.node.network_info.read().unwrap();
+            for (rep, online, last_vote, peered_rep) in peered {
+                let mut dto = RepWeightDto {
+                    weight: self.node.ledger.weight(&rep),
+                    last_vote_elapsed_ms: last_vote.map(|t| elapsed_ms(t, now).into()),
+                    online: Some(online.into()),
+                    ..Default::default()
+                };
+                if let Some(peered_rep) = peered_rep {
+                    dto.last_response_elapsed_ms =
+                        peered_rep.last_response.map(|t| elapsed_ms(t, now).into());
+                    dto.latency_ms = peered_rep
+                        .latency
+                        .map(|latency| (latency.as_millis() as u64).into());
+                    dto.unresponsive = Some(peered_rep.is_unresponsive().into());
+                    if let Some(channel) = network_info.get(peered_rep.channel_id) {
+                        dto.address = Some(channel.peering_addr_or_peer_addr());
+                        dto.node_id = channel.node_id().map(|id| id.to_string());
+                        dto.protocol_version = Some(channel.protocol_version().into());
+                    }
+                }
+                representatives_detailed.insert(Account::from(rep), dto);
+            }
         }
 
         if weight {
@@ -54,3 +113,7 @@ impl RpcCommandHandler {
         }
     }
 }
+
+fn elapsed_ms(time: Timestamp, now: Timestamp) -> u64 {
+    time.elapsed(now).as_millis() as u64
+}
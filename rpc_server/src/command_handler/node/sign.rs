@@ -24,11 +24,11 @@ impl RpcCommandHandler {
             key.into()
         } else {
             // Retrieving private key from wallet
-            if args.wallet.is_some() && args.account.is_some() {
+            if let (Some(wallet), Some(account)) = (args.wallet, args.account) {
                 self.node
                     .wallets
-                    .fetch(&args.wallet.unwrap(), &args.account.unwrap().into())?
-                    .into()
+                    .verify_key_access(&wallet, args.approval_password.as_deref())?;
+                self.node.wallets.fetch(&wallet, &account.into())?.into()
             } else {
                 PrivateKey::zero()
             }
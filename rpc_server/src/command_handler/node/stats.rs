@@ -1,7 +1,13 @@
 use crate::command_handler::RpcCommandHandler;
 use rsban_core::utils::ContainerInfo;
-use rsban_node::stats::StatsJsonWriterV2;
-use rsban_rpc_messages::{StatsArgs, StatsType, SuccessResponse};
+use rsban_node::{
+    consensus::{LatencyHistogram, LatencyHistograms, LATENCY_BUCKETS_MS},
+    stats::{DetailType, StatsJsonWriterV2},
+};
+use rsban_rpc_messages::{
+    ConfirmationLatencyDto, LatencyBucketDto, LatencyHistogramDto, LatencyHistogramsDto, StatsArgs,
+    StatsType, SuccessResponse,
+};
 
 impl RpcCommandHandler {
     pub(crate) fn stats(&self, args: StatsArgs) -> anyhow::Result<serde_json::Value> {
@@ -28,11 +34,72 @@ impl RpcCommandHandler {
                 .node("node", self.node.container_info())
                 .finish()
                 .into_json()),
+            StatsType::ConfirmationLatency => {
+                let latency = &self.node.confirmation_latency;
+                let dto = ConfirmationLatencyDto {
+                    by_source: latency
+                        .by_source()
+                        .iter()
+                        .map(|(source, histograms)| {
+                            (
+                                DetailType::from(*source).as_str().to_owned(),
+                                histograms_dto(histograms),
+                            )
+                        })
+                        .collect(),
+                    by_behavior: latency
+                        .by_behavior()
+                        .iter()
+                        .map(|(behavior, histograms)| {
+                            (
+                                DetailType::from(*behavior).as_str().to_owned(),
+                                histograms_dto(histograms),
+                            )
+                        })
+                        .collect(),
+                };
+                Ok(serde_json::to_value(dto)?)
+            }
         }
     }
 
     pub(crate) fn stats_clear(&self) -> SuccessResponse {
         self.node.stats.clear();
+        self.node.confirmation_latency.clear();
         SuccessResponse::new()
     }
 }
+
+fn histograms_dto(histograms: &LatencyHistograms) -> LatencyHistogramsDto {
+    LatencyHistogramsDto {
+        arrival_to_election: histogram_dto(&histograms.arrival_to_election),
+        election_to_quorum: histogram_dto(&histograms.election_to_quorum),
+        quorum_to_cemented: histogram_dto(&histograms.quorum_to_cemented),
+        arrival_to_cemented: histogram_dto(&histograms.arrival_to_cemented),
+    }
+}
+
+fn histogram_dto(histogram: &LatencyHistogram) -> LatencyHistogramDto {
+    let bounds = LATENCY_BUCKETS_MS
+        .iter()
+        .map(|bound| bound.to_string())
+        .chain(std::iter::once("+Inf".to_owned()));
+
+    let mut cumulative = 0;
+    let buckets = bounds
+        .zip(histogram.buckets.iter())
+        .map(|(le, count)| {
+            cumulative += count;
+            LatencyBucketDto {
+                le,
+                count: cumulative.into(),
+            }
+        })
+        .collect();
+
+    LatencyHistogramDto {
+        count: histogram.count.into(),
+        sum_ms: histogram.sum_ms.into(),
+        buckets,
+    }
+}
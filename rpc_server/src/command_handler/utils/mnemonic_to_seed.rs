@@ -0,0 +1,12 @@
+use anyhow::anyhow;
+use rsban_core::Mnemonic;
+use rsban_rpc_messages::{MnemonicToSeedArgs, MnemonicToSeedResponse};
+
+pub fn mnemonic_to_seed(args: MnemonicToSeedArgs) -> anyhow::Result<MnemonicToSeedResponse> {
+    let mnemonic = Mnemonic::parse(&args.mnemonic).map_err(|_| anyhow!("Invalid mnemonic"))?;
+    let passphrase = args.passphrase.unwrap_or_default();
+    Ok(MnemonicToSeedResponse {
+        seed: mnemonic.legacy_seed(),
+        bip39_seed: hex::encode_upper(mnemonic.bip39_seed(&passphrase)),
+    })
+}
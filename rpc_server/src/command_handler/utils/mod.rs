@@ -4,6 +4,7 @@ mod block_hash;
 mod deterministic_key;
 mod key_create;
 mod key_expand;
+mod mnemonic_to_seed;
 mod nano_to_raw;
 mod raw_to_nano;
 mod validate_account_number;
@@ -14,6 +15,7 @@ pub use block_hash::*;
 pub use deterministic_key::*;
 pub(crate) use key_create::*;
 pub use key_expand::*;
+pub use mnemonic_to_seed::*;
 pub use nano_to_raw::*;
 pub use raw_to_nano::*;
 pub use validate_account_number::*;
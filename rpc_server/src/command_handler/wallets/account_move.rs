@@ -4,6 +4,10 @@ use rsban_rpc_messages::{AccountMoveArgs, MovedResponse};
 
 impl RpcCommandHandler {
     pub(crate) fn account_move(&self, args: AccountMoveArgs) -> anyhow::Result<MovedResponse> {
+        // The moved keys are no longer governed by the spending policy of the source wallet
+        self.node
+            .wallets
+            .verify_key_access(&args.source, args.approval_password.as_deref())?;
         let public_keys: Vec<PublicKey> =
             args.accounts.iter().map(|account| account.into()).collect();
 
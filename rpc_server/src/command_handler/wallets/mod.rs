@@ -11,12 +11,17 @@ mod receive_minimum;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
+mod send_approve;
+mod send_id_info;
+mod send_reject;
 mod wallet_add;
 mod wallet_add_watch;
+mod wallet_backup;
 mod wallet_balances;
 mod wallet_change_seed;
 mod wallet_contains;
 mod wallet_create;
+mod wallet_create_mnemonic;
 mod wallet_destroy;
 mod wallet_export;
 mod wallet_frontiers;
@@ -25,10 +30,19 @@ mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_pending_sends;
 mod wallet_receivable;
+mod wallet_receive_policy;
+mod wallet_receive_policy_set;
 mod wallet_representative;
 mod wallet_representative_set;
 mod wallet_republish;
+mod wallet_restore;
+mod wallet_restore_mnemonic;
+mod wallet_send_approval_set;
+mod wallet_send_ids;
+mod wallet_spending_limit_set;
+mod wallet_spending_limits;
 mod wallet_work_get;
 mod work_get;
 mod work_set;
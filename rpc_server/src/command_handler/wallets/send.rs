@@ -1,11 +1,13 @@
+use super::wallet_pending_sends::to_pending_send_dto;
 use crate::command_handler::RpcCommandHandler;
 use anyhow::bail;
 use rsban_core::BlockDetails;
-use rsban_node::wallets::WalletsExt;
-use rsban_rpc_messages::{BlockDto, SendArgs};
+use rsban_node::wallets::{SendRequest, SpendingLimitExceeded, WalletsError, WalletsExt};
+use rsban_rpc_messages::{BlockDto, SendArgs, SendResponse};
 
 impl RpcCommandHandler {
-    pub(crate) fn send(&self, args: SendArgs) -> anyhow::Result<BlockDto> {
+    /// Returns the pending send instead of a block if the wallet requires send approval
+    pub(crate) fn send(&self, args: SendArgs) -> anyhow::Result<SendResponse> {
         let wallet_id = args.wallet;
         let amount = args.amount;
         // Sending 0 amount is invalid with state blocks
@@ -39,7 +41,23 @@ impl RpcCommandHandler {
         let generate_work = work == 0; // Disable work generation if "work" option is provided
         let send_id = args.id;
 
-        let block_hash = self.node.wallets.send_sync(
+        if self.node.wallets.requires_send_approval(&wallet_id) {
+            let request = self.node.wallets.request_send(
+                &wallet_id,
+                source,
+                destination,
+                amount,
+                work,
+                send_id,
+            )?;
+            return Ok(match request {
+                SendRequest::Pending(pending) => {
+                    SendResponse::PendingApproval(to_pending_send_dto(pending))
+                }
+                SendRequest::Sent(hash) => SendResponse::Block(BlockDto::new(hash)),
+            });
+        }
+        let block_hash = match self.node.wallets.try_send_sync(
             wallet_id,
             source,
             destination,
@@ -47,16 +65,21 @@ impl RpcCommandHandler {
             work,
             generate_work,
             send_id,
-        );
-
-        if block_hash.is_zero() {
-            if balance >= amount {
-                bail!("Error generating block")
-            } else {
-                bail!("Insufficient balance")
+        ) {
+            Ok(hash) => hash,
+            Err(e) => {
+                if let Some(exceeded) = e.downcast_ref::<SpendingLimitExceeded>() {
+                    bail!(exceeded.as_str())
+                } else if e.is::<WalletsError>() {
+                    return Err(e);
+                } else if balance >= amount {
+                    bail!("Error generating block")
+                } else {
+                    bail!("Insufficient balance")
+                }
             }
-        }
+        };
 
-        Ok(BlockDto::new(block_hash))
+        Ok(SendResponse::Block(BlockDto::new(block_hash)))
     }
 }
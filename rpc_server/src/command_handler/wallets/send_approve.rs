@@ -0,0 +1,13 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{BlockDto, SendApproveArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn send_approve(&self, args: SendApproveArgs) -> anyhow::Result<BlockDto> {
+        let block =
+            self.node
+                .wallets
+                .approve_send(&args.wallet, &args.id, &args.approval_password)?;
+        Ok(BlockDto::new(block.hash()))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::anyhow;
+use rsban_node::wallets::{SendIdInfo, SendIdStatus};
+use rsban_rpc_messages::{SendIdArgs, SendIdInfoDto, SendIdStatusDto};
+
+impl RpcCommandHandler {
+    pub(crate) fn send_id_info(&self, args: SendIdArgs) -> anyhow::Result<SendIdInfoDto> {
+        let info = self
+            .node
+            .wallets
+            .send_id_info(&args.id)?
+            .ok_or_else(|| anyhow!("Send id not found"))?;
+        Ok(to_send_id_dto(info))
+    }
+}
+
+pub(crate) fn to_send_id_dto(info: SendIdInfo) -> SendIdInfoDto {
+    SendIdInfoDto {
+        id: info.id,
+        wallet: info.record.wallet_id,
+        block: info.record.hash,
+        status: match info.status {
+            SendIdStatus::Pending => SendIdStatusDto::Pending,
+            SendIdStatus::Published => SendIdStatusDto::Published,
+            SendIdStatus::Confirmed => SendIdStatusDto::Confirmed,
+            SendIdStatus::Failed => SendIdStatusDto::Failed,
+        },
+        created: info.record.created.into(),
+        local_timestamp: info.local_timestamp.map(|t| t.into()),
+    }
+}
@@ -0,0 +1,11 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{SendRejectArgs, SuccessResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn send_reject(&self, args: SendRejectArgs) -> anyhow::Result<SuccessResponse> {
+        self.node
+            .wallets
+            .reject_send(&args.wallet, &args.id, &args.approval_password)?;
+        Ok(SuccessResponse::new())
+    }
+}
@@ -0,0 +1,20 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{WalletBackupArgs, WalletBackupResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_backup(
+        &self,
+        args: WalletBackupArgs,
+    ) -> anyhow::Result<WalletBackupResponse> {
+        self.node
+            .wallets
+            .verify_key_access(&args.wallet, args.approval_password.as_deref())?;
+        let backup = self
+            .node
+            .wallets
+            .backup_wallet(&args.wallet, &args.password)?;
+        Ok(WalletBackupResponse {
+            backup: hex::encode_upper(backup),
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use super::wallet_restore_mnemonic::MnemonicSeed;
+use crate::command_handler::RpcCommandHandler;
+use rsban_core::Mnemonic;
+use rsban_rpc_messages::{WalletCreateMnemonicArgs, WalletCreateMnemonicResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_create_mnemonic(
+        &self,
+        args: WalletCreateMnemonicArgs,
+    ) -> anyhow::Result<WalletCreateMnemonicResponse> {
+        let mnemonic = Mnemonic::random();
+        let seed = MnemonicSeed::new(&mnemonic, args.passphrase, args.derivation)?;
+
+        let (wallet, _, account) = self.create_mnemonic_wallet(&seed)?;
+
+        Ok(WalletCreateMnemonicResponse {
+            wallet,
+            mnemonic: mnemonic.to_string(),
+            account,
+        })
+    }
+}
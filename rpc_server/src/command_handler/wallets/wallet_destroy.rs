@@ -1,15 +1,18 @@
 use crate::command_handler::RpcCommandHandler;
 use anyhow::bail;
-use rsban_rpc_messages::{DestroyedResponse, WalletRpcMessage};
+use rsban_rpc_messages::{DestroyedResponse, WalletWithApprovalArgs};
 
 impl RpcCommandHandler {
     pub(crate) fn wallet_destroy(
         &self,
-        args: WalletRpcMessage,
+        args: WalletWithApprovalArgs,
     ) -> anyhow::Result<DestroyedResponse> {
         if !self.node.wallets.wallet_exists(&args.wallet) {
             bail!("Wallet not found");
         }
+        self.node
+            .wallets
+            .verify_key_access(&args.wallet, args.approval_password.as_deref())?;
         self.node.wallets.destroy(&args.wallet);
         let destroyed = !self.node.wallets.wallet_exists(&args.wallet);
         Ok(DestroyedResponse::new(destroyed))
@@ -1,8 +1,14 @@
 use crate::command_handler::RpcCommandHandler;
-use rsban_rpc_messages::{JsonResponse, WalletRpcMessage};
+use rsban_rpc_messages::{JsonResponse, WalletWithApprovalArgs};
 
 impl RpcCommandHandler {
-    pub(crate) fn wallet_export(&self, args: WalletRpcMessage) -> anyhow::Result<JsonResponse> {
+    pub(crate) fn wallet_export(
+        &self,
+        args: WalletWithApprovalArgs,
+    ) -> anyhow::Result<JsonResponse> {
+        self.node
+            .wallets
+            .verify_key_access(&args.wallet, args.approval_password.as_deref())?;
         let json = self.node.wallets.serialize(args.wallet)?;
         Ok(JsonResponse::new(json))
     }
@@ -0,0 +1,30 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::wallets::PendingSend;
+use rsban_rpc_messages::{PendingSendDto, WalletPendingSendsResponse, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_pending_sends(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletPendingSendsResponse> {
+        let pending = self
+            .node
+            .wallets
+            .pending_sends(&args.wallet)?
+            .into_iter()
+            .map(to_pending_send_dto)
+            .collect();
+        Ok(WalletPendingSendsResponse { pending })
+    }
+}
+
+pub(crate) fn to_pending_send_dto(send: PendingSend) -> PendingSendDto {
+    PendingSendDto {
+        id: send.id,
+        source: send.source,
+        destination: send.destination,
+        amount: send.amount,
+        send_id: send.send_id,
+        created: send.created.into(),
+    }
+}
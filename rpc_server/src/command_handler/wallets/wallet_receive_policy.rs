@@ -0,0 +1,19 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{WalletReceivePolicyResponse, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_receive_policy(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletReceivePolicyResponse> {
+        let policy = self.node.wallets.receive_policy(&args.wallet)?;
+        Ok(WalletReceivePolicyResponse {
+            receive_minimum: policy.receive_minimum_or(self.node.config.receive_minimum),
+            auto_receive: policy.auto_receive.into(),
+            allowed_senders: policy.allowed_senders,
+            denied_senders: policy.denied_senders,
+            max_receive_per_hour: policy.max_receive_per_hour,
+            received_last_hour: self.node.wallets.received_last_hour(&args.wallet),
+        })
+    }
+}
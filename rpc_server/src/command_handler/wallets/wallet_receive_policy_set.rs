@@ -0,0 +1,20 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::wallets::ReceivePolicy;
+use rsban_rpc_messages::{SuccessResponse, WalletReceivePolicySetArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_receive_policy_set(
+        &self,
+        args: WalletReceivePolicySetArgs,
+    ) -> anyhow::Result<SuccessResponse> {
+        let policy = ReceivePolicy {
+            receive_minimum: args.receive_minimum,
+            auto_receive: args.auto_receive.map(|i| i.inner()).unwrap_or(true),
+            allowed_senders: args.allowed_senders,
+            denied_senders: args.denied_senders,
+            max_receive_per_hour: args.max_receive_per_hour,
+        };
+        self.node.wallets.set_receive_policy(&args.wallet, policy)?;
+        Ok(SuccessResponse::new())
+    }
+}
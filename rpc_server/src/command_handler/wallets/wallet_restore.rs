@@ -0,0 +1,19 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::anyhow;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{WalletRestoreArgs, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_restore(
+        &self,
+        args: WalletRestoreArgs,
+    ) -> anyhow::Result<WalletRpcMessage> {
+        let backup = hex::decode(&args.backup).map_err(|_| anyhow!("Bad backup"))?;
+        let wallet = self
+            .node
+            .wallets
+            .restore_wallet(&backup, &args.password, &args.wallet_password)
+            .map_err(|e| anyhow!("Wallet restore failed: {}", e))?;
+        Ok(WalletRpcMessage::new(wallet))
+    }
+}
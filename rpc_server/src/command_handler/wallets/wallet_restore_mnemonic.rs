@@ -0,0 +1,74 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::{anyhow, bail};
+use rsban_core::{Account, Mnemonic, RawKey, Slip10Key, WalletId};
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{DerivationSchemeDto, WalletCreateResponse, WalletRestoreMnemonicArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_restore_mnemonic(
+        &self,
+        args: WalletRestoreMnemonicArgs,
+    ) -> anyhow::Result<WalletCreateResponse> {
+        let mnemonic = Mnemonic::parse(&args.mnemonic).map_err(|_| anyhow!("Invalid mnemonic"))?;
+        let seed = MnemonicSeed::new(&mnemonic, args.passphrase, args.derivation)?;
+
+        let (wallet, restored_count, last_restored_account) = self.create_mnemonic_wallet(&seed)?;
+
+        Ok(WalletCreateResponse {
+            wallet,
+            last_restored_account: Some(last_restored_account),
+            restored_count: Some(restored_count.into()),
+        })
+    }
+
+    /// Creates a new wallet with the given seed. The wallet is destroyed again if the
+    /// seed can't be set
+    pub(super) fn create_mnemonic_wallet(
+        &self,
+        seed: &MnemonicSeed,
+    ) -> anyhow::Result<(WalletId, u32, Account)> {
+        let wallet = WalletId::random();
+        self.node.wallets.create(wallet);
+        let result = match seed {
+            MnemonicSeed::Legacy(seed) => self.node.wallets.change_seed(wallet, seed, 0),
+            MnemonicSeed::Bip44(root) => self.node.wallets.change_bip44_seed(wallet, root, 0),
+        };
+        match result {
+            Ok((restored_count, last_account)) => Ok((wallet, restored_count, last_account)),
+            Err(e) => {
+                self.node.wallets.destroy(&wallet);
+                Err(e.into())
+            }
+        }
+    }
+}
+
+/// The wallet seed a mnemonic stands for, depending on the derivation scheme
+pub(super) enum MnemonicSeed {
+    Legacy(RawKey),
+    Bip44(Slip10Key),
+}
+
+impl MnemonicSeed {
+    pub(super) fn new(
+        mnemonic: &Mnemonic,
+        passphrase: Option<String>,
+        derivation: Option<DerivationSchemeDto>,
+    ) -> anyhow::Result<Self> {
+        let passphrase = passphrase.unwrap_or_default();
+        match derivation.unwrap_or(DerivationSchemeDto::Bip44) {
+            DerivationSchemeDto::Legacy => {
+                if !passphrase.is_empty() {
+                    bail!("Legacy derivation does not support a passphrase");
+                }
+                let seed = mnemonic
+                    .legacy_seed()
+                    .ok_or_else(|| anyhow!("Legacy derivation requires a 24 word mnemonic"))?;
+                Ok(Self::Legacy(seed))
+            }
+            DerivationSchemeDto::Bip44 => Ok(Self::Bip44(Slip10Key::banano_root(
+                &mnemonic.bip39_seed(&passphrase),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{SuccessResponse, WalletSendApprovalSetArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_send_approval_set(
+        &self,
+        args: WalletSendApprovalSetArgs,
+    ) -> anyhow::Result<SuccessResponse> {
+        if args.enabled.inner() {
+            self.node
+                .wallets
+                .enable_send_approval(&args.wallet, &args.approval_password)?;
+        } else {
+            self.node
+                .wallets
+                .disable_send_approval(&args.wallet, &args.approval_password)?;
+        }
+        Ok(SuccessResponse::new())
+    }
+}
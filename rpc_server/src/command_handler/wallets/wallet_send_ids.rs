@@ -0,0 +1,19 @@
+use super::send_id_info::to_send_id_dto;
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{WalletRpcMessage, WalletSendIdsResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_send_ids(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletSendIdsResponse> {
+        let send_ids = self
+            .node
+            .wallets
+            .send_ids_of_wallet(&args.wallet)?
+            .into_iter()
+            .map(to_send_id_dto)
+            .collect();
+        Ok(WalletSendIdsResponse::new(send_ids))
+    }
+}
@@ -0,0 +1,22 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::wallets::SpendingLimit;
+use rsban_rpc_messages::{SuccessResponse, WalletSpendingLimitSetArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_spending_limit_set(
+        &self,
+        args: WalletSpendingLimitSetArgs,
+    ) -> anyhow::Result<SuccessResponse> {
+        let limit = SpendingLimit {
+            per_transaction: args.per_transaction,
+            daily: args.daily,
+        };
+        self.node.wallets.set_spending_limit(
+            &args.wallet,
+            args.account,
+            limit,
+            args.approval_password.as_deref(),
+        )?;
+        Ok(SuccessResponse::new())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{AccountSpendingLimitDto, WalletRpcMessage, WalletSpendingLimitsResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_spending_limits(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletSpendingLimitsResponse> {
+        let wallets = &self.node.wallets;
+        let policy = wallets.spending_policy(&args.wallet)?;
+        let mut accounts = Vec::with_capacity(policy.accounts.len());
+        for (account, limit) in &policy.accounts {
+            accounts.push(AccountSpendingLimitDto {
+                account: *account,
+                per_transaction: limit.per_transaction,
+                daily: limit.daily,
+                spent_last_day: wallets.spent_last_day(&args.wallet, Some(*account))?,
+            });
+        }
+        Ok(WalletSpendingLimitsResponse {
+            per_transaction: policy.wallet.per_transaction,
+            daily: policy.wallet.daily,
+            spent_last_day: wallets.spent_last_day(&args.wallet, None)?,
+            approval_required: policy.requires_approval().into(),
+            accounts,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use rsban_core::{PrivateKey, UnsavedBlockLatticeBuilder};
+use rsban_rpc_messages::{ElectionArgs, ElectionStartArgs};
+use std::time::Duration;
+use test_helpers::{assert_timely_eq, assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn start_inspect_and_cancel_election() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.priority_scheduler_enabled = false;
+    config.hinted_scheduler.enabled = false;
+    config.optimistic_scheduler.enabled = false;
+    let node = system.build_node().config(config).finish();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let key = PrivateKey::new();
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice.genesis().send(&key, 100);
+    node.process_local(send.clone()).unwrap();
+
+    let started = node.runtime.block_on(async {
+        server
+            .client
+            .election_start(ElectionStartArgs::with_behavior(send.hash(), "hinted"))
+            .await
+            .unwrap()
+    });
+    assert_eq!(started.started, true.into());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.active.election(&send.qualified_root()).is_some(),
+        "election not started",
+    );
+
+    let info = node.runtime.block_on(async {
+        server
+            .client
+            .election_info(ElectionArgs::hash(send.hash()))
+            .await
+            .unwrap()
+    });
+    assert_eq!(info.root, send.qualified_root());
+    assert_eq!(info.behavior, "hinted");
+    assert_eq!(info.blocks.len(), 1);
+    assert_eq!(info.blocks[0].hash, send.hash());
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .election_cancel(ElectionArgs::root(send.qualified_root()))
+            .await
+            .unwrap()
+    });
+    assert_timely_eq(Duration::from_secs(5), || node.active.len(), 0);
+
+    let error = node.runtime.block_on(async {
+        server
+            .client
+            .election_info(ElectionArgs::hash(send.hash()))
+            .await
+            .unwrap_err()
+    });
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"Election not found\""
+    );
+}
+
+#[test]
+fn election_start_requires_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let key = PrivateKey::new();
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice.genesis().send(&key, 100);
+    node.process_local(send.clone()).unwrap();
+
+    let error = node.runtime.block_on(async {
+        server
+            .client
+            .election_start(ElectionStartArgs::new(send.hash()))
+            .await
+            .unwrap_err()
+    });
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"RPC control is disabled\""
+    );
+}
@@ -0,0 +1,69 @@
+use rsban_core::{PrivateKey, UnsavedBlockLatticeBuilder, DEV_GENESIS_KEY};
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::consensus::ElectionTracerConfig;
+use rsban_rpc_messages::{ElectionTraceArgs, ElectionTraceEventKindDto};
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn election_trace() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.election_tracer = ElectionTracerConfig {
+        accounts: vec![*DEV_GENESIS_ACCOUNT],
+        ..Default::default()
+    };
+    let node = system.build_node().config(config).finish();
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice.genesis().send(&PrivateKey::new(), 1);
+    node.process_active(send.clone());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        "send not confirmed",
+    );
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || {
+            node.election_tracer
+                .trace(&send.hash())
+                .is_some_and(|t| t.is_finished())
+        },
+        "election not finished",
+    );
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .election_trace(ElectionTraceArgs::for_hash(send.hash()))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.traces.len(), 1);
+    let trace = &result.traces[0];
+    assert_eq!(trace.hash, send.hash());
+    assert_eq!(trace.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(trace.winner, Some(send.hash()));
+    assert_eq!(trace.state.as_deref(), Some("confirmed"));
+    assert!(matches!(
+        trace.events[0].event,
+        ElectionTraceEventKindDto::Started { .. }
+    ));
+    assert!(trace.events.iter().any(|e| matches!(
+        &e.event,
+        ElectionTraceEventKindDto::StateChanged { state } if state == "confirmed"
+    )));
+
+    let all = node.runtime.block_on(async {
+        server
+            .client
+            .election_trace(ElectionTraceArgs::default())
+            .await
+            .unwrap()
+    });
+    assert_eq!(all.traces.len(), 1);
+}
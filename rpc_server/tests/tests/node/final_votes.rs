@@ -0,0 +1,63 @@
+use rsban_core::{PrivateKey, UnsavedBlockLatticeBuilder};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn inspect_and_prune_final_votes() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.final_vote_cleanup.enabled = false;
+    let node = system.build_node().config(config).finish();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let key = PrivateKey::new();
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice.genesis().send(&key, 100);
+    let send2 = lattice.genesis().send(&key, 100);
+    node.process(send1.clone()).unwrap();
+    node.process(send2.clone()).unwrap();
+    node.confirm(send2.hash());
+    {
+        let mut tx = node.ledger.rw_txn();
+        let final_votes = &node.ledger.store.final_vote;
+        final_votes.put(&mut tx, &send1.qualified_root(), &send1.hash());
+        final_votes.put(&mut tx, &send2.qualified_root(), &send2.hash());
+    }
+
+    let count = node
+        .runtime
+        .block_on(async { server.client.final_vote_count().await.unwrap() });
+    assert_eq!(count.count, 2.into());
+
+    let info = node
+        .runtime
+        .block_on(async { server.client.final_vote_info(send1.root()).await.unwrap() });
+    assert_eq!(info.votes.len(), 1);
+    assert_eq!(info.votes[0].root, send1.qualified_root());
+    assert_eq!(info.votes[0].hash, send1.hash());
+    assert_eq!(info.votes[0].cemented_depth, Some(1.into()));
+
+    let pruned = node
+        .runtime
+        .block_on(async { server.client.final_vote_prune(1).await.unwrap() });
+    assert_eq!(pruned.deleted, 1.into());
+
+    let count = node
+        .runtime
+        .block_on(async { server.client.final_vote_count().await.unwrap() });
+    assert_eq!(count.count, 1.into());
+}
+
+#[test]
+fn final_vote_prune_requires_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let error = node
+        .runtime
+        .block_on(async { server.client.final_vote_prune(1).await.unwrap_err() });
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"RPC control is disabled\""
+    );
+}
@@ -0,0 +1,75 @@
+use rsban_core::{PrivateKey, UnsavedBlockLatticeBuilder, DEV_GENESIS_KEY};
+use rsban_ledger::{BlockStatus, DEV_GENESIS_ACCOUNT};
+use rsban_rpc_messages::{ForksActiveArgs, ForksHistoryArgs};
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, start_election, System};
+
+#[test]
+fn forks_active_and_history() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let key = PrivateKey::new();
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice.genesis().send(&key, 100);
+    let mut fork_lattice = UnsavedBlockLatticeBuilder::new();
+    let send2 = fork_lattice.genesis().send(&key, 200);
+
+    node.process_local(send1.clone()).unwrap();
+    start_election(&node, &send1.hash());
+    assert_eq!(
+        node.process_local(send2.clone()).unwrap(),
+        BlockStatus::Fork
+    );
+
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || !node.fork_tracker.active().is_empty(),
+        "fork not detected",
+    );
+
+    let active = node.runtime.block_on(async {
+        server
+            .client
+            .forks_active(ForksActiveArgs::for_account(*DEV_GENESIS_ACCOUNT))
+            .await
+            .unwrap()
+    });
+    assert_eq!(active.forks.len(), 1);
+    assert_eq!(active.forks[0].root, send1.qualified_root());
+    assert_eq!(active.forks[0].candidates.len(), 2);
+    assert_eq!(active.forks[0].winner, None);
+
+    let other_account = node.runtime.block_on(async {
+        server
+            .client
+            .forks_active(ForksActiveArgs::for_account(key.account()))
+            .await
+            .unwrap()
+    });
+    assert!(other_account.forks.is_empty());
+
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send1.hash()),
+        "fork not resolved",
+    );
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || !node.fork_tracker.history().is_empty(),
+        "fork not in history",
+    );
+
+    let history = node.runtime.block_on(async {
+        server
+            .client
+            .forks_history(ForksHistoryArgs::default())
+            .await
+            .unwrap()
+    });
+    assert_eq!(history.forks.len(), 1);
+    assert_eq!(history.forks[0].winner, Some(send1.hash()));
+    assert!(history.forks[0].resolved.is_some());
+}
@@ -5,15 +5,23 @@ mod bootstrap_lazy;
 mod confirmation_active;
 mod confirmation_info;
 mod confirmation_quorum;
+mod election_control;
+mod election_trace;
+mod final_votes;
+mod forks;
 mod node_id;
+mod peer_ban;
 mod peers;
 mod populate_backlog;
 mod process;
+mod quorum_simulate;
 mod receivable;
 mod receivable_exists;
+mod representative_stats;
 mod representatives_online;
 mod republish;
 mod sign;
+mod stats;
 mod stats_clear;
 mod stop;
 mod telemetry;
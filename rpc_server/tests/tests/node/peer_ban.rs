@@ -0,0 +1,169 @@
+use rsban_rpc_messages::{PeerBanArgs, PeerDropArgs};
+use std::net::Ipv6Addr;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn ban_and_unban_subnet() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .peer_ban(PeerBanArgs::builder("10.1.2.3/16").duration(3600).build())
+            .await
+            .unwrap()
+    });
+
+    assert!(node.network_info.write().unwrap().is_excluded(
+        &"[::ffff:10.1.200.1]:7075".parse().unwrap(),
+        node.steady_clock.now()
+    ));
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.peer_exclusions().await.unwrap() });
+    assert_eq!(result.excluded.len(), 1);
+    assert_eq!(result.excluded[0].address, "10.1.0.0/16");
+    assert_eq!(result.excluded[0].reason, "banned");
+    assert!(result.excluded[0].excluded_until.is_some());
+
+    node.runtime
+        .block_on(async { server.client.peer_unban("10.1.0.0/16").await.unwrap() });
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.peer_exclusions().await.unwrap() });
+    assert!(result.excluded.is_empty());
+
+    let error = node
+        .runtime
+        .block_on(async { server.client.peer_unban("10.1.0.0/16").await })
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"Peer not excluded\""
+    );
+}
+
+#[test]
+fn persisted_ban() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .peer_ban(PeerBanArgs::builder("2001:db8::/32").persist().build())
+            .await
+            .unwrap()
+    });
+
+    let tx = node.ledger.read_txn();
+    assert_eq!(node.ledger.store.peer_ban.count(&tx), 1);
+    drop(tx);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.peer_exclusions().await.unwrap() });
+    assert_eq!(result.excluded[0].address, "2001:db8::/32");
+    assert_eq!(result.excluded[0].excluded_until, None);
+
+    node.runtime
+        .block_on(async { server.client.peer_unban("2001:db8::/32").await.unwrap() });
+
+    let tx = node.ledger.read_txn();
+    assert_eq!(node.ledger.store.peer_ban.count(&tx), 0);
+}
+
+#[test]
+fn invalid_address() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let error = node
+        .runtime
+        .block_on(async { server.client.peer_ban(PeerBanArgs::new("foo")).await })
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"Invalid address\""
+    );
+}
+
+#[test]
+fn drop_peer_by_node_id() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+    let server = setup_rpc_client_and_server(node1.clone(), true);
+
+    let result = node1.runtime.block_on(async {
+        server
+            .client
+            .peer_drop(PeerDropArgs::node(node2.node_id()))
+            .await
+            .unwrap()
+    });
+    assert!(result.dropped.inner() > 0);
+
+    let error = node1
+        .runtime
+        .block_on(async {
+            server
+                .client
+                .peer_drop(PeerDropArgs::channel(usize::MAX as u64))
+                .await
+        })
+        .unwrap_err();
+    assert_eq!(error.to_string(), "node returned error: \"Peer not found\"");
+}
+
+#[test]
+fn pin_and_unpin_peer() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    let address = Ipv6Addr::LOCALHOST;
+
+    node.runtime
+        .block_on(async { server.client.peer_pin(address, 7075).await.unwrap() });
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.peers_pinned().await.unwrap() });
+    assert_eq!(result.peers, vec!["[::1]:7075".parse().unwrap()]);
+
+    node.runtime
+        .block_on(async { server.client.peer_unpin(address, 7075).await.unwrap() });
+
+    let error = node
+        .runtime
+        .block_on(async { server.client.peer_unpin(address, 7075).await })
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"Peer not pinned\""
+    );
+}
+
+#[test]
+fn peer_ban_requires_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let error = node
+        .runtime
+        .block_on(async { server.client.peer_ban(PeerBanArgs::new("10.0.0.1")).await })
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"RPC control is disabled\""
+    );
+}
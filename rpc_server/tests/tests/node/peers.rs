@@ -39,6 +39,12 @@ fn peers_with_details() {
     match result {
         PeersDto::Detailed(peers) => {
             assert!(!peers.peers.is_empty());
+            let peer = peers.peers.values().next().unwrap();
+            assert_eq!(peer.mode.as_deref(), Some("realtime"));
+            assert!(peer.direction.is_some());
+            let traffic = peer.traffic.as_ref().unwrap();
+            assert!(traffic.bytes_in.inner() > 0);
+            assert!(traffic.bytes_out.inner() > 0);
         }
         PeersDto::Simple(_) => panic!("Expected Detailed peer data"),
     }
@@ -0,0 +1,71 @@
+use rsban_core::{Amount, PrivateKey};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
+use rsban_rpc_messages::WeightChangeDto;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn quorum_simulate() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let rep = PrivateKey::new().account();
+    let genesis_weight = node.ledger.weight(&DEV_GENESIS_PUB_KEY);
+    let moved = genesis_weight / 2;
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .quorum_simulate(vec![
+                WeightChangeDto::Move {
+                    from: *DEV_GENESIS_ACCOUNT,
+                    to: rep,
+                    amount: moved,
+                },
+                WeightChangeDto::Online {
+                    representative: rep,
+                },
+            ])
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.before.principal_representatives.len(), 1);
+    assert_eq!(
+        result.before.principal_representatives[0].account,
+        *DEV_GENESIS_ACCOUNT
+    );
+    assert_eq!(result.before.principal_representatives[0].tier, "tier_3");
+
+    let after = &result.after.principal_representatives;
+    assert_eq!(after.len(), 2);
+    assert!(after.iter().any(|r| r.account == rep && r.weight == moved));
+    assert_eq!(
+        result.after.trended_stake_total,
+        result.before.trended_stake_total
+    );
+}
+
+#[test]
+fn quorum_simulate_fails_with_insufficient_weight() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let error = node.runtime.block_on(async {
+        server
+            .client
+            .quorum_simulate(vec![WeightChangeDto::Move {
+                from: PrivateKey::new().account(),
+                to: *DEV_GENESIS_ACCOUNT,
+                amount: Amount::raw(1),
+            }])
+            .await
+            .unwrap_err()
+    });
+
+    assert_eq!(
+        error.to_string(),
+        "node returned error: \"Representative has insufficient weight\""
+    );
+}
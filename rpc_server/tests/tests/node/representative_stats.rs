@@ -0,0 +1,115 @@
+use rsban_core::{Amount, PublicKey, WalletId, DEV_GENESIS_KEY};
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::RepresentativeStatsArgs;
+use std::time::{Duration, SystemTime};
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn representative_stats() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets
+        .insert_adhoc2(&wallet, &(*DEV_GENESIS_KEY).raw_key(), true)
+        .unwrap();
+
+    let send = node
+        .wallets
+        .send_action2(
+            &wallet,
+            *DEV_GENESIS_ACCOUNT,
+            PublicKey::from(42).into(),
+            Amount::raw(1),
+            0,
+            true,
+            None,
+        )
+        .unwrap();
+    node.process_active(send.clone().into());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        "send not confirmed",
+    );
+
+    node.rep_stats
+        .sample([&DEV_GENESIS_KEY.public_key()], SystemTime::now());
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .representative_stats(RepresentativeStatsArgs::with_history(*DEV_GENESIS_ACCOUNT))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(f64::from(result.uptime), 100.0);
+    assert_eq!(u64::from(result.samples), 1);
+    assert!(u64::from(result.elections) >= 1);
+    assert!(u64::from(result.votes) >= 1);
+    assert_eq!(f64::from(result.participation), 100.0);
+    assert_eq!(result.history.unwrap().len(), 1);
+}
+
+#[test]
+fn representative_stats_unknown_rep() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .representative_stats(RepresentativeStatsArgs::new(PublicKey::from(42).into()))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.online, false.into());
+    assert_eq!(u64::from(result.samples), 0);
+    assert_eq!(result.history, None);
+}
+
+#[test]
+fn representative_stats_of_offline_rep_expire() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    // Samples are taken manually
+    config.rep_stats.enabled = false;
+    config.rep_stats.bucket_duration = Duration::from_secs(60);
+    config.rep_stats.max_buckets = 2;
+    let node = system.build_node().config(config).finish();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+    let rep = PublicKey::from(42);
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 1000);
+
+    node.rep_stats.sample([&rep], start);
+    node.rep_stats.sample([], start + Duration::from_secs(60));
+
+    let stats = |node: &rsban_node::Node| {
+        node.runtime.block_on(async {
+            server
+                .client
+                .representative_stats(RepresentativeStatsArgs::with_history(rep.into()))
+                .await
+                .unwrap()
+        })
+    };
+
+    // The rep is only stored for the first bucket, but it was offline in the second one
+    let result = stats(&node);
+    assert_eq!(u64::from(result.samples), 2);
+    assert_eq!(f64::from(result.uptime), 50.0);
+    assert_eq!(result.history.unwrap().len(), 2);
+
+    node.rep_stats
+        .sample([], start + Duration::from_secs(60 * 4));
+
+    let result = stats(&node);
+    assert_eq!(u64::from(result.samples), 0);
+    assert_eq!(result.history, Some(Vec::new()));
+}
@@ -1,9 +1,10 @@
-use rsban_core::{Amount, WalletId, DEV_GENESIS_KEY};
-use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_core::{Amount, NodeId, PrivateKey, WalletId, DEV_GENESIS_KEY};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
+use rsban_network::ChannelId;
 use rsban_node::wallets::WalletsExt;
 use rsban_rpc_messages::{RepresentativesOnlineArgs, RepresentativesOnlineResponse};
 use std::time::Duration;
-use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+use test_helpers::{assert_timely_msg, setup_new_account, setup_rpc_client_and_server, System};
 
 #[test]
 fn representatives_online() {
@@ -129,3 +130,112 @@ fn representatives_online() {
         "Node2 doesn't have the correct number of online representatives"
     );
 }
+
+#[test]
+fn representatives_online_with_details() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let node2 = system.make_node();
+    let server = setup_rpc_client_and_server(node2.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets
+        .insert_adhoc2(&wallet, &(*DEV_GENESIS_KEY).raw_key(), true)
+        .unwrap();
+
+    assert_timely_msg(
+        Duration::from_secs(10),
+        || {
+            node2
+                .online_reps
+                .lock()
+                .unwrap()
+                .peered_rep(&DEV_GENESIS_PUB_KEY)
+                .map(|rep| rep.latency.is_some())
+                .unwrap_or(false)
+        },
+        "genesis representative not peered",
+    );
+
+    let args = RepresentativesOnlineArgs::builder().details().build();
+    let result = node2
+        .runtime
+        .block_on(async { server.client.representatives_online(args).await })
+        .unwrap();
+
+    let RepresentativesOnlineResponse::Detailed(result) = result else {
+        panic!("Not a detailed result")
+    };
+
+    let rep = result.representatives.get(&*DEV_GENESIS_ACCOUNT).unwrap();
+    assert_eq!(rep.weight, node2.ledger.weight(&DEV_GENESIS_PUB_KEY));
+    assert_eq!(rep.address, Some(node.tcp_listener.local_address()));
+    assert_eq!(rep.node_id, Some(NodeId::from(&node.node_id).to_string()));
+    assert!(rep.protocol_version.is_some());
+    assert!(rep.latency_ms.is_some());
+    assert!(rep.last_response_elapsed_ms.is_some());
+    assert!(rep.last_vote_elapsed_ms.is_some());
+}
+
+#[test]
+fn representatives_online_with_details_includes_stale_peered_reps() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let rep = PrivateKey::new();
+    setup_new_account(
+        &node,
+        Amount::nano(1000),
+        &DEV_GENESIS_KEY,
+        &PrivateKey::new(),
+        rep.public_key(),
+        true,
+    );
+
+    // Genesis is peered, but its last vote is older than the weight period
+    let now = node.steady_clock.now();
+    let weight_period = Duration::from_secs(node.network_params.node.weight_period);
+    {
+        let mut online_reps = node.online_reps.lock().unwrap();
+        online_reps.vote_observed_directly(
+            *DEV_GENESIS_PUB_KEY,
+            ChannelId::from(42),
+            now - weight_period - Duration::from_secs(1),
+        );
+        online_reps.vote_observed(rep.public_key(), now);
+        assert_eq!(online_reps.online_reps().count(), 1);
+    }
+
+    let args = RepresentativesOnlineArgs::builder().weight().build();
+    let result = node
+        .runtime
+        .block_on(async { server.client.representatives_online(args).await })
+        .unwrap();
+    let RepresentativesOnlineResponse::Detailed(result) = result else {
+        panic!("Not a detailed result")
+    };
+    assert!(!result.representatives.contains_key(&*DEV_GENESIS_ACCOUNT));
+
+    let args = RepresentativesOnlineArgs::builder().details().build();
+    let result = node
+        .runtime
+        .block_on(async { server.client.representatives_online(args).await })
+        .unwrap();
+    let RepresentativesOnlineResponse::Detailed(result) = result else {
+        panic!("Not a detailed result")
+    };
+
+    let genesis = result.representatives.get(&*DEV_GENESIS_ACCOUNT).unwrap();
+    assert_eq!(genesis.online, Some(false.into()));
+    assert_eq!(genesis.unresponsive, Some(true.into()));
+    assert_eq!(genesis.last_vote_elapsed_ms, None);
+
+    let rep = result
+        .representatives
+        .get(&rep.public_key().as_account())
+        .unwrap();
+    assert_eq!(rep.online, Some(true.into()));
+    assert_eq!(rep.unresponsive, None);
+}
@@ -27,6 +27,7 @@ fn sign() {
         wallet: None,
         account: None,
         hash: None,
+        approval_password: None,
         key: Some(DEV_GENESIS_KEY.raw_key()),
     };
 
@@ -0,0 +1,49 @@
+use rsban_core::{PrivateKey, UnsavedBlockLatticeBuilder, DEV_GENESIS_KEY};
+use rsban_rpc_messages::{ConfirmationLatencyDto, StatsType};
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn confirmation_latency() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let key = PrivateKey::new();
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send = lattice.genesis().send(&key, 100);
+    node.process_local(send.clone()).unwrap();
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        "block not confirmed",
+    );
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || !node.confirmation_latency.by_source().is_empty(),
+        "latency not measured",
+    );
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .stats(StatsType::ConfirmationLatency)
+            .await
+            .unwrap()
+    });
+    let latency: ConfirmationLatencyDto = serde_json::from_value(result).unwrap();
+
+    let local = latency.by_source.get("local").unwrap();
+    assert_eq!(local.arrival_to_cemented.count, 1.into());
+    assert_eq!(
+        local.arrival_to_cemented.buckets.last().unwrap().count,
+        1.into()
+    );
+    assert_eq!(local.arrival_to_cemented.buckets.last().unwrap().le, "+Inf");
+    assert_eq!(latency.by_behavior.len(), 1);
+
+    node.runtime
+        .block_on(async { server.client.stats_clear().await.unwrap() });
+    assert!(node.confirmation_latency.by_source().is_empty());
+}
@@ -0,0 +1,48 @@
+use rsban_core::RawKey;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn mnemonic_to_seed() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let mnemonic = "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur";
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .mnemonic_to_seed(mnemonic, Some("some password".to_owned()))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        result.bip39_seed,
+        "0DC285FDE768F7FF29B66CE7252D56ED92FE003B605907F7A4F683C3DC8586D34A914D3C71FC099BB38EE4A59E5B081A3497B7A323E90CC68F67B5837690310C"
+    );
+    assert_eq!(
+        result.seed,
+        Some(
+            RawKey::decode_hex("46472FDE9424B1DDD633CAC96AC8B02B884CFD1E957D1BB559376DA42F3DBA5C")
+                .unwrap()
+        )
+    );
+}
+
+#[test]
+fn mnemonic_to_seed_fails_with_invalid_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.mnemonic_to_seed("banano", None).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid mnemonic\"".to_string())
+    );
+}
@@ -4,4 +4,5 @@ mod block_hash;
 mod deterministic_key;
 mod key_create;
 mod key_expand;
+mod mnemonic_to_seed;
 mod validate_account_number;
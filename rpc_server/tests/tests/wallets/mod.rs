@@ -11,12 +11,16 @@ mod receive_minimum;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
+mod send_approve;
+mod send_id_info;
 mod wallet_add;
 mod wallet_add_watch;
+mod wallet_backup;
 mod wallet_balances;
 mod wallet_change_seed;
 mod wallet_contains;
 mod wallet_create;
+mod wallet_create_mnemonic;
 mod wallet_destroy;
 mod wallet_export;
 mod wallet_frontiers;
@@ -26,9 +30,12 @@ mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
 mod wallet_receivable;
+mod wallet_receive_policy;
 mod wallet_representative;
 mod wallet_representative_set;
 mod wallet_republish;
+mod wallet_restore_mnemonic;
+mod wallet_spending_limits;
 mod wallet_work_get;
 mod work_get;
 mod work_set;
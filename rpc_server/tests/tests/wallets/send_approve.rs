@@ -0,0 +1,368 @@
+use rsban_core::{Account, Amount, Block, BlockHash, Link, WalletId, DEV_GENESIS_KEY};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_node::{wallets::WalletsExt, Node};
+use rsban_rpc_messages::{
+    BlockCreateArgs, BlockTypeDto, PendingSendDto, SendArgs, SendResponse, SignArgs,
+    WalletBackupArgs, WalletSpendingLimitSetArgs, WalletWithApprovalArgs,
+};
+use std::sync::Arc;
+use test_helpers::{setup_rpc_client_and_server, RpcServerGuard, System};
+
+fn setup(node: &Arc<Node>) -> (WalletId, RpcServerGuard) {
+    let wallet = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    node.runtime.block_on(async {
+        server
+            .client
+            .wallet_send_approval_set(wallet, true, "second factor")
+            .await
+            .unwrap()
+    });
+    (wallet, server)
+}
+
+fn send_args(wallet: WalletId) -> SendArgs {
+    SendArgs {
+        wallet,
+        source: *DEV_GENESIS_ACCOUNT,
+        destination: Account::from(1),
+        amount: Amount::raw(1000),
+        ..Default::default()
+    }
+}
+
+fn send_for_approval(node: &Node, server: &RpcServerGuard, wallet: WalletId) -> PendingSendDto {
+    let response = node
+        .runtime
+        .block_on(async { server.client.request_send(send_args(wallet)).await.unwrap() });
+    let SendResponse::PendingApproval(pending) = response else {
+        panic!("send doesn't wait for approval")
+    };
+    pending
+}
+
+#[test]
+fn send_approve() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let pending = send_for_approval(&node, &server, wallet);
+    assert_eq!(pending.amount, Amount::raw(1000));
+    assert_eq!(pending.destination, Account::from(1));
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_pending_sends(wallet).await.unwrap() });
+    assert_eq!(result.pending, vec![pending.clone()]);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_approve(wallet, pending.id.clone(), "wrong")
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid approval password\"".to_string())
+    );
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_approve(wallet, pending.id.clone(), "second factor")
+            .await
+            .unwrap()
+    });
+    assert!(node.block_exists(&result.block));
+    assert_eq!(
+        node.ledger
+            .any()
+            .account_balance(&node.ledger.read_txn(), &DEV_GENESIS_ACCOUNT)
+            .unwrap(),
+        Amount::MAX - Amount::raw(1000)
+    );
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_pending_sends(wallet).await.unwrap() });
+    assert!(result.pending.is_empty());
+}
+
+#[test]
+fn send_reject() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let pending = send_for_approval(&node, &server, wallet);
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .send_reject(wallet, pending.id.clone(), "second factor")
+            .await
+            .unwrap()
+    });
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_approve(wallet, pending.id.clone(), "second factor")
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Pending send not found\"".to_string())
+    );
+    assert_eq!(
+        node.ledger
+            .any()
+            .account_balance(&node.ledger.read_txn(), &DEV_GENESIS_ACCOUNT)
+            .unwrap(),
+        Amount::MAX
+    );
+}
+
+#[test]
+fn spending_limits_require_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_spending_limits(wallet).await.unwrap() });
+    assert!(result.approval_required.inner());
+
+    let args = WalletSpendingLimitSetArgs::builder(wallet)
+        .daily(Amount::raw(1))
+        .build();
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_spending_limit_set(args).await });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid approval password\"".to_string())
+    );
+}
+
+#[test]
+fn approval_password_must_differ_from_wallet_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_send_approval_set(wallet, true, "")
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some(
+            "node returned error: \"Approval password must differ from the wallet password\""
+                .to_string()
+        )
+    );
+}
+
+fn assert_approval_required<T: std::fmt::Debug>(result: anyhow::Result<T>) {
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Approval password required\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_export_requires_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_export(wallet).await });
+    assert_approval_required(result);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_export(WalletWithApprovalArgs::new(wallet).with_approval_password("wrong"))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid approval password\"".to_string())
+    );
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_export(
+                WalletWithApprovalArgs::new(wallet).with_approval_password("second factor"),
+            )
+            .await
+            .unwrap()
+    });
+    assert!(result
+        .json
+        .contains(&DEV_GENESIS_KEY.public_key().encode_hex()));
+}
+
+#[test]
+fn wallet_backup_requires_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_backup(WalletBackupArgs::new(wallet, "secret"))
+            .await
+    });
+    assert_approval_required(result);
+
+    let args = WalletBackupArgs::new(wallet, "secret").with_approval_password("second factor");
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_backup(args).await });
+    assert!(result.is_ok());
+}
+
+#[test]
+fn sign_with_wallet_key_requires_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+    let hash = BlockHash::from(1);
+
+    let args = SignArgs {
+        block: None,
+        key: None,
+        wallet: Some(wallet),
+        account: Some(*DEV_GENESIS_ACCOUNT),
+        hash: Some(hash),
+        approval_password: None,
+    };
+    let result = node
+        .runtime
+        .block_on(async { server.client.sign(args).await });
+    assert_approval_required(result);
+
+    let args = SignArgs {
+        block: None,
+        key: None,
+        wallet: Some(wallet),
+        account: Some(*DEV_GENESIS_ACCOUNT),
+        hash: Some(hash),
+        approval_password: Some("second factor".to_string()),
+    };
+    let result = node
+        .runtime
+        .block_on(async { server.client.sign(args).await.unwrap() });
+    assert_eq!(result.signature, DEV_GENESIS_KEY.sign(hash.as_bytes()));
+}
+
+#[test]
+fn block_create_with_wallet_key_requires_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let create_args = || {
+        BlockCreateArgs::new(
+            BlockTypeDto::State,
+            None,
+            None,
+            Some(wallet),
+            Some(*DEV_GENESIS_ACCOUNT),
+            None,
+            None,
+            Some(Account::from(1)),
+            Some(Link::zero()),
+            Some(*DEV_GENESIS_HASH),
+            None,
+            None,
+            None,
+        )
+    };
+    let result = node
+        .runtime
+        .block_on(async { server.client.block_create(create_args()).await });
+    assert_approval_required(result);
+
+    let mut args = create_args();
+    args.approval_password = Some("second factor".to_string());
+    let result = node
+        .runtime
+        .block_on(async { server.client.block_create(args).await.unwrap() });
+    let block: Block = result.block.into();
+    node.process(block.clone()).unwrap();
+    assert_eq!(
+        node.ledger
+            .any()
+            .block_account(&node.ledger.read_txn(), &block.hash()),
+        Some(*DEV_GENESIS_ACCOUNT)
+    );
+}
+
+#[test]
+fn account_move_requires_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (source, server) = setup(&node);
+    let target = WalletId::random();
+    node.wallets.create(target);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_move(target, source, vec![*DEV_GENESIS_ACCOUNT])
+            .await
+    });
+    assert_approval_required(result);
+    let source_accounts = node.wallets.get_accounts_of_wallet(&source).unwrap();
+    assert!(source_accounts.contains(&DEV_GENESIS_ACCOUNT));
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .account_move_with_approval(target, source, vec![*DEV_GENESIS_ACCOUNT], "second factor")
+            .await
+            .unwrap()
+    });
+    let target_accounts = node.wallets.get_accounts_of_wallet(&target).unwrap();
+    assert!(target_accounts.contains(&DEV_GENESIS_ACCOUNT));
+}
+
+#[test]
+fn wallet_destroy_requires_approval_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (wallet, server) = setup(&node);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_destroy(wallet).await });
+    assert_approval_required(result);
+    assert!(node.wallets.wallet_exists(&wallet));
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_destroy(
+                WalletWithApprovalArgs::new(wallet).with_approval_password("second factor"),
+            )
+            .await
+            .unwrap()
+    });
+    assert_eq!(result.destroyed, true.into());
+    assert!(!node.wallets.wallet_exists(&wallet));
+}
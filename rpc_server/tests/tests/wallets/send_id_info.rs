@@ -0,0 +1,83 @@
+use rsban_core::{Account, Amount, WalletId, DEV_GENESIS_KEY};
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::SendArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn send_id_info() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets
+        .insert_adhoc2(&wallet, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let send = node.runtime.block_on(async {
+        server
+            .client
+            .send(SendArgs {
+                wallet,
+                source: *DEV_GENESIS_ACCOUNT,
+                destination: Account::from(42),
+                amount: Amount::raw(1000),
+                id: Some("payout-1".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    });
+
+    let info = node
+        .runtime
+        .block_on(async { server.client.send_id_info("payout-1").await.unwrap() });
+
+    assert_eq!(info.id, "payout-1");
+    assert_eq!(info.wallet, wallet);
+    assert_eq!(info.block, send.block);
+    assert!(info.local_timestamp.is_some());
+
+    let send_ids = node
+        .runtime
+        .block_on(async { server.client.wallet_send_ids(wallet).await.unwrap() });
+
+    assert_eq!(send_ids.send_ids, vec![info]);
+}
+
+#[test]
+fn send_id_info_fails_when_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.send_id_info("unknown").await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Send id not found\"".to_string())
+    );
+}
+
+#[test]
+fn send_id_info_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.send_id_info("payout-1").await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
@@ -0,0 +1,202 @@
+use rsban_core::{Account, DerivationScheme, PrivateKey, PublicKey, RawKey, WalletId};
+use rsban_node::wallets::{WalletBackup, WalletsExt};
+use rsban_rpc_messages::WalletBackupArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_backup_and_restore() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = WalletId::random();
+    node.wallets.create(wallet);
+    node.wallets
+        .change_seed(wallet, &RawKey::from(1), 0)
+        .unwrap();
+    node.wallets.deterministic_insert2(&wallet, false).unwrap();
+    node.wallets
+        .insert_adhoc2(&wallet, &RawKey::from(2), false)
+        .unwrap();
+    node.wallets
+        .insert_watch(&wallet, &[PrivateKey::from(3).account()])
+        .unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let backup = node
+        .runtime
+        .block_on(async {
+            server
+                .client
+                .wallet_backup(WalletBackupArgs::new(wallet, "secret"))
+                .await
+                .unwrap()
+        })
+        .backup;
+
+    let restored = node
+        .runtime
+        .block_on(async {
+            server
+                .client
+                .wallet_restore(backup, "secret", "restored")
+                .await
+                .unwrap()
+        })
+        .wallet;
+
+    assert_ne!(restored, wallet);
+    let mut expected = node.wallets.get_accounts_of_wallet(&wallet).unwrap();
+    let mut accounts = node.wallets.get_accounts_of_wallet(&restored).unwrap();
+    expected.sort();
+    accounts.sort();
+    assert_eq!(accounts, expected);
+    assert_eq!(node.wallets.get_seed(restored).unwrap(), RawKey::from(1));
+
+    node.wallets.lock(&restored).unwrap();
+    assert!(node.wallets.enter_password(restored, "").is_err());
+    node.wallets.enter_password(restored, "restored").unwrap();
+}
+
+#[test]
+fn wallet_restore_fails_with_wrong_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = node.wallets.wallet_ids()[0];
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let backup = node
+        .runtime
+        .block_on(async {
+            server
+                .client
+                .wallet_backup(WalletBackupArgs::new(wallet, "secret"))
+                .await
+                .unwrap()
+        })
+        .backup;
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_restore(backup, "wrong", "restored")
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet restore failed: invalid password or corrupted wallet backup\"".to_string())
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+}
+
+#[test]
+fn wallet_restore_fails_for_bip44_backup_without_chain_code() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let backup = WalletBackup {
+        seed: RawKey::from(1),
+        derivation: DerivationScheme::Bip44,
+        chain_code: None,
+        deterministic_index: 1,
+        deterministic_accounts: vec![0],
+        representative: PublicKey::from(2),
+        adhoc_keys: Vec::new(),
+        watch_accounts: vec![Account::from(3)],
+    };
+    let backup = hex::encode_upper(backup.encrypt("secret"));
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_restore(backup, "secret", "restored")
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet restore failed: BIP44 wallet backup without chain code\"".to_string())
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+}
+
+#[test]
+fn wallet_restore_requires_wallet_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = node.wallets.wallet_ids()[0];
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let backup = node
+        .runtime
+        .block_on(async {
+            server
+                .client
+                .wallet_backup(WalletBackupArgs::new(wallet, "secret"))
+                .await
+                .unwrap()
+        })
+        .backup;
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_restore(backup, "secret", "").await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet restore failed: a password for the restored wallet is required\"".to_string())
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+}
+
+#[test]
+fn wallet_backup_fails_with_locked_wallet() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = node.wallets.wallet_ids()[0];
+    node.wallets.lock(&wallet).unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_backup(WalletBackupArgs::new(wallet, "secret"))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet is locked\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_backup_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = node.wallets.wallet_ids()[0];
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_backup(WalletBackupArgs::new(wallet, "secret"))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
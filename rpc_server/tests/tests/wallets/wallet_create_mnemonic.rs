@@ -0,0 +1,49 @@
+use rsban_core::{Mnemonic, Slip10Key};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_create_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_create_mnemonic(Some("secret".to_owned()), None)
+            .await
+            .unwrap()
+    });
+
+    assert!(node.wallets.wallet_ids().contains(&result.wallet));
+
+    let mnemonic = Mnemonic::parse(&result.mnemonic).unwrap();
+    assert_eq!(mnemonic.word_count(), 24);
+    let expected_key = Slip10Key::banano_root(&mnemonic.bip39_seed("secret"))
+        .derive(0)
+        .key;
+    assert_eq!(
+        node.wallets
+            .fetch(&result.wallet, &result.account.into())
+            .unwrap(),
+        expected_key
+    );
+}
+
+#[test]
+fn wallet_create_mnemonic_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_create_mnemonic(None, None).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
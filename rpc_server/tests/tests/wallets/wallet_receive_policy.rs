@@ -0,0 +1,103 @@
+use rsban_core::{Account, Amount, WalletId};
+use rsban_rpc_messages::WalletReceivePolicySetArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_receive_policy_default() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_receive_policy(wallet).await.unwrap() });
+
+    assert_eq!(result.receive_minimum, node.config.receive_minimum);
+    assert!(result.auto_receive.inner());
+    assert!(result.allowed_senders.is_empty());
+    assert!(result.denied_senders.is_empty());
+    assert_eq!(result.max_receive_per_hour, None);
+    assert_eq!(result.received_last_hour, Amount::zero());
+}
+
+#[test]
+fn wallet_receive_policy_set() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletReceivePolicySetArgs::builder(wallet)
+        .receive_minimum(Amount::raw(1))
+        .disable_auto_receive()
+        .allowed_senders(vec![Account::from(1)])
+        .denied_senders(vec![Account::from(2)])
+        .max_receive_per_hour(Amount::raw(1000))
+        .build();
+    node.runtime
+        .block_on(async { server.client.wallet_receive_policy_set(args).await.unwrap() });
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_receive_policy(wallet).await.unwrap() });
+
+    assert_eq!(result.receive_minimum, Amount::raw(1));
+    assert!(!result.auto_receive.inner());
+    assert_eq!(result.allowed_senders, vec![Account::from(1)]);
+    assert_eq!(result.denied_senders, vec![Account::from(2)]);
+    assert_eq!(result.max_receive_per_hour, Some(Amount::raw(1000)));
+
+    // Omitted fields are reset
+    node.runtime.block_on(async {
+        server
+            .client
+            .wallet_receive_policy_set(WalletReceivePolicySetArgs::new(wallet))
+            .await
+            .unwrap()
+    });
+    assert_eq!(
+        node.wallets.receive_policy(&wallet).unwrap(),
+        Default::default()
+    );
+}
+
+#[test]
+fn wallet_receive_policy_fails_with_wallet_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_receive_policy(WalletId::zero()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet not found\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_receive_policy_set_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_receive_policy_set(WalletReceivePolicySetArgs::new(wallet))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
@@ -0,0 +1,115 @@
+use rsban_core::{deterministic_key, Account, Mnemonic, PublicKey, RawKey, Slip10Key};
+use rsban_rpc_messages::{DerivationSchemeDto, WalletRestoreMnemonicArgs};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+const MNEMONIC: &str = "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur";
+
+#[test]
+fn wallet_restore_mnemonic_bip44() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletRestoreMnemonicArgs::new(MNEMONIC).with_passphrase("some password");
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_restore_mnemonic(args).await.unwrap() });
+
+    let bip39_seed = Mnemonic::parse(MNEMONIC)
+        .unwrap()
+        .bip39_seed("some password");
+    let expected_key = Slip10Key::banano_root(&bip39_seed).derive(0).key;
+    let expected_account: Account = PublicKey::try_from(&expected_key).unwrap().into();
+
+    assert_eq!(result.last_restored_account, Some(expected_account));
+    assert_eq!(result.restored_count, Some(1.into()));
+    assert_eq!(
+        node.wallets
+            .fetch(&result.wallet, &expected_account.into())
+            .unwrap(),
+        expected_key
+    );
+}
+
+#[test]
+fn wallet_restore_mnemonic_legacy() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let seed = RawKey::from(42);
+    let mnemonic = Mnemonic::from_seed(&seed).to_string();
+    let args =
+        WalletRestoreMnemonicArgs::new(mnemonic).with_derivation(DerivationSchemeDto::Legacy);
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_restore_mnemonic(args).await.unwrap() });
+
+    let expected_account: Account = PublicKey::try_from(&deterministic_key(&seed, 0))
+        .unwrap()
+        .into();
+    assert_eq!(result.last_restored_account, Some(expected_account));
+    assert_eq!(node.wallets.get_seed(result.wallet).unwrap(), seed);
+}
+
+#[test]
+fn wallet_restore_mnemonic_legacy_fails_with_passphrase() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let args = WalletRestoreMnemonicArgs::new(MNEMONIC)
+        .with_passphrase("some password")
+        .with_derivation(DerivationSchemeDto::Legacy);
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_restore_mnemonic(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some(
+            "node returned error: \"Legacy derivation does not support a passphrase\"".to_string()
+        )
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+}
+
+#[test]
+fn wallet_restore_mnemonic_fails_with_invalid_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletRestoreMnemonicArgs::new("edge defense waste");
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_restore_mnemonic(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid mnemonic\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_restore_mnemonic_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let args = WalletRestoreMnemonicArgs::new(MNEMONIC);
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_restore_mnemonic(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
@@ -0,0 +1,170 @@
+use rsban_core::{Account, Amount, WalletId, DEV_GENESIS_KEY};
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{SendArgs, WalletSpendingLimitSetArgs};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+fn send_args(wallet: WalletId, amount: Amount) -> SendArgs {
+    SendArgs {
+        wallet,
+        source: *DEV_GENESIS_ACCOUNT,
+        destination: Account::from(1),
+        amount,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn wallet_spending_limit_set() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletSpendingLimitSetArgs::builder(wallet)
+        .per_transaction(Amount::raw(100))
+        .daily(Amount::raw(150))
+        .build();
+    node.runtime
+        .block_on(async { server.client.wallet_spending_limit_set(args).await.unwrap() });
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .send(send_args(wallet, Amount::raw(100)))
+            .await
+            .unwrap()
+    });
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.send(send_args(wallet, Amount::raw(51))).await });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Amount exceeds the daily spending limit\"".to_string())
+    );
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_spending_limits(wallet).await.unwrap() });
+    assert_eq!(result.per_transaction, Some(Amount::raw(100)));
+    assert_eq!(result.daily, Some(Amount::raw(150)));
+    assert_eq!(result.spent_last_day, Amount::raw(100));
+    assert!(!result.approval_required.inner());
+    assert!(result.accounts.is_empty());
+}
+
+#[test]
+fn account_spending_limit() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletSpendingLimitSetArgs::builder(wallet)
+        .account(*DEV_GENESIS_ACCOUNT)
+        .per_transaction(Amount::raw(100))
+        .build();
+    node.runtime
+        .block_on(async { server.client.wallet_spending_limit_set(args).await.unwrap() });
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send(send_args(wallet, Amount::raw(101)))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Amount exceeds the per transaction limit\"".to_string())
+    );
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_spending_limits(wallet).await.unwrap() });
+    assert_eq!(result.accounts.len(), 1);
+    assert_eq!(result.accounts[0].account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(result.accounts[0].per_transaction, Some(Amount::raw(100)));
+    assert_eq!(result.accounts[0].spent_last_day, Amount::zero());
+}
+
+#[test]
+fn wallet_with_spending_limits_refuses_key_access() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletSpendingLimitSetArgs::builder(wallet)
+        .daily(Amount::raw(150))
+        .build();
+    node.runtime
+        .block_on(async { server.client.wallet_spending_limit_set(args).await.unwrap() });
+
+    let expected = Some(
+        "node returned error: \"Not allowed while the wallet has spending limits\"".to_string(),
+    );
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_export(wallet).await });
+    assert_eq!(result.err().map(|e| e.to_string()), expected);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_destroy(wallet).await });
+    assert_eq!(result.err().map(|e| e.to_string()), expected);
+    assert!(node.wallets.wallet_exists(&wallet));
+}
+
+#[test]
+fn wallet_spending_limit_set_fails_with_account_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = WalletSpendingLimitSetArgs::builder(wallet)
+        .account(Account::from(1))
+        .daily(Amount::raw(1))
+        .build();
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_spending_limit_set(args).await });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_spending_limit_set_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet = node.wallets.wallet_ids()[0];
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_spending_limit_set(WalletSpendingLimitSetArgs::new(wallet))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
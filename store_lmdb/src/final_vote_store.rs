@@ -114,6 +114,10 @@ impl LmdbFinalVoteStore {
         }
     }
 
+    pub fn del_qualified_root(&self, txn: &mut LmdbWriteTransaction, root: &QualifiedRoot) {
+        let _ = txn.delete(self.database, &root.to_bytes(), None);
+    }
+
     pub fn count(&self, txn: &dyn Transaction) -> u64 {
         txn.count(self.database)
     }
@@ -191,6 +195,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn delete_qualified_root() {
+        let root = QualifiedRoot::new_test_instance();
+        let fixture = Fixture::with_stored_entries(vec![(root.clone(), BlockHash::from(333))]);
+        let mut txn = fixture.env.tx_begin_write();
+        let delete_tracker = txn.track_deletions();
+
+        fixture.store.del_qualified_root(&mut txn, &root);
+
+        assert_eq!(
+            delete_tracker.output(),
+            vec![DeleteEvent {
+                key: root.to_bytes().to_vec(),
+                database: TEST_DATABASE,
+            }]
+        )
+    }
+
     #[test]
     fn del_unknown_root_should_not_remove() {
         let fixture = Fixture::with_stored_entries(vec![(
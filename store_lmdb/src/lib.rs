@@ -12,10 +12,14 @@ mod final_vote_store;
 mod iterator;
 mod lmdb_config;
 mod lmdb_env;
+mod local_vote_store;
 mod online_weight_store;
+mod peer_ban_store;
+mod peer_reputation_store;
 mod peer_store;
 mod pending_store;
 mod pruned_store;
+mod rep_stats_store;
 mod rep_weight_store;
 mod store;
 mod version_store;
@@ -29,10 +33,14 @@ pub use final_vote_store::LmdbFinalVoteStore;
 pub use iterator::{BinaryDbIterator, LmdbIterator, LmdbIteratorImpl};
 pub use lmdb_config::{LmdbConfig, SyncStrategy};
 pub use lmdb_env::*;
+pub use local_vote_store::*;
 pub use online_weight_store::LmdbOnlineWeightStore;
+pub use peer_ban_store::*;
+pub use peer_reputation_store::*;
 pub use peer_store::*;
 pub use pending_store::{ConfiguredPendingDatabaseBuilder, LmdbPendingStore};
 pub use pruned_store::{ConfiguredPrunedDatabaseBuilder, LmdbPrunedStore};
+pub use rep_stats_store::*;
 pub use rep_weight_store::*;
 use rsban_nullable_lmdb::{
     InactiveTransaction, LmdbDatabase, LmdbEnvironment, RoCursor, RoTransaction, RwTransaction,
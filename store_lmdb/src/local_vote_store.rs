@@ -0,0 +1,208 @@
+use crate::{iterator::LmdbIterator, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsban_core::{BlockHash, Root};
+use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Non-final votes that were generated by the local representatives.
+/// Used to prevent a restarted node from voting for a different block on the same root.
+/// The entries are ordered by time, so that expired votes can be deleted without a full scan.
+/// Key: vote time in milliseconds since epoch (8 bytes, big endian) + root (32 bytes)
+/// Value: block hash (32 bytes)
+pub struct LmdbLocalVoteStore {
+    database: LmdbDatabase,
+    put_listener: OutputListenerMt<(Root, BlockHash)>,
+}
+
+impl LmdbLocalVoteStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("local_votes"), DatabaseFlags::empty())?;
+
+        Ok(Self {
+            database,
+            put_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn track_puts(&self) -> Arc<OutputTrackerMt<(Root, BlockHash)>> {
+        self.put_listener.track()
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        root: &Root,
+        hash: &BlockHash,
+        time: SystemTime,
+    ) {
+        self.put_listener.emit((*root, *hash));
+        txn.put(
+            self.database,
+            &key_bytes(time, root),
+            hash.as_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    /// Iterates the votes from the oldest to the newest
+    pub fn iter<'a>(
+        &self,
+        txn: &'a dyn Transaction,
+    ) -> impl Iterator<Item = (Root, (BlockHash, SystemTime))> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read local vote database");
+        LmdbIterator::new(cursor, |k, v| {
+            let (time, root) = parse_key(k);
+            (root, (BlockHash::from_slice(v).unwrap_or_default(), time))
+        })
+    }
+
+    /// Deletes all votes that were generated before `cutoff`. Only the expired
+    /// entries at the start of the table are visited
+    pub fn delete_before(&self, txn: &mut LmdbWriteTransaction, cutoff: SystemTime) -> usize {
+        let expired: Vec<_> = self
+            .iter(txn)
+            .take_while(|(_, (_, time))| *time < cutoff)
+            .map(|(root, (_, time))| key_bytes(time, &root))
+            .collect();
+        for key in &expired {
+            let _ = txn.delete(self.database, key, None);
+        }
+        expired.len()
+    }
+}
+
+fn key_bytes(time: SystemTime, root: &Root) -> [u8; 40] {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let mut key = [0; 40];
+    key[..8].copy_from_slice(&millis.to_be_bytes());
+    key[8..].copy_from_slice(root.as_bytes());
+    key
+}
+
+fn parse_key(bytes: &[u8]) -> (SystemTime, Root) {
+    let millis = u64::from_be_bytes(bytes[..8].try_into().unwrap());
+    let root = Root::from_slice(&bytes[8..40]).unwrap_or_default();
+    (UNIX_EPOCH + Duration::from_millis(millis), root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeleteEvent, PutEvent};
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let time = UNIX_EPOCH + Duration::from_secs(1);
+
+        fixture
+            .store
+            .put(&mut txn, &Root::from(1), &BlockHash::from(2), time);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: LmdbDatabase::new_null(42),
+                key: key_bytes(time, &Root::from(1)).to_vec(),
+                value: BlockHash::from(2).as_bytes().to_vec(),
+                flags: WriteFlags::empty()
+            }]
+        )
+    }
+
+    #[test]
+    fn iter() {
+        let time = UNIX_EPOCH + Duration::from_secs(1);
+        let env = LmdbEnv::new_null_with()
+            .database("local_votes", LmdbDatabase::new_null(42))
+            .entry(
+                &key_bytes(time, &Root::from(1)),
+                BlockHash::from(2).as_bytes(),
+            )
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.iter(&txn).collect::<Vec<_>>(),
+            vec![(Root::from(1), (BlockHash::from(2), time))]
+        );
+    }
+
+    #[test]
+    fn delete_before() {
+        let old = UNIX_EPOCH + Duration::from_secs(1);
+        let new = UNIX_EPOCH + Duration::from_secs(100);
+        let env = LmdbEnv::new_null_with()
+            .database("local_votes", LmdbDatabase::new_null(42))
+            .entry(
+                &key_bytes(old, &Root::from(1)),
+                BlockHash::from(2).as_bytes(),
+            )
+            .entry(
+                &key_bytes(new, &Root::from(3)),
+                BlockHash::from(4).as_bytes(),
+            )
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let mut txn = fixture.env.tx_begin_write();
+        let delete_tracker = txn.track_deletions();
+
+        let deleted = fixture.store.delete_before(&mut txn, new);
+
+        assert_eq!(deleted, 1);
+        assert_eq!(
+            delete_tracker.output(),
+            vec![DeleteEvent {
+                database: LmdbDatabase::new_null(42),
+                key: key_bytes(old, &Root::from(1)).to_vec(),
+            }]
+        );
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbLocalVoteStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_env(LmdbEnv::new_null())
+        }
+
+        fn with_env(env: LmdbEnv) -> Self {
+            let env = Arc::new(env);
+            Self {
+                env: env.clone(),
+                store: LmdbLocalVoteStore::new(env).unwrap(),
+            }
+        }
+    }
+}
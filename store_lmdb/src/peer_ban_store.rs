@@ -0,0 +1,212 @@
+use crate::{iterator::LmdbIterator, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsban_core::utils::{BufferWriter, Serialize};
+use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::{
+    net::Ipv6Addr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Peer bans which were added by the node operator.
+/// Key: network address (16 bytes) + prefix length (1 byte)
+/// Value: end of the ban in milliseconds since epoch (8 bytes, big endian). `u64::MAX` means forever
+pub struct LmdbPeerBanStore {
+    database: LmdbDatabase,
+    delete_listener: OutputListenerMt<(Ipv6Addr, u8)>,
+}
+
+impl LmdbPeerBanStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("peer_bans"), DatabaseFlags::empty())?;
+
+        Ok(Self {
+            database,
+            delete_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    /// `until` = `None` bans the subnet permanently
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        network: Ipv6Addr,
+        prefix_len: u8,
+        until: Option<SystemTime>,
+    ) {
+        txn.put(
+            self.database,
+            &subnet_key(network, prefix_len),
+            &until_bytes(until),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn track_deletions(&self) -> Arc<OutputTrackerMt<(Ipv6Addr, u8)>> {
+        self.delete_listener.track()
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, network: Ipv6Addr, prefix_len: u8) {
+        self.delete_listener.emit((network, prefix_len));
+        let _ = txn.delete(self.database, &subnet_key(network, prefix_len), None);
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    pub fn iter<'a>(
+        &self,
+        txn: &'a dyn Transaction,
+    ) -> impl Iterator<Item = (Ipv6Addr, u8, Option<SystemTime>)> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read peer ban database");
+        PeerBanIterator(LmdbIterator::new(cursor, |k, v| {
+            let network: [u8; 16] = k[..16].try_into().unwrap();
+            let millis = u64::from_be_bytes(v.try_into().unwrap());
+            let until = if millis == u64::MAX {
+                None
+            } else {
+                Some(UNIX_EPOCH + Duration::from_millis(millis))
+            };
+            (
+                PeerBanKey {
+                    network: Ipv6Addr::from(network),
+                    prefix_len: k[16],
+                },
+                until,
+            )
+        }))
+    }
+}
+
+struct PeerBanKey {
+    network: Ipv6Addr,
+    prefix_len: u8,
+}
+
+impl Serialize for PeerBanKey {
+    fn serialize(&self, stream: &mut dyn BufferWriter) {
+        stream.write_bytes_safe(&subnet_key(self.network, self.prefix_len))
+    }
+}
+
+pub struct PeerBanIterator<'txn>(LmdbIterator<'txn, PeerBanKey, Option<SystemTime>>);
+
+impl<'txn> Iterator for PeerBanIterator<'txn> {
+    type Item = (Ipv6Addr, u8, Option<SystemTime>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|(key, until)| (key.network, key.prefix_len, until))
+    }
+}
+
+fn subnet_key(network: Ipv6Addr, prefix_len: u8) -> [u8; 17] {
+    let mut key = [0; 17];
+    key[..16].copy_from_slice(&network.octets());
+    key[16] = prefix_len;
+    key
+}
+
+fn until_bytes(until: Option<SystemTime>) -> [u8; 8] {
+    let millis = match until {
+        Some(until) => until
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+        None => u64::MAX,
+    };
+    millis.to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PutEvent;
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+
+        fixture.store.put(
+            &mut txn,
+            Ipv6Addr::new(1, 2, 3, 4, 5, 6, 7, 0),
+            112,
+            Some(UNIX_EPOCH + Duration::from_secs(1)),
+        );
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: LmdbDatabase::new_null(42),
+                key: vec![0, 1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0, 7, 0, 0, 112],
+                value: 1000u64.to_be_bytes().to_vec(),
+                flags: WriteFlags::empty()
+            }]
+        )
+    }
+
+    #[test]
+    fn iter() {
+        let env = LmdbEnv::new_null_with()
+            .database("peer_bans", LmdbDatabase::new_null(42))
+            .entry(
+                &subnet_key(Ipv6Addr::LOCALHOST, 128),
+                &until_bytes(Some(UNIX_EPOCH + Duration::from_secs(1))),
+            )
+            .entry(&subnet_key(Ipv6Addr::UNSPECIFIED, 0), &until_bytes(None))
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        let bans: Vec<_> = fixture.store.iter(&txn).collect();
+
+        assert_eq!(
+            bans,
+            vec![
+                (Ipv6Addr::UNSPECIFIED, 0, None),
+                (
+                    Ipv6Addr::LOCALHOST,
+                    128,
+                    Some(UNIX_EPOCH + Duration::from_secs(1))
+                ),
+            ]
+        );
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbPeerBanStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_env(LmdbEnv::new_null())
+        }
+
+        fn with_env(env: LmdbEnv) -> Self {
+            let env = Arc::new(env);
+            Self {
+                env: env.clone(),
+                store: LmdbPeerBanStore::new(env).unwrap(),
+            }
+        }
+    }
+}
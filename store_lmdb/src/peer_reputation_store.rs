@@ -0,0 +1,199 @@
+use crate::{iterator::LmdbIterator, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsban_core::{
+    utils::{BufferReader, BufferWriter, Deserialize, Serialize},
+    PeerReputation,
+};
+use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::{net::Ipv6Addr, sync::Arc};
+
+/// Reputation of peers, so that it is known after a restart which peers are reliable.
+/// Key: IP address of the peer (16 bytes)
+/// Value: `PeerReputation`
+pub struct LmdbPeerReputationStore {
+    database: LmdbDatabase,
+    put_listener: OutputListenerMt<(Ipv6Addr, PeerReputation)>,
+}
+
+impl LmdbPeerReputationStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("peer_reputation"), DatabaseFlags::empty())?;
+
+        Ok(Self {
+            database,
+            put_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn track_puts(&self) -> Arc<OutputTrackerMt<(Ipv6Addr, PeerReputation)>> {
+        self.put_listener.track()
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, ip: Ipv6Addr, reputation: &PeerReputation) {
+        self.put_listener.emit((ip, reputation.clone()));
+        txn.put(
+            self.database,
+            &ip.octets(),
+            &reputation.to_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, ip: Ipv6Addr) -> Option<PeerReputation> {
+        match txn.get(self.database, &ip.octets()) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok(bytes) => {
+                let mut stream = BufferReader::new(bytes);
+                PeerReputation::deserialize(&mut stream).ok()
+            }
+            Err(e) => {
+                panic!("Could not load peer reputation: {:?}", e);
+            }
+        }
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, ip: Ipv6Addr) {
+        let _ = txn.delete(self.database, &ip.octets(), None);
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    pub fn iter<'a>(
+        &self,
+        txn: &'a dyn Transaction,
+    ) -> impl Iterator<Item = (Ipv6Addr, PeerReputation)> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read peer reputation database");
+        PeerReputationIterator(LmdbIterator::new(cursor, |k, v| {
+            let ip: [u8; 16] = k.try_into().unwrap();
+            let mut stream = BufferReader::new(v);
+            (
+                IpKey(Ipv6Addr::from(ip)),
+                PeerReputation::deserialize(&mut stream).unwrap_or_default(),
+            )
+        }))
+    }
+}
+
+struct IpKey(Ipv6Addr);
+
+impl Serialize for IpKey {
+    fn serialize(&self, stream: &mut dyn BufferWriter) {
+        stream.write_bytes_safe(&self.0.octets())
+    }
+}
+
+pub struct PeerReputationIterator<'txn>(LmdbIterator<'txn, IpKey, PeerReputation>);
+
+impl<'txn> Iterator for PeerReputationIterator<'txn> {
+    type Item = (Ipv6Addr, PeerReputation);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, reputation)| (key.0, reputation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PutEvent;
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let reputation = PeerReputation::new_test_instance();
+
+        fixture
+            .store
+            .put(&mut txn, Ipv6Addr::LOCALHOST, &reputation);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: LmdbDatabase::new_null(42),
+                key: Ipv6Addr::LOCALHOST.octets().to_vec(),
+                value: reputation.to_bytes().to_vec(),
+                flags: WriteFlags::empty()
+            }]
+        )
+    }
+
+    #[test]
+    fn get() {
+        let reputation = PeerReputation::new_test_instance();
+        let env = LmdbEnv::new_null_with()
+            .database("peer_reputation", LmdbDatabase::new_null(42))
+            .entry(&Ipv6Addr::LOCALHOST.octets(), &reputation.to_bytes())
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.get(&txn, Ipv6Addr::LOCALHOST),
+            Some(reputation)
+        );
+        assert_eq!(fixture.store.get(&txn, Ipv6Addr::UNSPECIFIED), None);
+    }
+
+    #[test]
+    fn iter() {
+        let reputation = PeerReputation::new_test_instance();
+        let env = LmdbEnv::new_null_with()
+            .database("peer_reputation", LmdbDatabase::new_null(42))
+            .entry(&Ipv6Addr::LOCALHOST.octets(), &reputation.to_bytes())
+            .entry(
+                &Ipv6Addr::UNSPECIFIED.octets(),
+                &PeerReputation::default().to_bytes(),
+            )
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        let reputations: Vec<_> = fixture.store.iter(&txn).collect();
+
+        assert_eq!(
+            reputations,
+            vec![
+                (Ipv6Addr::UNSPECIFIED, PeerReputation::default()),
+                (Ipv6Addr::LOCALHOST, reputation),
+            ]
+        );
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbPeerReputationStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_env(LmdbEnv::new_null())
+        }
+
+        fn with_env(env: LmdbEnv) -> Self {
+            let env = Arc::new(env);
+            Self {
+                env: env.clone(),
+                store: LmdbPeerReputationStore::new(env).unwrap(),
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    iterator::{LmdbIterator, LmdbRangeIterator},
+    LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction,
+};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsban_core::{
+    utils::{BufferReader, Deserialize},
+    PublicKey, RepStatsBucket, RepStatsKey,
+};
+use rsban_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::sync::Arc;
+
+/// Uptime and vote participation history of representatives
+/// Key: `RepStatsKey` (representative + bucket start in seconds since epoch)
+/// Value: `RepStatsBucket`
+pub struct LmdbRepStatsStore {
+    database: LmdbDatabase,
+    put_listener: OutputListenerMt<(RepStatsKey, RepStatsBucket)>,
+}
+
+impl LmdbRepStatsStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("rep_stats"), DatabaseFlags::empty())?;
+
+        Ok(Self {
+            database,
+            put_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn track_puts(&self) -> Arc<OutputTrackerMt<(RepStatsKey, RepStatsBucket)>> {
+        self.put_listener.track()
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, key: &RepStatsKey, bucket: &RepStatsBucket) {
+        self.put_listener.emit((*key, bucket.clone()));
+        txn.put(
+            self.database,
+            &key.to_bytes(),
+            &bucket.to_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, key: &RepStatsKey) -> Option<RepStatsBucket> {
+        match txn.get(self.database, &key.to_bytes()) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok(bytes) => {
+                let mut stream = BufferReader::new(bytes);
+                RepStatsBucket::deserialize(&mut stream).ok()
+            }
+            Err(e) => {
+                panic!("Could not load rep stats: {:?}", e);
+            }
+        }
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, key: &RepStatsKey) {
+        let _ = txn.delete(self.database, &key.to_bytes(), None);
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    pub fn iter<'a>(
+        &self,
+        txn: &'a dyn Transaction,
+    ) -> impl Iterator<Item = (RepStatsKey, RepStatsBucket)> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read rep stats database");
+        LmdbIterator::new(cursor, |k, v| {
+            let mut stream = BufferReader::new(k);
+            let key = RepStatsKey::deserialize(&mut stream).unwrap();
+            let mut stream = BufferReader::new(v);
+            (
+                key,
+                RepStatsBucket::deserialize(&mut stream).unwrap_or_default(),
+            )
+        })
+    }
+
+    /// All buckets of the given representative, oldest first
+    pub fn iter_rep<'a>(
+        &self,
+        txn: &'a dyn Transaction,
+        representative: PublicKey,
+    ) -> impl Iterator<Item = (RepStatsKey, RepStatsBucket)> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read rep stats database");
+        LmdbRangeIterator::new(
+            cursor,
+            RepStatsKey::new(representative, 0)..=RepStatsKey::new(representative, u64::MAX),
+        )
+    }
+
+    /// Deletes all buckets that started before `cutoff` (seconds since epoch)
+    pub fn delete_before(&self, txn: &mut LmdbWriteTransaction, cutoff: u64) -> usize {
+        let expired: Vec<_> = self
+            .iter(txn)
+            .filter(|(key, _)| key.bucket_start < cutoff)
+            .map(|(key, _)| key)
+            .collect();
+        for key in &expired {
+            self.del(txn, key);
+        }
+        expired.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeleteEvent, PutEvent};
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let key = RepStatsKey::new(PublicKey::from(1), 3600);
+        let bucket = RepStatsBucket::new_test_instance();
+
+        fixture.store.put(&mut txn, &key, &bucket);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: LmdbDatabase::new_null(42),
+                key: key.to_bytes().to_vec(),
+                value: bucket.to_bytes().to_vec(),
+                flags: WriteFlags::empty()
+            }]
+        )
+    }
+
+    #[test]
+    fn get() {
+        let key = RepStatsKey::new(PublicKey::from(1), 3600);
+        let bucket = RepStatsBucket::new_test_instance();
+        let env = LmdbEnv::new_null_with()
+            .database("rep_stats", LmdbDatabase::new_null(42))
+            .entry(&key.to_bytes(), &bucket.to_bytes())
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(fixture.store.get(&txn, &key), Some(bucket));
+        assert_eq!(
+            fixture
+                .store
+                .get(&txn, &RepStatsKey::new(PublicKey::from(1), 7200)),
+            None
+        );
+    }
+
+    #[test]
+    fn iter_rep() {
+        let bucket = RepStatsBucket::new_test_instance();
+        let rep1_a = RepStatsKey::new(PublicKey::from(1), 3600);
+        let rep1_b = RepStatsKey::new(PublicKey::from(1), 7200);
+        let rep2 = RepStatsKey::new(PublicKey::from(2), 3600);
+        let env = LmdbEnv::new_null_with()
+            .database("rep_stats", LmdbDatabase::new_null(42))
+            .entry(&rep1_a.to_bytes(), &bucket.to_bytes())
+            .entry(&rep1_b.to_bytes(), &RepStatsBucket::default().to_bytes())
+            .entry(&rep2.to_bytes(), &bucket.to_bytes())
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        let buckets: Vec<_> = fixture.store.iter_rep(&txn, PublicKey::from(1)).collect();
+
+        assert_eq!(
+            buckets,
+            vec![(rep1_a, bucket), (rep1_b, RepStatsBucket::default())]
+        );
+    }
+
+    #[test]
+    fn delete_before() {
+        let bucket = RepStatsBucket::new_test_instance();
+        let old = RepStatsKey::new(PublicKey::from(1), 3600);
+        let new = RepStatsKey::new(PublicKey::from(1), 7200);
+        let env = LmdbEnv::new_null_with()
+            .database("rep_stats", LmdbDatabase::new_null(42))
+            .entry(&old.to_bytes(), &bucket.to_bytes())
+            .entry(&new.to_bytes(), &bucket.to_bytes())
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let mut txn = fixture.env.tx_begin_write();
+        let delete_tracker = txn.track_deletions();
+
+        let deleted = fixture.store.delete_before(&mut txn, 7200);
+
+        assert_eq!(deleted, 1);
+        assert_eq!(
+            delete_tracker.output(),
+            vec![DeleteEvent {
+                database: LmdbDatabase::new_null(42),
+                key: old.to_bytes().to_vec(),
+            }]
+        );
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbRepStatsStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_env(LmdbEnv::new_null())
+        }
+
+        fn with_env(env: LmdbEnv) -> Self {
+            let env = Arc::new(env);
+            Self {
+                env: env.clone(),
+                store: LmdbRepStatsStore::new(env).unwrap(),
+            }
+        }
+    }
+}
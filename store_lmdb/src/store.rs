@@ -1,9 +1,9 @@
 use crate::{
     EnvOptions, LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbDatabase,
-    LmdbEnv, LmdbFinalVoteStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore,
-    LmdbPrunedStore, LmdbReadTransaction, LmdbRepWeightStore, LmdbVersionStore,
-    LmdbWriteTransaction, NullTransactionTracker, TransactionTracker, STORE_VERSION_CURRENT,
-    STORE_VERSION_MINIMUM,
+    LmdbEnv, LmdbFinalVoteStore, LmdbLocalVoteStore, LmdbOnlineWeightStore, LmdbPeerBanStore,
+    LmdbPeerReputationStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction,
+    LmdbRepStatsStore, LmdbRepWeightStore, LmdbVersionStore, LmdbWriteTransaction,
+    NullTransactionTracker, TransactionTracker, STORE_VERSION_CURRENT, STORE_VERSION_MINIMUM,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
 use lmdb_sys::{MDB_CP_COMPACT, MDB_SUCCESS};
@@ -60,8 +60,12 @@ pub struct LmdbStore {
     pub pruned: Arc<LmdbPrunedStore>,
     pub rep_weight: Arc<LmdbRepWeightStore>,
     pub peer: Arc<LmdbPeerStore>,
+    pub peer_ban: Arc<LmdbPeerBanStore>,
+    pub peer_reputation: Arc<LmdbPeerReputationStore>,
+    pub rep_stats: Arc<LmdbRepStatsStore>,
     pub confirmation_height: Arc<LmdbConfirmationHeightStore>,
     pub final_vote: Arc<LmdbFinalVoteStore>,
+    pub local_vote: Arc<LmdbLocalVoteStore>,
     pub version: Arc<LmdbVersionStore>,
 }
 
@@ -142,8 +146,12 @@ impl LmdbStore {
             pruned: Arc::new(LmdbPrunedStore::new(env.clone())?),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone())?),
             peer: Arc::new(LmdbPeerStore::new(env.clone())?),
+            peer_ban: Arc::new(LmdbPeerBanStore::new(env.clone())?),
+            peer_reputation: Arc::new(LmdbPeerReputationStore::new(env.clone())?),
+            rep_stats: Arc::new(LmdbRepStatsStore::new(env.clone())?),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone())?),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone())?),
+            local_vote: Arc::new(LmdbLocalVoteStore::new(env.clone())?),
             version: Arc::new(LmdbVersionStore::new(env.clone())?),
             env,
         })
@@ -3,13 +3,14 @@ use crate::{
 };
 use anyhow::bail;
 use lmdb::{DatabaseFlags, WriteFlags};
+use num_traits::FromPrimitive;
 use rsban_core::{
     deterministic_key,
     utils::{
         BufferReader, BufferWriter, Deserialize, FixedSizeSerialize, MutStreamAdapter, Serialize,
         Stream, StreamExt,
     },
-    Account, KeyDerivationFunction, PublicKey, RawKey,
+    Account, DerivationScheme, KeyDerivationFunction, PublicKey, RawKey, Slip10Key,
 };
 use std::io::Write;
 use std::{
@@ -91,7 +92,7 @@ pub struct LmdbWalletStore {
 }
 
 impl LmdbWalletStore {
-    pub const VERSION_CURRENT: u32 = 4;
+    pub const VERSION_CURRENT: u32 = 5;
     pub fn new(
         fanout: usize,
         kdf: KeyDerivationFunction,
@@ -142,6 +143,7 @@ impl LmdbWalletStore {
                 &WalletValue::new(RawKey::zero(), 0),
             );
         }
+        store.upgrade(txn)?;
         {
             let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
             let mut guard = store.fans.lock().unwrap();
@@ -190,6 +192,7 @@ impl LmdbWalletStore {
         store.ensure_key_exists(txn, &Self::salt_special())?;
         store.ensure_key_exists(txn, &Self::check_special())?;
         store.ensure_key_exists(txn, &Self::representative_special())?;
+        store.upgrade(txn)?;
         let mut guard = store.fans.lock().unwrap();
         guard.password.value_set(RawKey::zero());
         let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
@@ -232,7 +235,7 @@ impl LmdbWalletStore {
         PublicKey::from(4)
     }
 
-    /// Wallet seed for deterministic key generation
+    /// Wallet seed for deterministic key generation with the legacy scheme
     pub fn seed_special() -> PublicKey {
         PublicKey::from(5)
     }
@@ -242,10 +245,59 @@ impl LmdbWalletStore {
         PublicKey::from(6)
     }
 
-    pub fn special_count() -> PublicKey {
+    /// Scheme used to derive deterministic keys from the seed
+    pub fn derivation_special() -> PublicKey {
         PublicKey::from(7)
     }
 
+    /// SLIP-10 chain code of the seed, only used by BIP44 wallets
+    pub fn chain_code_special() -> PublicKey {
+        PublicKey::from(8)
+    }
+
+    /// Random value used to derive the initialization vectors of the BIP44 root key
+    /// and its chain code
+    pub fn chain_code_salt_special() -> PublicKey {
+        PublicKey::from(9)
+    }
+
+    /// SLIP-10 root key at m/44'/198', only used by BIP44 wallets
+    pub fn bip44_root_special() -> PublicKey {
+        PublicKey::from(10)
+    }
+
+    pub fn special_count() -> PublicKey {
+        PublicKey::from(11)
+    }
+
+    /// Upgrades a wallet that was created with an older store version
+    fn upgrade(&self, txn: &mut LmdbWriteTransaction) -> anyhow::Result<()> {
+        let version = self.version(txn);
+        if version < 5 {
+            self.upgrade_v4_v5(txn)?;
+        }
+        Ok(())
+    }
+
+    /// Version 5 reserves additional special keys for BIP44 wallets. A version 4 wallet
+    /// may hold watch-only accounts at these keys. Such a wallet isn't upgraded, because
+    /// its accounts would be mistaken for wallet settings
+    fn upgrade_v4_v5(&self, txn: &mut LmdbWriteTransaction) -> anyhow::Result<()> {
+        for i in
+            Self::derivation_special().number().low_u64()..Self::special_count().number().low_u64()
+        {
+            let key = PublicKey::from(i);
+            if txn.get(self.db_handle(), key.as_bytes()).is_ok() {
+                bail!(
+                    "wallet contains account {} whose key is reserved by wallet version 5. Remove it with a previous node version",
+                    key.as_account().encode_account()
+                );
+            }
+        }
+        self.version_put(txn, 5);
+        Ok(())
+    }
+
     pub fn initialize(&self, txn: &mut LmdbWriteTransaction, path: &Path) -> anyhow::Result<()> {
         let path_str = path
             .as_os_str()
@@ -320,13 +372,83 @@ impl LmdbWalletStore {
         let iv = self.salt(txn).initialization_vector_high();
         let ciphertext = prv.encrypt(&password_l, &iv);
         self.entry_put_raw(txn, &Self::seed_special(), &WalletValue::new(ciphertext, 0));
+        self.derivation_scheme_set(txn, DerivationScheme::Legacy);
         self.deterministic_clear(txn);
     }
 
+    /// Switches the wallet to BIP44 derivation. `root` is the key at m/44'/198'. The legacy
+    /// seed isn't used anymore, until the wallet is switched back with `set_seed`
+    pub fn set_bip44_seed(&self, txn: &mut LmdbWriteTransaction, root: &Slip10Key) {
+        let password_l = self.wallet_key(txn);
+        let salt = RawKey::random();
+        self.entry_put_raw(
+            txn,
+            &Self::chain_code_salt_special(),
+            &WalletValue::new(salt, 0),
+        );
+        let key_ciphertext = root
+            .key
+            .encrypt(&password_l, &salt.initialization_vector_high());
+        self.entry_put_raw(
+            txn,
+            &Self::bip44_root_special(),
+            &WalletValue::new(key_ciphertext, 0),
+        );
+        let ciphertext = root
+            .chain_code
+            .encrypt(&password_l, &salt.initialization_vector_low());
+        self.entry_put_raw(
+            txn,
+            &Self::chain_code_special(),
+            &WalletValue::new(ciphertext, 0),
+        );
+        self.derivation_scheme_set(txn, DerivationScheme::Bip44);
+        self.deterministic_clear(txn);
+    }
+
+    /// The SLIP-10 root of a BIP44 wallet
+    pub fn bip44_root(&self, txn: &dyn Transaction) -> Slip10Key {
+        let value = self.entry_get_raw(txn, &Self::bip44_root_special());
+        let password = self.wallet_key(txn);
+        let salt = self
+            .entry_get_raw(txn, &Self::chain_code_salt_special())
+            .key;
+        Slip10Key {
+            key: value
+                .key
+                .decrypt(&password, &salt.initialization_vector_high()),
+            chain_code: self.chain_code(txn),
+        }
+    }
+
+    pub fn chain_code(&self, txn: &dyn Transaction) -> RawKey {
+        let value = self.entry_get_raw(txn, &Self::chain_code_special());
+        let password = self.wallet_key(txn);
+        let salt = self
+            .entry_get_raw(txn, &Self::chain_code_salt_special())
+            .key;
+        value
+            .key
+            .decrypt(&password, &salt.initialization_vector_low())
+    }
+
+    pub fn derivation_scheme(&self, txn: &dyn Transaction) -> DerivationScheme {
+        // Wallets created before BIP44 support don't have this entry and use the legacy scheme
+        let value = self.entry_get_raw(txn, &Self::derivation_special());
+        DerivationScheme::from_u32(value.key.number().low_u32()).unwrap_or_default()
+    }
+
+    fn derivation_scheme_set(&self, txn: &mut LmdbWriteTransaction, scheme: DerivationScheme) {
+        let value = WalletValue::new(RawKey::from(scheme as u64), 0);
+        self.entry_put_raw(txn, &Self::derivation_special(), &value);
+    }
+
     pub fn deterministic_key(&self, txn: &dyn Transaction, index: u32) -> RawKey {
         debug_assert!(self.valid_password(txn));
-        let seed = self.seed(txn);
-        deterministic_key(&seed, index)
+        match self.derivation_scheme(txn) {
+            DerivationScheme::Legacy => deterministic_key(&self.seed(txn), index),
+            DerivationScheme::Bip44 => self.bip44_root(txn).derive(index).key,
+        }
     }
 
     pub fn deterministic_index_get(&self, txn: &dyn Transaction) -> u32 {
@@ -511,7 +633,7 @@ impl LmdbWalletStore {
             self.valid_password_locked(&guard, txn)
         };
 
-        if is_valid && self.version(txn) != 4 {
+        if is_valid && self.version(txn) != Self::VERSION_CURRENT {
             panic!("invalid wallet store version!");
         }
 
@@ -27,6 +27,9 @@ use std::{
 };
 use tracing_subscriber::EnvFilter;
 
+mod simulator;
+pub use simulator::*;
+
 pub struct System {
     runtime: Arc<AsyncRuntime>,
     network_params: NetworkParams,
@@ -0,0 +1,654 @@
+//! In-process network simulator.
+//!
+//! The simulated nodes are real `Node`s, but they aren't connected via TCP.
+//! Every link between two nodes is a pair of nulled channels. Messages that
+//! a node publishes on such a channel are handed to a [`VirtualNetwork`]
+//! which delivers them to the inbound queue of the other node, after a
+//! latency measured on a virtual clock.
+//!
+//! Latency jitter and packet loss are derived from the seed, the link and the
+//! sequence number of the message on that link, so a [`VirtualNetwork`] that is
+//! fed the same messages delays and drops them the same way in every run with
+//! the same seed. The messages that the nodes published during a step are sent
+//! at the end of it, sorted by sender, receiver and content.
+//!
+//! All nodes share a nulled `SteadyClock` which follows the virtual clock, so
+//! timeouts and the node's timers are measured in virtual time. The nodes don't
+//! open any ports. After each step the simulator waits until the nodes have
+//! processed everything that was delivered. Some components of the node still
+//! measure real time, so the virtual clock is never allowed to run ahead of it.
+//! Because of them, the nodes themselves aren't deterministic and two runs with
+//! the same seed can publish and deliver different messages.
+
+use crate::System;
+use rsban_core::{utils::MemoryStream, work::WorkPoolImpl, Networks, WalletId};
+use rsban_messages::{Message, MessageType};
+use rsban_network::{ChannelDirection, ChannelId, ChannelInfo, ChannelMode};
+use rsban_node::{
+    config::{NodeConfig, NodeFlags},
+    unique_path,
+    utils::AsyncRuntime,
+    wallets::WalletsExt,
+    NetworkParams, Node, NodeBuilder, NodeCallbacks, NodeExt,
+};
+use rsban_nullable_clock::SteadyClock;
+use rsban_nullable_tcp::TcpStream;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    net::{Ipv4Addr, SocketAddrV6},
+    sync::{Arc, Mutex},
+    thread::{sleep, yield_now},
+    time::{Duration, Instant},
+};
+
+/// Index of a node in the order it was added to the simulation
+pub type SimNodeId = usize;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LinkConfig {
+    pub latency: Duration,
+    /// A random delay between zero and `jitter` is added to the latency
+    pub jitter: Duration,
+    /// Probability between 0 and 1 that a message gets lost
+    pub loss: f64,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        Self {
+            latency: Duration::from_millis(50),
+            jitter: Duration::ZERO,
+            loss: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct SimulationStats {
+    pub sent: u64,
+    pub delivered: u64,
+    /// Dropped because of packet loss
+    pub lost: u64,
+    /// Dropped because sender and receiver were in different partitions
+    pub partitioned: u64,
+    /// Dropped by the message filter
+    pub filtered: u64,
+    /// Dropped because the inbound queue of the receiver was full
+    pub queue_full: u64,
+}
+
+/// Schedules messages between simulated nodes on a virtual clock
+pub struct VirtualNetwork<T> {
+    seed: u64,
+    now: Duration,
+    default_link: LinkConfig,
+    links: HashMap<(SimNodeId, SimNodeId), LinkConfig>,
+    sequences: HashMap<(SimNodeId, SimNodeId), u64>,
+    /// Partition index by node. Nodes that aren't listed are in partition 0
+    partitions: HashMap<SimNodeId, usize>,
+    in_flight: BinaryHeap<InFlight<T>>,
+    next_order: u64,
+    stats: SimulationStats,
+}
+
+impl<T> VirtualNetwork<T> {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            now: Duration::ZERO,
+            default_link: LinkConfig::default(),
+            links: HashMap::new(),
+            sequences: HashMap::new(),
+            partitions: HashMap::new(),
+            in_flight: BinaryHeap::new(),
+            next_order: 0,
+            stats: SimulationStats::default(),
+        }
+    }
+
+    /// Time elapsed on the virtual clock
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    pub fn stats(&self) -> SimulationStats {
+        self.stats
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Used for all links without an explicit configuration
+    pub fn set_default_link(&mut self, link: LinkConfig) {
+        self.default_link = link;
+    }
+
+    /// Configures the link in both directions
+    pub fn set_link(&mut self, a: SimNodeId, b: SimNodeId, link: LinkConfig) {
+        self.links.insert((a, b), link);
+        self.links.insert((b, a), link);
+    }
+
+    pub fn link(&self, from: SimNodeId, to: SimNodeId) -> LinkConfig {
+        self.links
+            .get(&(from, to))
+            .cloned()
+            .unwrap_or(self.default_link)
+    }
+
+    /// Only nodes in the same group can talk to each other. Messages that
+    /// are in flight between groups get lost.
+    pub fn partition(&mut self, groups: &[&[SimNodeId]]) {
+        self.partitions.clear();
+        for (i, group) in groups.iter().enumerate() {
+            for node in group.iter() {
+                self.partitions.insert(*node, i);
+            }
+        }
+    }
+
+    pub fn heal(&mut self) {
+        self.partitions.clear();
+    }
+
+    pub fn is_partitioned(&self, a: SimNodeId, b: SimNodeId) -> bool {
+        self.partition_of(a) != self.partition_of(b)
+    }
+
+    fn partition_of(&self, node: SimNodeId) -> usize {
+        self.partitions.get(&node).cloned().unwrap_or_default()
+    }
+
+    pub fn send(&mut self, from: SimNodeId, to: SimNodeId, payload: T) {
+        self.stats.sent += 1;
+        if self.is_partitioned(from, to) {
+            self.stats.partitioned += 1;
+            return;
+        }
+
+        let sequence = self.sequences.entry((from, to)).or_default();
+        *sequence += 1;
+        let random = mix(mix(mix(self.seed ^ from as u64) ^ to as u64) ^ *sequence);
+
+        let link = self.link(from, to);
+        if unit_interval(random) < link.loss {
+            self.stats.lost += 1;
+            return;
+        }
+
+        let jitter = link.jitter.mul_f64(unit_interval(mix(random)));
+        self.in_flight.push(InFlight {
+            deliver_at: self.now + link.latency + jitter,
+            order: self.next_order,
+            from,
+            to,
+            payload,
+        });
+        self.next_order += 1;
+    }
+
+    /// Moves the virtual clock forward and returns the messages that
+    /// arrived in the meantime, in order of arrival
+    pub fn advance(&mut self, duration: Duration) -> Vec<(SimNodeId, SimNodeId, T)> {
+        self.now += duration;
+        let mut arrived = Vec::new();
+        while self
+            .in_flight
+            .peek()
+            .map(|m| m.deliver_at <= self.now)
+            .unwrap_or(false)
+        {
+            let message = self.in_flight.pop().unwrap();
+            if self.is_partitioned(message.from, message.to) {
+                self.stats.partitioned += 1;
+            } else {
+                arrived.push((message.from, message.to, message.payload));
+            }
+        }
+        arrived
+    }
+}
+
+struct InFlight<T> {
+    deliver_at: Duration,
+    order: u64,
+    from: SimNodeId,
+    to: SimNodeId,
+    payload: T,
+}
+
+impl<T> PartialEq for InFlight<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for InFlight<T> {}
+
+impl<T> PartialOrd for InFlight<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for InFlight<T> {
+    // Reversed, because the BinaryHeap is a max-heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.deliver_at, other.order).cmp(&(self.deliver_at, self.order))
+    }
+}
+
+/// splitmix64
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn unit_interval(random: u64) -> f64 {
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+pub type MessageFilter = Box<dyn Fn(SimNodeId, SimNodeId, &Message) -> bool + Send>;
+
+/// A message that arrived in the inbound queue of a node
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Delivery {
+    /// Time on the virtual clock
+    pub time: Duration,
+    pub from: SimNodeId,
+    pub to: SimNodeId,
+    pub message_type: MessageType,
+}
+
+/// Runs several nodes that are connected through a [`VirtualNetwork`]
+pub struct Simulator {
+    runtime: Arc<AsyncRuntime>,
+    network_params: NetworkParams,
+    work: Arc<WorkPoolImpl>,
+    /// Follows the virtual clock of the network
+    clock: Arc<SteadyClock>,
+    real_start: Instant,
+    pub nodes: Vec<Arc<Node>>,
+    network: Arc<Mutex<VirtualNetwork<Message>>>,
+    /// Messages published in the current step as (sender, receiver, message)
+    outbox: Arc<Mutex<Vec<(SimNodeId, SimNodeId, Message)>>>,
+    trace: Vec<Delivery>,
+    /// Receiver of the messages that a node publishes on a channel
+    routes: Arc<Mutex<HashMap<(SimNodeId, ChannelId), SimNodeId>>>,
+    /// Channel of the receiver on which messages of the sender arrive
+    inbound_channels: HashMap<(SimNodeId, SimNodeId), Arc<ChannelInfo>>,
+    filter: Option<MessageFilter>,
+}
+
+impl Simulator {
+    /// Virtual time that passes in one step
+    pub const TICK: Duration = Duration::from_millis(10);
+    /// Upper bound of the real time that the nodes get to become idle after a step.
+    /// This only guards against nodes that never become idle
+    const MAX_CATCH_UP: Duration = Duration::from_secs(1);
+
+    pub fn new(seed: u64) -> Self {
+        let network_params = NetworkParams::new(Networks::BananoDevNetwork);
+        Self {
+            runtime: Arc::new(AsyncRuntime::default()),
+            work: Arc::new(WorkPoolImpl::new(
+                network_params.work.clone(),
+                1,
+                Duration::ZERO,
+            )),
+            network_params,
+            clock: Arc::new(SteadyClock::new_null()),
+            real_start: Instant::now(),
+            nodes: Vec::new(),
+            network: Arc::new(Mutex::new(VirtualNetwork::new(seed))),
+            outbox: Arc::new(Mutex::new(Vec::new())),
+            trace: Vec::new(),
+            routes: Arc::new(Mutex::new(HashMap::new())),
+            inbound_channels: HashMap::new(),
+            filter: None,
+        }
+    }
+
+    /// TCP connections are disabled, so that the simulated nodes
+    /// can only talk to each other through the virtual network
+    pub fn default_config() -> NodeConfig {
+        let mut config = System::default_config_without_backlog_population();
+        config.peering_port = Some(0);
+        config.tcp_incoming_connections_max = 0;
+        config.tcp.max_outbound_connections = 0;
+        config
+    }
+
+    pub fn default_flags() -> NodeFlags {
+        let mut flags = NodeFlags::new();
+        flags.disable_legacy_bootstrap = true;
+        flags.disable_lazy_bootstrap = true;
+        flags.disable_wallet_bootstrap = true;
+        flags.disable_ongoing_bootstrap = true;
+        flags.disable_max_peers_per_ip = true;
+        flags.disable_max_peers_per_subnetwork = true;
+        flags
+    }
+
+    pub fn add_node(&mut self) -> Arc<Node> {
+        self.add_node_with(Self::default_config(), Self::default_flags())
+    }
+
+    pub fn add_node_with(&mut self, config: NodeConfig, flags: NodeFlags) -> Arc<Node> {
+        let sim_id = self.nodes.len();
+        let outbox = self.outbox.clone();
+        let routes = self.routes.clone();
+        let callbacks = NodeCallbacks::builder()
+            .on_publish(move |channel_id, message| {
+                let receiver = routes.lock().unwrap().get(&(sim_id, channel_id)).cloned();
+                if let Some(receiver) = receiver {
+                    outbox
+                        .lock()
+                        .unwrap()
+                        .push((sim_id, receiver, message.clone()));
+                }
+            })
+            .finish();
+
+        let node = NodeBuilder::new(self.network_params.network.current_network)
+            .runtime(self.runtime.tokio.handle().clone())
+            .data_path(unique_path().expect("Could not get a unique path"))
+            .config(config)
+            .network_params(self.network_params.clone())
+            .flags(flags)
+            .work(self.work.clone())
+            .callbacks(callbacks)
+            .steady_clock(self.clock.clone())
+            .finish()
+            .unwrap();
+        let node = Arc::new(node);
+        node.wallets.create(WalletId::random());
+        node.start();
+        self.nodes.push(node.clone());
+        node
+    }
+
+    /// Creates a link between two nodes
+    pub fn connect(&mut self, a: SimNodeId, b: SimNodeId) {
+        let _guard = self.runtime.tokio.enter();
+        let channel_a = self.add_channel(a, b);
+        let channel_b = self.add_channel(b, a);
+        let mut routes = self.routes.lock().unwrap();
+        routes.insert((a, channel_a.channel_id()), b);
+        routes.insert((b, channel_b.channel_id()), a);
+        self.inbound_channels.insert((a, b), channel_a);
+        self.inbound_channels.insert((b, a), channel_b);
+    }
+
+    pub fn connect_all(&mut self) {
+        for a in 0..self.nodes.len() {
+            for b in a + 1..self.nodes.len() {
+                self.connect(a, b);
+            }
+        }
+    }
+
+    /// Adds a channel to `node` that leads to `peer`
+    fn add_channel(&self, node: SimNodeId, peer: SimNodeId) -> Arc<ChannelInfo> {
+        let peer_addr = Self::virtual_address(peer);
+        let channel = self.nodes[node]
+            .network
+            .add(
+                TcpStream::new_null_with_peer_addr(peer_addr),
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+            )
+            .expect("Could not add simulated channel");
+        channel.info.set_node_id(self.nodes[peer].node_id());
+        channel.info.set_peering_addr(peer_addr);
+        channel.info.set_mode(ChannelMode::Realtime);
+        channel.info.clone()
+    }
+
+    fn virtual_address(node: SimNodeId) -> SocketAddrV6 {
+        let ip = Ipv4Addr::new(10, 0, (node >> 8) as u8, node as u8);
+        SocketAddrV6::new(ip.to_ipv6_mapped(), 7075, 0, 0)
+    }
+
+    pub fn set_default_link(&mut self, link: LinkConfig) {
+        self.network.lock().unwrap().set_default_link(link);
+    }
+
+    pub fn set_link(&mut self, a: SimNodeId, b: SimNodeId, link: LinkConfig) {
+        self.network.lock().unwrap().set_link(a, b, link);
+    }
+
+    pub fn partition(&mut self, groups: &[&[SimNodeId]]) {
+        self.network.lock().unwrap().partition(groups);
+    }
+
+    pub fn heal(&mut self) {
+        self.network.lock().unwrap().heal();
+    }
+
+    /// Messages for which the filter returns false are dropped on arrival.
+    /// This can be used to simulate nodes that misbehave.
+    pub fn set_filter(
+        &mut self,
+        filter: impl Fn(SimNodeId, SimNodeId, &Message) -> bool + Send + 'static,
+    ) {
+        self.filter = Some(Box::new(filter));
+    }
+
+    /// Sends a message as if `from` had published it
+    pub fn inject(&mut self, from: SimNodeId, to: SimNodeId, message: Message) {
+        self.network.lock().unwrap().send(from, to, message);
+    }
+
+    pub fn now(&self) -> Duration {
+        self.network.lock().unwrap().now()
+    }
+
+    pub fn stats(&self) -> SimulationStats {
+        self.network.lock().unwrap().stats()
+    }
+
+    /// All messages that arrived at the nodes so far, in order of arrival
+    pub fn trace(&self) -> &[Delivery] {
+        &self.trace
+    }
+
+    /// Advances the virtual clock by one tick, delivers the arrived messages
+    /// and sends the messages that the nodes published in response
+    pub fn step(&mut self) {
+        let arrived = self.network.lock().unwrap().advance(Self::TICK);
+        self.clock.advance(Self::TICK);
+        for (from, to, message) in arrived {
+            self.deliver(from, to, message);
+        }
+        self.wait_until_idle();
+        self.send_published();
+
+        // Components that measure real time must not fall behind the virtual clock
+        let ahead = self.now().saturating_sub(self.real_start.elapsed());
+        if !ahead.is_zero() {
+            sleep(ahead);
+        }
+    }
+
+    /// Waits until the nodes processed everything that was delivered, so that
+    /// the virtual clock doesn't run away from them
+    fn wait_until_idle(&self) {
+        let start = Instant::now();
+        while self.nodes.iter().any(|n| !Self::is_idle(n)) && start.elapsed() < Self::MAX_CATCH_UP {
+            yield_now();
+        }
+    }
+
+    fn is_idle(node: &Node) -> bool {
+        node.inbound_message_queue.size() == 0
+            && node.block_processor.total_queue_len() == 0
+            && node.vote_processor_queue.is_empty()
+            && node.request_aggregator.is_empty()
+            && node.confirming_set.len() == 0
+    }
+
+    /// Hands the messages of this step to the network. They are sorted first, so that
+    /// sequence numbers and losses don't depend on the order in which the node's
+    /// threads published them within the step
+    fn send_published(&self) {
+        let mut published = std::mem::take(&mut *self.outbox.lock().unwrap());
+        published.sort_by_cached_key(|(from, to, message)| {
+            let mut bytes = MemoryStream::new();
+            message.serialize(&mut bytes);
+            (*from, *to, message.message_type() as u8, bytes.to_vec())
+        });
+        let mut network = self.network.lock().unwrap();
+        for (from, to, message) in published {
+            network.send(from, to, message);
+        }
+    }
+
+    fn deliver(&mut self, from: SimNodeId, to: SimNodeId, message: Message) {
+        if let Some(filter) = &self.filter {
+            if !filter(from, to, &message) {
+                self.network.lock().unwrap().stats.filtered += 1;
+                return;
+            }
+        }
+
+        let node = &self.nodes[to];
+        let channel = &self.inbound_channels[&(to, from)];
+        channel.set_last_activity(node.steady_clock.now());
+        let message_type = message.message_type();
+        let delivered = node.inbound_message_queue.put(message, channel.clone());
+
+        let mut network = self.network.lock().unwrap();
+        if delivered {
+            network.stats.delivered += 1;
+            self.trace.push(Delivery {
+                time: network.now(),
+                from,
+                to,
+                message_type,
+            });
+        } else {
+            network.stats.queue_full += 1;
+        }
+    }
+
+    pub fn run_for(&mut self, duration: Duration) {
+        let end = self.now() + duration;
+        while self.now() < end {
+            self.step();
+        }
+    }
+
+    /// Runs until the condition is met. Returns false if it isn't met
+    /// within the given virtual time
+    pub fn run_until(
+        &mut self,
+        timeout: Duration,
+        mut condition: impl FnMut(&Simulator) -> bool,
+    ) -> bool {
+        let end = self.now() + timeout;
+        while !condition(self) {
+            if self.now() >= end {
+                return false;
+            }
+            self.step();
+        }
+        true
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        for node in &self.nodes {
+            node.stop();
+            std::fs::remove_dir_all(&node.data_path).expect("Could not delete node data dir");
+        }
+        self.work.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deliver_after_latency() {
+        let mut network = VirtualNetwork::new(1);
+        network.send(0, 1, "a");
+        assert!(network.advance(Duration::from_millis(49)).is_empty());
+        assert_eq!(network.advance(Duration::from_millis(1)), [(0, 1, "a")]);
+        assert_eq!(network.in_flight(), 0);
+    }
+
+    #[test]
+    fn deliver_in_order_of_arrival() {
+        let mut network = VirtualNetwork::new(1);
+        network.set_link(
+            0,
+            1,
+            LinkConfig {
+                latency: Duration::from_millis(100),
+                ..Default::default()
+            },
+        );
+        network.send(0, 1, "slow");
+        network.send(0, 2, "fast");
+        network.send(0, 2, "fast2");
+        assert_eq!(
+            network.advance(Duration::from_secs(1)),
+            [(0, 2, "fast"), (0, 2, "fast2"), (0, 1, "slow")]
+        );
+    }
+
+    #[test]
+    fn same_seed_gives_same_losses_and_delays() {
+        let run = |seed| {
+            let mut network = VirtualNetwork::new(seed);
+            network.set_default_link(LinkConfig {
+                latency: Duration::from_millis(10),
+                jitter: Duration::from_millis(100),
+                loss: 0.3,
+            });
+            for i in 0..100 {
+                network.send(i % 3, 3, i);
+            }
+            let mut arrivals = Vec::new();
+            for _ in 0..20 {
+                arrivals.push(network.advance(Duration::from_millis(10)));
+            }
+            (arrivals, network.stats())
+        };
+
+        let (arrivals, stats) = run(7);
+        assert_eq!(run(7), (arrivals.clone(), stats));
+        assert_ne!(run(8).0, arrivals);
+        assert!(stats.lost > 0);
+        assert_eq!(
+            stats.lost as usize + arrivals.iter().map(|a| a.len()).sum::<usize>(),
+            100
+        );
+    }
+
+    #[test]
+    fn drop_messages_between_partitions() {
+        let mut network = VirtualNetwork::new(1);
+        network.send(0, 2, "in flight");
+        network.partition(&[&[0, 1], &[2]]);
+        network.send(0, 1, "same partition");
+        network.send(0, 2, "other partition");
+
+        assert_eq!(
+            network.advance(Duration::from_secs(1)),
+            [(0, 1, "same partition")]
+        );
+        assert_eq!(network.stats().partitioned, 2);
+
+        network.heal();
+        network.send(0, 2, "healed");
+        assert_eq!(network.advance(Duration::from_secs(1)), [(0, 2, "healed")]);
+    }
+}
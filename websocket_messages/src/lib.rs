@@ -30,6 +30,10 @@ pub enum Topic {
     Telemetry,
     /// New block arrival message
     NewUnconfirmedBlock,
+    /// A watch-only wallet account received a confirmed send
+    WatchReceive,
+    /// A fork was detected or resolved
+    Forks,
     /// Auxiliary length, not a valid topic, must be the last enum
     Length,
 }
@@ -179,6 +183,8 @@ pub fn to_topic(topic: impl AsRef<str>) -> Topic {
         "bootstrap" => Topic::Bootstrap,
         "telemetry" => Topic::Telemetry,
         "new_unconfirmed_block" => Topic::NewUnconfirmedBlock,
+        "watch_receive" => Topic::WatchReceive,
+        "forks" => Topic::Forks,
         _ => Topic::Invalid,
     }
 }
@@ -1,7 +1,11 @@
 use super::{ConfirmationJsonOptions, ConfirmationOptions, Options, WebsocketSessionEntry};
 use crate::WebsocketSession;
 use rsban_core::{Account, Amount, BlockSideband, MaybeSavedBlock, VoteWithWeightInfo};
-use rsban_node::{consensus::ElectionStatus, wallets::Wallets};
+use rsban_node::{
+    consensus::{BlockTimestamps, ElectionStatus},
+    stats::DetailType,
+    wallets::Wallets,
+};
 use rsban_websocket_messages::{OutgoingMessageEnvelope, Topic};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -12,7 +16,7 @@ use std::{
         atomic::{AtomicUsize, Ordering},
         Arc, Condvar, Mutex, Weak,
     },
-    time::UNIX_EPOCH,
+    time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
     net::{TcpListener, TcpStream},
@@ -25,7 +29,7 @@ pub struct WebsocketListener {
     endpoint: Mutex<SocketAddr>,
     tx_stop: Mutex<Option<oneshot::Sender<()>>>,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; Topic::Length as usize]>,
     sessions: Arc<Mutex<Vec<Weak<WebsocketSessionEntry>>>>,
     tokio: tokio::runtime::Handle,
     bound: Mutex<bool>,
@@ -121,6 +125,7 @@ impl WebsocketListener {
         subtype: &str,
         election_status_a: &ElectionStatus,
         election_votes_a: &Vec<VoteWithWeightInfo>,
+        latency: Option<&BlockTimestamps>,
     ) {
         let mut msg_with_block = None;
         let mut msg_without_block = None;
@@ -150,6 +155,7 @@ impl WebsocketListener {
                             include_block,
                             election_status_a,
                             election_votes_a,
+                            latency,
                             conf_opts,
                         ));
                     } else if !include_block && msg_without_block.is_none() {
@@ -161,6 +167,7 @@ impl WebsocketListener {
                             include_block,
                             election_status_a,
                             election_votes_a,
+                            latency,
                             conf_opts,
                         ));
                     }
@@ -232,7 +239,7 @@ impl WebsocketListenerExt for Arc<WebsocketListener> {
 async fn accept_connection(
     stream: TcpStream,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; Topic::Length as usize]>,
     remote_endpoint: SocketAddr,
     tx_send: mpsc::Sender<OutgoingMessageEnvelope>,
     mut rx_send: mpsc::Receiver<OutgoingMessageEnvelope>,
@@ -277,6 +284,7 @@ fn block_confirmed_message(
     include_block: bool,
     election_status: &ElectionStatus,
     election_votes: &[VoteWithWeightInfo],
+    latency: Option<&BlockTimestamps>,
     options: &ConfirmationOptions,
 ) -> OutgoingMessageEnvelope {
     let election_info = if options.include_election_info || options.include_election_info_with_votes
@@ -285,6 +293,7 @@ fn block_confirmed_message(
         if options.include_election_info_with_votes {
             info.votes = Some(election_votes.iter().map(|v| v.into()).collect());
         }
+        info.latency = latency.map(|l| l.into());
         Some(info)
     } else {
         None
@@ -366,7 +375,11 @@ pub struct ElectionInfo {
     pub voters: String,
     pub request_count: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub behavior: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub votes: Option<Vec<JsonVoteSummary>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency: Option<JsonConfirmationLatency>,
 }
 
 impl From<&ElectionStatus> for ElectionInfo {
@@ -384,7 +397,41 @@ impl From<&ElectionStatus> for ElectionInfo {
             blocks: value.block_count.to_string(),
             voters: value.voter_count.to_string(),
             request_count: value.confirmation_request_count.to_string(),
+            behavior: value
+                .behavior
+                .map(|b| DetailType::from(b).as_str().to_owned()),
             votes: None,
+            latency: None,
+        }
+    }
+}
+
+/// Milliseconds between the stages a block went through until it was cemented
+#[derive(Serialize, Deserialize)]
+pub struct JsonConfirmationLatency {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrival_to_election: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub election_to_quorum: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_to_cemented: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrival_to_cemented: Option<String>,
+}
+
+impl From<&BlockTimestamps> for JsonConfirmationLatency {
+    fn from(value: &BlockTimestamps) -> Self {
+        let millis = |d: Option<Duration>| d.map(|d| d.as_millis().to_string());
+        Self {
+            source: value
+                .source
+                .map(|s| DetailType::from(s).as_str().to_owned()),
+            arrival_to_election: millis(value.arrival_to_election()),
+            election_to_quorum: millis(value.election_to_quorum()),
+            quorum_to_cemented: millis(value.quorum_to_cemented()),
+            arrival_to_cemented: millis(value.arrival_to_cemented()),
         }
     }
 }
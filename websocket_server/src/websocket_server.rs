@@ -7,9 +7,10 @@ use rsban_node::{
     bootstrap::{BootstrapCallbackData, BootstrapInitiator, BootstrapStarted, BootstrapStopped},
     config::WebsocketConfig,
     consensus::{
-        ActiveElections, ElectionStatus, ElectionStatusType, ProcessLiveDispatcher, VoteProcessor,
+        ActiveElections, ConfirmationLatency, ElectionStatus, ElectionStatusType, Fork, ForkEvent,
+        ForkTracker, ProcessLiveDispatcher, VoteProcessor,
     },
-    wallets::Wallets,
+    wallets::{Wallets, WatchReceive},
     Telemetry,
 };
 use rsban_websocket_messages::{new_block_arrived_message, OutgoingMessageEnvelope, Topic};
@@ -30,6 +31,8 @@ pub fn create_websocket_server(
     vote_processor: &VoteProcessor,
     process_live_dispatcher: &ProcessLiveDispatcher,
     bootstrap_initiator: &BootstrapInitiator,
+    fork_tracker: &ForkTracker,
+    confirmation_latency: Arc<ConfirmationLatency>,
 ) -> Option<Arc<WebsocketListener>> {
     if !config.enabled {
         return None;
@@ -41,7 +44,11 @@ pub fn create_websocket_server(
     };
 
     let endpoint = SocketAddr::new(address, config.port);
-    let server = Arc::new(WebsocketListener::new(endpoint, wallets, tokio.clone()));
+    let server = Arc::new(WebsocketListener::new(
+        endpoint,
+        wallets.clone(),
+        tokio.clone(),
+    ));
 
     let server_w = Arc::downgrade(&server);
     active_elections.on_election_ended(Box::new(
@@ -70,7 +77,16 @@ pub fn create_websocket_server(
                         ""
                     };
 
-                    server.broadcast_confirmation(block, &account, &amount, subtype, status, votes);
+                    let latency = confirmation_latency.timestamps(&block.hash());
+                    server.broadcast_confirmation(
+                        block,
+                        &account,
+                        &amount,
+                        subtype,
+                        status,
+                        votes,
+                        latency.as_ref(),
+                    );
                 }
             }
         },
@@ -143,9 +159,88 @@ pub fn create_websocket_server(
         }
     }));
 
+    let server_w: std::sync::Weak<WebsocketListener> = Arc::downgrade(&server);
+    wallets.on_watch_receive(Box::new(move |event| {
+        if let Some(server) = server_w.upgrade() {
+            if server.any_subscriber(Topic::WatchReceive) {
+                server.broadcast(&watch_receive(event));
+            }
+        }
+    }));
+
+    let server_w: std::sync::Weak<WebsocketListener> = Arc::downgrade(&server);
+    fork_tracker.on_fork_event(Box::new(move |event, fork| {
+        if let Some(server) = server_w.upgrade() {
+            if server.any_subscriber(Topic::Forks) {
+                server.broadcast(&fork_event(event, fork));
+            }
+        }
+    }));
+
     Some(server)
 }
 
+pub fn watch_receive(event: &WatchReceive) -> OutgoingMessageEnvelope {
+    let mut result = OutgoingMessageEnvelope::new(
+        Topic::WatchReceive,
+        WatchReceived {
+            wallet: event.wallet_id.to_string(),
+            account: event.account.encode_account(),
+            hash: event.hash.to_string(),
+            source: event.source.encode_account(),
+            amount: event.amount.to_string_dec(),
+        },
+    );
+    result.hash = Some(event.hash);
+    result
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WatchReceived {
+    pub wallet: String,
+    pub account: String,
+    pub hash: String,
+    pub source: String,
+    pub amount: String,
+}
+
+pub fn fork_event(event: ForkEvent, fork: &Fork) -> OutgoingMessageEnvelope {
+    OutgoingMessageEnvelope::new(
+        Topic::Forks,
+        ForkMessage {
+            event: event.as_str().to_owned(),
+            root: format!("{}{}", fork.root.root, fork.root.previous),
+            account: fork.account.encode_account(),
+            candidates: fork
+                .candidates
+                .iter()
+                .map(|candidate| ForkCandidateMessage {
+                    hash: candidate.hash.to_string(),
+                    tally: candidate.tally.to_string_dec(),
+                })
+                .collect(),
+            winner: fork.winner.map(|winner| winner.to_string()),
+        },
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ForkMessage {
+    /// "detected" or "resolved"
+    pub event: String,
+    pub root: String,
+    pub account: String,
+    pub candidates: Vec<ForkCandidateMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ForkCandidateMessage {
+    pub hash: String,
+    pub tally: String,
+}
+
 fn telemetry_received(data: &TelemetryData, endpoint: SocketAddrV6) -> OutgoingMessageEnvelope {
     OutgoingMessageEnvelope::new(
         Topic::Telemetry,
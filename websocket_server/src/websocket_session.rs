@@ -78,14 +78,14 @@ impl WebsocketSessionEntry {
 pub struct WebsocketSession {
     entry: Arc<WebsocketSessionEntry>,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; Topic::Length as usize]>,
     remote_endpoint: SocketAddr,
 }
 
 impl WebsocketSession {
     pub fn new(
         wallets: Arc<Wallets>,
-        topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+        topic_subscriber_count: Arc<[AtomicUsize; Topic::Length as usize]>,
         remote_endpoint: SocketAddr,
         entry: Arc<WebsocketSessionEntry>,
     ) -> Self {
@@ -2,19 +2,20 @@ use core::panic;
 use futures_util::{SinkExt, StreamExt};
 use rsban_core::{
     Account, Amount, Block, JsonBlock, Networks, PrivateKey, SendBlockArgs,
-    UnsavedBlockLatticeBuilder, Vote, VoteCode, DEV_GENESIS_KEY,
+    UnsavedBlockLatticeBuilder, Vote, VoteCode, WalletId, DEV_GENESIS_KEY,
 };
 use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
 use rsban_messages::{Message, Publish};
 use rsban_node::{
     bootstrap::{BootstrapInitiatorExt, BootstrapStarted},
     config::{NetworkConstants, NodeConfig, WebsocketConfig},
+    wallets::WalletsExt,
     Node,
 };
 use rsban_websocket_messages::{OutgoingMessageEnvelope, Topic};
 use rsban_websocket_server::{
-    create_websocket_server, vote_received, BlockConfirmed, TelemetryReceived, VoteReceived,
-    WebsocketListener, WebsocketListenerExt,
+    create_websocket_server, vote_received, BlockConfirmed, ForkMessage, TelemetryReceived,
+    VoteReceived, WatchReceived, WebsocketListener, WebsocketListenerExt,
 };
 use std::{sync::Arc, time::Duration};
 use test_helpers::{assert_timely, get_available_port, make_fake_channel, System};
@@ -267,6 +268,11 @@ fn confirmation_options() {
         assert_ne!(election_info.tally, "0");
         assert_ne!(election_info.time, "0");
         assert!(election_info.votes.is_none());
+        assert!(election_info.behavior.is_some());
+        let latency = election_info.latency.unwrap();
+        assert!(latency.source.is_some());
+        assert!(latency.election_to_quorum.is_some());
+        assert!(latency.arrival_to_cemented.is_some());
 
         ws_stream
             .send(tungstenite::Message::Text(
@@ -708,6 +714,128 @@ fn new_unconfirmed_block() {
     });
 }
 
+#[test]
+fn watch_receive() {
+    let mut system = System::new();
+    let (node1, _websocket) = create_node_with_websocket(&mut system);
+    let wallet_id = node1.wallets.wallet_ids()[0];
+    node1
+        .wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+    let watched = WalletId::random();
+    node1.wallets.create(watched);
+    let watched_account = PrivateKey::from(1).account();
+    node1
+        .wallets
+        .insert_watch(&watched, &[watched_account])
+        .unwrap();
+
+    node1.runtime.block_on(async {
+        let mut ws_stream = connect_websocket(&node1).await;
+        ws_stream
+            .send(tungstenite::Message::Text(
+                r#"{"action": "subscribe", "topic": "watch_receive", "ack": true}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+        //await ack
+        ws_stream.next().await.unwrap().unwrap();
+
+        let node_l = node1.clone();
+        let send = spawn_blocking(move || {
+            node_l.wallets.send_sync(
+                wallet_id,
+                *DEV_GENESIS_ACCOUNT,
+                watched_account,
+                Amount::raw(42),
+                0,
+                true,
+                None,
+            )
+        })
+        .await
+        .unwrap();
+
+        let response = timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let tungstenite::Message::Text(response) = response else {
+            panic!("not a text message");
+        };
+
+        let response_json: OutgoingMessageEnvelope = serde_json::from_str(&response).unwrap();
+        assert_eq!(response_json.topic, Some(Topic::WatchReceive));
+        assert_eq!(response_json.hash, Some(send));
+
+        let message: WatchReceived =
+            serde_json::from_value(response_json.message.unwrap()).unwrap();
+        assert_eq!(message.wallet, watched.to_string());
+        assert_eq!(message.account, watched_account.encode_account());
+        assert_eq!(message.source, DEV_GENESIS_ACCOUNT.encode_account());
+        assert_eq!(message.amount, "42");
+    });
+}
+
+#[test]
+fn fork_detected() {
+    let mut system = System::new();
+    let (node1, _websocket) = create_node_with_websocket(&mut system);
+    let key = PrivateKey::new();
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice.genesis().send(&key, 100);
+    let mut fork_lattice = UnsavedBlockLatticeBuilder::new();
+    let send2 = fork_lattice.genesis().send(&key, 200);
+
+    node1.runtime.block_on(async {
+        let mut ws_stream = connect_websocket(&node1).await;
+        ws_stream
+            .send(tungstenite::Message::Text(
+                r#"{"action": "subscribe", "topic": "forks", "ack": true}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+        //await ack
+        ws_stream.next().await.unwrap().unwrap();
+
+        let node_l = node1.clone();
+        let hashes = (send1.hash(), send2.hash());
+        spawn_blocking(move || {
+            node_l.process_local(send1).unwrap();
+            node_l.process_local(send2).unwrap();
+        })
+        .await
+        .unwrap();
+
+        let response = timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let tungstenite::Message::Text(response) = response else {
+            panic!("not a text message");
+        };
+
+        let response_json: OutgoingMessageEnvelope = serde_json::from_str(&response).unwrap();
+        assert_eq!(response_json.topic, Some(Topic::Forks));
+
+        let message: ForkMessage = serde_json::from_value(response_json.message.unwrap()).unwrap();
+        assert_eq!(message.event, "detected");
+        assert_eq!(message.account, DEV_GENESIS_ACCOUNT.encode_account());
+        assert_eq!(
+            message
+                .candidates
+                .iter()
+                .map(|c| c.hash.clone())
+                .collect::<Vec<_>>(),
+            vec![hashes.0.to_string(), hashes.1.to_string()]
+        );
+        assert_eq!(message.winner, None);
+    });
+}
+
 fn create_node_with_websocket(system: &mut System) -> (Arc<Node>, Arc<WebsocketListener>) {
     let websocket_port = get_available_port();
     let config = NodeConfig {
@@ -732,6 +860,8 @@ fn create_node_with_websocket(system: &mut System) -> (Arc<Node>, Arc<WebsocketL
         &node.vote_processor,
         &node.process_live_dispatcher,
         &node.bootstrap_initiator,
+        &node.fork_tracker,
+        node.confirmation_latency.clone(),
     )
     .unwrap();
 